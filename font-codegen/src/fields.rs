@@ -403,7 +403,9 @@ fn traversal_arm_for_field(
         }
         // See if there are better ways to handle these hardcoded types
         // <https://github.com/googlefonts/fontations/issues/659>
-        FieldType::Struct { typ } if typ == "ValueRecord" || typ == "SbitLineMetrics" => {
+        FieldType::Struct { typ }
+            if typ == "ValueRecord" || typ == "SbitLineMetrics" || typ == "MathValueRecord" =>
+        {
             let offset_data = pass_data
                 .cloned()
                 .unwrap_or_else(|| fld.offset_getter_data_src());
@@ -591,6 +593,10 @@ impl Field {
             FieldType::Offset { typ, .. } | FieldType::Scalar { typ } => {
                 quote!(#typ::RAW_BYTE_LEN)
             }
+            // fixed size records embedded directly in a table
+            FieldType::Struct { typ } if !self.has_computed_len() => {
+                quote!(#typ::RAW_BYTE_LEN)
+            }
             FieldType::Struct { .. }
             | FieldType::Array { .. }
             | FieldType::ComputedArray { .. }
@@ -695,7 +701,12 @@ impl Field {
         let is_var_array = self.is_var_array();
         let is_versioned = self.is_version_dependent();
 
+        // fixed size records are returned by reference, like records in arrays
+        let is_struct = matches!(self.typ, FieldType::Struct { .. }) && !self.has_computed_len();
         let mut return_type = self.raw_getter_return_type();
+        if is_struct {
+            return_type = quote!(&'a #return_type);
+        }
         if is_versioned {
             return_type = quote!(Option<#return_type>);
         }
@@ -708,6 +719,8 @@ impl Field {
             quote!(VarLenArray::read(self.data.split_off(range.start).unwrap()).unwrap())
         } else if is_array {
             quote!(self.data.read_array(range).unwrap())
+        } else if is_struct {
+            quote!(self.data.read_ref_at(range.start).unwrap())
         } else {
             quote!(self.data.read_at(range.start).unwrap())
         };
@@ -936,6 +949,9 @@ impl Field {
             && !self.is_version_dependent()
         {
             let typ = self.typ.cooked_type_tokens();
+            if let FieldType::Struct { .. } = &self.typ {
+                return quote!( cursor.advance_by(#typ::RAW_BYTE_LEN); );
+            }
             return quote!( cursor.advance::<#typ>(); );
         }
 
@@ -1005,7 +1021,15 @@ impl Field {
             panic!("Should have resolved {self:?}")
         }
         let len_expr = match self.attrs.count.as_deref() {
-            Some(Count::All(_)) => quote!(cursor.remaining_bytes()),
+            // Ignore any trailing partial element in arrays of multi-byte
+            // types so that reading the array can't fail.
+            Some(Count::All(_)) => match &self.typ {
+                FieldType::Array { inner_typ } if inner_typ.cooked_type_tokens() != "u8" => {
+                    let inner_typ = inner_typ.cooked_type_tokens();
+                    quote!(cursor.remaining_bytes() / #inner_typ::RAW_BYTE_LEN * #inner_typ::RAW_BYTE_LEN)
+                }
+                _ => quote!(cursor.remaining_bytes()),
+            },
             Some(other) => {
                 let count_expr = other.count_expr();
                 let size_expr = match &self.typ {
@@ -1019,11 +1043,15 @@ impl Field {
                     }
                     _ => unreachable!("count not valid here"),
                 };
+                let is_byte_array = matches!(&self.typ, FieldType::Array { inner_typ }
+                    if inner_typ.cooked_type_tokens() == "u8");
                 match other {
                     Count::SingleArg(CountArg::Literal(lit)) if lit.base10_digits() == "1" => {
                         // Prevent identity-op clippy error with `1 * size`
                         size_expr
                     }
+                    // Likewise for `count * 1` with byte arrays
+                    _ if is_byte_array => count_expr,
                     _ => quote!(  #count_expr * #size_expr ),
                 }
             }
@@ -16,11 +16,17 @@ pub static COLR_GRADIENT_RECT: &[u8] =
 
 pub static VAZIRMATN_VAR: &[u8] = include_bytes!("../test_data/ttf/vazirmatn_var_trimmed.ttf");
 
+pub static VAZIRMATN_VAR_VERTICAL: &[u8] =
+    include_bytes!("../test_data/ttf/vazirmatn_var_vertical.ttf");
+
 pub static NAMES_ONLY: &[u8] = include_bytes!("../test_data/ttf/names_only.ttf");
 
 pub static VAZIRMATN_VAR_GLYPHS: &str =
     include_str!("../test_data/extracted/vazirmatn_var_trimmed-glyphs.txt");
 
+pub static VAZIRMATN_VAR_MASKS: &str =
+    include_str!("../test_data/extracted/vazirmatn_var_trimmed-masks.txt");
+
 pub static SIMPLE_GLYF: &[u8] = include_bytes!("../test_data/ttf/simple_glyf.ttf");
 
 pub static NOTO_SERIF_DISPLAY_TRIMMED: &[u8] =
@@ -35,10 +41,28 @@ pub static CANTARELL_VF_TRIMMED: &[u8] =
 pub static CANTARELL_VF_TRIMMED_GLYPHS: &str =
     include_str!("../test_data/extracted/cantarell_vf_trimmed-glyphs.txt");
 
+pub static CANTARELL_VF_TRIMMED_AUTOHINT_GLYPHS: &str =
+    include_str!("../test_data/extracted/cantarell_vf_trimmed-autohint.txt");
+
+pub static CANTARELL_VF_TRIMMED_MASKS: &str =
+    include_str!("../test_data/extracted/cantarell_vf_trimmed-masks.txt");
+
 pub static CHARSTRING_PATH_OPS: &[u8] = include_bytes!("../test_data/ttf/charstring_path_ops.ttf");
 
 pub static EMBEDDED_BITMAPS: &[u8] = include_bytes!("../test_data/ttf/embedded_bitmaps.ttf");
 
+pub static TTHINT_SUBSET: &[u8] = include_bytes!("../test_data/ttf/tthint_subset.ttf");
+
+pub static TTHINT_SUBSET_GLYPHS: &str =
+    include_str!("../test_data/extracted/tthint_subset-glyphs.txt");
+
+pub static TTHINT_VAR: &[u8] = include_bytes!("../test_data/ttf/tthint_var.ttf");
+
+pub static TTHINT_VAR_GLYPHS: &str = include_str!("../test_data/extracted/tthint_var-glyphs.txt");
+
+pub static TTHINT_SUBSET_MASKS: &str =
+    include_str!("../test_data/extracted/tthint_subset-masks.txt");
+
 pub mod post {
 
     #[rustfmt::skip]
@@ -68,6 +92,25 @@ pub mod post {
         0x02, 0x68, 0x69, // 2, h i
         0x4, 0x68, 0x6f, 0x6c, 0x61, // 4, h o l a
     ];
+
+    #[rustfmt::skip]
+    pub static VERSION_2_5: &[u8] = &[
+        0x00, 0x02, 0x50, 0x00, // version 2.5
+        0x00, 0x00, 0x00, 0x00, // italic angle
+        0xFF, 0xb5,             // underlinePosition -75
+        0x00, 0x32,             // underlineThickness 50
+        0x00, 0x00, 0x00, 0x00, // fixedpitch
+        0x00, 0x00, 0x00, 0x00, // min/max mem:
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x04,             // numGlyphs 4
+                                // offsets:
+        0x00,                   // glyph 0 -> name 0 ('.notdef')
+        0x02,                   // glyph 1 -> name 3 ('space')
+        0x22,                   // glyph 2 -> name 36 ('A')
+        0x22,                   // glyph 3 -> name 37 ('B')
+    ];
 }
 
 pub mod cff2 {
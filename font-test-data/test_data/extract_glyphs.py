@@ -18,6 +18,18 @@ SAMPLE_SIZES = [0, 16, 50]
 # results among FreeType, freetype-py and read-fonts.
 SAMPLE_COORDS = [-1.0, -0.2000122, 0.0, 0.2999878, 1.0]
 
+# Fonts with names beginning with this prefix contain TrueType instructions
+# and are additionally sampled in each hinting mode at these sizes.
+HINTED_FONT_PREFIX = "tthint"
+HINTED_SAMPLE_SIZES = [8, 16, 50]
+HINTING_MODES = ["full", "light", "light-subpixel", "vertical-subpixel", "none"]
+
+# Fonts that are additionally sampled with FreeType's auto-hinter (in light
+# mode, which only adjusts vertical positions) at the default location. These
+# are written to a separate file since our auto-hinter is only expected to
+# approximate FreeType's results.
+AUTOHINTED_FONTS = ["cantarell_vf_trimmed"]
+
 
 class DecomposeContext:
     def __init__(self, is_scaled: bool, is_cff: bool):
@@ -67,12 +79,16 @@ class GlyphData:
     def add_glyph(self, face: freetype.Face, size, glyph_id, coords=[], hinting="none"):
         face.set_pixel_sizes(size, size)
         flags = freetype.FT_LOAD_NO_AUTOHINT | freetype.FT_LOAD_NO_BITMAP
-        if hinting == "full":
-            flags |= freetype.FT_LOAD_TARGET_NORMAL
+        if hinting == "auto":
+            flags = freetype.FT_LOAD_FORCE_AUTOHINT | freetype.FT_LOAD_NO_BITMAP | freetype.FT_LOAD_TARGET_LIGHT
+        elif hinting == "full":
+            flags |= freetype.FT_LOAD_TARGET_MONO
         elif hinting == "light":
             flags |= freetype.FT_LOAD_TARGET_LIGHT
         elif hinting == "light-subpixel":
             flags |= freetype.FT_LOAD_TARGET_LCD
+        elif hinting == "vertical-subpixel":
+            flags |= freetype.FT_LOAD_TARGET_LCD_V
         else:
             flags |= freetype.FT_LOAD_NO_HINTING
             hinting = "none"
@@ -131,7 +147,15 @@ except:
 
 glyphs = GlyphData()
 
-if axis_count > 0:
+if os.path.basename(font_path).startswith(HINTED_FONT_PREFIX):
+    # Hinted variable fonts are sampled at each of the coordinates
+    coord_sets = [[coord] * axis_count for coord in SAMPLE_COORDS] if axis_count > 0 else [[]]
+    for coords in coord_sets:
+        for glyph_id in range(0, face.num_glyphs):
+            for size in HINTED_SAMPLE_SIZES:
+                for hinting in HINTING_MODES:
+                    glyphs.add_glyph(face, size, glyph_id, coords, hinting=hinting)
+elif axis_count > 0:
     for coord in SAMPLE_COORDS:
         coords = [coord] * axis_count
         for glyph_id in range(0, face.num_glyphs):
@@ -145,3 +169,17 @@ else:
 f = open(out_path, "w")
 f.write(glyphs.data)
 f.close()
+
+font_name = os.path.splitext(os.path.basename(font_path))[0]
+if font_name in AUTOHINTED_FONTS:
+    autohint_path = os.path.join(out_dir, font_name) + "-autohint.txt"
+    print("Extracting auto-hinted glyphs to \"%s\"..." % autohint_path)
+    glyphs = GlyphData()
+    # Reset any variations applied above
+    coords = [0.0] * axis_count
+    for glyph_id in range(0, face.num_glyphs):
+        for size in HINTED_SAMPLE_SIZES:
+            glyphs.add_glyph(face, size, glyph_id, coords, hinting="auto")
+    f = open(autohint_path, "w")
+    f.write(glyphs.data)
+    f.close()
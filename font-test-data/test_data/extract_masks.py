@@ -0,0 +1,61 @@
+# Script for generating anti-aliased coverage masks for all glyphs in a font
+# using the FreeType smooth rasterizer.
+
+import sys
+import os
+import freetype
+
+# See extract_glyphs.py
+assert freetype.version() == (2, 13, 0)
+
+# Only a few fonts are sampled since the masks are large. Each entry maps
+# a font name to the hinting mode and sizes (in pixels per em) used.
+MASK_FONTS = {
+    "vazirmatn_var_trimmed": ("none", [16, 50]),
+    "cantarell_vf_trimmed": ("none", [16, 50]),
+    "tthint_subset": ("full", [16]),
+}
+
+
+def add_mask(face: freetype.Face, size, glyph_id, hinting):
+    face.set_pixel_sizes(size, size)
+    flags = freetype.FT_LOAD_NO_AUTOHINT | freetype.FT_LOAD_NO_BITMAP
+    if hinting == "full":
+        flags |= freetype.FT_LOAD_TARGET_MONO
+    else:
+        flags |= freetype.FT_LOAD_NO_HINTING
+    face.load_glyph(glyph_id, flags)
+    face.glyph.render(freetype.FT_RENDER_MODE_NORMAL)
+    bitmap = face.glyph.bitmap
+    data = "mask {} {} {}\n".format(glyph_id, size, hinting)
+    data += "placement {} {} {} {}\n".format(
+        face.glyph.bitmap_left, face.glyph.bitmap_top, bitmap.width, bitmap.rows)
+    for y in range(bitmap.rows):
+        row = bitmap.buffer[y * bitmap.pitch:y * bitmap.pitch + bitmap.width]
+        data += "".join("{:02x}".format(value) for value in row) + "\n"
+    data += "-\n"
+    return data
+
+
+font_path = sys.argv[1]
+font_name = os.path.splitext(os.path.basename(font_path))[0]
+
+if font_name not in MASK_FONTS:
+    exit(0)
+
+font_dir = os.path.abspath(os.path.dirname(os.path.dirname(font_path)))
+out_path = os.path.join(font_dir, "extracted", font_name) + "-masks.txt"
+
+print("Extracting masks from \"%s\" to \"%s\"..." % (font_path, out_path))
+
+face = freetype.Face(font_path)
+hinting, sizes = MASK_FONTS[font_name]
+masks = ""
+
+for size in sizes:
+    for glyph_id in range(0, face.num_glyphs):
+        masks += add_mask(face, size, glyph_id, hinting)
+
+f = open(out_path, "w")
+f.write(masks)
+f.close()
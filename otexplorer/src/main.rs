@@ -108,19 +108,34 @@ fn get_some_table<'a>(
         tables::cmap::Cmap::TAG => font.cmap().map(|x| Box::new(x) as _),
         tables::fvar::Fvar::TAG => font.fvar().map(|x| Box::new(x) as _),
         tables::avar::Avar::TAG => font.avar().map(|x| Box::new(x) as _),
+        tables::base::Base::TAG => font.base().map(|x| Box::new(x) as _),
+        tables::math::Math::TAG => font.math().map(|x| Box::new(x) as _),
         tables::gdef::Gdef::TAG => font.gdef().map(|x| Box::new(x) as _),
         tables::glyf::Glyf::TAG => font.glyf().map(|x| Box::new(x) as _),
         tables::head::Head::TAG => font.head().map(|x| Box::new(x) as _),
         tables::hhea::Hhea::TAG => font.hhea().map(|x| Box::new(x) as _),
         tables::hmtx::Hmtx::TAG => font.hmtx().map(|x| Box::new(x) as _),
+        tables::kern::Kern::TAG => font.kern().map(|x| Box::new(x) as _),
+        tables::kerx::Kerx::TAG => font.kerx().map(|x| Box::new(x) as _),
         tables::loca::Loca::TAG => font.loca(None).map(|x| Box::new(x) as _),
         tables::maxp::Maxp::TAG => font.maxp().map(|x| Box::new(x) as _),
         tables::name::Name::TAG => font.name().map(|x| Box::new(x) as _),
         tables::post::Post::TAG => font.post().map(|x| Box::new(x) as _),
         tables::colr::Colr::TAG => font.colr().map(|x| Box::new(x) as _),
         tables::stat::Stat::TAG => font.stat().map(|x| Box::new(x) as _),
+        tables::svg::Svg::TAG => font.svg().map(|x| Box::new(x) as _),
+        tables::cvt::Cvt::TAG => font.cvt().map(|x| Box::new(x) as _),
+        tables::cvar::Cvar::TAG => font.cvar().map(|x| Box::new(x) as _),
+        tables::fpgm::Fpgm::TAG => font.fpgm().map(|x| Box::new(x) as _),
+        tables::prep::Prep::TAG => font.prep().map(|x| Box::new(x) as _),
+        tables::gasp::Gasp::TAG => font.gasp().map(|x| Box::new(x) as _),
+        tables::morx::Morx::TAG => font.morx().map(|x| Box::new(x) as _),
+        tables::trak::Trak::TAG => font.trak().map(|x| Box::new(x) as _),
+        tables::ankr::Ankr::TAG => font.ankr().map(|x| Box::new(x) as _),
+        tables::feat::Feat::TAG => font.feat().map(|x| Box::new(x) as _),
         tables::vhea::Vhea::TAG => font.vhea().map(|x| Box::new(x) as _),
         tables::vmtx::Vmtx::TAG => font.vmtx().map(|x| Box::new(x) as _),
+        tables::vorg::Vorg::TAG => font.vorg().map(|x| Box::new(x) as _),
         _ => Err(ReadError::TableIsMissing(tag)),
     }
 }
@@ -47,7 +47,7 @@ impl<'a> FontRead<'a> for CffHeader<'a> {
         cursor.advance::<u8>();
         let hdr_size: u8 = cursor.read()?;
         cursor.advance::<u8>();
-        let _padding_byte_len = transforms::subtract(hdr_size, 4_usize) * u8::RAW_BYTE_LEN;
+        let _padding_byte_len = transforms::subtract(hdr_size, 4_usize);
         cursor.advance_by(_padding_byte_len);
         let trailing_data_byte_len = cursor.remaining_bytes();
         cursor.advance_by(trailing_data_byte_len);
@@ -314,7 +314,7 @@ impl<'a> FontRead<'a> for Cmap0<'a> {
         cursor.advance::<u16>();
         cursor.advance::<u16>();
         cursor.advance::<u16>();
-        let glyph_id_array_byte_len = 256_usize * u8::RAW_BYTE_LEN;
+        let glyph_id_array_byte_len = 256_usize;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap0Marker {
             glyph_id_array_byte_len,
@@ -628,7 +628,8 @@ impl<'a> FontRead<'a> for Cmap4<'a> {
         cursor.advance_by(id_delta_byte_len);
         let id_range_offsets_byte_len = transforms::half(seg_count_x2) * u16::RAW_BYTE_LEN;
         cursor.advance_by(id_range_offsets_byte_len);
-        let glyph_id_array_byte_len = cursor.remaining_bytes();
+        let glyph_id_array_byte_len =
+            cursor.remaining_bytes() / u16::RAW_BYTE_LEN * u16::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap4Marker {
             end_code_byte_len,
@@ -924,7 +925,7 @@ impl<'a> FontRead<'a> for Cmap8<'a> {
         cursor.advance::<u16>();
         cursor.advance::<u32>();
         cursor.advance::<u32>();
-        let is32_byte_len = 8192_usize * u8::RAW_BYTE_LEN;
+        let is32_byte_len = 8192_usize;
         cursor.advance_by(is32_byte_len);
         let num_groups: u32 = cursor.read()?;
         let groups_byte_len = num_groups as usize * SequentialMapGroup::RAW_BYTE_LEN;
@@ -1128,7 +1129,8 @@ impl<'a> FontRead<'a> for Cmap10<'a> {
         cursor.advance::<u32>();
         cursor.advance::<u32>();
         cursor.advance::<u32>();
-        let glyph_id_array_byte_len = cursor.remaining_bytes();
+        let glyph_id_array_byte_len =
+            cursor.remaining_bytes() / u16::RAW_BYTE_LEN * u16::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap10Marker {
             glyph_id_array_byte_len,
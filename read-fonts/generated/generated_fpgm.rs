@@ -0,0 +1,68 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The [fpgm (Font Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/fpgm) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct FpgmMarker {
+    instructions_byte_len: usize,
+}
+
+impl FpgmMarker {
+    fn instructions_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + self.instructions_byte_len
+    }
+}
+
+impl TopLevelTable for Fpgm<'_> {
+    /// `fpgm`
+    const TAG: Tag = Tag::new(b"fpgm");
+}
+
+impl<'a> FontRead<'a> for Fpgm<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        let instructions_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(instructions_byte_len);
+        cursor.finish(FpgmMarker {
+            instructions_byte_len,
+        })
+    }
+}
+
+/// The [fpgm (Font Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/fpgm) table
+pub type Fpgm<'a> = TableRef<'a, FpgmMarker>;
+
+impl<'a> Fpgm<'a> {
+    /// Instructions. n is the number of uint8 items that fit in the size
+    /// of the table.
+    pub fn instructions(&self) -> &'a [u8] {
+        let range = self.shape.instructions_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Fpgm<'a> {
+    fn type_name(&self) -> &str {
+        "Fpgm"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("instructions", self.instructions())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Fpgm<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
@@ -556,7 +556,7 @@ impl<'a> FontRead<'a> for Os2<'a> {
         cursor.advance::<i16>();
         cursor.advance::<i16>();
         cursor.advance::<i16>();
-        let panose_10_byte_len = 10_usize * u8::RAW_BYTE_LEN;
+        let panose_10_byte_len = 10_usize;
         cursor.advance_by(panose_10_byte_len);
         cursor.advance::<u32>();
         cursor.advance::<u32>();
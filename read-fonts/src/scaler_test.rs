@@ -48,6 +48,10 @@ impl Pen for Path {
             // recent move_to which copies FreeType's behavior.
             //
             // Except for CFF, which has its own logic for handling this :(
+            //
+            // FreeType also omits the line when the contour ends with an
+            // off curve point because the final quad already returns to the
+            // start point.
             let last_move = self
                 .elements
                 .iter()
@@ -55,6 +59,11 @@ impl Pen for Path {
                 .find(|element| matches!(*element, PathElement::MoveTo(_)))
                 .copied();
             if let Some(PathElement::MoveTo(point)) = last_move {
+                if let Some(PathElement::QuadTo([.., x, y])) = self.elements.last() {
+                    if [*x, *y] == point {
+                        return;
+                    }
+                }
                 self.elements.push(PathElement::LineTo(point));
             }
         }
@@ -65,6 +74,8 @@ impl Pen for Path {
 pub struct GlyphOutline {
     pub glyph_id: GlyphId,
     pub size: f32,
+    /// The hinting mode or `None` if the outline is unhinted.
+    pub hinting: Option<String>,
     pub coords: Vec<F2Dot14>,
     pub points: Vec<Point<F26Dot6>>,
     pub contours: Vec<u16>,
@@ -84,6 +95,10 @@ pub fn parse_glyph_outlines(source: &str) -> Vec<GlyphOutline> {
             let parts = line.split(' ').collect::<Vec<_>>();
             cur_outline.glyph_id = GlyphId::new(parts[1].parse().unwrap());
             cur_outline.size = parts[2].parse().unwrap();
+            cur_outline.hinting = parts
+                .get(3)
+                .filter(|mode| **mode != "none")
+                .map(|mode| mode.to_string());
         } else if line.starts_with("coords") {
             for coord in line.split(' ').skip(1) {
                 cur_outline
@@ -149,6 +164,54 @@ pub fn parse_glyph_outlines(source: &str) -> Vec<GlyphOutline> {
     outlines
 }
 
+/// Coverage mask rendered by FreeType.
+#[derive(Clone, Default, Debug)]
+pub struct GlyphMask {
+    pub glyph_id: GlyphId,
+    pub size: f32,
+    /// The hinting mode or `None` if the outline is unhinted.
+    pub hinting: Option<String>,
+    pub left: i32,
+    pub top: i32,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+pub fn parse_glyph_masks(source: &str) -> Vec<GlyphMask> {
+    let mut masks = vec![];
+    let mut cur_mask = GlyphMask::default();
+    for line in source.lines() {
+        let line = line.trim();
+        if line == "-" {
+            masks.push(cur_mask.clone());
+        } else if line.starts_with("mask") {
+            cur_mask = GlyphMask::default();
+            let parts = line.split(' ').collect::<Vec<_>>();
+            cur_mask.glyph_id = GlyphId::new(parts[1].parse().unwrap());
+            cur_mask.size = parts[2].parse().unwrap();
+            cur_mask.hinting = parts
+                .get(3)
+                .filter(|mode| **mode != "none")
+                .map(|mode| mode.to_string());
+        } else if line.starts_with("placement") {
+            let parts = line.split(' ').collect::<Vec<_>>();
+            cur_mask.left = parts[1].parse().unwrap();
+            cur_mask.top = parts[2].parse().unwrap();
+            cur_mask.width = parts[3].parse().unwrap();
+            cur_mask.height = parts[4].parse().unwrap();
+        } else {
+            // Each remaining line is a row of hex encoded coverage values
+            for i in (0..line.len()).step_by(2) {
+                cur_mask
+                    .data
+                    .push(u8::from_str_radix(&line[i..i + 2], 16).unwrap());
+            }
+        }
+    }
+    masks
+}
+
 fn parse_points<F>(source: &str) -> Vec<[F; 2]>
 where
     F: FromStr + Copy + Default,
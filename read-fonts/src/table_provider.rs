@@ -72,6 +72,10 @@ pub trait TableProvider<'a> {
         self.expect_table()
     }
 
+    fn vorg(&self) -> Result<tables::vorg::Vorg<'a>, ReadError> {
+        self.expect_table()
+    }
+
     fn mvar(&self) -> Result<tables::mvar::Mvar<'a>, ReadError> {
         self.expect_table()
     }
@@ -118,6 +122,10 @@ pub trait TableProvider<'a> {
         self.expect_table()
     }
 
+    fn base(&self) -> Result<tables::base::Base<'a>, ReadError> {
+        self.expect_table()
+    }
+
     fn gdef(&self) -> Result<tables::gdef::Gdef<'a>, ReadError> {
         self.expect_table()
     }
@@ -161,9 +169,61 @@ pub trait TableProvider<'a> {
         tables::sbix::Sbix::read(data, num_glyphs)
     }
 
+    fn kern(&self) -> Result<tables::kern::Kern<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn kerx(&self) -> Result<tables::kerx::Kerx<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn morx(&self) -> Result<tables::morx::Morx<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn trak(&self) -> Result<tables::trak::Trak<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn ankr(&self) -> Result<tables::ankr::Ankr<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn feat(&self) -> Result<tables::feat::Feat<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn math(&self) -> Result<tables::math::Math<'a>, ReadError> {
+        self.expect_table()
+    }
+
     fn stat(&self) -> Result<tables::stat::Stat<'a>, ReadError> {
         self.expect_table()
     }
+
+    fn svg(&self) -> Result<tables::svg::Svg<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn cvt(&self) -> Result<tables::cvt::Cvt<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn cvar(&self) -> Result<tables::cvar::Cvar<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn fpgm(&self) -> Result<tables::fpgm::Fpgm<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn prep(&self) -> Result<tables::prep::Prep<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn gasp(&self) -> Result<tables::gasp::Gasp<'a>, ReadError> {
+        self.expect_table()
+    }
 }
 
 #[cfg(test)]
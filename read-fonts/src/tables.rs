@@ -1,5 +1,7 @@
 //! The various font tables
 
+pub mod aat;
+pub mod ankr;
 pub mod avar;
 pub mod base;
 pub mod bitmap;
@@ -10,9 +12,14 @@ pub mod cff2;
 pub mod cmap;
 pub mod colr;
 pub mod cpal;
+pub mod cvar;
+pub mod cvt;
 pub mod ebdt;
 pub mod eblc;
+pub mod feat;
+pub mod fpgm;
 pub mod fvar;
+pub mod gasp;
 pub mod gdef;
 pub mod glyf;
 pub mod gpos;
@@ -22,17 +29,25 @@ pub mod head;
 pub mod hhea;
 pub mod hmtx;
 pub mod hvar;
+pub mod kern;
+pub mod kerx;
 pub mod layout;
 pub mod loca;
+pub mod math;
 pub mod maxp;
+pub mod morx;
 pub mod mvar;
 pub mod name;
 pub mod os2;
 pub mod post;
 pub mod postscript;
+pub mod prep;
 pub mod sbix;
 pub mod stat;
+pub mod svg;
+pub mod trak;
 pub mod variations;
 pub mod vhea;
 pub mod vmtx;
+pub mod vorg;
 pub mod vvar;
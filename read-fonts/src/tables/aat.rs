@@ -0,0 +1,480 @@
+//! Common structures shared by the Apple Advanced Typography
+//! ([AAT](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Tables.html))
+//! tables.
+
+include!("../../generated/generated_aat.rs");
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Class for the end of text.
+pub const CLASS_END_OF_TEXT: u16 = 0;
+/// Class for glyphs not covered by the class table.
+pub const CLASS_OUT_OF_BOUNDS: u16 = 1;
+/// Class for glyphs that have been deleted by a previous subtable.
+pub const CLASS_DELETED_GLYPH: u16 = 2;
+/// Class for the end of a line.
+pub const CLASS_END_OF_LINE: u16 = 3;
+
+/// Glyph identifier used to mark deleted glyphs.
+pub const DELETED_GLYPH: u16 = 0xFFFF;
+
+impl<'a> Lookup<'a> {
+    /// Returns the raw bytes of the value associated with the given glyph.
+    ///
+    /// The `value_size` is only used by formats that don't encode the size
+    /// of their values.
+    fn value_data(&self, glyph_id: u16, value_size: usize) -> Option<&'a [u8]> {
+        match self {
+            Self::Format0(lookup) => {
+                let start = glyph_id as usize * value_size;
+                lookup.values_data().get(start..start + value_size)
+            }
+            Self::Format2(lookup) => {
+                let unit_size = lookup.unit_size() as usize;
+                let unit = search_units(
+                    lookup.segments_data(),
+                    unit_size,
+                    lookup.n_units() as usize,
+                    |unit| {
+                        let last = u16::read(unit.get(0..2)?)?;
+                        let first = u16::read(unit.get(2..4)?)?;
+                        Some(cmp_segment(glyph_id, first, last))
+                    },
+                )?;
+                unit.get(4..)
+            }
+            Self::Format4(lookup) => {
+                let segments = lookup.segments();
+                let ix = segments
+                    .binary_search_by(|segment| {
+                        cmp_segment(glyph_id, segment.first_glyph(), segment.last_glyph())
+                    })
+                    .ok()?;
+                let segment = &segments[ix];
+                let start = segment.value_offset() as usize
+                    + (glyph_id - segment.first_glyph()) as usize * value_size;
+                lookup
+                    .offset_data()
+                    .as_bytes()
+                    .get(start..start + value_size)
+            }
+            Self::Format6(lookup) => {
+                let unit_size = lookup.unit_size() as usize;
+                let unit = search_units(
+                    lookup.entries_data(),
+                    unit_size,
+                    lookup.n_units() as usize,
+                    |unit| Some(u16::read(unit.get(0..2)?)?.cmp(&glyph_id)),
+                )?;
+                unit.get(2..)
+            }
+            Self::Format8(lookup) => {
+                let ix = glyph_id.checked_sub(lookup.first_glyph())?;
+                if ix >= lookup.glyph_count() {
+                    return None;
+                }
+                // The value array follows the 6 byte header
+                let start = 6 + ix as usize * 2;
+                lookup.offset_data().as_bytes().get(start..start + 2)
+            }
+            Self::Format10(lookup) => {
+                let unit_size = lookup.unit_size() as usize;
+                let start = glyph_id.checked_sub(lookup.first_glyph())? as usize * unit_size;
+                lookup.values_data().get(start..start + unit_size)
+            }
+        }
+    }
+}
+
+/// Returns the ordering of a segment relative to the given glyph.
+fn cmp_segment(glyph_id: u16, first: u16, last: u16) -> Ordering {
+    if last < glyph_id {
+        Ordering::Less
+    } else if first > glyph_id {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Performs a binary search over an array of units with the given size.
+fn search_units(
+    data: &[u8],
+    unit_size: usize,
+    n_units: usize,
+    mut f: impl FnMut(&[u8]) -> Option<Ordering>,
+) -> Option<&[u8]> {
+    let (mut lo, mut hi) = (0, n_units);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let unit = data.get(mid * unit_size..(mid + 1) * unit_size)?;
+        match f(unit)? {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Some(unit),
+        }
+    }
+    None
+}
+
+/// A lookup table with values of a specific type.
+///
+/// Values stored with fewer bytes than the size of `T` are zero extended.
+pub struct TypedLookup<'a, T> {
+    lookup: Lookup<'a>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Lookup table with 16-bit values.
+pub type LookupU16<'a> = TypedLookup<'a, u16>;
+
+/// Lookup table with 32-bit values.
+pub type LookupU32<'a> = TypedLookup<'a, u32>;
+
+/// Lookup table that maps glyphs to glyphs.
+pub type LookupGlyphId<'a> = TypedLookup<'a, GlyphId>;
+
+impl<'a, T> TypedLookup<'a, T> {
+    /// Returns the underlying untyped lookup table.
+    pub fn lookup(&self) -> &Lookup<'a> {
+        &self.lookup
+    }
+}
+
+impl<'a, T: Scalar> TypedLookup<'a, T> {
+    /// Returns the value associated with the given glyph.
+    pub fn value(&self, glyph_id: GlyphId) -> Option<T> {
+        let data = self.lookup.value_data(glyph_id.to_u16(), T::RAW_BYTE_LEN)?;
+        // Values may be stored with fewer bytes than the requested type
+        // (format 8 and 10 in particular) so pad with leading zeros
+        let padding = T::RAW_BYTE_LEN.checked_sub(data.len())?;
+        if padding == 0 {
+            return T::read(data);
+        }
+        let mut buf = [0u8; 8];
+        let buf = buf.get_mut(..T::RAW_BYTE_LEN)?;
+        buf[padding..].copy_from_slice(data);
+        T::read(buf)
+    }
+}
+
+impl<'a, T> FontRead<'a> for TypedLookup<'a, T> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        Ok(Self {
+            lookup: Lookup::read(data)?,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a, T> SomeTable<'a> for TypedLookup<'a, T> {
+    fn type_name(&self) -> &str {
+        self.lookup.type_name()
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        self.lookup.get_field(idx)
+    }
+}
+
+/// Data stored in an entry of an extended state table, following the
+/// new state and flags.
+pub trait EntryPayload: Sized {
+    /// Size of the payload in bytes.
+    const SIZE: usize;
+
+    /// Reads the payload at the given offset.
+    fn read_at(data: FontData, offset: usize) -> Result<Self, ReadError>;
+}
+
+impl EntryPayload for () {
+    const SIZE: usize = 0;
+
+    fn read_at(_data: FontData, _offset: usize) -> Result<Self, ReadError> {
+        Ok(())
+    }
+}
+
+impl EntryPayload for u16 {
+    const SIZE: usize = 2;
+
+    fn read_at(data: FontData, offset: usize) -> Result<Self, ReadError> {
+        data.read_at(offset)
+    }
+}
+
+/// Entry in an extended state table.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct StateEntry<T = ()> {
+    /// Index of the next state.
+    pub new_state: u16,
+    /// Action flags. The meaning of these depends on the subtable type.
+    pub flags: u16,
+    /// Additional data for the entry.
+    pub payload: T,
+}
+
+impl<T> StateEntry<T> {
+    /// Flag that is shared by all state machine subtables.
+    pub const DONT_ADVANCE: u16 = 0x4000;
+
+    /// Returns true if the glyph pointer should remain on the current
+    /// glyph.
+    pub fn dont_advance(&self) -> bool {
+        self.flags & Self::DONT_ADVANCE != 0
+    }
+}
+
+/// Extended state table, used to drive the state machines in the `morx`
+/// and `kerx` tables.
+///
+/// The type parameter is the additional data stored in each entry.
+pub struct ExtendedStateTable<'a, T = ()> {
+    header: StxHeader<'a>,
+    n_classes: usize,
+    class_table: LookupU16<'a>,
+    state_array: &'a [BigEndian<u16>],
+    entry_table: FontData<'a>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> ExtendedStateTable<'a, T> {
+    /// Size of the header of an extended state table.
+    pub const HEADER_LEN: usize = 16;
+
+    /// Returns the header of the state table.
+    pub fn header(&self) -> &StxHeader<'a> {
+        &self.header
+    }
+
+    /// Returns the number of classes, which is the number of entries in a
+    /// single state of the state array.
+    pub fn n_classes(&self) -> usize {
+        self.n_classes
+    }
+
+    /// Returns the lookup table that maps glyphs to classes.
+    pub fn class_table(&self) -> &LookupU16<'a> {
+        &self.class_table
+    }
+
+    /// Returns the state array.
+    pub fn state_array(&self) -> &'a [BigEndian<u16>] {
+        self.state_array
+    }
+
+    /// Returns the class of the given glyph.
+    ///
+    /// Glyphs that are not covered by the class table are assigned
+    /// [`CLASS_OUT_OF_BOUNDS`] and the deleted glyph is assigned
+    /// [`CLASS_DELETED_GLYPH`].
+    pub fn class(&self, glyph_id: GlyphId) -> u16 {
+        if glyph_id.to_u16() == DELETED_GLYPH {
+            return CLASS_DELETED_GLYPH;
+        }
+        self.class_table
+            .value(glyph_id)
+            .unwrap_or(CLASS_OUT_OF_BOUNDS)
+    }
+}
+
+impl<'a, T: EntryPayload> ExtendedStateTable<'a, T> {
+    /// Returns the entry for the given state and class.
+    pub fn entry(&self, state: u16, class: u16) -> Result<StateEntry<T>, ReadError> {
+        let class = class as usize;
+        if class >= self.n_classes {
+            return Err(ReadError::OutOfBounds);
+        }
+        let entry_ix = self
+            .state_array
+            .get(state as usize * self.n_classes + class)
+            .ok_or(ReadError::OutOfBounds)?
+            .get() as usize;
+        let offset = entry_ix * (4 + T::SIZE);
+        Ok(StateEntry {
+            new_state: self.entry_table.read_at(offset)?,
+            flags: self.entry_table.read_at(offset + 2)?,
+            payload: T::read_at(self.entry_table, offset + 4)?,
+        })
+    }
+}
+
+impl<'a, T> FontRead<'a> for ExtendedStateTable<'a, T> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let header = StxHeader::read(data)?;
+        Ok(Self {
+            n_classes: header.n_classes() as usize,
+            class_table: header.class_table()?,
+            state_array: header.state_array()?.data(),
+            entry_table: FontData::new(header.entry_table()?.data()),
+            header,
+            _marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a, T> SomeTable<'a> for ExtendedStateTable<'a, T> {
+    fn type_name(&self) -> &str {
+        self.header.type_name()
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        self.header.get_field(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    fn lookup_u16(buf: &BeBuffer) -> LookupU16<'_> {
+        LookupU16::read(buf.font_data()).unwrap()
+    }
+
+    #[test]
+    fn lookup_format0() {
+        let buf = BeBuffer::new().push(0u16).extend([10u16, 20, 30]);
+        let lookup = lookup_u16(&buf);
+        assert_eq!(lookup.value(gid(0)), Some(10));
+        assert_eq!(lookup.value(gid(2)), Some(30));
+        assert_eq!(lookup.value(gid(3)), None);
+    }
+
+    #[test]
+    fn lookup_format2() {
+        let buf = BeBuffer::new()
+            .extend([2u16, 6, 3, 12, 1, 6])
+            // segments of (last, first, value) including the terminator
+            .extend([5u16, 2, 100])
+            .extend([20u16, 10, 200])
+            .extend([0xFFFFu16, 0xFFFF, 0]);
+        let lookup = lookup_u16(&buf);
+        assert_eq!(lookup.value(gid(1)), None);
+        assert_eq!(lookup.value(gid(2)), Some(100));
+        assert_eq!(lookup.value(gid(5)), Some(100));
+        assert_eq!(lookup.value(gid(7)), None);
+        assert_eq!(lookup.value(gid(15)), Some(200));
+        assert_eq!(lookup.value(gid(21)), None);
+    }
+
+    #[test]
+    fn lookup_format4() {
+        let buf = BeBuffer::new()
+            .extend([4u16, 6, 2, 12, 1, 0])
+            // segments of (last, first, offset)
+            .extend([3u16, 1, 24])
+            .extend([11u16, 10, 30])
+            // values
+            .extend([1u16, 2, 3])
+            .extend([4u16, 5]);
+        let lookup = lookup_u16(&buf);
+        assert_eq!(lookup.value(gid(0)), None);
+        assert_eq!(lookup.value(gid(1)), Some(1));
+        assert_eq!(lookup.value(gid(3)), Some(3));
+        assert_eq!(lookup.value(gid(10)), Some(4));
+        assert_eq!(lookup.value(gid(11)), Some(5));
+        assert_eq!(lookup.value(gid(12)), None);
+    }
+
+    #[test]
+    fn lookup_format6() {
+        let buf = BeBuffer::new()
+            .extend([6u16, 6, 3, 12, 1, 6])
+            // entries of (glyph, value) with 32-bit values
+            .extend([2u16])
+            .push(0x10000u32)
+            .extend([7u16])
+            .push(0x20000u32)
+            .extend([9u16])
+            .push(0x30000u32);
+        let lookup = LookupU32::read(buf.font_data()).unwrap();
+        assert_eq!(lookup.value(gid(2)), Some(0x10000));
+        assert_eq!(lookup.value(gid(7)), Some(0x20000));
+        assert_eq!(lookup.value(gid(9)), Some(0x30000));
+        assert_eq!(lookup.value(gid(8)), None);
+    }
+
+    #[test]
+    fn lookup_format8() {
+        let buf = BeBuffer::new().extend([8u16, 5, 3]).extend([40u16, 41, 42]);
+        let lookup = LookupGlyphId::read(buf.font_data()).unwrap();
+        assert_eq!(lookup.value(gid(4)), None);
+        assert_eq!(lookup.value(gid(5)), Some(gid(40)));
+        assert_eq!(lookup.value(gid(7)), Some(gid(42)));
+        assert_eq!(lookup.value(gid(8)), None);
+        // zero extended to a larger type
+        let lookup = LookupU32::read(buf.font_data()).unwrap();
+        assert_eq!(lookup.value(gid(6)), Some(41));
+    }
+
+    #[test]
+    fn lookup_format10() {
+        let buf = BeBuffer::new()
+            .extend([10u16, 1, 3, 4])
+            .extend([1u8, 2, 3, 4]);
+        let lookup = lookup_u16(&buf);
+        assert_eq!(lookup.value(gid(2)), None);
+        assert_eq!(lookup.value(gid(3)), Some(1));
+        assert_eq!(lookup.value(gid(6)), Some(4));
+        assert_eq!(lookup.value(gid(7)), None);
+        // values that are too large for the requested type are rejected
+        let buf = BeBuffer::new().extend([10u16, 4, 0, 1]).push(1u32);
+        assert_eq!(lookup_u16(&buf).value(gid(0)), None);
+    }
+
+    #[test]
+    fn unknown_lookup_format() {
+        let buf = BeBuffer::new().extend([3u16, 0, 0]);
+        assert!(matches!(
+            LookupU16::read(buf.font_data()),
+            Err(ReadError::InvalidFormat(3))
+        ));
+    }
+
+    #[test]
+    fn extended_state_table() {
+        let buf = BeBuffer::new()
+            // header: 5 classes and offsets to class table, state array and
+            // entry table
+            .extend([5u32, 16, 28, 48])
+            // class lookup (format 8): glyphs 10..=12 map to class 4
+            .extend([8u16, 10, 3, 4, 4, 4])
+            // state array: two states with five classes each
+            .extend([0u16, 0, 0, 0, 1])
+            .extend([0u16, 0, 0, 0, 2])
+            // entries: (new state, flags, payload)
+            .extend([0u16, 0, 0xFFFF])
+            .extend([1u16, 0x4000, 3])
+            .extend([0u16, 0x8000, 7]);
+        let table = ExtendedStateTable::<u16>::read(buf.font_data()).unwrap();
+        assert_eq!(table.n_classes(), 5);
+        assert_eq!(table.class(gid(9)), CLASS_OUT_OF_BOUNDS);
+        assert_eq!(table.class(gid(11)), 4);
+        assert_eq!(table.class(gid(DELETED_GLYPH)), CLASS_DELETED_GLYPH);
+        let entry = table.entry(0, 4).unwrap();
+        assert_eq!(
+            entry,
+            StateEntry {
+                new_state: 1,
+                flags: 0x4000,
+                payload: 3
+            }
+        );
+        assert!(entry.dont_advance());
+        let entry = table.entry(entry.new_state, 4).unwrap();
+        assert_eq!(entry.new_state, 0);
+        assert_eq!(entry.payload, 7);
+        assert!(!entry.dont_advance());
+        assert_eq!(table.entry(0, CLASS_END_OF_TEXT).unwrap().payload, 0xFFFF);
+        assert!(table.entry(0, 5).is_err());
+        assert!(table.entry(100, 0).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+//! The [anchor point](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6ankr.html) table.
+
+use super::aat::LookupU16;
+
+include!("../../generated/generated_ankr.rs");
+
+impl<'a> Ankr<'a> {
+    /// Returns the anchor points for the given glyph.
+    ///
+    /// Glyphs that are not covered by the lookup table have no anchor
+    /// points.
+    pub fn anchor_points(&self, glyph_id: GlyphId) -> Result<&'a [AnchorPoint], ReadError> {
+        let Some(offset) = self.lookup_table()?.value(glyph_id) else {
+            return Ok(&[]);
+        };
+        let offset = self.glyph_data_table_offset() as usize + offset as usize;
+        self.offset_data()
+            .split_off(offset)
+            .ok_or(ReadError::OutOfBounds)
+            .and_then(GlyphDataEntry::read)
+            .map(|entry| entry.anchor_points())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn anchor_points() {
+        let buf = BeBuffer::new()
+            .extend([0u16, 0])
+            .extend([12u32, 32])
+            // lookup (format 6): glyph 3 at offset 0, glyph 5 at offset 12
+            .extend([6u16, 4, 2, 8, 1, 0])
+            .extend([3u16, 0, 5, 12])
+            // glyph data
+            .push(2u32)
+            .extend([10i16, 20, -5, 600])
+            .push(1u32)
+            .extend([7i16, 8]);
+        let ankr = Ankr::read(buf.font_data()).unwrap();
+        let points = |gid| {
+            ankr.anchor_points(GlyphId::new(gid))
+                .unwrap()
+                .iter()
+                .map(|point| (point.x(), point.y()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(points(3), [(10, 20), (-5, 600)]);
+        assert_eq!(points(5), [(7, 8)]);
+        assert!(points(4).is_empty());
+    }
+}
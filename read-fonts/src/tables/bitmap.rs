@@ -319,3 +319,28 @@ impl SbitLineMetrics {
         FieldType::Record(self.traverse(data))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::BeBuffer, FontRead};
+
+    #[test]
+    fn trailing_partial_offset_ignored() {
+        let data = BeBuffer::new()
+            .extend([1u16, 1]) // index format, image format
+            .push(0u32) // image data offset
+            .extend([0u32, 10])
+            .extend([0xABu8, 0xCD]);
+        let subtable = IndexSubtable1::read(data.font_data()).unwrap();
+        assert_eq!(subtable.sbit_offsets().len(), 2);
+
+        let data = BeBuffer::new()
+            .extend([3u16, 1]) // index format, image format
+            .push(0u32) // image data offset
+            .extend([0u16, 10])
+            .push(0xABu8);
+        let subtable = IndexSubtable3::read(data.font_data()).unwrap();
+        assert_eq!(subtable.sbit_offsets().len(), 2);
+    }
+}
@@ -2,9 +2,10 @@
 
 include!("../../generated/generated_cff.rs");
 
-use super::postscript::{Index1, Latin1String, StringId};
+use super::postscript::{dict, Charset, Encoding, Error, Index1, Latin1String, StringId};
 
 /// The [Compact Font Format](https://learn.microsoft.com/en-us/typography/opentype/spec/cff) table.
+#[derive(Clone)]
 pub struct Cff<'a> {
     header: CffHeader<'a>,
     names: Index1<'a>,
@@ -78,6 +79,67 @@ impl<'a> Cff<'a> {
     pub fn global_subrs(&self) -> Index1<'a> {
         self.global_subrs.clone()
     }
+
+    /// Returns the charset for the font in the font set at the given index.
+    ///
+    /// For CID-keyed fonts, the charset maps glyph identifiers to CIDs
+    /// rather than string identifiers.
+    ///
+    /// See "Charsets" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=21>
+    pub fn charset(&self, top_dict_index: usize) -> Result<Charset<'a>, Error> {
+        let offsets = self.top_dict_offsets(top_dict_index)?;
+        let num_glyphs = offsets.num_glyphs.ok_or(Error::MissingCharstrings)?;
+        if offsets.is_cid {
+            Charset::new_cid(self.offset_data(), offsets.charset, num_glyphs)
+        } else {
+            Charset::new(self.offset_data(), offsets.charset, num_glyphs)
+        }
+    }
+
+    /// Returns the encoding for the font in the font set at the given index.
+    ///
+    /// Returns `None` for CID-keyed fonts which do not have an encoding.
+    ///
+    /// See "Encodings" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=18>
+    pub fn encoding(&self, top_dict_index: usize) -> Result<Option<Encoding<'a>>, Error> {
+        let offsets = self.top_dict_offsets(top_dict_index)?;
+        if offsets.is_cid {
+            return Ok(None);
+        }
+        Encoding::new(self.offset_data(), offsets.encoding).map(Some)
+    }
+
+    fn top_dict_offsets(&self, top_dict_index: usize) -> Result<TopDictOffsets, Error> {
+        let top_dict_data = self.top_dicts.get(top_dict_index)?;
+        // Both charset and encoding default to offset 0 which selects the
+        // ISOAdobe charset and Standard encoding, respectively.
+        let mut offsets = TopDictOffsets::default();
+        for entry in dict::entries(top_dict_data, None) {
+            match entry? {
+                dict::Entry::Charset(offset) => offsets.charset = offset,
+                dict::Entry::Encoding(offset) => offsets.encoding = offset,
+                dict::Entry::Ros { .. } => offsets.is_cid = true,
+                dict::Entry::CharstringsOffset(offset) => {
+                    let data = self
+                        .offset_data()
+                        .split_off(offset)
+                        .ok_or(ReadError::OutOfBounds)?;
+                    offsets.num_glyphs = Some(Index1::read(data)?.count() as u32);
+                }
+                _ => {}
+            }
+        }
+        Ok(offsets)
+    }
+}
+
+/// Values from a top DICT required to load the charset and encoding.
+#[derive(Default)]
+struct TopDictOffsets {
+    charset: usize,
+    encoding: usize,
+    num_glyphs: Option<u32>,
+    is_cid: bool,
 }
 
 impl TopLevelTable for Cff<'_> {
@@ -114,7 +176,7 @@ impl<'a> FontRead<'a> for Cff<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{tables::postscript::StringId, FontRef, TableProvider};
+    use crate::{tables::postscript::StringId, types::GlyphId, FontRef, TableProvider};
 
     #[test]
     fn read_noto_serif_display_cff() {
@@ -151,4 +213,24 @@ mod tests {
             "Noto Serif Display"
         );
     }
+
+    #[test]
+    fn charset_and_encoding() {
+        let font = FontRef::new(font_test_data::NOTO_SERIF_DISPLAY_TRIMMED).unwrap();
+        let cff = font.cff().unwrap();
+        let charset = cff.charset(0).unwrap();
+        assert!(!charset.is_cid());
+        let names = charset
+            .iter()
+            .map(|(_, sid)| cff.string(sid).unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [".notdef", "i", "j", "k", "l"]);
+        // Reverse lookup for "k"
+        assert_eq!(charset.glyph_id(StringId::new(76)), Some(GlyphId::new(3)));
+        let encoding = cff.encoding(0).unwrap().unwrap();
+        let mapped = encoding.iter(&charset).collect::<Vec<_>>();
+        let expected = [(b'i', 1), (b'j', 2), (b'k', 3), (b'l', 4)]
+            .map(|(code, gid)| (code, GlyphId::new(gid)));
+        assert_eq!(mapped, expected);
+    }
 }
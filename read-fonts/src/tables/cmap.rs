@@ -14,28 +14,224 @@ pub enum MapVariant {
 }
 
 impl<'a> Cmap<'a> {
-    /// Map a codepoint to a nominal glyph identifier
+    /// Map a Unicode codepoint to a nominal glyph identifier
     ///
-    /// This uses the first available subtable that provides a valid mapping.
+    /// This uses the first available Unicode subtable that provides a valid
+    /// mapping. Subtables with other encodings are ignored since their
+    /// character codes are not Unicode codepoints; use
+    /// [`CmapSubtable::map_codepoint`] to map raw character codes through a
+    /// specific subtable.
     ///
-    /// # Note:
+    /// Format 13 subtables map ranges of codepoints to a single glyph and
+    /// are only consulted if no other subtable provides a mapping.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let codepoint = codepoint.into();
+        let subtables = || {
+            self.encoding_records()
+                .iter()
+                .filter(|record| record.is_unicode())
+                .filter_map(|record| record.subtable(self.offset_data()).ok())
+        };
+        subtables()
+            .filter(|subtable| !matches!(subtable, CmapSubtable::Format13(_)))
+            .find_map(|subtable| subtable.map_codepoint(codepoint))
+            .or_else(|| {
+                subtables()
+                    .filter(|subtable| matches!(subtable, CmapSubtable::Format13(_)))
+                    .find_map(|subtable| subtable.map_codepoint(codepoint))
+            })
+    }
+}
+
+impl EncodingRecord {
+    /// Returns true if the subtable for this record maps Unicode codepoints.
+    ///
+    /// This includes all records on the Unicode platform along with the
+    /// Windows BMP (3, 1), full repertoire (3, 10) and symbol (3, 0)
+    /// encodings.
+    pub fn is_unicode(&self) -> bool {
+        match self.platform_id() {
+            PlatformId::Unicode => true,
+            PlatformId::Windows => matches!(self.encoding_id(), 0 | 1 | 10),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> CmapSubtable<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    ///
+    /// The codepoint is interpreted as a raw character code in the encoding
+    /// of the subtable, which is not necessarily Unicode.
+    ///
+    /// Returns `None` for format 14 subtables which only map variation
+    /// sequences. Use [`Cmap14::map_variant`] for those.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let codepoint = codepoint.into();
+        match self {
+            Self::Format0(format0) => format0.map_codepoint(codepoint),
+            Self::Format2(format2) => format2.map_codepoint(codepoint),
+            Self::Format4(format4) => format4.map_codepoint(codepoint),
+            Self::Format6(format6) => format6.map_codepoint(codepoint),
+            Self::Format8(format8) => format8.map_codepoint(codepoint),
+            Self::Format10(format10) => format10.map_codepoint(codepoint),
+            Self::Format12(format12) => format12.map_codepoint(codepoint),
+            Self::Format13(format13) => format13.map_codepoint(codepoint),
+            Self::Format14(_) => None,
+        }
+    }
+}
+
+impl<'a> Cmap0<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let codepoint = codepoint.into();
+        let gid = *self.glyph_id_array().get(codepoint as usize)?;
+        (gid != 0).then_some(GlyphId::new(gid as u16))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap0Iter<'a> {
+        Cmap0Iter {
+            glyph_ids: self.glyph_id_array().iter().enumerate(),
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap0Iter<'a> {
+    glyph_ids: std::iter::Enumerate<std::slice::Iter<'a, u8>>,
+}
+
+impl<'a> Iterator for Cmap0Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (codepoint, &gid) = self.glyph_ids.next()?;
+            if gid != 0 {
+                return Some((codepoint as u32, GlyphId::new(gid as u16)));
+            }
+        }
+    }
+}
+
+impl<'a> Cmap2<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
     ///
-    /// Mapping logic is currently only implemented for the most common subtable
-    /// formats.
+    /// The codepoint is interpreted as a character code in the encoding of
+    /// the subtable where two byte codes are represented as
+    /// `(high_byte << 8) | low_byte`.
     pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
         let codepoint = codepoint.into();
-        for record in self.encoding_records() {
-            if let Ok(subtable) = record.subtable(self.offset_data()) {
-                if let Some(gid) = match subtable {
-                    CmapSubtable::Format4(format4) => format4.map_codepoint(codepoint),
-                    CmapSubtable::Format12(format12) => format12.map_codepoint(codepoint),
-                    _ => None,
-                } {
-                    return Some(gid);
+        if codepoint > 0xFFFF {
+            return None;
+        }
+        let high_byte = codepoint >> 8;
+        let low_byte = codepoint & 0xFF;
+        let sub_header_ix = if high_byte == 0 {
+            // Single byte codes always use the first subheader but are
+            // not valid if the byte is the first of a two byte code
+            if self.sub_header_index(low_byte)? != 0 {
+                return None;
+            }
+            0
+        } else {
+            // Subheader 0 is reserved for single byte codes so a high byte
+            // that maps to it is not the first of a two byte code
+            match self.sub_header_index(high_byte)? {
+                0 => return None,
+                ix => ix,
+            }
+        };
+        let (sub_header, sub_header_offset) = self.sub_header(sub_header_ix)?;
+        let entry = low_byte.checked_sub(sub_header.first_code() as u32)?;
+        if entry >= sub_header.entry_count() as u32 {
+            return None;
+        }
+        // The range offset is relative to the position of the
+        // id_range_offset field itself
+        let id_range_offset_pos = sub_header_offset + 6;
+        let offset =
+            id_range_offset_pos + sub_header.id_range_offset() as usize + entry as usize * 2;
+        let gid: u16 = self.offset_data().read_at(offset).ok()?;
+        (gid != 0).then(|| GlyphId::new((gid as i32 + sub_header.id_delta() as i32) as u16))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap2Iter<'a> {
+        Cmap2Iter {
+            subtable: self.clone(),
+            cur_range: 0..0,
+            next_high_byte: 0,
+        }
+    }
+
+    /// Returns the subheader index for the given high byte.
+    fn sub_header_index(&self, high_byte: u32) -> Option<usize> {
+        // Keys are stored as the subheader index multiplied by 8
+        Some(self.sub_header_keys().get(high_byte as usize)?.get() as usize / 8)
+    }
+
+    /// Returns the subheader at the given index along with its offset
+    /// from the start of the subtable.
+    fn sub_header(&self, index: usize) -> Option<(&'a SubHeader, usize)> {
+        // Subheaders immediately follow the key array
+        let offset = self.shape.sub_header_keys_byte_range().end + index * SubHeader::RAW_BYTE_LEN;
+        let sub_header = self.offset_data().read_ref_at(offset).ok()?;
+        Some((sub_header, offset))
+    }
+
+    /// Returns the range of character codes that might be mapped for the
+    /// given high byte.
+    fn code_range(&self, high_byte: u32) -> Option<Range<u32>> {
+        match self.sub_header_index(high_byte)? {
+            0 => Some(high_byte..high_byte + 1),
+            ix => {
+                let (sub_header, _) = self.sub_header(ix)?;
+                let start = (high_byte << 8) | sub_header.first_code() as u32;
+                let end = (start + sub_header.entry_count() as u32).min((high_byte + 1) << 8);
+                Some(start..end)
+            }
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap2Iter<'a> {
+    subtable: Cmap2<'a>,
+    cur_range: Range<u32>,
+    next_high_byte: u32,
+}
+
+impl<'a> Iterator for Cmap2Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(codepoint) = self.cur_range.next() {
+                match self.subtable.map_codepoint(codepoint) {
+                    Some(glyph_id) if glyph_id != GlyphId::NOTDEF => {
+                        return Some((codepoint, glyph_id))
+                    }
+                    _ => continue,
                 }
             }
+            if self.next_high_byte > 0xFF {
+                return None;
+            }
+            self.cur_range = self
+                .subtable
+                .code_range(self.next_high_byte)
+                .unwrap_or_default();
+            self.next_high_byte += 1;
         }
-        None
     }
 }
 
@@ -148,6 +344,168 @@ impl<'a> Iterator for Cmap4Iter<'a> {
     }
 }
 
+impl<'a> Cmap6<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let index = codepoint.into().checked_sub(self.first_code() as u32)?;
+        let gid = self.glyph_id_array().get(index as usize)?.get();
+        (gid != 0).then_some(GlyphId::new(gid))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap6Iter<'a> {
+        Cmap6Iter {
+            first_code: self.first_code() as u32,
+            glyph_ids: self.glyph_id_array().iter().enumerate(),
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap6Iter<'a> {
+    first_code: u32,
+    glyph_ids: std::iter::Enumerate<std::slice::Iter<'a, BigEndian<u16>>>,
+}
+
+impl<'a> Iterator for Cmap6Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_trimmed_mapping(self.first_code, &mut self.glyph_ids)
+    }
+}
+
+impl<'a> Cmap8<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    ///
+    /// The codepoint is expected to be the full 32-bit character code so
+    /// the `is32` array is not required for lookup.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let codepoint = codepoint.into();
+        let groups = self.groups();
+        let ix = groups
+            .binary_search_by(|group| {
+                if codepoint < group.start_char_code() {
+                    core::cmp::Ordering::Greater
+                } else if codepoint > group.end_char_code() {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .ok()?;
+        let group = groups.get(ix)?;
+        Some(sequential_glyph_id(
+            codepoint,
+            group.start_char_code(),
+            group.start_glyph_id(),
+        ))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap8Iter<'a> {
+        Cmap8Iter {
+            groups: self.groups().iter(),
+            cur_range: 0..0,
+            cur_start_code: 0,
+            cur_start_glyph_id: 0,
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap8Iter<'a> {
+    groups: std::slice::Iter<'a, SequentialMapGroup>,
+    cur_range: Range<u32>,
+    cur_start_code: u32,
+    cur_start_glyph_id: u32,
+}
+
+impl<'a> Iterator for Cmap8Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(codepoint) = self.cur_range.next() {
+                let glyph_id =
+                    sequential_glyph_id(codepoint, self.cur_start_code, self.cur_start_glyph_id);
+                // The table might explicitly map some codepoints to 0. Avoid
+                // returning those here.
+                if glyph_id == GlyphId::NOTDEF {
+                    continue;
+                }
+                return Some((codepoint, glyph_id));
+            }
+            let group = self.groups.next()?;
+            self.cur_start_code = group.start_char_code();
+            self.cur_start_glyph_id = group.start_glyph_id();
+            // Use saturating add here because the group range is inclusive
+            self.cur_range = group.start_char_code()..group.end_char_code().saturating_add(1);
+        }
+    }
+}
+
+impl<'a> Cmap10<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let index = codepoint.into().checked_sub(self.start_char_code())?;
+        let gid = self.glyph_id_array().get(index as usize)?.get();
+        (gid != 0).then_some(GlyphId::new(gid))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap10Iter<'a> {
+        Cmap10Iter {
+            start_char_code: self.start_char_code(),
+            glyph_ids: self.glyph_id_array().iter().enumerate(),
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap10Iter<'a> {
+    start_char_code: u32,
+    glyph_ids: std::iter::Enumerate<std::slice::Iter<'a, BigEndian<u16>>>,
+}
+
+impl<'a> Iterator for Cmap10Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_trimmed_mapping(self.start_char_code, &mut self.glyph_ids)
+    }
+}
+
+/// Returns the next non-zero mapping from a trimmed array subtable
+/// (formats 6 and 10).
+fn next_trimmed_mapping(
+    first_code: u32,
+    glyph_ids: &mut std::iter::Enumerate<std::slice::Iter<BigEndian<u16>>>,
+) -> Option<(u32, GlyphId)> {
+    loop {
+        let (index, gid) = glyph_ids.next()?;
+        let gid = gid.get();
+        if gid != 0 {
+            return Some((first_code.checked_add(index as u32)?, GlyphId::new(gid)));
+        }
+    }
+}
+
+/// Computes the glyph identifier for a codepoint in a sequential map
+/// group (formats 8 and 12).
+fn sequential_glyph_id(codepoint: u32, start_char_code: u32, start_glyph_id: u32) -> GlyphId {
+    GlyphId::new(start_glyph_id.wrapping_add(codepoint.wrapping_sub(start_char_code)) as u16)
+}
+
 impl<'a> Cmap12<'a> {
     /// Maps a codepoint to a nominal glyph identifier.
     pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
@@ -188,7 +546,7 @@ impl<'a> Cmap12<'a> {
         start_char_code: u32,
         start_glyph_id: u32,
     ) -> GlyphId {
-        GlyphId::new(start_glyph_id.wrapping_add(codepoint.wrapping_sub(start_char_code)) as u16)
+        sequential_glyph_id(codepoint, start_char_code, start_glyph_id)
     }
 
     /// Returns the codepoint range and start glyph id for the group
@@ -255,6 +613,68 @@ impl<'a> Iterator for Cmap12Iter<'a> {
     }
 }
 
+impl<'a> Cmap13<'a> {
+    /// Maps a codepoint to a nominal glyph identifier.
+    ///
+    /// Every codepoint in a group maps to the same glyph so this format is
+    /// typically used for "last resort" fonts.
+    pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
+        let codepoint = codepoint.into();
+        let groups = self.groups();
+        let ix = groups
+            .binary_search_by(|group| {
+                if codepoint < group.start_char_code() {
+                    core::cmp::Ordering::Greater
+                } else if codepoint > group.end_char_code() {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .ok()?;
+        Some(GlyphId::new(groups.get(ix)?.glyph_id() as u16))
+    }
+
+    /// Returns an iterator over all (codepoint, glyph identifier) pairs
+    /// in the subtable.
+    pub fn iter(&self) -> Cmap13Iter<'a> {
+        Cmap13Iter {
+            groups: self.groups().iter(),
+            cur_range: 0..0,
+            cur_glyph_id: GlyphId::NOTDEF,
+        }
+    }
+}
+
+/// Iterator over all (codepoint, glyph identifier) pairs in
+/// the subtable.
+#[derive(Clone)]
+pub struct Cmap13Iter<'a> {
+    groups: std::slice::Iter<'a, ConstantMapGroup>,
+    cur_range: Range<u32>,
+    cur_glyph_id: GlyphId,
+}
+
+impl<'a> Iterator for Cmap13Iter<'a> {
+    type Item = (u32, GlyphId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(codepoint) = self.cur_range.next() {
+                return Some((codepoint, self.cur_glyph_id));
+            }
+            let group = self.groups.next()?;
+            self.cur_glyph_id = GlyphId::new(group.glyph_id() as u16);
+            // Skip groups that map to notdef
+            self.cur_range = if self.cur_glyph_id == GlyphId::NOTDEF {
+                0..0
+            } else {
+                group.start_char_code()..group.end_char_code().saturating_add(1)
+            };
+        }
+    }
+}
+
 impl<'a> Cmap14<'a> {
     /// Maps a codepoint and variation selector to a nominal glyph identifier.
     pub fn map_variant(
@@ -458,7 +878,7 @@ impl<'a> Iterator for NonDefaultUvsIter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{FontRef, GlyphId, TableProvider};
+    use crate::{test_helpers::BeBuffer, FontRef, GlyphId, TableProvider};
 
     #[test]
     fn map_codepoints() {
@@ -476,6 +896,38 @@ mod tests {
         assert_eq!(cmap.map_codepoint('B'), None);
     }
 
+    #[test]
+    fn map_codepoint_ignores_non_unicode_subtables() {
+        // (1, 0) format 0 maps 0xC4 to glyph 9 and 0xC5 to glyph 10
+        let mut glyph_ids = [0u8; 256];
+        glyph_ids[0xC4] = 9;
+        glyph_ids[0xC5] = 10;
+        // (3, 1) format 4 maps U+00C4 to glyph 5
+        let data = BeBuffer::new()
+            .extend([0u16, 2]) // version, num tables
+            .extend([1u16, 0]) // platform, encoding
+            .push(20u32) // offset
+            .extend([3u16, 1]) // platform, encoding
+            .push(282u32) // offset
+            .extend([0u16, 262, 0]) // format, length, language
+            .extend(glyph_ids)
+            .extend([4u16, 32, 0, 4, 4, 1, 0]) // format, length, language, seg_count_x2, search params
+            .extend([0xC4u16, 0xFFFF]) // end codes
+            .push(0u16) // reserved pad
+            .extend([0xC4u16, 0xFFFF]) // start codes
+            .extend([5i16 - 0xC4, 1]) // id deltas
+            .extend([0u16, 0]); // id range offsets
+        let cmap = Cmap::read(data.font_data()).unwrap();
+        let records = cmap.encoding_records();
+        assert!(!records[0].is_unicode());
+        assert!(records[1].is_unicode());
+        let mac_roman = records[0].subtable(cmap.offset_data()).unwrap();
+        assert_eq!(mac_roman.map_codepoint(0xC4_u32), Some(GlyphId::new(9)));
+        assert_eq!(cmap.map_codepoint('\u{c4}'), Some(GlyphId::new(5)));
+        // the Macintosh subtable is never used for Unicode lookups
+        assert_eq!(cmap.map_codepoint('\u{c5}'), None);
+    }
+
     #[test]
     fn map_variants() {
         use super::MapVariant::*;
@@ -540,6 +992,185 @@ mod tests {
         assert_eq!(count, 7);
     }
 
+    #[test]
+    fn trailing_partial_glyph_id_ignored() {
+        // a single 0xFFFF segment followed by one glyph id and a stray byte
+        let data = BeBuffer::new()
+            .extend([4u16, 0, 0, 2, 2, 0, 0]) // format, length, language, seg_count_x2, search params
+            .push(0xFFFFu16) // end code
+            .push(0u16) // reserved pad
+            .push(0xFFFFu16) // start code
+            .push(1i16) // id delta
+            .push(0u16) // id range offset
+            .push(7u16) // glyph id array
+            .push(0xABu8);
+        let cmap4 = Cmap4::read(data.font_data()).unwrap();
+        assert_eq!(cmap4.glyph_id_array().len(), 1);
+
+        let data = BeBuffer::new()
+            .extend([10u16, 0]) // format, reserved
+            .extend([0u32, 0, 0x20, 2]) // length, language, start char, num chars
+            .extend([3u16, 4]) // glyph id array
+            .push(0xABu8);
+        let cmap10 = Cmap10::read(data.font_data()).unwrap();
+        assert_eq!(cmap10.glyph_id_array().len(), 2);
+    }
+
+    #[test]
+    fn cmap0_map_and_iter() {
+        let mut glyph_ids = [0u8; 256];
+        glyph_ids[b'A' as usize] = 5;
+        glyph_ids[b'B' as usize] = 6;
+        let buf = BeBuffer::new().extend([0u16, 262, 0]).extend(glyph_ids);
+        let cmap0 = Cmap0::read(buf.font_data()).unwrap();
+        assert_eq!(cmap0.map_codepoint('A'), Some(GlyphId::new(5)));
+        assert_eq!(cmap0.map_codepoint('B'), Some(GlyphId::new(6)));
+        assert_eq!(cmap0.map_codepoint('C'), None);
+        assert_eq!(cmap0.map_codepoint(0x100_u32), None);
+        let pairs = cmap0.iter().collect::<Vec<_>>();
+        assert_eq!(pairs, [(0x41, GlyphId::new(5)), (0x42, GlyphId::new(6))]);
+    }
+
+    #[test]
+    fn cmap2_map_and_iter() {
+        let mut keys = [0u16; 256];
+        // 0x81 is the first byte of two byte codes that use subheader 1
+        keys[0x81] = 8;
+        let buf = BeBuffer::new()
+            .extend([2u16, 544, 0])
+            .extend(keys)
+            // subheader 0: single byte codes 0x20..=0x22
+            .extend([0x20u16, 3, 0, 10])
+            // subheader 1: low bytes 0x40..=0x41 with delta 10
+            .extend([0x40u16, 2, 10, 8])
+            // glyph arrays for subheaders 0 and 1
+            .extend([1u16, 2, 0])
+            .extend([3u16, 4]);
+        let cmap2 = Cmap2::read(buf.font_data()).unwrap();
+        assert_eq!(cmap2.map_codepoint(0x20_u32), Some(GlyphId::new(1)));
+        assert_eq!(cmap2.map_codepoint(0x21_u32), Some(GlyphId::new(2)));
+        assert_eq!(cmap2.map_codepoint(0x22_u32), None);
+        // Lead byte is not a valid single byte code
+        assert_eq!(cmap2.map_codepoint(0x81_u32), None);
+        assert_eq!(cmap2.map_codepoint(0x8140_u32), Some(GlyphId::new(13)));
+        assert_eq!(cmap2.map_codepoint(0x8141_u32), Some(GlyphId::new(14)));
+        assert_eq!(cmap2.map_codepoint(0x8142_u32), None);
+        assert_eq!(cmap2.map_codepoint(0x8240_u32), None);
+        let pairs = cmap2.iter().collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [
+                (0x20, GlyphId::new(1)),
+                (0x21, GlyphId::new(2)),
+                (0x8140, GlyphId::new(13)),
+                (0x8141, GlyphId::new(14))
+            ]
+        );
+    }
+
+    #[test]
+    fn cmap6_map_and_iter() {
+        let buf = BeBuffer::new().extend([6u16, 16, 0, 0x30, 3, 7, 0, 9]);
+        let cmap6 = Cmap6::read(buf.font_data()).unwrap();
+        assert_eq!(cmap6.map_codepoint('/'), None);
+        assert_eq!(cmap6.map_codepoint('0'), Some(GlyphId::new(7)));
+        assert_eq!(cmap6.map_codepoint('1'), None);
+        assert_eq!(cmap6.map_codepoint('2'), Some(GlyphId::new(9)));
+        assert_eq!(cmap6.map_codepoint('3'), None);
+        let pairs = cmap6.iter().collect::<Vec<_>>();
+        assert_eq!(pairs, [(0x30, GlyphId::new(7)), (0x32, GlyphId::new(9))]);
+    }
+
+    #[test]
+    fn cmap8_map_and_iter() {
+        let buf = BeBuffer::new()
+            .extend([8u16, 0])
+            .extend([8216u32 + 24, 0])
+            .extend([0u8; 8192])
+            .push(2u32)
+            .extend([0x41u32, 0x42, 10])
+            .extend([0x10000u32, 0x10000, 20]);
+        let cmap8 = Cmap8::read(buf.font_data()).unwrap();
+        assert_eq!(cmap8.map_codepoint('A'), Some(GlyphId::new(10)));
+        assert_eq!(cmap8.map_codepoint('B'), Some(GlyphId::new(11)));
+        assert_eq!(cmap8.map_codepoint('C'), None);
+        assert_eq!(cmap8.map_codepoint(0x10000_u32), Some(GlyphId::new(20)));
+        let pairs = cmap8.iter().collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [
+                (0x41, GlyphId::new(10)),
+                (0x42, GlyphId::new(11)),
+                (0x10000, GlyphId::new(20))
+            ]
+        );
+    }
+
+    #[test]
+    fn cmap10_map_and_iter() {
+        let buf = BeBuffer::new()
+            .extend([10u16, 0])
+            .extend([26u32, 0, 0x10400, 3])
+            .extend([4u16, 0, 6]);
+        let cmap10 = Cmap10::read(buf.font_data()).unwrap();
+        assert_eq!(cmap10.map_codepoint(0x103FF_u32), None);
+        assert_eq!(cmap10.map_codepoint(0x10400_u32), Some(GlyphId::new(4)));
+        assert_eq!(cmap10.map_codepoint(0x10401_u32), None);
+        assert_eq!(cmap10.map_codepoint(0x10402_u32), Some(GlyphId::new(6)));
+        let pairs = cmap10.iter().collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [(0x10400, GlyphId::new(4)), (0x10402, GlyphId::new(6))]
+        );
+    }
+
+    #[test]
+    fn cmap13_map_and_iter() {
+        let buf = BeBuffer::new()
+            .extend([13u16, 0])
+            .extend([40u32, 0, 2])
+            .extend([0x41u32, 0x43, 3])
+            .extend([0x61u32, 0x62, 4]);
+        let cmap13 = Cmap13::read(buf.font_data()).unwrap();
+        assert_eq!(cmap13.map_codepoint('@'), None);
+        assert_eq!(cmap13.map_codepoint('A'), Some(GlyphId::new(3)));
+        assert_eq!(cmap13.map_codepoint('C'), Some(GlyphId::new(3)));
+        assert_eq!(cmap13.map_codepoint('b'), Some(GlyphId::new(4)));
+        assert_eq!(cmap13.map_codepoint('c'), None);
+        let pairs = cmap13.iter().collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [
+                (0x41, GlyphId::new(3)),
+                (0x42, GlyphId::new(3)),
+                (0x43, GlyphId::new(3)),
+                (0x61, GlyphId::new(4)),
+                (0x62, GlyphId::new(4))
+            ]
+        );
+    }
+
+    #[test]
+    fn format13_is_last_resort() {
+        let buf = BeBuffer::new()
+            .extend([0u16, 2])
+            // format 13 record is listed first
+            .extend([0u16, 6])
+            .push(20u32)
+            .extend([0u16, 3])
+            .push(48u32)
+            // format 13 subtable mapping everything to glyph 1
+            .extend([13u16, 0])
+            .extend([28u32, 0, 1, 0, 0x10FFFF, 1])
+            // format 6 subtable
+            .extend([6u16, 16, 0, 0x30, 3, 7, 0, 9]);
+        let cmap = Cmap::read(buf.font_data()).unwrap();
+        assert_eq!(cmap.map_codepoint('0'), Some(GlyphId::new(7)));
+        assert_eq!(cmap.map_codepoint('1'), Some(GlyphId::new(1)));
+        assert_eq!(cmap.map_codepoint('2'), Some(GlyphId::new(9)));
+        assert_eq!(cmap.map_codepoint('A'), Some(GlyphId::new(1)));
+    }
+
     fn find_cmap4<'a>(cmap: &Cmap<'a>) -> Option<Cmap4<'a>> {
         cmap.encoding_records()
             .iter()
@@ -0,0 +1,217 @@
+//! The [cvar (CVT Variations)](https://learn.microsoft.com/en-us/typography/opentype/spec/cvar)
+//! table
+
+include!("../../generated/generated_cvar.rs");
+
+use super::variations::{
+    PackedDeltas, PackedPointNumbers, TupleVariationCount, TupleVariationHeader,
+    TupleVariationHeaderIter,
+};
+
+impl<'a> Cvar<'a> {
+    fn raw_tuple_header_data(&self) -> FontData<'a> {
+        let range = self.shape.tuple_variation_headers_byte_range();
+        self.data.split_off(range.start).unwrap()
+    }
+
+    /// Returns an iterator over all of the variation tuples for the control
+    /// value table.
+    ///
+    /// The `axis_count` parameter must match the number of axes in the
+    /// 'fvar' table.
+    pub fn tuples(&self, axis_count: u16) -> Result<CvtVariationIter<'a>, ReadError> {
+        let data = self.data()?;
+        // if there are shared point numbers, get them now
+        let (shared_point_numbers, serialized_data) =
+            if self.tuple_variation_count().shared_point_numbers() {
+                let (packed, data) = PackedPointNumbers::split_off_front(data);
+                (Some(packed), data)
+            } else {
+                (None, data)
+            };
+        let count = self.tuple_variation_count().count() as usize;
+        Ok(CvtVariationIter {
+            remaining: count,
+            axis_count,
+            shared_point_numbers,
+            header_iter: TupleVariationHeaderIter::new(
+                self.raw_tuple_header_data(),
+                count,
+                axis_count,
+            ),
+            serialized_data,
+        })
+    }
+
+    /// Computes the accumulated deltas for the given set of normalized
+    /// coordinates and adds them to `deltas`.
+    ///
+    /// The `deltas` slice should have one entry for each value in the
+    /// 'cvt ' table. Deltas for out of range indices are ignored. The
+    /// varied control values are the sum of the original values and the
+    /// resulting deltas.
+    pub fn deltas(
+        &self,
+        axis_count: u16,
+        coords: &[F2Dot14],
+        deltas: &mut [Fixed],
+    ) -> Result<(), ReadError> {
+        for tuple in self.tuples(axis_count)? {
+            let Some(scalar) = tuple.compute_scalar(coords) else {
+                continue;
+            };
+            for (index, delta) in tuple.deltas() {
+                if let Some(value) = deltas.get_mut(index as usize) {
+                    *value += Fixed::from_i32(delta as i32) * scalar;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the [`CvtVariation`]s in the 'cvar' table.
+pub struct CvtVariationIter<'a> {
+    remaining: usize,
+    axis_count: u16,
+    shared_point_numbers: Option<PackedPointNumbers<'a>>,
+    header_iter: TupleVariationHeaderIter<'a>,
+    serialized_data: FontData<'a>,
+}
+
+impl<'a> Iterator for CvtVariationIter<'a> {
+    type Item = CvtVariation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let header = self.header_iter.next()?.ok()?;
+        let data_len = header.variation_data_size() as usize;
+        let var_data = self.serialized_data.take_up_to(data_len)?;
+        let (point_numbers, packed_deltas) = if header.tuple_index().private_point_numbers() {
+            PackedPointNumbers::split_off_front(var_data)
+        } else {
+            (self.shared_point_numbers.clone()?, var_data)
+        };
+        Some(CvtVariation {
+            axis_count: self.axis_count,
+            header,
+            packed_deltas: PackedDeltas::new(packed_deltas),
+            point_numbers,
+        })
+    }
+}
+
+/// A single set of tuple variation data for the control value table.
+#[derive(Clone)]
+pub struct CvtVariation<'a> {
+    axis_count: u16,
+    header: TupleVariationHeader<'a>,
+    packed_deltas: PackedDeltas<'a>,
+    point_numbers: PackedPointNumbers<'a>,
+}
+
+impl<'a> CvtVariation<'a> {
+    /// Returns true if this tuple provides deltas for all control values.
+    pub fn has_deltas_for_all_values(&self) -> bool {
+        self.point_numbers.count() == 0
+    }
+
+    /// Compute the scalar for this tuple at a given point in design space.
+    ///
+    /// Returns `None` if this tuple is not applicable at the provided coordinates
+    /// (e.g. if the resulting scalar is zero) or if the peak tuple is missing.
+    pub fn compute_scalar(&self, coords: &[F2Dot14]) -> Option<Fixed> {
+        // peak tuples must always be embedded in the 'cvar' table
+        let peak = self.header.peak_tuple()?;
+        self.header.compute_scalar(peak, self.axis_count, coords)
+    }
+
+    /// Iterate over the (control value index, delta) pairs for this tuple.
+    ///
+    /// This does not account for scaling.
+    pub fn deltas(&self) -> impl Iterator<Item = (u16, i16)> + 'a {
+        let all_values = self.has_deltas_for_all_values();
+        let mut points = self.point_numbers.iter();
+        let mut next_index = 0u16;
+        self.packed_deltas.iter().map_while(move |delta| {
+            let index = if all_values {
+                let index = next_index;
+                next_index = next_index.checked_add(1)?;
+                index
+            } else {
+                points.next()?
+            };
+            Some((index, delta))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    // Two axes; one tuple with private points for values 1 and 3 peaking
+    // at (1, 0), and one tuple with deltas for all values peaking at
+    // (0, -1).
+    fn cvar_data() -> BeBuffer {
+        BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            // tuple count, offset to serialized data
+            .extend([2u16, 24])
+            // header: data size, flags (embedded peak | private points), peak
+            .extend([7u16, 0xA000])
+            .extend([F2Dot14::from_f32(1.0), F2Dot14::from_f32(0.0)])
+            // header: data size, flags (embedded peak | private points), peak
+            .extend([10u16, 0xA000])
+            .extend([F2Dot14::from_f32(0.0), F2Dot14::from_f32(-1.0)])
+            // point numbers: 2 points, byte run of 2: 1, +2
+            .extend([2u8, 1, 1, 2])
+            // deltas: byte run of 2
+            .extend([1u8, 10, 0xF6])
+            // point numbers: all points
+            .push(0u8)
+            // deltas: word run of 4
+            .push(0x43u8)
+            .extend([100i16, 200, 300, 400])
+    }
+
+    #[test]
+    fn tuples() {
+        let buf = cvar_data();
+        let cvar = Cvar::read(buf.font_data()).unwrap();
+        let tuples = cvar.tuples(2).unwrap().collect::<Vec<_>>();
+        assert_eq!(tuples.len(), 2);
+        assert!(!tuples[0].has_deltas_for_all_values());
+        assert_eq!(tuples[0].deltas().collect::<Vec<_>>(), [(1, 10), (3, -10)]);
+        assert!(tuples[1].has_deltas_for_all_values());
+        assert_eq!(
+            tuples[1].deltas().collect::<Vec<_>>(),
+            [(0, 100), (1, 200), (2, 300), (3, 400)]
+        );
+        let coords = [F2Dot14::from_f32(0.5), F2Dot14::from_f32(0.0)];
+        assert_eq!(
+            tuples[0].compute_scalar(&coords),
+            Some(Fixed::from_f64(0.5))
+        );
+        assert_eq!(tuples[1].compute_scalar(&coords), None);
+    }
+
+    #[test]
+    fn deltas() {
+        let buf = cvar_data();
+        let cvar = Cvar::read(buf.font_data()).unwrap();
+        let coords = [F2Dot14::from_f32(0.5), F2Dot14::from_f32(-0.25)];
+        let mut deltas = [Fixed::ZERO; 4];
+        cvar.deltas(2, &coords, &mut deltas).unwrap();
+        let deltas = deltas.map(Fixed::to_f64);
+        assert_eq!(deltas, [25.0, 55.0, 75.0, 95.0]);
+        // no variation at the default location
+        let mut deltas = [Fixed::ZERO; 4];
+        cvar.deltas(2, &[], &mut deltas).unwrap();
+        assert_eq!(deltas, [Fixed::ZERO; 4]);
+    }
+}
@@ -0,0 +1,22 @@
+//! The [cvt (Control Value Table)](https://learn.microsoft.com/en-us/typography/opentype/spec/cvt) table
+
+include!("../../generated/generated_cvt.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn values() {
+        // trailing odd byte is ignored
+        let buf = BeBuffer::new().extend([10i16, -20, 300]).push(0u8);
+        let cvt = Cvt::read(buf.font_data()).unwrap();
+        let values = cvt
+            .values()
+            .iter()
+            .map(|value| value.get().to_i16())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [10, -20, 300]);
+    }
+}
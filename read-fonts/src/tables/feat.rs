@@ -0,0 +1,81 @@
+//! The [feature name](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6feat.html) table.
+
+include!("../../generated/generated_feat.rs");
+
+impl<'a> Feat<'a> {
+    /// Returns the feature name record for the given feature type.
+    pub fn find(&self, feature: u16) -> Option<FeatureName> {
+        let names = self.names();
+        let ix = names
+            .binary_search_by(|name| name.feature().cmp(&feature))
+            .ok()?;
+        names.get(ix).cloned()
+    }
+}
+
+impl FeatureName {
+    /// Flag indicating that the settings of this feature are mutually
+    /// exclusive.
+    pub const EXCLUSIVE: u16 = 0x8000;
+    /// Flag indicating that the low byte of the flags is the index of the
+    /// default setting.
+    pub const HAS_DEFAULT_SETTING: u16 = 0x4000;
+
+    /// Returns true if the settings of this feature are mutually exclusive.
+    pub fn is_exclusive(&self) -> bool {
+        self.feature_flags() & Self::EXCLUSIVE != 0
+    }
+
+    /// Returns the index of the default setting in the setting name array.
+    pub fn default_setting_index(&self) -> u16 {
+        let flags = self.feature_flags();
+        if flags & Self::HAS_DEFAULT_SETTING != 0 {
+            flags & 0xFF
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn feature_names() {
+        let buf = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .extend([2u16, 0])
+            .push(0u32)
+            // feature names: (feature, n_settings, offset, flags, name)
+            .extend([1u16, 1])
+            .push(36u32)
+            .extend([0u16, 260])
+            .extend([37u16, 2])
+            .push(40u32)
+            .extend([0xC001u16, 261])
+            // setting names
+            .extend([0u16, 262])
+            .extend([0u16, 263, 1, 264]);
+        let feat = Feat::read(buf.font_data()).unwrap();
+        assert_eq!(feat.feature_name_count(), 2);
+        assert!(feat.find(2).is_none());
+        let ligatures = feat.find(1).unwrap();
+        assert!(!ligatures.is_exclusive());
+        assert_eq!(ligatures.default_setting_index(), 0);
+        assert_eq!(ligatures.name_index(), NameId::new(260));
+        let settings = ligatures.setting_table(feat.offset_data()).unwrap();
+        assert_eq!(settings.settings().len(), 1);
+        let lower_case = feat.find(37).unwrap();
+        assert!(lower_case.is_exclusive());
+        assert_eq!(lower_case.default_setting_index(), 1);
+        let settings = lower_case.setting_table(feat.offset_data()).unwrap();
+        let settings = settings
+            .settings()
+            .iter()
+            .map(|setting| (setting.setting(), setting.name_index().to_u16()))
+            .collect::<Vec<_>>();
+        assert_eq!(settings, [(0, 263), (1, 264)]);
+    }
+}
@@ -0,0 +1,3 @@
+//! The [fpgm (Font Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/fpgm) table
+
+include!("../../generated/generated_fpgm.rs");
@@ -0,0 +1,49 @@
+//! The [gasp (Grid-fitting and Scan-conversion Procedure)](https://learn.microsoft.com/en-us/typography/opentype/spec/gasp) table
+
+include!("../../generated/generated_gasp.rs");
+
+impl<'a> Gasp<'a> {
+    /// Returns the rasterizer behavior for the given size in pixels per
+    /// em.
+    ///
+    /// Returns `None` if no range covers the given size.
+    pub fn behavior(&self, ppem: u16) -> Option<GaspRangeBehavior> {
+        self.gasp_ranges()
+            .iter()
+            .find(|range| ppem <= range.range_max_ppem())
+            .map(|range| range.range_gasp_behavior())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn behavior_for_ppem() {
+        let buf = BeBuffer::new()
+            .extend([1u16, 3])
+            .extend([8u16, 0x2])
+            .extend([16u16, 0x1 | 0x4])
+            .extend([0xFFFFu16, 0xF]);
+        let gasp = Gasp::read(buf.font_data()).unwrap();
+        assert_eq!(gasp.num_ranges(), 3);
+        let behaviors = [0u16, 8, 9, 16, 17, 0xFFFF]
+            .into_iter()
+            .map(|ppem| gasp.behavior(ppem).unwrap().bits())
+            .collect::<Vec<_>>();
+        assert_eq!(behaviors, [0x2, 0x2, 0x5, 0x5, 0xF, 0xF]);
+        let behavior = gasp.behavior(12).unwrap();
+        assert!(behavior.contains(GaspRangeBehavior::GASP_GRIDFIT));
+        assert!(!behavior.contains(GaspRangeBehavior::GASP_DOGRAY));
+    }
+
+    #[test]
+    fn no_covering_range() {
+        let buf = BeBuffer::new().extend([0u16, 1]).extend([20u16, 0x3]);
+        let gasp = Gasp::read(buf.font_data()).unwrap();
+        assert!(gasp.behavior(20).is_some());
+        assert!(gasp.behavior(21).is_none());
+    }
+}
@@ -61,7 +61,7 @@ impl PointFlags {
     // and OFF_CURVE_CUBIC bits, per FreeType and TrueType convention.
     const CURVE_MASK: u8 = 0x81;
     const ON_CURVE: u8 = 0x1;
-    const OFF_CURVE_CUBIC: u8 = 0x8;
+    const OFF_CURVE_CUBIC: u8 = 0x80;
 
     /// Creates a new on curve point flag.
     pub fn on_curve() -> Self {
@@ -920,6 +920,74 @@ mod tests {
         assert_eq!(pen.0.trim(), expected);
     }
 
+    #[test]
+    fn path_close_matches_freetype() {
+        use crate::scaler_test::{Path, PathElement};
+        fn pt(x: i32, y: i32) -> Point<F26Dot6> {
+            Point::new(x, y).map(|x| F26Dot6::from_bits(x * 64))
+        }
+        let points = [
+            // contour ending with an on curve point
+            pt(0, 0),
+            pt(10, 0),
+            pt(10, 10),
+            // contour ending with an off curve point
+            pt(20, 0),
+            pt(30, 0),
+            pt(30, 10),
+        ];
+        let on = PointFlags::on_curve();
+        let off = PointFlags::off_curve_quad();
+        let flags = [on, on, on, on, on, off];
+        let contours = [2, 5];
+        let mut path = Path::default();
+        to_path(&points, &flags, &contours, &mut path).unwrap();
+        // FT_Outline_Decompose closes the first contour with a line but
+        // the second with the quad back to the start point
+        assert_eq!(
+            path.elements,
+            [
+                PathElement::MoveTo([0.0, 0.0]),
+                PathElement::LineTo([10.0, 0.0]),
+                PathElement::LineTo([10.0, 10.0]),
+                PathElement::LineTo([0.0, 0.0]),
+                PathElement::MoveTo([20.0, 0.0]),
+                PathElement::LineTo([30.0, 0.0]),
+                PathElement::QuadTo([30.0, 10.0, 20.0, 0.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn point_flags_and_markers() {
+        let on = PointFlags::on_curve();
+        let quad = PointFlags::off_curve_quad();
+        let cubic = PointFlags::off_curve_cubic();
+        // curve type survives a round trip through bits
+        assert!(PointFlags::from_bits(on.0).is_on_curve());
+        assert!(PointFlags::from_bits(quad.0).is_off_curve_quad());
+        assert!(PointFlags::from_bits(cubic.0).is_off_curve_cubic());
+        // markers must not change the curve type of a point
+        let all_markers = [
+            PointMarker::HAS_DELTA,
+            PointMarker::TOUCHED_X,
+            PointMarker::TOUCHED_Y,
+        ];
+        for flags in [on, quad, cubic] {
+            let mut marked = flags;
+            for marker in all_markers {
+                marked.set_marker(marker);
+                assert!(marked.has_marker(marker));
+            }
+            assert_eq!(marked.is_on_curve(), flags.is_on_curve());
+            assert_eq!(marked.is_off_curve_quad(), flags.is_off_curve_quad());
+            assert_eq!(marked.is_off_curve_cubic(), flags.is_off_curve_cubic());
+            marked.clear_marker(PointMarker::TOUCHED);
+            marked.clear_marker(PointMarker::HAS_DELTA);
+            assert_eq!(marked, flags);
+        }
+    }
+
     #[test]
     fn simple_glyph() {
         let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
@@ -221,7 +221,6 @@ impl<'a> TupleVariation<'a> {
             .unwrap_or_default()
     }
 
-    // transcribed from pinot/moscato
     /// Compute the scalar for a this tuple at a given point in design space.
     ///
     /// The `coords` slice must be of lesser or equal length to the number of axes.
@@ -230,46 +229,8 @@ impl<'a> TupleVariation<'a> {
     /// Returns `None` if this tuple is not applicable at the provided coordinates
     /// (e.g. if the resulting scalar is zero).
     pub fn compute_scalar(&self, coords: &[F2Dot14]) -> Option<Fixed> {
-        const ZERO: Fixed = Fixed::ZERO;
-        let mut scalar = Fixed::ONE;
-        let peak = self.peak();
-        let inter_start = self.header.intermediate_start_tuple();
-        let inter_end = self.header.intermediate_end_tuple();
-        if peak.len() != self.axis_count as usize {
-            return None;
-        }
-
-        for i in 0..self.axis_count {
-            let i = i as usize;
-            let coord = coords.get(i).copied().unwrap_or_default().to_fixed();
-            let peak = peak.get(i).unwrap_or_default().to_fixed();
-            if peak == ZERO || peak == coord {
-                continue;
-            }
-
-            if coord == ZERO {
-                return None;
-            }
-
-            if let (Some(inter_start), Some(inter_end)) = (&inter_start, &inter_end) {
-                let start = inter_start.get(i).unwrap_or_default().to_fixed();
-                let end = inter_end.get(i).unwrap_or_default().to_fixed();
-                if coord <= start || coord >= end {
-                    return None;
-                }
-                if coord < peak {
-                    scalar = scalar.mul_div(coord - start, peak - start);
-                } else {
-                    scalar = scalar.mul_div(end - coord, end - peak);
-                }
-            } else {
-                if coord < peak.min(ZERO) || coord > peak.max(ZERO) {
-                    return None;
-                }
-                scalar = scalar.mul_div(coord, peak);
-            }
-        }
-        Some(scalar)
+        self.header
+            .compute_scalar(self.peak(), self.axis_count, coords)
     }
 
     /// Iterate over the deltas for this tuple.
@@ -0,0 +1,882 @@
+//! The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table.
+//!
+//! This table exists in two variants: the OpenType version with a 16-bit
+//! header and the Apple version described in the
+//! [TrueType reference manual](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kern.html).
+
+include!("../../generated/generated_kern.rs");
+
+/// Size of the OpenType subtable header.
+const OT_SUBTABLE_HEADER_LEN: usize = 6;
+/// Size of the AAT subtable header.
+const AAT_SUBTABLE_HEADER_LEN: usize = 8;
+
+/// The [kern](https://learn.microsoft.com/en-us/typography/opentype/spec/kern)
+/// table, in either the OpenType or Apple format.
+#[derive(Clone)]
+pub enum Kern<'a> {
+    Ot(OtKern<'a>),
+    Aat(AatKern<'a>),
+}
+
+impl TopLevelTable for Kern<'_> {
+    const TAG: Tag = Tag::new(b"kern");
+}
+
+impl<'a> FontRead<'a> for Kern<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        // The OpenType version is a 16-bit zero while the Apple version is
+        // a 32-bit 1.0, so the first two bytes are enough to distinguish them
+        match data.read_at::<u16>(0)? {
+            0 => OtKern::read(data).map(Self::Ot),
+            1 => AatKern::read(data).map(Self::Aat),
+            version => Err(ReadError::InvalidFormat(version as _)),
+        }
+    }
+}
+
+impl<'a> Kern<'a> {
+    /// Returns true if this table uses the Apple header.
+    pub fn is_aat(&self) -> bool {
+        matches!(self, Self::Aat(_))
+    }
+
+    /// Returns the number of subtables.
+    pub fn n_tables(&self) -> u32 {
+        match self {
+            Self::Ot(kern) => kern.n_tables() as u32,
+            Self::Aat(kern) => kern.n_tables(),
+        }
+    }
+
+    /// Returns an iterator over all of the subtables in this table.
+    pub fn subtables(&self) -> Subtables<'a> {
+        match self {
+            Self::Ot(kern) => kern.subtables(),
+            Self::Aat(kern) => kern.subtables(),
+        }
+    }
+}
+
+impl<'a> OtKern<'a> {
+    /// Returns an iterator over all of the subtables in this table.
+    pub fn subtables(&self) -> Subtables<'a> {
+        Subtables::new(self.subtable_data(), self.n_tables() as u32, false)
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_subtables(&self) -> FieldType<'a> {
+        traverse_subtables(self.subtables())
+    }
+}
+
+impl<'a> AatKern<'a> {
+    /// Returns an iterator over all of the subtables in this table.
+    pub fn subtables(&self) -> Subtables<'a> {
+        Subtables::new(self.subtable_data(), self.n_tables(), true)
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_subtables(&self) -> FieldType<'a> {
+        traverse_subtables(self.subtables())
+    }
+}
+
+/// Iterator over the subtables of a `kern` table.
+#[derive(Clone)]
+pub struct Subtables<'a> {
+    data: FontData<'a>,
+    remaining: u32,
+    is_aat: bool,
+}
+
+impl<'a> Subtables<'a> {
+    fn new(data: &'a [u8], count: u32, is_aat: bool) -> Self {
+        Self {
+            data: FontData::new(data),
+            remaining: count,
+            is_aat,
+        }
+    }
+
+    fn read_next(&mut self) -> Result<Subtable<'a>, ReadError> {
+        let len = if self.is_aat {
+            self.data.read_at::<u32>(0)? as usize
+        } else {
+            self.data.read_at::<u16>(2)? as usize
+        };
+        // The 16-bit length of OpenType subtables frequently overflows for
+        // large format 0 subtables so the last one always uses the
+        // remainder of the table
+        let data = if self.remaining == 0 {
+            self.data
+        } else {
+            self.data.slice(..len).ok_or(ReadError::OutOfBounds)?
+        };
+        self.data = self.data.split_off(len).unwrap_or_default();
+        if self.is_aat {
+            AatSubtable::read(data).map(Subtable::Aat)
+        } else {
+            OtSubtable::read(data).map(Subtable::Ot)
+        }
+    }
+}
+
+impl<'a> Iterator for Subtables<'a> {
+    type Item = Result<Subtable<'a>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        let result = self.read_next();
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+}
+
+/// A subtable in the `kern` table.
+#[derive(Clone)]
+pub enum Subtable<'a> {
+    Ot(OtSubtable<'a>),
+    Aat(AatSubtable<'a>),
+}
+
+impl<'a> Subtable<'a> {
+    /// Returns true if the subtable contains horizontal kerning values.
+    pub fn is_horizontal(&self) -> bool {
+        match self {
+            Self::Ot(subtable) => subtable.is_horizontal(),
+            Self::Aat(subtable) => subtable.is_horizontal(),
+        }
+    }
+
+    /// Returns true if the subtable contains cross-stream kerning values.
+    pub fn is_cross_stream(&self) -> bool {
+        match self {
+            Self::Ot(subtable) => subtable.is_cross_stream(),
+            Self::Aat(subtable) => subtable.is_cross_stream(),
+        }
+    }
+
+    /// Returns true if the subtable contains minimum values rather than
+    /// kerning adjustments.
+    ///
+    /// This is only supported by OpenType subtables.
+    pub fn is_minimum(&self) -> bool {
+        match self {
+            Self::Ot(subtable) => subtable.is_minimum(),
+            Self::Aat(_) => false,
+        }
+    }
+
+    /// Returns true if values in this subtable should replace the
+    /// accumulated adjustment rather than being added to it.
+    ///
+    /// This is only supported by OpenType subtables.
+    pub fn is_override(&self) -> bool {
+        match self {
+            Self::Ot(subtable) => subtable.is_override(),
+            Self::Aat(_) => false,
+        }
+    }
+
+    /// Returns true if the subtable contains kerning values for a tuple
+    /// of a variable font.
+    ///
+    /// This is only supported by Apple subtables.
+    pub fn is_variable(&self) -> bool {
+        match self {
+            Self::Ot(_) => false,
+            Self::Aat(subtable) => subtable.is_variable(),
+        }
+    }
+
+    /// Returns the subtable format.
+    pub fn format(&self) -> u8 {
+        match self {
+            Self::Ot(subtable) => subtable.format(),
+            Self::Aat(subtable) => subtable.format(),
+        }
+    }
+
+    /// Returns the format specific subtable.
+    pub fn kind(&self) -> Result<SubtableKind<'a>, ReadError> {
+        match self {
+            Self::Ot(subtable) => subtable.kind(),
+            Self::Aat(subtable) => subtable.kind(),
+        }
+    }
+}
+
+impl<'a> OtSubtable<'a> {
+    const HORIZONTAL: u16 = 0x0001;
+    const MINIMUM: u16 = 0x0002;
+    const CROSS_STREAM: u16 = 0x0004;
+    const OVERRIDE: u16 = 0x0008;
+
+    /// Returns true if the subtable contains horizontal kerning values.
+    pub fn is_horizontal(&self) -> bool {
+        self.coverage() & Self::HORIZONTAL != 0
+    }
+
+    /// Returns true if the subtable contains cross-stream kerning values.
+    pub fn is_cross_stream(&self) -> bool {
+        self.coverage() & Self::CROSS_STREAM != 0
+    }
+
+    /// Returns true if the subtable contains minimum values rather than
+    /// kerning adjustments.
+    pub fn is_minimum(&self) -> bool {
+        self.coverage() & Self::MINIMUM != 0
+    }
+
+    /// Returns true if values in this subtable should replace the
+    /// accumulated adjustment rather than being added to it.
+    pub fn is_override(&self) -> bool {
+        self.coverage() & Self::OVERRIDE != 0
+    }
+
+    /// Returns the subtable format, stored in the high byte of the coverage
+    /// field.
+    pub fn format(&self) -> u8 {
+        (self.coverage() >> 8) as u8
+    }
+
+    /// Returns the format specific subtable.
+    pub fn kind(&self) -> Result<SubtableKind<'a>, ReadError> {
+        SubtableKind::read(self.data(), self.format(), OT_SUBTABLE_HEADER_LEN)
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_kind(&self) -> FieldType<'a> {
+        FieldType::offset(
+            traversal::OffsetType::Offset16(OT_SUBTABLE_HEADER_LEN as u16),
+            self.kind(),
+        )
+    }
+}
+
+impl<'a> AatSubtable<'a> {
+    const VERTICAL: u16 = 0x8000;
+    const CROSS_STREAM: u16 = 0x4000;
+    const VARIATION: u16 = 0x2000;
+
+    /// Returns true if the subtable contains horizontal kerning values.
+    pub fn is_horizontal(&self) -> bool {
+        self.coverage() & Self::VERTICAL == 0
+    }
+
+    /// Returns true if the subtable contains cross-stream kerning values.
+    pub fn is_cross_stream(&self) -> bool {
+        self.coverage() & Self::CROSS_STREAM != 0
+    }
+
+    /// Returns true if the subtable contains kerning values for the tuple
+    /// specified by [`tuple_index`](Self::tuple_index).
+    pub fn is_variable(&self) -> bool {
+        self.coverage() & Self::VARIATION != 0
+    }
+
+    /// Returns the subtable format, stored in the low byte of the coverage
+    /// field.
+    pub fn format(&self) -> u8 {
+        self.coverage() as u8
+    }
+
+    /// Returns the format specific subtable.
+    pub fn kind(&self) -> Result<SubtableKind<'a>, ReadError> {
+        SubtableKind::read(self.data(), self.format(), AAT_SUBTABLE_HEADER_LEN)
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_kind(&self) -> FieldType<'a> {
+        FieldType::offset(
+            traversal::OffsetType::Offset16(AAT_SUBTABLE_HEADER_LEN as u16),
+            self.kind(),
+        )
+    }
+}
+
+/// The various `kern` subtable formats.
+#[derive(Clone)]
+pub enum SubtableKind<'a> {
+    Format0(Subtable0<'a>),
+    Format1(Subtable1<'a>),
+    Format2(Subtable2<'a>),
+    Format3(Subtable3<'a>),
+}
+
+impl<'a> SubtableKind<'a> {
+    fn read(data: &'a [u8], format: u8, header_len: usize) -> Result<Self, ReadError> {
+        let data = FontData::new(data);
+        match format {
+            0 => Subtable0::read(data).map(Self::Format0),
+            1 => Subtable1::read(data).map(Self::Format1),
+            2 => Subtable2::read(data, header_len).map(Self::Format2),
+            3 => Subtable3::read(data).map(Self::Format3),
+            _ => Err(ReadError::InvalidFormat(format as _)),
+        }
+    }
+
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    ///
+    /// Format 1 subtables are driven by a state machine that operates on
+    /// the full glyph sequence so this always returns `None` for those.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        match self {
+            Self::Format0(subtable) => subtable.kerning(left, right),
+            Self::Format1(_) => None,
+            Self::Format2(subtable) => subtable.kerning(left, right),
+            Self::Format3(subtable) => subtable.kerning(left, right),
+        }
+    }
+}
+
+impl<'a> Subtable0<'a> {
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    ///
+    /// Uses a binary search over the pairs which are required to be sorted.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        let pairs = self.pairs();
+        let ix = pairs
+            .binary_search_by(|pair| (pair.left(), pair.right()).cmp(&(left, right)))
+            .ok()?;
+        Some(pairs[ix].value().to_i16() as i32)
+    }
+}
+
+/// Entry in the state machine of a type 1 `kern` subtable.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Subtable1Entry {
+    /// Byte offset from the start of the subtable to the row of the next
+    /// state in the state array.
+    pub new_state: u16,
+    /// Action flags.
+    pub flags: u16,
+}
+
+impl Subtable1Entry {
+    /// Returns true if the current glyph should be pushed onto the kerning
+    /// stack.
+    pub fn push(&self) -> bool {
+        self.flags & 0x8000 != 0
+    }
+
+    /// Returns true if the current glyph should be processed again.
+    pub fn dont_advance(&self) -> bool {
+        self.flags & 0x4000 != 0
+    }
+
+    /// Returns the byte offset from the start of the subtable to the list
+    /// of kerning values to apply, or zero if no values are applied.
+    pub fn value_offset(&self) -> u16 {
+        self.flags & 0x3FFF
+    }
+}
+
+impl<'a> Subtable1<'a> {
+    /// Class for the end of text.
+    pub const END_OF_TEXT: u8 = 0;
+    /// Class for glyphs not covered by the class table.
+    pub const OUT_OF_BOUNDS: u8 = 1;
+
+    /// Returns the class of the given glyph.
+    pub fn class(&self, glyph_id: GlyphId) -> Result<u8, ReadError> {
+        let class_table = self.class_table()?;
+        let class = glyph_id
+            .to_u16()
+            .checked_sub(class_table.first_glyph().to_u16())
+            .and_then(|ix| class_table.class_array().get(ix as usize).copied());
+        Ok(class.unwrap_or(Self::OUT_OF_BOUNDS))
+    }
+
+    /// Returns the initial state, as a byte offset to the first row of the
+    /// state array.
+    pub fn initial_state(&self) -> u16 {
+        self.state_array_offset()
+    }
+
+    /// Returns the entry for the given state and class.
+    ///
+    /// The state is the byte offset from the start of the subtable to a
+    /// row of the state array, as stored in [`Subtable1Entry::new_state`].
+    pub fn entry(&self, state: u16, class: u8) -> Result<Subtable1Entry, ReadError> {
+        let data = self.offset_data();
+        let entry_ix = data.read_at::<u8>(state as usize + class as usize)? as usize;
+        let entry_offset = self.entry_table_offset() as usize + entry_ix * 4;
+        Ok(Subtable1Entry {
+            new_state: data.read_at(entry_offset)?,
+            flags: data.read_at(entry_offset + 2)?,
+        })
+    }
+
+    /// Returns an iterator over the list of kerning values at the given
+    /// offset.
+    ///
+    /// Each value applies to a glyph popped from the kerning stack. The
+    /// list ends with a value that has the low bit set and the low bit of
+    /// every value should be cleared before it is applied. The value
+    /// `0x8001` indicates that cross-stream kerning should be reset.
+    pub fn values(&self, value_offset: u16) -> impl Iterator<Item = i16> + 'a {
+        let data = self.offset_data();
+        let mut offset = Some(value_offset as usize);
+        std::iter::from_fn(move || {
+            let value = data.read_at::<i16>(offset?).ok()?;
+            offset = if value & 1 == 0 {
+                offset.map(|offset| offset + 2)
+            } else {
+                None
+            };
+            Some(value)
+        })
+    }
+}
+
+/// The type 2 `kern` subtable.
+///
+/// This is a two dimensional array of kerning values indexed by glyph
+/// classes. The offsets in this subtable are relative to the start of the
+/// containing subtable header so this type is not generated.
+#[derive(Clone)]
+pub struct Subtable2<'a> {
+    data: FontData<'a>,
+    /// Size of the header of the containing subtable.
+    header_len: usize,
+}
+
+impl<'a> Subtable2<'a> {
+    /// Reads the subtable from data following a subtable header with the
+    /// given size.
+    pub fn read(data: FontData<'a>, header_len: usize) -> Result<Self, ReadError> {
+        // Ensure the fixed size fields are available
+        data.read_at::<u16>(6)?;
+        Ok(Self { data, header_len })
+    }
+
+    /// The width, in bytes, of a row in the subtable.
+    pub fn row_width(&self) -> u16 {
+        self.data.read_at(0).unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the left-hand class table.
+    pub fn left_offset_table(&self) -> u16 {
+        self.data.read_at(2).unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the right-hand class table.
+    pub fn right_offset_table(&self) -> u16 {
+        self.data.read_at(4).unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the start of the kerning
+    /// array.
+    pub fn array_offset(&self) -> u16 {
+        self.data.read_at(6).unwrap_or_default()
+    }
+
+    /// Returns the left-hand class table.
+    ///
+    /// Class values in this table are byte offsets from the start of the
+    /// subtable to the row of the kerning array for the glyph.
+    pub fn left_class_table(&self) -> Result<Subtable2ClassTable<'a>, ReadError> {
+        self.class_table(self.left_offset_table())
+    }
+
+    /// Returns the right-hand class table.
+    ///
+    /// Class values in this table are byte offsets from the start of a row
+    /// of the kerning array.
+    pub fn right_class_table(&self) -> Result<Subtable2ClassTable<'a>, ReadError> {
+        self.class_table(self.right_offset_table())
+    }
+
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        let left_class = self.left_class_table().ok()?.class(left)? as usize;
+        // Offsets below the kerning array are invalid and generally mean
+        // the glyph has no kerning
+        if left_class < self.array_offset() as usize {
+            return None;
+        }
+        // Glyphs not covered by the right class table use the first column
+        let right_class = self
+            .right_class_table()
+            .ok()
+            .and_then(|table| table.class(right))
+            .unwrap_or(0) as usize;
+        let offset = (left_class + right_class).checked_sub(self.header_len)?;
+        Some(self.data.read_at::<i16>(offset).ok()? as i32)
+    }
+
+    fn class_table(&self, offset: u16) -> Result<Subtable2ClassTable<'a>, ReadError> {
+        let offset = (offset as usize)
+            .checked_sub(self.header_len)
+            .ok_or(ReadError::OutOfBounds)?;
+        self.data
+            .split_off(offset)
+            .ok_or(ReadError::OutOfBounds)
+            .and_then(Subtable2ClassTable::read)
+    }
+}
+
+impl<'a> Subtable2ClassTable<'a> {
+    /// Returns the class value for the given glyph.
+    pub fn class(&self, glyph_id: GlyphId) -> Option<u16> {
+        let ix = glyph_id.to_u16().checked_sub(self.first_glyph().to_u16())?;
+        self.offsets().get(ix as usize).map(|offset| offset.get())
+    }
+}
+
+impl<'a> Subtable3<'a> {
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        let left_class = *self.left_class().get(left.to_u16() as usize)? as usize;
+        let right_class = *self.right_class().get(right.to_u16() as usize)? as usize;
+        let index_ix = left_class * self.right_class_count() as usize + right_class;
+        let value_ix = *self.kern_index().get(index_ix)? as usize;
+        Some(self.kern_value().get(value_ix)?.get().to_i16() as i32)
+    }
+}
+
+#[cfg(feature = "traversal")]
+fn traverse_subtables<'a>(subtables: Subtables<'a>) -> FieldType<'a> {
+    FieldType::Array(Box::new(SubtableArray(
+        subtables.filter_map(|subtable| subtable.ok()).collect(),
+    )))
+}
+
+#[cfg(feature = "traversal")]
+struct SubtableArray<'a>(Vec<Subtable<'a>>);
+
+#[cfg(feature = "traversal")]
+impl<'a> traversal::SomeArray<'a> for SubtableArray<'a> {
+    fn type_name(&self) -> &str {
+        "Subtable"
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get(&self, idx: usize) -> Option<FieldType<'a>> {
+        let subtable = self.0.get(idx)?.clone();
+        let data = match &subtable {
+            Subtable::Ot(subtable) => subtable.offset_data(),
+            Subtable::Aat(subtable) => subtable.offset_data(),
+        };
+        Some(FieldType::Record(RecordResolver {
+            name: subtable.type_name_static(),
+            get_field: Box::new(move |idx, _| subtable.get_field(idx)),
+            data,
+        }))
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> Subtable<'a> {
+    fn type_name_static(&self) -> &'static str {
+        match self {
+            Self::Ot(_) => "OtSubtable",
+            Self::Aat(_) => "AatSubtable",
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Subtable<'a> {
+    fn type_name(&self) -> &str {
+        self.type_name_static()
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match self {
+            Self::Ot(subtable) => subtable.get_field(idx),
+            Self::Aat(subtable) => subtable.get_field(idx),
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Kern<'a> {
+    fn type_name(&self) -> &str {
+        match self {
+            Self::Ot(kern) => kern.type_name(),
+            Self::Aat(kern) => kern.type_name(),
+        }
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match self {
+            Self::Ot(kern) => kern.get_field(idx),
+            Self::Aat(kern) => kern.get_field(idx),
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for SubtableKind<'a> {
+    fn type_name(&self) -> &str {
+        match self {
+            Self::Format0(subtable) => subtable.type_name(),
+            Self::Format1(subtable) => subtable.type_name(),
+            Self::Format2(subtable) => subtable.type_name(),
+            Self::Format3(subtable) => subtable.type_name(),
+        }
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match self {
+            Self::Format0(subtable) => subtable.get_field(idx),
+            Self::Format1(subtable) => subtable.get_field(idx),
+            Self::Format2(subtable) => subtable.get_field(idx),
+            Self::Format3(subtable) => subtable.get_field(idx),
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Subtable2<'a> {
+    fn type_name(&self) -> &str {
+        "Subtable2"
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        let class_table = |offset: u16| {
+            FieldType::offset(
+                traversal::OffsetType::Offset16(offset),
+                self.class_table(offset),
+            )
+        };
+        match idx {
+            0usize => Some(Field::new("row_width", self.row_width())),
+            1usize => Some(Field::new(
+                "left_offset_table",
+                class_table(self.left_offset_table()),
+            )),
+            2usize => Some(Field::new(
+                "right_offset_table",
+                class_table(self.right_offset_table()),
+            )),
+            3usize => Some(Field::new("array_offset", self.array_offset())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    /// OpenType format 0 subtable with the given coverage and pairs.
+    fn ot_format0(buf: BeBuffer, coverage: u16, pairs: &[(u16, u16, i16)]) -> BeBuffer {
+        let len = 6 + 8 + pairs.len() * 6;
+        let mut buf =
+            buf.extend([0u16, len as u16, coverage])
+                .extend([pairs.len() as u16, 0, 0, 0]);
+        for (left, right, value) in pairs {
+            buf = buf.extend([*left, *right]).push(*value);
+        }
+        buf
+    }
+
+    #[test]
+    fn ot_format0_pairs() {
+        let buf = BeBuffer::new().extend([0u16, 1]);
+        let buf = ot_format0(buf, 0x0001, &[(1, 2, -10), (1, 5, 20), (3, 1, -50)]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        assert!(!kern.is_aat());
+        let subtables = kern.subtables().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(subtables.len(), 1);
+        let subtable = &subtables[0];
+        assert!(subtable.is_horizontal());
+        assert!(!subtable.is_cross_stream());
+        assert_eq!(subtable.format(), 0);
+        let SubtableKind::Format0(format0) = subtable.kind().unwrap() else {
+            panic!("expected format 0 subtable");
+        };
+        assert_eq!(format0.n_pairs(), 3);
+        assert_eq!(format0.kerning(gid(1), gid(2)), Some(-10));
+        assert_eq!(format0.kerning(gid(1), gid(5)), Some(20));
+        assert_eq!(format0.kerning(gid(3), gid(1)), Some(-50));
+        assert_eq!(format0.kerning(gid(2), gid(1)), None);
+        assert_eq!(format0.kerning(gid(3), gid(2)), None);
+    }
+
+    #[test]
+    fn ot_coverage_flags() {
+        let buf = BeBuffer::new().extend([0u16, 3]);
+        let buf = ot_format0(buf, 0x0001, &[(1, 2, 10)]);
+        let buf = ot_format0(buf, 0x0005, &[(1, 2, 20)]);
+        let buf = ot_format0(buf, 0x000B, &[(1, 2, 30)]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        let flags = kern
+            .subtables()
+            .map(|subtable| {
+                let subtable = subtable.unwrap();
+                (
+                    subtable.is_horizontal(),
+                    subtable.is_cross_stream(),
+                    subtable.is_minimum(),
+                    subtable.is_override(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                (true, false, false, false),
+                (true, true, false, false),
+                (true, false, true, true)
+            ]
+        );
+        let values = kern
+            .subtables()
+            .map(|subtable| subtable.unwrap().kind().unwrap().kerning(gid(1), gid(2)))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [Some(10), Some(20), Some(30)]);
+    }
+
+    #[test]
+    fn ot_last_subtable_length_overflow() {
+        // The length of the last subtable is ignored since it commonly
+        // overflows
+        let pairs = [(1, 2, 10), (3, 4, 20)];
+        let buf = ot_format0(BeBuffer::new().extend([0u16, 1]), 0x0001, &pairs);
+        let mut data = buf.to_vec();
+        data[6..8].copy_from_slice(&4u16.to_be_bytes());
+        let kern = Kern::read(FontData::new(&data)).unwrap();
+        let subtable = kern.subtables().next().unwrap().unwrap();
+        let kind = subtable.kind().unwrap();
+        assert_eq!(kind.kerning(gid(3), gid(4)), Some(20));
+    }
+
+    #[test]
+    fn aat_format2() {
+        let buf = BeBuffer::new()
+            // version 1.0, nTables
+            .extend([1u16, 0])
+            .push(1u32)
+            // length, coverage (format 2), tuple index
+            .push(40u32)
+            .extend([2u16, 0])
+            // rowWidth, leftClassTable, rightClassTable, array
+            .extend([4u16, 16, 24, 32])
+            // left class table: glyphs 4..=5
+            .extend([4u16, 2, 32, 36])
+            // right class table: glyphs 10..=11
+            .extend([10u16, 2, 0, 2])
+            // kerning array
+            .extend([0i16, -20, 5, 15]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        assert!(kern.is_aat());
+        assert_eq!(kern.n_tables(), 1);
+        let subtable = kern.subtables().next().unwrap().unwrap();
+        assert!(subtable.is_horizontal());
+        assert!(!subtable.is_variable());
+        assert_eq!(subtable.format(), 2);
+        let SubtableKind::Format2(format2) = subtable.kind().unwrap() else {
+            panic!("expected format 2 subtable");
+        };
+        assert_eq!(format2.row_width(), 4);
+        assert_eq!(format2.left_class_table().unwrap().class(gid(5)), Some(36));
+        assert_eq!(format2.kerning(gid(4), gid(10)), Some(0));
+        assert_eq!(format2.kerning(gid(4), gid(11)), Some(-20));
+        assert_eq!(format2.kerning(gid(5), gid(10)), Some(5));
+        assert_eq!(format2.kerning(gid(5), gid(11)), Some(15));
+        // Right glyph outside of the class table uses class 0
+        assert_eq!(format2.kerning(gid(5), gid(12)), Some(5));
+        assert_eq!(format2.kerning(gid(6), gid(10)), None);
+    }
+
+    #[test]
+    fn aat_format3() {
+        let buf = BeBuffer::new()
+            .extend([1u16, 0])
+            .push(1u32)
+            // length, coverage (vertical, format 3), tuple index
+            .push(30u32)
+            .extend([0x8003u16, 0])
+            // glyphCount, kernValueCount, leftClassCount, rightClassCount,
+            // flags
+            .push(4u16)
+            .extend([3u8, 2, 2, 0])
+            // kern values
+            .extend([0i16, -30, 40])
+            // left classes
+            .extend([0u8, 1, 0, 1])
+            // right classes
+            .extend([0u8, 0, 1, 1])
+            // kern indices
+            .extend([0u8, 1, 2, 0]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        let subtable = kern.subtables().next().unwrap().unwrap();
+        assert!(!subtable.is_horizontal());
+        let SubtableKind::Format3(format3) = subtable.kind().unwrap() else {
+            panic!("expected format 3 subtable");
+        };
+        assert_eq!(format3.kerning(gid(0), gid(1)), Some(0));
+        assert_eq!(format3.kerning(gid(0), gid(2)), Some(-30));
+        assert_eq!(format3.kerning(gid(1), gid(0)), Some(40));
+        assert_eq!(format3.kerning(gid(3), gid(3)), Some(0));
+        assert_eq!(format3.kerning(gid(4), gid(0)), None);
+    }
+
+    #[test]
+    fn aat_format1_state_machine() {
+        let buf = BeBuffer::new()
+            .extend([1u16, 0])
+            .push(1u32)
+            // length, coverage (format 1), tuple index
+            .push(50u32)
+            .extend([1u16, 0])
+            // stateSize, classTable, stateArray, entryTable, valueTable
+            .extend([5u16, 10, 16, 26, 38])
+            // class table: glyphs 3..=4
+            .extend([3u16, 2])
+            .extend([4u8, 4])
+            // state array: two states with five classes each
+            .extend([0u8, 0, 0, 0, 1])
+            .extend([0u8, 0, 0, 0, 2])
+            // entry table
+            .extend([16u16, 0])
+            .extend([21u16, 0x8000])
+            .extend([16u16, 0x8000 | 38])
+            // value table
+            .extend([-40i16, 21]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        let subtable = kern.subtables().next().unwrap().unwrap();
+        let SubtableKind::Format1(format1) = subtable.kind().unwrap() else {
+            panic!("expected format 1 subtable");
+        };
+        assert_eq!(format1.class(gid(3)).unwrap(), 4);
+        assert_eq!(format1.class(gid(5)).unwrap(), Subtable1::OUT_OF_BOUNDS);
+        let state = format1.initial_state();
+        let entry = format1.entry(state, 4).unwrap();
+        assert!(entry.push());
+        assert_eq!(entry.value_offset(), 0);
+        let entry = format1.entry(entry.new_state, 4).unwrap();
+        assert!(entry.push());
+        assert!(!entry.dont_advance());
+        assert_eq!(entry.new_state, state);
+        let values = format1.values(entry.value_offset()).collect::<Vec<_>>();
+        assert_eq!(values, [-40, 21]);
+        // Pair lookups are not supported for state machines
+        assert_eq!(subtable.kind().unwrap().kerning(gid(3), gid(4)), None);
+    }
+
+    #[test]
+    fn unknown_version() {
+        let buf = BeBuffer::new().extend([2u16, 0]);
+        assert!(Kern::read(buf.font_data()).is_err());
+    }
+}
@@ -0,0 +1,661 @@
+//! The [kerx (Extended Kerning)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kerx.html) table.
+
+use super::aat::{ExtendedStateTable, LookupU16, LookupU32};
+
+include!("../../generated/generated_kerx.rs");
+
+/// Size of the subtable header.
+const SUBTABLE_HEADER_LEN: usize = 12;
+
+impl<'a> Kerx<'a> {
+    /// Returns an iterator over all of the subtables in this table.
+    pub fn subtables(&self) -> Subtables<'a> {
+        Subtables {
+            data: FontData::new(self.subtable_data()),
+            remaining: self.n_tables(),
+        }
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_subtables(&self) -> FieldType<'a> {
+        FieldType::Array(Box::new(SubtableArray(
+            self.subtables()
+                .filter_map(|subtable| subtable.ok())
+                .collect(),
+        )))
+    }
+}
+
+/// Iterator over the subtables of a `kerx` table.
+#[derive(Clone)]
+pub struct Subtables<'a> {
+    data: FontData<'a>,
+    remaining: u32,
+}
+
+impl<'a> Subtables<'a> {
+    fn read_next(&mut self) -> Result<Subtable<'a>, ReadError> {
+        let len = self.data.read_at::<u32>(0)? as usize;
+        let data = self.data.slice(..len).ok_or(ReadError::OutOfBounds)?;
+        self.data = self.data.split_off(len).unwrap_or_default();
+        Subtable::read(data)
+    }
+}
+
+impl<'a> Iterator for Subtables<'a> {
+    type Item = Result<Subtable<'a>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        let result = self.read_next();
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+}
+
+impl<'a> Subtable<'a> {
+    const VERTICAL: u32 = 0x80000000;
+    const CROSS_STREAM: u32 = 0x40000000;
+    const VARIATION: u32 = 0x20000000;
+
+    /// Returns true if the subtable contains horizontal kerning values.
+    pub fn is_horizontal(&self) -> bool {
+        self.coverage() & Self::VERTICAL == 0
+    }
+
+    /// Returns true if the subtable contains cross-stream kerning values.
+    pub fn is_cross_stream(&self) -> bool {
+        self.coverage() & Self::CROSS_STREAM != 0
+    }
+
+    /// Returns true if the subtable contains variation kerning values.
+    pub fn is_variable(&self) -> bool {
+        self.coverage() & Self::VARIATION != 0
+    }
+
+    /// Returns the subtable format, stored in the low byte of the coverage
+    /// field.
+    pub fn format(&self) -> u8 {
+        self.coverage() as u8
+    }
+
+    /// Returns the format specific subtable.
+    pub fn kind(&self) -> Result<SubtableKind<'a>, ReadError> {
+        let data = FontData::new(self.data());
+        match self.format() {
+            0 => Subtable0::read(data).map(SubtableKind::Format0),
+            1 => Subtable1::read(data).map(SubtableKind::Format1),
+            // Offsets in these formats are relative to the start of the
+            // subtable header
+            2 => Subtable2::read(self.offset_data()).map(SubtableKind::Format2),
+            4 => Subtable4::read(data).map(SubtableKind::Format4),
+            6 => Subtable6::read(self.offset_data()).map(SubtableKind::Format6),
+            format => Err(ReadError::InvalidFormat(format as _)),
+        }
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_kind(&self) -> FieldType<'a> {
+        FieldType::offset(
+            traversal::OffsetType::Offset32(SUBTABLE_HEADER_LEN as u32),
+            self.kind(),
+        )
+    }
+}
+
+/// The various `kerx` subtable formats.
+pub enum SubtableKind<'a> {
+    Format0(Subtable0<'a>),
+    Format1(Subtable1<'a>),
+    Format2(Subtable2<'a>),
+    Format4(Subtable4<'a>),
+    Format6(Subtable6<'a>),
+}
+
+impl<'a> SubtableKind<'a> {
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    ///
+    /// Format 1 and 4 subtables are driven by a state machine that operates
+    /// on the full glyph sequence so this always returns `None` for those.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        match self {
+            Self::Format0(subtable) => subtable.kerning(left, right),
+            Self::Format2(subtable) => subtable.kerning(left, right),
+            Self::Format6(subtable) => subtable.kerning(left, right),
+            Self::Format1(_) | Self::Format4(_) => None,
+        }
+    }
+}
+
+impl<'a> Subtable0<'a> {
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        let pairs = self.pairs();
+        let ix = pairs
+            .binary_search_by(|pair| (pair.left(), pair.right()).cmp(&(left, right)))
+            .ok()?;
+        Some(pairs[ix].value().to_i16() as i32)
+    }
+}
+
+/// The type 1 `kerx` subtable.
+///
+/// This is a state machine driven contextual kerning subtable. The entry
+/// payload is an index into the kerning value table, or `0xFFFF` if no
+/// values are applied.
+pub struct Subtable1<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a, u16>,
+    values: &'a [BigEndian<i16>],
+}
+
+impl<'a> Subtable1<'a> {
+    /// Entry flag: push the current glyph onto the kerning stack.
+    pub const PUSH: u16 = 0x8000;
+    /// Entry flag: reset the cross-stream kerning.
+    pub const RESET: u16 = 0x2000;
+
+    /// Returns the kerning values starting at the given index.
+    ///
+    /// One value is applied to each glyph popped from the kerning stack.
+    pub fn values(&self, index: u16) -> Option<&'a [BigEndian<i16>]> {
+        self.values.get(index as usize..)
+    }
+}
+
+impl<'a> FontRead<'a> for Subtable1<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let offset = data.read_at::<u32>(ExtendedStateTable::<()>::HEADER_LEN)? as usize;
+        let values = data.split_off(offset).ok_or(ReadError::OutOfBounds)?;
+        let len = values.len() / 2 * 2;
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+            values: values.read_array(0..len)?,
+        })
+    }
+}
+
+/// The type 2 `kerx` subtable.
+///
+/// This is a two dimensional array of kerning values indexed by glyph
+/// classes. The offsets in this subtable are relative to the start of the
+/// subtable header so this type is not generated.
+pub struct Subtable2<'a> {
+    data: FontData<'a>,
+    left_class_table: LookupU16<'a>,
+    right_class_table: LookupU16<'a>,
+}
+
+impl<'a> Subtable2<'a> {
+    /// Reads the subtable from data beginning with the subtable header.
+    pub fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let lookup = |offset: u32| {
+            data.split_off(offset as usize)
+                .ok_or(ReadError::OutOfBounds)
+                .and_then(LookupU16::read)
+        };
+        Ok(Self {
+            data,
+            left_class_table: lookup(data.read_at(SUBTABLE_HEADER_LEN + 4)?)?,
+            right_class_table: lookup(data.read_at(SUBTABLE_HEADER_LEN + 8)?)?,
+        })
+    }
+
+    /// The number of bytes in each row of the kerning value array.
+    pub fn row_width(&self) -> u32 {
+        self.data.read_at(SUBTABLE_HEADER_LEN).unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the left-hand class table.
+    pub fn left_offset_table(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 4)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the right-hand class table.
+    pub fn right_offset_table(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 8)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the start of the kerning
+    /// array.
+    pub fn array_offset(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 12)
+            .unwrap_or_default()
+    }
+
+    /// Returns the left-hand class table.
+    ///
+    /// Values in this table are indices of the first value of a row of the
+    /// kerning array.
+    pub fn left_class_table(&self) -> &LookupU16<'a> {
+        &self.left_class_table
+    }
+
+    /// Returns the right-hand class table.
+    ///
+    /// Values in this table are indices of a value within a row of the
+    /// kerning array.
+    pub fn right_class_table(&self) -> &LookupU16<'a> {
+        &self.right_class_table
+    }
+
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        // Glyphs not covered by the class tables use the first row or
+        // column
+        let left = self.left_class_table.value(left).unwrap_or(0) as usize;
+        let right = self.right_class_table.value(right).unwrap_or(0) as usize;
+        let offset = self.array_offset() as usize + (left + right) * 2;
+        Some(self.data.read_at::<i16>(offset).ok()? as i32)
+    }
+}
+
+/// The type 4 `kerx` subtable.
+///
+/// This is a state machine driven subtable that attaches glyphs using
+/// control points or anchor points. The entry payload is an index into the
+/// action table, or `0xFFFF` if no action is performed.
+pub struct Subtable4<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a, u16>,
+    flags: u32,
+    actions: FontData<'a>,
+}
+
+impl<'a> Subtable4<'a> {
+    /// Entry flag: make the current glyph the marked glyph.
+    pub const MARK: u16 = 0x8000;
+    /// Actions are pairs of control point indices.
+    pub const CONTROL_POINT_ACTIONS: u8 = 0;
+    /// Actions are pairs of anchor point indices into the `ankr` table.
+    pub const ANCHOR_POINT_ACTIONS: u8 = 1;
+    /// Actions are pairs of coordinates.
+    pub const CONTROL_POINT_COORDINATE_ACTIONS: u8 = 2;
+
+    /// Returns the subtable flags.
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Returns the type of the actions in this subtable.
+    pub fn action_type(&self) -> u8 {
+        ((self.flags & 0xC0000000) >> 30) as u8
+    }
+
+    /// Returns the action at the given index.
+    pub fn action(&self, index: u16) -> Result<Subtable4Action, ReadError> {
+        let index = index as usize;
+        match self.action_type() {
+            Self::CONTROL_POINT_ACTIONS | Self::ANCHOR_POINT_ACTIONS => {
+                let offset = index * 4;
+                let mark = self.actions.read_at(offset)?;
+                let current = self.actions.read_at(offset + 2)?;
+                Ok(if self.action_type() == Self::CONTROL_POINT_ACTIONS {
+                    Subtable4Action::ControlPoints { mark, current }
+                } else {
+                    Subtable4Action::AnchorPoints { mark, current }
+                })
+            }
+            Self::CONTROL_POINT_COORDINATE_ACTIONS => {
+                let offset = index * 8;
+                Ok(Subtable4Action::Coordinates {
+                    mark: (
+                        self.actions.read_at(offset)?,
+                        self.actions.read_at(offset + 2)?,
+                    ),
+                    current: (
+                        self.actions.read_at(offset + 4)?,
+                        self.actions.read_at(offset + 6)?,
+                    ),
+                })
+            }
+            action_type => Err(ReadError::InvalidFormat(action_type as _)),
+        }
+    }
+}
+
+impl<'a> FontRead<'a> for Subtable4<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let flags = data.read_at::<u32>(ExtendedStateTable::<()>::HEADER_LEN)?;
+        let offset = (flags & 0x00FFFFFF) as usize;
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+            flags,
+            actions: data.split_off(offset).ok_or(ReadError::OutOfBounds)?,
+        })
+    }
+}
+
+/// An action in a type 4 `kerx` subtable which aligns the marked glyph
+/// with the current glyph.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Subtable4Action {
+    /// Indices of control points in the marked and current glyphs.
+    ControlPoints { mark: u16, current: u16 },
+    /// Indices of anchor points, from the `ankr` table, in the marked and
+    /// current glyphs.
+    AnchorPoints { mark: u16, current: u16 },
+    /// Coordinates, in font units, in the marked and current glyphs.
+    Coordinates {
+        mark: (i16, i16),
+        current: (i16, i16),
+    },
+}
+
+/// The type 6 `kerx` subtable.
+///
+/// This is a two dimensional array of kerning values indexed by glyph
+/// specific row and column indices. The offsets in this subtable are
+/// relative to the start of the subtable header so this type is not
+/// generated.
+pub struct Subtable6<'a> {
+    data: FontData<'a>,
+    row_index_table: Subtable6Lookup<'a>,
+    column_index_table: Subtable6Lookup<'a>,
+}
+
+/// Index lookup table in a type 6 subtable.
+enum Subtable6Lookup<'a> {
+    Short(LookupU16<'a>),
+    Long(LookupU32<'a>),
+}
+
+impl<'a> Subtable6Lookup<'a> {
+    fn value(&self, glyph_id: GlyphId) -> Option<u32> {
+        match self {
+            Self::Short(lookup) => lookup.value(glyph_id).map(|value| value as u32),
+            Self::Long(lookup) => lookup.value(glyph_id),
+        }
+    }
+}
+
+impl<'a> Subtable6<'a> {
+    /// Flag indicating that the index lookups and kerning values are 32 bits.
+    pub const VALUES_ARE_LONG: u32 = 0x00000001;
+
+    /// Reads the subtable from data beginning with the subtable header.
+    pub fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let is_long = data.read_at::<u32>(SUBTABLE_HEADER_LEN)? & Self::VALUES_ARE_LONG != 0;
+        let lookup = |field_offset: usize| -> Result<_, ReadError> {
+            let offset = data.read_at::<u32>(field_offset)? as usize;
+            let data = data.split_off(offset).ok_or(ReadError::OutOfBounds)?;
+            Ok(if is_long {
+                Subtable6Lookup::Long(LookupU32::read(data)?)
+            } else {
+                Subtable6Lookup::Short(LookupU16::read(data)?)
+            })
+        };
+        Ok(Self {
+            data,
+            row_index_table: lookup(SUBTABLE_HEADER_LEN + 8)?,
+            column_index_table: lookup(SUBTABLE_HEADER_LEN + 12)?,
+        })
+    }
+
+    /// Returns the subtable flags.
+    pub fn flags(&self) -> u32 {
+        self.data.read_at(SUBTABLE_HEADER_LEN).unwrap_or_default()
+    }
+
+    /// Returns the number of rows in the kerning value array.
+    pub fn row_count(&self) -> u16 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 4)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of columns in the kerning value array.
+    pub fn column_count(&self) -> u16 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 6)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the row index lookup table.
+    pub fn row_index_table_offset(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 8)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the column index lookup
+    /// table.
+    pub fn column_index_table_offset(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 12)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the start of the kerning
+    /// array.
+    pub fn kerning_array_offset(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 16)
+            .unwrap_or_default()
+    }
+
+    /// Offset from beginning of the subtable to the start of the kerning
+    /// vectors, used for variable fonts.
+    pub fn kerning_vector_offset(&self) -> u32 {
+        self.data
+            .read_at(SUBTABLE_HEADER_LEN + 20)
+            .unwrap_or_default()
+    }
+
+    /// Returns the kerning adjustment, in font units, for the given pair of
+    /// glyphs.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i32> {
+        let row = self.row_index_table.value(left).unwrap_or(0) as usize;
+        let column = self.column_index_table.value(right).unwrap_or(0) as usize;
+        let index = row + column;
+        let array_offset = self.kerning_array_offset() as usize;
+        if self.flags() & Self::VALUES_ARE_LONG != 0 {
+            self.data.read_at::<i32>(array_offset + index * 4).ok()
+        } else {
+            self.data
+                .read_at::<i16>(array_offset + index * 2)
+                .ok()
+                .map(|value| value as i32)
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+struct SubtableArray<'a>(Vec<Subtable<'a>>);
+
+#[cfg(feature = "traversal")]
+impl<'a> traversal::SomeArray<'a> for SubtableArray<'a> {
+    fn type_name(&self) -> &str {
+        "Subtable"
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get(&self, idx: usize) -> Option<FieldType<'a>> {
+        let subtable = self.0.get(idx)?.clone();
+        let data = subtable.offset_data();
+        Some(FieldType::Record(RecordResolver {
+            name: "Subtable",
+            get_field: Box::new(move |idx, _| subtable.get_field(idx)),
+            data,
+        }))
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for SubtableKind<'a> {
+    fn type_name(&self) -> &str {
+        match self {
+            Self::Format0(subtable) => subtable.type_name(),
+            Self::Format1(_) => "Subtable1",
+            Self::Format2(_) => "Subtable2",
+            Self::Format4(_) => "Subtable4",
+            Self::Format6(_) => "Subtable6",
+        }
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match self {
+            Self::Format0(subtable) => subtable.get_field(idx),
+            Self::Format1(subtable) => subtable.state_table.get_field(idx),
+            Self::Format2(subtable) => match idx {
+                0usize => Some(Field::new("row_width", subtable.row_width())),
+                1usize => Some(Field::new(
+                    "left_offset_table",
+                    subtable.left_offset_table(),
+                )),
+                2usize => Some(Field::new(
+                    "right_offset_table",
+                    subtable.right_offset_table(),
+                )),
+                3usize => Some(Field::new("array_offset", subtable.array_offset())),
+                _ => None,
+            },
+            Self::Format4(subtable) => subtable.state_table.get_field(idx),
+            Self::Format6(subtable) => match idx {
+                0usize => Some(Field::new("flags", subtable.flags())),
+                1usize => Some(Field::new("row_count", subtable.row_count())),
+                2usize => Some(Field::new("column_count", subtable.column_count())),
+                3usize => Some(Field::new(
+                    "kerning_array_offset",
+                    subtable.kerning_array_offset(),
+                )),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    /// Returns an extended state table with one extra header field and
+    /// a 16-bit payload.
+    ///
+    /// There are five classes and one state: glyph 10 is assigned class 4
+    /// which uses the second entry while all other classes use the first.
+    fn state_table(extra: u32, entries: &[u16]) -> BeBuffer {
+        BeBuffer::new()
+            .extend([5u32, 20, 28, 38, extra])
+            .extend([8u16, 10, 1, 4])
+            .extend([0u16, 0, 0, 0, 1])
+            .extend(entries.iter().copied())
+    }
+
+    #[test]
+    fn kerning_formats() {
+        let buf = BeBuffer::new()
+            .extend([2u16, 0])
+            .push(3u32)
+            // format 0
+            .extend([40u32, 0, 0])
+            .extend([2u32, 0, 0, 0])
+            .extend([1u16, 2])
+            .push(-10i16)
+            .extend([3u16, 4])
+            .push(20i16)
+            // format 2, vertical
+            .extend([56u32, 0x80000002, 0])
+            .extend([4u32, 28, 38, 48])
+            .extend([8u16, 1, 2, 0, 2])
+            .extend([8u16, 5, 2, 0, 1])
+            .extend([0i16, -5, -10, -15])
+            // format 6, cross-stream
+            .extend([64u32, 0x40000006, 0])
+            .push(0u32)
+            .extend([2u16, 2])
+            .extend([36u32, 46, 56, 0])
+            .extend([8u16, 1, 2, 0, 2])
+            .extend([8u16, 5, 2, 0, 1])
+            .extend([1i16, 2, 3, 4]);
+        let kerx = Kerx::read(buf.font_data()).unwrap();
+        let subtables = kerx.subtables().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(subtables.len(), 3);
+        let formats = subtables
+            .iter()
+            .map(|subtable| {
+                (
+                    subtable.format(),
+                    subtable.is_horizontal(),
+                    subtable.is_cross_stream(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            formats,
+            [(0, true, false), (2, false, false), (6, true, true)]
+        );
+        let kinds = subtables
+            .iter()
+            .map(|subtable| subtable.kind().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kinds[0].kerning(gid(1), gid(2)), Some(-10));
+        assert_eq!(kinds[0].kerning(gid(3), gid(4)), Some(20));
+        assert_eq!(kinds[0].kerning(gid(2), gid(1)), None);
+        let pairs = [(1, 5), (1, 6), (2, 5), (2, 6), (3, 5)];
+        let kerning = |kind: &SubtableKind| {
+            pairs
+                .iter()
+                .map(|(left, right)| kind.kerning(gid(*left), gid(*right)).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kerning(&kinds[1]), [0, -5, -10, -15, 0]);
+        assert_eq!(kerning(&kinds[2]), [1, 2, 3, 4, 1]);
+    }
+
+    #[test]
+    fn format1_values() {
+        let buf = state_table(50, &[0, 0, 0xFFFF, 0, 0x8000, 1]).extend([10i16, -20, 31]);
+        let subtable = Subtable1::read(buf.font_data()).unwrap();
+        let entry = subtable.state_table.entry(0, 4).unwrap();
+        assert_ne!(entry.flags & Subtable1::PUSH, 0);
+        let values = subtable.values(entry.payload).unwrap();
+        assert_eq!(values, [-20, 31]);
+        assert!(subtable.values(4).is_none());
+    }
+
+    #[test]
+    fn format4_actions() {
+        let buf =
+            state_table(0x40000000 | 50, &[0, 0, 0xFFFF, 0, 0x8000, 1]).extend([0u16, 1, 2, 3]);
+        let subtable = Subtable4::read(buf.font_data()).unwrap();
+        assert_eq!(subtable.action_type(), Subtable4::ANCHOR_POINT_ACTIONS);
+        let entry = subtable.state_table.entry(0, 4).unwrap();
+        assert_eq!(
+            subtable.action(entry.payload).unwrap(),
+            Subtable4Action::AnchorPoints {
+                mark: 2,
+                current: 3
+            }
+        );
+        assert!(subtable.action(2).is_err());
+        let buf = state_table(0x80000000 | 50, &[0, 0, 0xFFFF, 0, 0x8000, 0])
+            .extend([-1i16, 2, 300, -400]);
+        let subtable = Subtable4::read(buf.font_data()).unwrap();
+        assert_eq!(
+            subtable.action(0).unwrap(),
+            Subtable4Action::Coordinates {
+                mark: (-1, 2),
+                current: (300, -400)
+            }
+        );
+    }
+}
@@ -184,12 +184,18 @@ impl ClassDefFormat1<'_> {
 impl ClassDefFormat2<'_> {
     /// Get the class for this glyph id
     pub fn get(&self, gid: GlyphId) -> u16 {
-        self.class_range_records()
-            .iter()
-            .find_map(|record| {
-                (record.start_glyph_id() >= gid && record.end_glyph_id() <= gid)
-                    .then_some(record.class())
+        let records = self.class_range_records();
+        records
+            .binary_search_by(|rec| {
+                if rec.end_glyph_id() < gid {
+                    Ordering::Less
+                } else if rec.start_glyph_id() > gid {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
             })
+            .map(|idx| records[idx].class())
             .unwrap_or(0)
     }
 }
@@ -235,4 +241,21 @@ mod tests {
         assert_eq!(coverage.get(GlyphId::new(39)), Some(14));
         assert_eq!(coverage.get(GlyphId::new(40)), None);
     }
+
+    #[test]
+    fn classdef_get_format2() {
+        // manually generated, glyphs (5..10) in class 1 and (30..40) in class 2
+        const CLASS2_DATA: FontData =
+            FontData::new(&[0, 2, 0, 2, 0, 5, 0, 9, 0, 1, 0, 30, 0, 39, 0, 2]);
+        let classdef = ClassDefFormat2::read(CLASS2_DATA).unwrap();
+        assert_eq!(classdef.get(GlyphId::new(2)), 0);
+        assert_eq!(classdef.get(GlyphId::new(5)), 1);
+        assert_eq!(classdef.get(GlyphId::new(7)), 1);
+        assert_eq!(classdef.get(GlyphId::new(9)), 1);
+        assert_eq!(classdef.get(GlyphId::new(10)), 0);
+        assert_eq!(classdef.get(GlyphId::new(30)), 2);
+        assert_eq!(classdef.get(GlyphId::new(35)), 2);
+        assert_eq!(classdef.get(GlyphId::new(39)), 2);
+        assert_eq!(classdef.get(GlyphId::new(40)), 0);
+    }
 }
@@ -0,0 +1,229 @@
+//! The [MATH](https://learn.microsoft.com/en-us/typography/opentype/spec/math) table
+
+use super::layout::{CoverageTable, DeviceOrVariationIndex};
+
+include!("../../generated/generated_math.rs");
+
+impl<'a> MathGlyphInfo<'a> {
+    /// Returns true if the glyph is an extended shape.
+    pub fn is_extended_shape(&self, glyph_id: GlyphId) -> bool {
+        self.extended_shape_coverage()
+            .and_then(|coverage| coverage.ok()?.get(glyph_id))
+            .is_some()
+    }
+}
+
+impl<'a> MathItalicsCorrectionInfo<'a> {
+    /// Returns the italics correction value for the given glyph.
+    ///
+    /// The device offset of the returned record is relative to the start
+    /// of this table.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<MathValueRecord> {
+        let ix = self.italics_correction_coverage().ok()?.get(glyph_id)?;
+        self.italics_correction().get(ix as usize).cloned()
+    }
+}
+
+impl<'a> MathTopAccentAttachment<'a> {
+    /// Returns the horizontal position of the top accent attachment point
+    /// for the given glyph.
+    ///
+    /// The device offset of the returned record is relative to the start
+    /// of this table.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<MathValueRecord> {
+        let ix = self.top_accent_coverage().ok()?.get(glyph_id)?;
+        self.top_accent_attachment().get(ix as usize).cloned()
+    }
+}
+
+impl<'a> MathKernInfo<'a> {
+    /// Returns the kerning record for the given glyph.
+    ///
+    /// The offsets in the returned record are relative to the start of this
+    /// table.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<MathKernInfoRecord> {
+        let ix = self.math_kern_coverage().ok()?.get(glyph_id)?;
+        self.math_kern_info_records().get(ix as usize).cloned()
+    }
+}
+
+impl<'a> MathVariants<'a> {
+    /// Returns the construction for the given glyph when growing in the
+    /// vertical direction.
+    pub fn vert_glyph_construction(
+        &self,
+        glyph_id: GlyphId,
+    ) -> Option<Result<MathGlyphConstruction<'a>, ReadError>> {
+        let ix = self.vert_glyph_coverage().ok()?.get(glyph_id)?;
+        Some(self.vert_glyph_constructions().get(ix as usize))
+    }
+
+    /// Returns the construction for the given glyph when growing in the
+    /// horizontal direction.
+    pub fn horiz_glyph_construction(
+        &self,
+        glyph_id: GlyphId,
+    ) -> Option<Result<MathGlyphConstruction<'a>, ReadError>> {
+        let ix = self.horiz_glyph_coverage().ok()?.get(glyph_id)?;
+        Some(self.horiz_glyph_constructions().get(ix as usize))
+    }
+}
+
+impl GlyphPart {
+    /// Returns true if this part can be skipped or repeated.
+    pub fn is_extender(&self) -> bool {
+        self.part_flags().contains(GlyphPartFlags::EXTENDER_FLAG)
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl MathValueRecord {
+    pub(crate) fn traversal_type<'a>(&self, data: FontData<'a>) -> FieldType<'a> {
+        FieldType::Record(self.clone().traverse(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn constants() {
+        let mut buf = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .extend([10u16, 0, 0])
+            // percent scale down, min heights
+            .extend([80i16, 60])
+            .extend([1500u16, 1300]);
+        // math leading with a device table at offset 214
+        buf = buf.extend([150i16, 214]);
+        for i in 1..51i16 {
+            buf = buf.extend([i * 10, 0]);
+        }
+        let buf = buf
+            .push(60i16)
+            // device: size 12, 2-bit deltas
+            .extend([12u16, 12, 1, 0x4000]);
+        let math = Math::read(buf.font_data()).unwrap();
+        let constants = math.math_constants().unwrap();
+        assert_eq!(constants.script_percent_scale_down(), 80);
+        assert_eq!(constants.script_script_percent_scale_down(), 60);
+        assert_eq!(constants.delimited_sub_formula_min_height().to_u16(), 1500);
+        assert_eq!(constants.display_operator_min_height().to_u16(), 1300);
+        let leading = constants.math_leading();
+        assert_eq!(leading.value().to_i16(), 150);
+        assert!(matches!(
+            leading.device(constants.offset_data()),
+            Some(Ok(DeviceOrVariationIndex::Device(_)))
+        ));
+        assert_eq!(constants.axis_height().value().to_i16(), 10);
+        assert!(constants.axis_height().device_offset().is_null());
+        assert_eq!(constants.radical_kern_after_degree().value().to_i16(), 500);
+        assert_eq!(constants.radical_degree_bottom_raise_percent(), 60);
+    }
+
+    #[test]
+    fn glyph_info() {
+        let buf = BeBuffer::new()
+            // offsets: italics correction, top accent, extended shapes, kern info
+            .extend([8u16, 28, 42, 48])
+            // italics correction info: coverage at 12, 2 values
+            .extend([12u16, 2])
+            .extend([50i16, 0, -20, 0])
+            // coverage format 1: glyphs 3, 7
+            .extend([1u16, 2, 3, 7])
+            // top accent attachment: coverage at 8, 1 value with device at 52
+            .extend([8u16, 1])
+            .extend([300i16, 52])
+            // coverage format 1: glyph 7
+            .extend([1u16, 1, 7])
+            // extended shapes: glyph 9
+            .extend([1u16, 1, 9])
+            // kern info: coverage at 12, 1 record with top right kern at 18
+            .extend([12u16, 1])
+            .extend([18u16, 0, 0, 0])
+            // coverage format 1: glyph 3
+            .extend([1u16, 1, 3])
+            // math kern: 1 height, 2 values
+            .extend([1u16, 100, 0])
+            .extend([-10i16, 0, -30, 0])
+            // device: size 12, 2-bit deltas
+            .extend([12u16, 12, 1, 0x4000]);
+        let info = MathGlyphInfo::read(buf.font_data()).unwrap();
+        let italics = info.math_italics_correction_info().unwrap().unwrap();
+        assert_eq!(italics.get(GlyphId::new(3)).unwrap().value().to_i16(), 50);
+        assert_eq!(italics.get(GlyphId::new(7)).unwrap().value().to_i16(), -20);
+        assert!(italics.get(GlyphId::new(4)).is_none());
+        let accents = info.math_top_accent_attachment().unwrap().unwrap();
+        let accent = accents.get(GlyphId::new(7)).unwrap();
+        assert_eq!(accent.value().to_i16(), 300);
+        assert!(accent.device(accents.offset_data()).is_some());
+        assert!(accents.get(GlyphId::new(3)).is_none());
+        assert!(info.is_extended_shape(GlyphId::new(9)));
+        assert!(!info.is_extended_shape(GlyphId::new(3)));
+        let kern_info = info.math_kern_info().unwrap().unwrap();
+        let record = kern_info.get(GlyphId::new(3)).unwrap();
+        assert!(record.top_left_math_kern(kern_info.offset_data()).is_none());
+        let kern = record
+            .top_right_math_kern(kern_info.offset_data())
+            .unwrap()
+            .unwrap();
+        let heights = kern
+            .correction_height()
+            .iter()
+            .map(|height| height.value().to_i16())
+            .collect::<Vec<_>>();
+        let values = kern
+            .kern_values()
+            .iter()
+            .map(|value| value.value().to_i16())
+            .collect::<Vec<_>>();
+        assert_eq!(heights, [100]);
+        assert_eq!(values, [-10, -30]);
+    }
+
+    #[test]
+    fn variants() {
+        let buf = BeBuffer::new()
+            // min overlap, vert coverage, horiz coverage, counts, offsets
+            .extend([20u16, 12, 18, 1, 0, 22])
+            // vert coverage: glyph 5
+            .extend([1u16, 1, 5])
+            // horiz coverage: empty
+            .extend([1u16, 0])
+            // construction: assembly at 12, 2 variants
+            .extend([12u16, 2])
+            .extend([6u16, 500, 8, 1000])
+            // assembly: italics correction, 2 parts
+            .extend([0u16, 0, 2])
+            .extend([10u16, 0, 100, 400, 0])
+            .extend([11u16, 100, 100, 300, 1]);
+        let variants = MathVariants::read(buf.font_data()).unwrap();
+        assert_eq!(variants.min_connector_overlap(), UfWord::new(20));
+        assert!(variants.horiz_glyph_construction(GlyphId::new(5)).is_none());
+        assert!(variants.vert_glyph_construction(GlyphId::new(4)).is_none());
+        let construction = variants
+            .vert_glyph_construction(GlyphId::new(5))
+            .unwrap()
+            .unwrap();
+        let sizes = construction
+            .math_glyph_variant_records()
+            .iter()
+            .map(|record| {
+                (
+                    record.variant_glyph().to_u16(),
+                    record.advance_measurement().to_u16(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [(6, 500), (8, 1000)]);
+        let assembly = construction.glyph_assembly().unwrap().unwrap();
+        let parts = assembly
+            .part_records()
+            .iter()
+            .map(|part| (part.glyph_id().to_u16(), part.is_extender()))
+            .collect::<Vec<_>>();
+        assert_eq!(parts, [(10, false), (11, true)]);
+    }
+}
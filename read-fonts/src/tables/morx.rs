@@ -0,0 +1,603 @@
+//! The [morx (Extended Glyph Metamorphosis)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6morx.html) table.
+
+use super::aat::{EntryPayload, ExtendedStateTable, LookupGlyphId};
+
+include!("../../generated/generated_morx.rs");
+
+/// Size of the subtable header.
+#[cfg(feature = "traversal")]
+const SUBTABLE_HEADER_LEN: usize = 12;
+
+impl<'a> Morx<'a> {
+    /// Returns an iterator over all of the chains in this table.
+    pub fn chains(&self) -> Chains<'a> {
+        Chains {
+            data: FontData::new(self.chain_data()),
+            remaining: self.n_chains(),
+        }
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_chains(&self) -> FieldType<'a> {
+        traverse_tables("Chain", self.chains())
+    }
+}
+
+/// Iterator over the chains of a `morx` table.
+#[derive(Clone)]
+pub struct Chains<'a> {
+    data: FontData<'a>,
+    remaining: u32,
+}
+
+impl<'a> Iterator for Chains<'a> {
+    type Item = Result<Chain<'a>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        let result = read_sized(&mut self.data, 4).and_then(Chain::read);
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+}
+
+impl<'a> Chain<'a> {
+    /// Returns an iterator over all of the subtables in this chain.
+    pub fn subtables(&self) -> Subtables<'a> {
+        Subtables {
+            data: FontData::new(self.subtable_data()),
+            remaining: self.n_subtables(),
+        }
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_subtables(&self) -> FieldType<'a> {
+        traverse_tables("Subtable", self.subtables())
+    }
+}
+
+/// Iterator over the subtables of a `morx` chain.
+#[derive(Clone)]
+pub struct Subtables<'a> {
+    data: FontData<'a>,
+    remaining: u32,
+}
+
+impl<'a> Iterator for Subtables<'a> {
+    type Item = Result<Subtable<'a>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        let result = read_sized(&mut self.data, 0).and_then(Subtable::read);
+        if result.is_err() {
+            self.remaining = 0;
+        }
+        Some(result)
+    }
+}
+
+/// Splits off the next item from the front of `data`, using the 32-bit
+/// length stored at `len_offset`.
+fn read_sized<'a>(data: &mut FontData<'a>, len_offset: usize) -> Result<FontData<'a>, ReadError> {
+    let len = data.read_at::<u32>(len_offset)? as usize;
+    let item = data.slice(..len).ok_or(ReadError::OutOfBounds)?;
+    *data = data.split_off(len).unwrap_or_default();
+    Ok(item)
+}
+
+impl<'a> Subtable<'a> {
+    /// Subtable only applies to vertical text.
+    pub const VERTICAL: u32 = 0x80000000;
+    /// Subtable is processed in descending order.
+    pub const DESCENDING: u32 = 0x40000000;
+    /// Subtable applies to text of any direction.
+    pub const ANY_DIRECTION: u32 = 0x20000000;
+    /// Subtable is processed in logical rather than layout order.
+    pub const LOGICAL: u32 = 0x10000000;
+
+    /// Returns true if the subtable only applies to vertical text.
+    ///
+    /// This is ignored if [`is_any_direction`](Self::is_any_direction) is
+    /// true.
+    pub fn is_vertical(&self) -> bool {
+        self.coverage() & Self::VERTICAL != 0
+    }
+
+    /// Returns true if the glyphs should be processed in descending order.
+    pub fn is_descending(&self) -> bool {
+        self.coverage() & Self::DESCENDING != 0
+    }
+
+    /// Returns true if the subtable applies to text of any direction.
+    pub fn is_any_direction(&self) -> bool {
+        self.coverage() & Self::ANY_DIRECTION != 0
+    }
+
+    /// Returns true if the glyphs should be processed in logical order,
+    /// where [`is_descending`](Self::is_descending) refers to reverse
+    /// logical order.
+    pub fn is_logical(&self) -> bool {
+        self.coverage() & Self::LOGICAL != 0
+    }
+
+    /// Returns the subtable type, stored in the low byte of the coverage
+    /// field.
+    pub fn subtable_type(&self) -> u8 {
+        self.coverage() as u8
+    }
+
+    /// Returns the type specific subtable.
+    pub fn kind(&self) -> Result<SubtableKind<'a>, ReadError> {
+        let data = FontData::new(self.data());
+        match self.subtable_type() {
+            0 => RearrangementSubtable::read(data).map(SubtableKind::Rearrangement),
+            1 => ContextualSubtable::read(data).map(SubtableKind::Contextual),
+            2 => LigatureSubtable::read(data).map(SubtableKind::Ligature),
+            4 => LookupGlyphId::read(data).map(SubtableKind::NonContextual),
+            5 => InsertionSubtable::read(data).map(SubtableKind::Insertion),
+            other => Err(ReadError::InvalidFormat(other as _)),
+        }
+    }
+
+    #[cfg(feature = "traversal")]
+    fn traverse_kind(&self) -> FieldType<'a> {
+        FieldType::offset(
+            traversal::OffsetType::Offset32(SUBTABLE_HEADER_LEN as u32),
+            self.kind(),
+        )
+    }
+}
+
+/// The various `morx` subtable types.
+pub enum SubtableKind<'a> {
+    Rearrangement(RearrangementSubtable<'a>),
+    Contextual(ContextualSubtable<'a>),
+    Ligature(LigatureSubtable<'a>),
+    NonContextual(LookupGlyphId<'a>),
+    Insertion(InsertionSubtable<'a>),
+}
+
+/// The rearrangement subtable.
+///
+/// This is a state machine that reorders a marked range of glyphs.
+pub struct RearrangementSubtable<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a>,
+}
+
+impl<'a> RearrangementSubtable<'a> {
+    /// Entry flag: the current glyph is the first glyph in the marked range.
+    pub const MARK_FIRST: u16 = 0x8000;
+    /// Entry flag: the current glyph is the last glyph in the marked range.
+    pub const MARK_LAST: u16 = 0x2000;
+    /// Entry flag mask for the rearrangement verb.
+    pub const VERB: u16 = 0x000F;
+}
+
+impl<'a> FontRead<'a> for RearrangementSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+        })
+    }
+}
+
+impl EntryPayload for ContextualEntryData {
+    const SIZE: usize = Self::RAW_BYTE_LEN;
+
+    fn read_at(data: FontData, offset: usize) -> Result<Self, ReadError> {
+        data.read_ref_at::<Self>(offset).cloned()
+    }
+}
+
+/// The contextual glyph substitution subtable.
+pub struct ContextualSubtable<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a, ContextualEntryData>,
+    substitution_table_offset: u32,
+    substitution_table: FontData<'a>,
+}
+
+impl<'a> ContextualSubtable<'a> {
+    /// Entry flag: make the current glyph the marked glyph.
+    pub const SET_MARK: u16 = 0x8000;
+
+    /// Returns the byte offset from the start of the subtable to the array
+    /// of offsets to substitution lookups.
+    pub fn substitution_table_offset(&self) -> u32 {
+        self.substitution_table_offset
+    }
+
+    /// Returns the substitution lookup at the given index, as referenced by
+    /// [`ContextualEntryData`].
+    pub fn substitution_lookup(&self, index: u16) -> Result<LookupGlyphId<'a>, ReadError> {
+        let offset = self.substitution_table.read_at::<u32>(index as usize * 4)? as usize;
+        self.substitution_table
+            .split_off(offset)
+            .ok_or(ReadError::OutOfBounds)
+            .and_then(LookupGlyphId::read)
+    }
+}
+
+impl<'a> FontRead<'a> for ContextualSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let substitution_table_offset =
+            data.read_at::<u32>(ExtendedStateTable::<()>::HEADER_LEN)?;
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+            substitution_table_offset,
+            substitution_table: data
+                .split_off(substitution_table_offset as usize)
+                .ok_or(ReadError::OutOfBounds)?,
+        })
+    }
+}
+
+/// The ligature subtable.
+///
+/// The entry payload is an index into the ligature action table.
+pub struct LigatureSubtable<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a, u16>,
+    ligature_actions: &'a [BigEndian<u32>],
+    components: &'a [BigEndian<u16>],
+    ligatures: &'a [BigEndian<GlyphId>],
+}
+
+impl<'a> LigatureSubtable<'a> {
+    /// Entry flag: push the current glyph onto the component stack.
+    pub const SET_COMPONENT: u16 = 0x8000;
+    /// Entry flag: perform the ligature actions referenced by the entry.
+    pub const PERFORM_ACTION: u16 = 0x2000;
+
+    /// Returns the ligature action at the given index.
+    pub fn action(&self, index: u16) -> Option<LigatureAction> {
+        self.ligature_actions
+            .get(index as usize)
+            .map(|action| LigatureAction(action.get()))
+    }
+
+    /// Returns the value of the component table at the given index.
+    pub fn component(&self, index: usize) -> Option<u16> {
+        self.components.get(index).map(|value| value.get())
+    }
+
+    /// Returns the ligature glyph at the given index.
+    pub fn ligature(&self, index: usize) -> Option<GlyphId> {
+        self.ligatures.get(index).map(|glyph| glyph.get())
+    }
+}
+
+impl<'a> FontRead<'a> for LigatureSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let header_len = ExtendedStateTable::<()>::HEADER_LEN;
+        // The sizes of these arrays are not stored so each extends to the
+        // end of the subtable
+        let array_at = |field: usize| {
+            let offset = data.read_at::<u32>(header_len + field * 4)? as usize;
+            data.split_off(offset).ok_or(ReadError::OutOfBounds)
+        };
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+            ligature_actions: trailing_array(array_at(0)?),
+            components: trailing_array(array_at(1)?),
+            ligatures: trailing_array(array_at(2)?),
+        })
+    }
+}
+
+/// Returns an array of `T` covering all complete items in `data`.
+fn trailing_array<'a, T: FromBytes>(data: FontData<'a>) -> &'a [T] {
+    let len = data.len() / T::RAW_BYTE_LEN * T::RAW_BYTE_LEN;
+    data.read_array(0..len).unwrap_or_default()
+}
+
+/// An action in the ligature subtable.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LigatureAction(pub u32);
+
+impl LigatureAction {
+    /// This is the last action in the list.
+    pub const LAST: u32 = 0x80000000;
+    /// Store the ligature at the current cumulated index in the ligature
+    /// table in place of the marked glyph.
+    pub const STORE: u32 = 0x40000000;
+    /// Mask for the offset to the component table.
+    pub const OFFSET: u32 = 0x3FFFFFFF;
+
+    /// Returns true if this is the last action in the list.
+    pub fn is_last(self) -> bool {
+        self.0 & Self::LAST != 0
+    }
+
+    /// Returns true if the ligature should be stored.
+    pub fn is_store(self) -> bool {
+        self.0 & Self::STORE != 0
+    }
+
+    /// Returns the signed offset, which is added to the glyph popped from
+    /// the component stack to form an index into the component table.
+    pub fn component_offset(self) -> i32 {
+        // Sign extend the 30-bit value
+        ((self.0 << 2) as i32) >> 2
+    }
+}
+
+impl EntryPayload for InsertionEntryData {
+    const SIZE: usize = Self::RAW_BYTE_LEN;
+
+    fn read_at(data: FontData, offset: usize) -> Result<Self, ReadError> {
+        data.read_ref_at::<Self>(offset).cloned()
+    }
+}
+
+/// The insertion subtable.
+pub struct InsertionSubtable<'a> {
+    /// The state machine.
+    pub state_table: ExtendedStateTable<'a, InsertionEntryData>,
+    insertion_glyphs: &'a [BigEndian<GlyphId>],
+}
+
+impl<'a> InsertionSubtable<'a> {
+    /// Entry flag: make the current glyph the marked glyph.
+    pub const SET_MARK: u16 = 0x8000;
+    /// Entry flag: the glyphs inserted at the current position are
+    /// kashida-like.
+    pub const CURRENT_IS_KASHIDA_LIKE: u16 = 0x2000;
+    /// Entry flag: the glyphs inserted at the marked position are
+    /// kashida-like.
+    pub const MARKED_IS_KASHIDA_LIKE: u16 = 0x1000;
+    /// Entry flag: insert the current glyphs before rather than after the
+    /// current glyph.
+    pub const CURRENT_INSERT_BEFORE: u16 = 0x0800;
+    /// Entry flag: insert the marked glyphs before rather than after the
+    /// marked glyph.
+    pub const MARKED_INSERT_BEFORE: u16 = 0x0400;
+    /// Entry flag mask for the number of glyphs to insert at the current
+    /// position.
+    pub const CURRENT_INSERT_COUNT: u16 = 0x03E0;
+    /// Entry flag mask for the number of glyphs to insert at the marked
+    /// position.
+    pub const MARKED_INSERT_COUNT: u16 = 0x001F;
+
+    /// Returns the number of glyphs to insert at the current position for
+    /// the given entry flags.
+    pub fn current_insert_count(flags: u16) -> usize {
+        ((flags & Self::CURRENT_INSERT_COUNT) >> 5) as usize
+    }
+
+    /// Returns the number of glyphs to insert at the marked position for
+    /// the given entry flags.
+    pub fn marked_insert_count(flags: u16) -> usize {
+        (flags & Self::MARKED_INSERT_COUNT) as usize
+    }
+
+    /// Returns `count` glyphs starting at the given index of the insertion
+    /// glyph table.
+    pub fn glyphs(&self, index: u16, count: usize) -> Option<&'a [BigEndian<GlyphId>]> {
+        let start = index as usize;
+        self.insertion_glyphs.get(start..start.checked_add(count)?)
+    }
+}
+
+impl<'a> FontRead<'a> for InsertionSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let offset = data.read_at::<u32>(ExtendedStateTable::<()>::HEADER_LEN)? as usize;
+        let insertion_glyphs = data.split_off(offset).ok_or(ReadError::OutOfBounds)?;
+        Ok(Self {
+            state_table: ExtendedStateTable::read(data)?,
+            insertion_glyphs: trailing_array(insertion_glyphs),
+        })
+    }
+}
+
+#[cfg(feature = "traversal")]
+fn traverse_tables<'a, M: Clone + 'a>(
+    type_name: &'static str,
+    items: impl Iterator<Item = Result<TableRef<'a, M>, ReadError>>,
+) -> FieldType<'a>
+where
+    TableRef<'a, M>: SomeTable<'a>,
+{
+    FieldType::Array(Box::new(TableArray(
+        type_name,
+        items.filter_map(|item| item.ok()).collect(),
+    )))
+}
+
+#[cfg(feature = "traversal")]
+struct TableArray<'a, M>(&'static str, Vec<TableRef<'a, M>>);
+
+#[cfg(feature = "traversal")]
+impl<'a, M: Clone + 'a> traversal::SomeArray<'a> for TableArray<'a, M>
+where
+    TableRef<'a, M>: SomeTable<'a>,
+{
+    fn type_name(&self) -> &str {
+        self.0
+    }
+
+    fn len(&self) -> usize {
+        self.1.len()
+    }
+
+    fn get(&self, idx: usize) -> Option<FieldType<'a>> {
+        let item = self.1.get(idx)?.clone();
+        let data = item.offset_data();
+        Some(FieldType::Record(RecordResolver {
+            name: self.0,
+            get_field: Box::new(move |idx, _| item.get_field(idx)),
+            data,
+        }))
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for SubtableKind<'a> {
+    fn type_name(&self) -> &str {
+        match self {
+            Self::Rearrangement(_) => "RearrangementSubtable",
+            Self::Contextual(_) => "ContextualSubtable",
+            Self::Ligature(_) => "LigatureSubtable",
+            Self::NonContextual(lookup) => lookup.type_name(),
+            Self::Insertion(_) => "InsertionSubtable",
+        }
+    }
+
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match self {
+            Self::Rearrangement(subtable) => subtable.state_table.get_field(idx),
+            Self::Contextual(subtable) => subtable.state_table.get_field(idx),
+            Self::Ligature(subtable) => subtable.state_table.get_field(idx),
+            Self::NonContextual(lookup) => lookup.get_field(idx),
+            Self::Insertion(subtable) => subtable.state_table.get_field(idx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::aat::StateEntry;
+    use crate::test_helpers::BeBuffer;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    /// Returns an extended state table with the given extra header fields
+    /// and entries.
+    ///
+    /// There are five classes and one state: glyph 10 is assigned class 4
+    /// which uses the second entry while all other classes use the first.
+    fn state_table(extra: &[u32], entries: &[u16]) -> BeBuffer {
+        let header_len = 16 + extra.len() as u32 * 4;
+        BeBuffer::new()
+            .extend([5, header_len, header_len + 8, header_len + 18])
+            .extend(extra.iter().copied())
+            .extend([8u16, 10, 1, 4])
+            .extend([0u16, 0, 0, 0, 1])
+            .extend(entries.iter().copied())
+    }
+
+    #[test]
+    fn chains_and_subtables() {
+        let rearrangement = state_table(&[], &[0, 0, 0, 0xA003]);
+        let buf = BeBuffer::new()
+            .extend([2u16, 0])
+            .push(1u32)
+            // chain header with one feature
+            .extend([1u32, 16 + 12 + 22 + 12 + rearrangement.len() as u32, 1, 2])
+            .extend([1u16, 0])
+            .extend([1u32, 0xFFFFFFFE])
+            // noncontextual subtable mapping glyphs 5 and 6
+            .extend([22u32, 0x20000004, 1])
+            .extend([8u16, 5, 2, 50, 51])
+            // rearrangement subtable
+            .extend([12 + rearrangement.len() as u32, 0x80000000, 1])
+            .extend(rearrangement.iter().copied());
+        let morx = Morx::read(buf.font_data()).unwrap();
+        assert_eq!(morx.version(), 2);
+        let chains = morx.chains().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(chains.len(), 1);
+        let chain = &chains[0];
+        assert_eq!(chain.default_flags(), 1);
+        let feature = &chain.features()[0];
+        assert_eq!(feature.feature_type(), 1);
+        assert_eq!(feature.disable_flags(), 0xFFFFFFFE);
+        let subtables = chain.subtables().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(subtables.len(), 2);
+        let noncontextual = &subtables[0];
+        assert!(noncontextual.is_any_direction());
+        assert!(!noncontextual.is_vertical());
+        assert_eq!(noncontextual.subtable_type(), 4);
+        let Ok(SubtableKind::NonContextual(lookup)) = noncontextual.kind() else {
+            panic!("expected noncontextual subtable");
+        };
+        assert_eq!(lookup.value(gid(6)), Some(gid(51)));
+        assert_eq!(lookup.value(gid(7)), None);
+        let rearrangement = &subtables[1];
+        assert!(rearrangement.is_vertical());
+        let Ok(SubtableKind::Rearrangement(subtable)) = rearrangement.kind() else {
+            panic!("expected rearrangement subtable");
+        };
+        let entry = subtable
+            .state_table
+            .entry(0, subtable.state_table.class(gid(10)));
+        let flags = entry.unwrap().flags;
+        assert_ne!(flags & RearrangementSubtable::MARK_FIRST, 0);
+        assert_ne!(flags & RearrangementSubtable::MARK_LAST, 0);
+        assert_eq!(flags & RearrangementSubtable::VERB, 3);
+    }
+
+    #[test]
+    fn contextual() {
+        let buf = state_table(&[54], &[0, 0, 0xFFFF, 0xFFFF, 0, 0x8000, 0xFFFF, 0])
+            .push(4u32)
+            .extend([8u16, 10, 1, 20]);
+        let subtable = ContextualSubtable::read(buf.font_data()).unwrap();
+        assert_eq!(subtable.substitution_table_offset(), 54);
+        let entry = subtable.state_table.entry(0, 4).unwrap();
+        assert_ne!(entry.flags & ContextualSubtable::SET_MARK, 0);
+        assert_eq!(entry.payload.mark_index(), 0xFFFF);
+        let lookup = subtable
+            .substitution_lookup(entry.payload.current_index())
+            .unwrap();
+        assert_eq!(lookup.value(gid(10)), Some(gid(20)));
+        assert!(subtable.substitution_lookup(1).is_err());
+    }
+
+    #[test]
+    fn ligature() {
+        let buf = state_table(&[58, 66, 72], &[0, 0, 0, 0, 0xA000, 0])
+            .extend([0x3FFFFFFFu32, 0xC0000002])
+            .extend([0u16, 1, 2])
+            .extend([100u16, 101]);
+        let subtable = LigatureSubtable::read(buf.font_data()).unwrap();
+        let entry = subtable.state_table.entry(0, 4).unwrap();
+        assert_ne!(entry.flags & LigatureSubtable::SET_COMPONENT, 0);
+        assert_ne!(entry.flags & LigatureSubtable::PERFORM_ACTION, 0);
+        let first = subtable.action(entry.payload).unwrap();
+        assert!(!first.is_last());
+        assert!(!first.is_store());
+        assert_eq!(first.component_offset(), -1);
+        let second = subtable.action(entry.payload + 1).unwrap();
+        assert!(second.is_last());
+        assert!(second.is_store());
+        assert_eq!(second.component_offset(), 2);
+        assert_eq!(subtable.action(5), None);
+        assert_eq!(subtable.component(2), Some(2));
+        assert_eq!(subtable.ligature(1), Some(gid(101)));
+        assert_eq!(subtable.ligature(2), None);
+    }
+
+    #[test]
+    fn insertion() {
+        let flags = InsertionSubtable::CURRENT_INSERT_BEFORE | (2 << 5) | 1;
+        let buf = state_table(&[54], &[0, 0, 0xFFFF, 0xFFFF, 0, flags, 0, 2]).extend([7u16, 8, 9]);
+        let subtable = InsertionSubtable::read(buf.font_data()).unwrap();
+        let StateEntry { flags, payload, .. } = subtable.state_table.entry(0, 4).unwrap();
+        assert_ne!(flags & InsertionSubtable::CURRENT_INSERT_BEFORE, 0);
+        let current_count = InsertionSubtable::current_insert_count(flags);
+        let marked_count = InsertionSubtable::marked_insert_count(flags);
+        assert_eq!((current_count, marked_count), (2, 1));
+        let glyphs = |index, count| {
+            subtable
+                .glyphs(index, count)
+                .map(|glyphs| glyphs.iter().map(|g| g.get().to_u16()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            glyphs(payload.current_insert_index(), current_count),
+            Some(vec![7, 8])
+        );
+        assert_eq!(
+            glyphs(payload.marked_insert_index(), marked_count),
+            Some(vec![9])
+        );
+        assert_eq!(glyphs(2, 2), None);
+        let entry = subtable.state_table.entry(0, 0).unwrap();
+        assert_eq!(entry.payload.current_insert_index(), 0xFFFF);
+    }
+}
@@ -3,6 +3,8 @@
 use std::fmt;
 
 mod blend;
+mod charset;
+mod encoding;
 mod fd_select;
 mod index;
 mod stack;
@@ -14,6 +16,8 @@ pub mod dict;
 include!("../../generated/generated_postscript.rs");
 
 pub use blend::BlendState;
+pub use charset::{Charset, CharsetIter};
+pub use encoding::{Encoding, STANDARD_ENCODING};
 pub use index::Index;
 pub use stack::{Number, Stack};
 pub use string::{Latin1String, StringId, STANDARD_STRINGS};
@@ -36,6 +40,8 @@ pub enum Error {
     MissingBlendState,
     MissingPrivateDict,
     MissingCharstrings,
+    MissingSeacContext,
+    InvalidSeacCode(i32),
     Read(ReadError),
 }
 
@@ -106,6 +112,15 @@ impl fmt::Display for Error {
             Self::MissingCharstrings => {
                 write!(f, "CFF table does not contain a charstrings index")
             }
+            Self::MissingSeacContext => {
+                write!(
+                    f,
+                    "encountered an implied seac operator but no charstrings or charset were provided"
+                )
+            }
+            Self::InvalidSeacCode(code) => {
+                write!(f, "seac code {code} does not map to a glyph in the font")
+            }
             Self::Read(err) => write!(f, "{err}"),
         }
     }
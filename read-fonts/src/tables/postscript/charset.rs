@@ -0,0 +1,426 @@
+//! Parsing for CFF charsets.
+
+use types::GlyphId;
+
+use super::{CustomCharset, Error, StringId};
+use crate::{FontData, FontRead, ReadError};
+
+/// Mapping between glyph identifiers and string identifiers.
+///
+/// In a CFF font, glyph names are stored as string identifiers that are
+/// associated with each glyph by the charset.
+///
+/// In CID-keyed fonts, the charset instead maps glyph identifiers to
+/// CIDs. The values are stored in the same format so the methods that
+/// produce or accept string identifiers can be used with the raw CID
+/// values. The [`cid`](Self::cid) and
+/// [`glyph_id_for_cid`](Self::glyph_id_for_cid) methods are provided for
+/// convenience.
+///
+/// See "Charsets" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=21>
+#[derive(Clone)]
+pub struct Charset<'a> {
+    kind: CharsetKind<'a>,
+    num_glyphs: u32,
+    is_cid: bool,
+}
+
+impl<'a> Charset<'a> {
+    /// Creates a new charset from the offset in the top DICT.
+    ///
+    /// The `cff_data` parameter should contain the full `CFF` table and
+    /// `num_glyphs` is the number of charstrings in the font.
+    pub fn new(
+        cff_data: FontData<'a>,
+        charset_offset: usize,
+        num_glyphs: u32,
+    ) -> Result<Self, Error> {
+        Self::new_impl(cff_data, charset_offset, num_glyphs, false)
+    }
+
+    /// Creates a new charset for a CID-keyed font from the offset in the
+    /// top DICT.
+    ///
+    /// The `cff_data` parameter should contain the full `CFF` table and
+    /// `num_glyphs` is the number of charstrings in the font.
+    pub fn new_cid(
+        cff_data: FontData<'a>,
+        charset_offset: usize,
+        num_glyphs: u32,
+    ) -> Result<Self, Error> {
+        Self::new_impl(cff_data, charset_offset, num_glyphs, true)
+    }
+
+    fn new_impl(
+        cff_data: FontData<'a>,
+        charset_offset: usize,
+        num_glyphs: u32,
+        is_cid: bool,
+    ) -> Result<Self, Error> {
+        let kind = match charset_offset {
+            0 => CharsetKind::IsoAdobe,
+            1 => CharsetKind::Expert,
+            2 => CharsetKind::ExpertSubset,
+            _ => {
+                let data = cff_data
+                    .split_off(charset_offset)
+                    .ok_or(ReadError::OutOfBounds)?;
+                CharsetKind::Custom(CustomCharset::read(data)?)
+            }
+        };
+        Ok(Self {
+            kind,
+            num_glyphs,
+            is_cid,
+        })
+    }
+
+    /// Returns the number of glyphs covered by the charset.
+    pub fn num_glyphs(&self) -> u32 {
+        self.num_glyphs
+    }
+
+    /// Returns true if the charset belongs to a CID-keyed font and maps
+    /// glyph identifiers to CIDs.
+    pub fn is_cid(&self) -> bool {
+        self.is_cid
+    }
+
+    /// Returns an iterator over all pairs of glyph identifiers and
+    /// string identifiers (or CIDs) in the charset.
+    pub fn iter(&self) -> CharsetIter<'a> {
+        CharsetIter {
+            charset: self.clone(),
+            cur: 0,
+            range_ix: 0,
+            range_offset: 0,
+        }
+    }
+
+    /// Returns the CID for the given glyph identifier.
+    ///
+    /// Returns `None` if the charset does not belong to a CID-keyed font.
+    pub fn cid(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.is_cid
+            .then(|| self.string_id(glyph_id))
+            .flatten()
+            .map(|sid| sid.to_u16())
+    }
+
+    /// Returns the glyph identifier for the given CID.
+    ///
+    /// Returns `None` if the charset does not belong to a CID-keyed font.
+    pub fn glyph_id_for_cid(&self, cid: u16) -> Option<GlyphId> {
+        self.is_cid
+            .then(|| self.glyph_id(StringId::new(cid)))
+            .flatten()
+    }
+
+    /// Returns the string identifier for the given glyph identifier.
+    pub fn string_id(&self, glyph_id: GlyphId) -> Option<StringId> {
+        let gid = glyph_id.to_u16();
+        if gid as u32 >= self.num_glyphs {
+            return None;
+        }
+        // The first glyph is always .notdef and is omitted from the data.
+        if gid == 0 {
+            return Some(StringId::new(0));
+        }
+        match &self.kind {
+            CharsetKind::IsoAdobe => {
+                // The ISOAdobe charset is an identity mapping of all glyphs
+                // up to SID 228.
+                (gid <= 228).then_some(StringId::new(gid))
+            }
+            CharsetKind::Expert => EXPERT_CHARSET.get(gid as usize).copied().map(StringId::new),
+            CharsetKind::ExpertSubset => EXPERT_SUBSET_CHARSET
+                .get(gid as usize)
+                .copied()
+                .map(StringId::new),
+            CharsetKind::Custom(custom) => match custom {
+                CustomCharset::Format0(fmt) => fmt
+                    .glyph()
+                    .get(gid as usize - 1)
+                    .map(|sid| StringId::new(sid.get())),
+                CustomCharset::Format1(fmt) => {
+                    let ranges = fmt.ranges().iter().map(|r| (r.first(), r.n_left() as u16));
+                    self.range_string_id(ranges, gid)
+                }
+                CustomCharset::Format2(fmt) => {
+                    let ranges = fmt.ranges().iter().map(|r| (r.first(), r.n_left()));
+                    self.range_string_id(ranges, gid)
+                }
+            },
+        }
+    }
+
+    /// Returns the glyph identifier for the given string identifier.
+    pub fn glyph_id(&self, string_id: StringId) -> Option<GlyphId> {
+        let sid = string_id.to_u16();
+        if sid == 0 {
+            return Some(GlyphId::NOTDEF);
+        }
+        let gid = match &self.kind {
+            CharsetKind::IsoAdobe => (sid <= 228).then_some(sid)?,
+            CharsetKind::Expert => EXPERT_CHARSET.iter().position(|n| *n == sid)? as u16,
+            CharsetKind::ExpertSubset => {
+                EXPERT_SUBSET_CHARSET.iter().position(|n| *n == sid)? as u16
+            }
+            CharsetKind::Custom(custom) => match custom {
+                CustomCharset::Format0(fmt) => {
+                    fmt.glyph().iter().position(|n| n.get() == sid)? as u16 + 1
+                }
+                CustomCharset::Format1(fmt) => {
+                    let ranges = fmt.ranges().iter().map(|r| (r.first(), r.n_left() as u16));
+                    self.range_glyph_id(ranges, sid)?
+                }
+                CustomCharset::Format2(fmt) => {
+                    let ranges = fmt.ranges().iter().map(|r| (r.first(), r.n_left()));
+                    self.range_glyph_id(ranges, sid)?
+                }
+            },
+        };
+        ((gid as u32) < self.num_glyphs).then_some(GlyphId::new(gid))
+    }
+
+    /// Finds the string identifier for a glyph in a sequence of
+    /// `(first, n_left)` ranges.
+    fn range_string_id(
+        &self,
+        ranges: impl Iterator<Item = (u16, u16)>,
+        gid: u16,
+    ) -> Option<StringId> {
+        let mut first_gid = 1u32;
+        for (first_sid, n_left) in ranges {
+            if first_gid >= self.num_glyphs {
+                break;
+            }
+            let count = n_left as u32 + 1;
+            if (gid as u32) < first_gid + count {
+                let sid = first_sid as u32 + (gid as u32 - first_gid);
+                return u16::try_from(sid).ok().map(StringId::new);
+            }
+            first_gid += count;
+        }
+        None
+    }
+
+    /// Finds the glyph identifier for a string in a sequence of
+    /// `(first, n_left)` ranges.
+    fn range_glyph_id(&self, ranges: impl Iterator<Item = (u16, u16)>, sid: u16) -> Option<u16> {
+        let mut first_gid = 1u32;
+        for (first_sid, n_left) in ranges {
+            if first_gid >= self.num_glyphs {
+                break;
+            }
+            if sid >= first_sid && (sid - first_sid) <= n_left {
+                return u16::try_from(first_gid + (sid - first_sid) as u32).ok();
+            }
+            first_gid += n_left as u32 + 1;
+        }
+        None
+    }
+}
+
+/// Iterator over the glyph identifier and string identifier (or CID) pairs
+/// in a charset.
+#[derive(Clone)]
+pub struct CharsetIter<'a> {
+    charset: Charset<'a>,
+    cur: u32,
+    range_ix: usize,
+    range_offset: u16,
+}
+
+impl Iterator for CharsetIter<'_> {
+    type Item = (GlyphId, StringId);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let gid = self.cur;
+        if gid >= self.charset.num_glyphs || gid > u16::MAX as u32 {
+            return None;
+        }
+        self.cur += 1;
+        let glyph_id = GlyphId::new(gid as u16);
+        if gid == 0 {
+            return Some((glyph_id, StringId::new(0)));
+        }
+        // Walk the ranges incrementally rather than searching from the
+        // start for each glyph.
+        let (first, n_left) = match &self.charset.kind {
+            CharsetKind::Custom(CustomCharset::Format1(fmt)) => {
+                let range = fmt.ranges().get(self.range_ix)?;
+                (range.first(), range.n_left() as u16)
+            }
+            CharsetKind::Custom(CustomCharset::Format2(fmt)) => {
+                let range = fmt.ranges().get(self.range_ix)?;
+                (range.first(), range.n_left())
+            }
+            _ => return Some((glyph_id, self.charset.string_id(glyph_id)?)),
+        };
+        let sid = first.checked_add(self.range_offset)?;
+        if self.range_offset >= n_left {
+            self.range_ix += 1;
+            self.range_offset = 0;
+        } else {
+            self.range_offset += 1;
+        }
+        Some((glyph_id, StringId::new(sid)))
+    }
+}
+
+enum CharsetKind<'a> {
+    IsoAdobe,
+    Expert,
+    ExpertSubset,
+    Custom(CustomCharset<'a>),
+}
+
+// Generated format enums don't implement Clone.
+impl Clone for CharsetKind<'_> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::IsoAdobe => Self::IsoAdobe,
+            Self::Expert => Self::Expert,
+            Self::ExpertSubset => Self::ExpertSubset,
+            Self::Custom(custom) => Self::Custom(match custom {
+                CustomCharset::Format0(fmt) => CustomCharset::Format0(fmt.clone()),
+                CustomCharset::Format1(fmt) => CustomCharset::Format1(fmt.clone()),
+                CustomCharset::Format2(fmt) => CustomCharset::Format2(fmt.clone()),
+            }),
+        }
+    }
+}
+
+/// Predefined Expert charset.
+///
+/// See "Appendix C - Predefined Charsets" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=47>
+#[rustfmt::skip]
+const EXPERT_CHARSET: [u16; 166] = [
+    0, 1, 229, 230, 231, 232, 233, 234, 235, 236, 237, 238, 13, 14, 15, 99,
+    239, 240, 241, 242, 243, 244, 245, 246, 247, 248, 27, 28, 249, 250, 251, 252,
+    253, 254, 255, 256, 257, 258, 259, 260, 261, 262, 263, 264, 265, 266, 109, 110,
+    267, 268, 269, 270, 271, 272, 273, 274, 275, 276, 277, 278, 279, 280, 281, 282,
+    283, 284, 285, 286, 287, 288, 289, 290, 291, 292, 293, 294, 295, 296, 297, 298,
+    299, 300, 301, 302, 303, 304, 305, 306, 307, 308, 309, 310, 311, 312, 313, 314,
+    315, 316, 317, 318, 158, 155, 163, 319, 320, 321, 322, 323, 324, 325, 326, 150,
+    164, 169, 327, 328, 329, 330, 331, 332, 333, 334, 335, 336, 337, 338, 339, 340,
+    341, 342, 343, 344, 345, 346, 347, 348, 349, 350, 351, 352, 353, 354, 355, 356,
+    357, 358, 359, 360, 361, 362, 363, 364, 365, 366, 367, 368, 369, 370, 371, 372,
+    373, 374, 375, 376, 377, 378,
+];
+
+/// Predefined Expert Subset charset.
+///
+/// See "Appendix C - Predefined Charsets" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=49>
+#[rustfmt::skip]
+const EXPERT_SUBSET_CHARSET: [u16; 87] = [
+    0, 1, 231, 232, 235, 236, 237, 238, 13, 14, 15, 99, 239, 240, 241, 242,
+    243, 244, 245, 246, 247, 248, 27, 28, 249, 250, 251, 253, 254, 255, 256, 257,
+    258, 259, 260, 261, 262, 263, 264, 265, 266, 109, 110, 267, 268, 269, 270, 272,
+    300, 301, 302, 305, 314, 315, 158, 155, 163, 320, 321, 322, 323, 324, 325, 326,
+    150, 164, 169, 327, 328, 329, 330, 331, 332, 333, 334, 335, 336, 337, 338, 339,
+    340, 341, 342, 343, 344, 345, 346,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn custom_charset_formats() {
+        // Glyphs 1..=5 map to SIDs 10, 11, 12, 40, 41. Each charset is
+        // preceded by 4 bytes of padding since offsets 0..=2 are reserved
+        // for the predefined charsets.
+        let sids = [10u16, 11, 12, 40, 41];
+        let format0 = BeBuffer::new().push(0u32).push(0u8).extend(sids);
+        let format1 = BeBuffer::new()
+            .push(0u32)
+            .push(1u8)
+            .push(10u16)
+            .push(2u8)
+            .push(40u16)
+            .push(1u8);
+        let format2 = BeBuffer::new()
+            .push(0u32)
+            .push(2u8)
+            .extend([10u16, 2, 40, 1]);
+        for buf in [format0, format1, format2] {
+            let charset = Charset::new(buf.font_data(), 4, 6).unwrap();
+            assert_eq!(charset.string_id(GlyphId::NOTDEF), Some(StringId::new(0)));
+            for (gid, sid) in sids.iter().enumerate() {
+                let gid = GlyphId::new(gid as u16 + 1);
+                let sid = StringId::new(*sid);
+                assert_eq!(charset.string_id(gid), Some(sid));
+                assert_eq!(charset.glyph_id(sid), Some(gid));
+            }
+            assert_eq!(charset.string_id(GlyphId::new(6)), None);
+            assert_eq!(charset.glyph_id(StringId::new(13)), None);
+        }
+    }
+
+    #[test]
+    fn charset_iter() {
+        // Two ranges where the second is truncated by the glyph count
+        let data = BeBuffer::new()
+            .push(0u32)
+            .push(1u8)
+            .push(10u16)
+            .push(1u8)
+            .push(300u16)
+            .push(5u8);
+        let charset = Charset::new(data.font_data(), 4, 5).unwrap();
+        let pairs = charset
+            .iter()
+            .map(|(gid, sid)| (gid.to_u16(), sid.to_u16()))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(0, 0), (1, 10), (2, 11), (3, 300), (4, 301)]);
+        let iso_adobe = Charset::new(FontData::new(&[]), 0, 300).unwrap();
+        assert_eq!(iso_adobe.iter().count(), 229);
+    }
+
+    #[test]
+    fn cid_charset() {
+        // Glyphs 1..=3 map to CIDs 1000..=1002
+        let data = BeBuffer::new()
+            .push(0u32)
+            .push(2u8)
+            .push(1000u16)
+            .push(2u16);
+        let charset = Charset::new_cid(data.font_data(), 4, 4).unwrap();
+        assert!(charset.is_cid());
+        assert_eq!(charset.glyph_id_for_cid(1001), Some(GlyphId::new(2)));
+        assert_eq!(charset.cid(GlyphId::new(3)), Some(1002));
+        assert_eq!(charset.glyph_id_for_cid(1003), None);
+        // CID lookups are not available for name-keyed fonts
+        let charset = Charset::new(data.font_data(), 4, 4).unwrap();
+        assert_eq!(charset.glyph_id_for_cid(1001), None);
+    }
+
+    #[test]
+    fn predefined_charsets() {
+        let data = FontData::new(&[]);
+        let iso_adobe = Charset::new(data, 0, 300).unwrap();
+        assert_eq!(
+            iso_adobe.string_id(GlyphId::new(36)),
+            Some(StringId::new(36))
+        );
+        assert_eq!(
+            iso_adobe.glyph_id(StringId::new(228)),
+            Some(GlyphId::new(228))
+        );
+        assert_eq!(iso_adobe.string_id(GlyphId::new(229)), None);
+        let expert = Charset::new(data, 1, 166).unwrap();
+        // "exclamsmall"
+        assert_eq!(expert.string_id(GlyphId::new(2)), Some(StringId::new(229)));
+        assert_eq!(expert.glyph_id(StringId::new(378)), Some(GlyphId::new(165)));
+        let expert_subset = Charset::new(data, 2, 87).unwrap();
+        // "dollaroldstyle"
+        assert_eq!(
+            expert_subset.string_id(GlyphId::new(2)),
+            Some(StringId::new(231))
+        );
+        assert_eq!(expert_subset.glyph_id(StringId::new(229)), None);
+    }
+}
@@ -1,6 +1,6 @@
 //! Parsing for PostScript charstrings.
 
-use super::{BlendState, Error, Index, Stack};
+use super::{BlendState, Charset, Error, Index, Number, Stack, StringId, STANDARD_ENCODING};
 use crate::{
     types::{Fixed, Pen, Point},
     Cursor,
@@ -12,6 +12,20 @@ use crate::{
 /// <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=33>
 pub const NESTING_DEPTH_LIMIT: u32 = 10;
 
+/// Number of elements in the transient array used by the legacy `put` and
+/// `get` operators.
+///
+/// See "Appendix B Type 2 Charstring Implementation Limits" at
+/// <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=33>
+const TRANSIENT_ARRAY_SIZE: usize = 32;
+
+/// Initial seed for the pseudo-random number generator used by the legacy
+/// `random` operator.
+///
+/// FreeType seeds this from the font; we use a fixed value so that
+/// evaluation is deterministic.
+const RANDOM_SEED: u32 = 0x2F5C_8A31;
+
 /// Trait for processing commands resulting from charstring evaluation.
 ///
 /// During processing, the path construction operators (see "4.1 Path
@@ -90,6 +104,10 @@ where
 /// item variation store, then `blend_state` must be provided, otherwise
 /// `Error::MissingBlendState` will be returned if a blend operator is
 /// present.
+///
+/// CFF charstrings that use the implied `seac` operator for accent
+/// composition must be evaluated with [`evaluate_with_seac`], otherwise
+/// `Error::MissingSeacContext` will be returned.
 pub fn evaluate(
     charstring_data: &[u8],
     global_subrs: Index,
@@ -97,7 +115,38 @@ pub fn evaluate(
     blend_state: Option<BlendState>,
     sink: &mut impl CommandSink,
 ) -> Result<(), Error> {
-    let mut evaluator = Evaluator::new(global_subrs, subrs, blend_state, sink);
+    let mut evaluator = Evaluator::new(global_subrs, subrs, blend_state, None, sink);
+    evaluator.evaluate(charstring_data, 0)?;
+    Ok(())
+}
+
+/// Glyph data used to resolve the base and accent components of the
+/// implied `seac` operator in CFF charstrings.
+///
+/// See "Appendix C Compatibility and Deprecated Operators" at
+/// <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=35>
+#[derive(Clone)]
+pub struct SeacContext<'a> {
+    /// Index containing the charstrings for all glyphs in the font.
+    pub charstrings: Index<'a>,
+    /// Charset used to map the component characters to glyphs.
+    pub charset: Charset<'a>,
+}
+
+/// Evaluates the given charstring, resolving the components of the implied
+/// `seac` operator with the given context, and emits the resulting commands
+/// to the specified sink.
+///
+/// See [`evaluate`] for details on the remaining parameters.
+pub fn evaluate_with_seac(
+    charstring_data: &[u8],
+    global_subrs: Index,
+    subrs: Option<Index>,
+    blend_state: Option<BlendState>,
+    seac: &SeacContext,
+    sink: &mut impl CommandSink,
+) -> Result<(), Error> {
+    let mut evaluator = Evaluator::new(global_subrs, subrs, blend_state, Some(seac), sink);
     evaluator.evaluate(charstring_data, 0)?;
     Ok(())
 }
@@ -108,6 +157,7 @@ struct Evaluator<'a, S> {
     global_subrs: Index<'a>,
     subrs: Option<Index<'a>>,
     blend_state: Option<BlendState<'a>>,
+    seac: Option<&'a SeacContext<'a>>,
     sink: &'a mut S,
     is_open: bool,
     have_read_width: bool,
@@ -116,6 +166,8 @@ struct Evaluator<'a, S> {
     y: Fixed,
     stack: Stack,
     stack_ix: usize,
+    transient: [Number; TRANSIENT_ARRAY_SIZE],
+    random: u32,
 }
 
 impl<'a, S> Evaluator<'a, S>
@@ -126,12 +178,14 @@ where
         global_subrs: Index<'a>,
         subrs: Option<Index<'a>>,
         blend_state: Option<BlendState<'a>>,
+        seac: Option<&'a SeacContext<'a>>,
         sink: &'a mut S,
     ) -> Self {
         Self {
             global_subrs,
             subrs,
             blend_state,
+            seac,
             sink,
             is_open: false,
             have_read_width: false,
@@ -140,6 +194,8 @@ where
             x: Fixed::ZERO,
             y: Fixed::ZERO,
             stack_ix: 0,
+            transient: [Number::I32(0); TRANSIENT_ARRAY_SIZE],
+            random: RANDOM_SEED,
         }
     }
 
@@ -232,18 +288,33 @@ where
                 return Ok(false);
             }
             // End the current charstring
-            // TODO: handle implied 'seac' operator
             // Spec: <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=21>
             // FT: <https://gitlab.freedesktop.org/freetype/freetype/-/blob/80a507a6b8e3d2906ad2c8ba69329bd2fb2a85ef/src/psaux/psintrp.c#L2463>
             EndChar => {
-                if !self.stack.is_empty() && !self.have_read_width {
+                let stack_len = self.stack.len();
+                let mut i = 0;
+                if (stack_len == 1 || stack_len == 5) && !self.have_read_width {
                     self.have_read_width = true;
-                    self.stack.clear();
+                    i = 1;
                 }
                 if self.is_open {
                     self.is_open = false;
                     self.sink.close();
                 }
+                // Four remaining arguments signify the implied 'seac'
+                // operator
+                // Spec: <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=35>
+                // FT: <https://gitlab.freedesktop.org/freetype/freetype/-/blob/80a507a6b8e3d2906ad2c8ba69329bd2fb2a85ef/src/psaux/psintrp.c#L1286>
+                if stack_len - i == 4 {
+                    let [adx, ady] = self.stack.fixed_array::<2>(i)?;
+                    let bchar = self.stack.get_i32(i + 2)?;
+                    let achar = self.stack.get_i32(i + 3)?;
+                    self.reset_stack();
+                    self.evaluate_seac_component(bchar, Fixed::ZERO, Fixed::ZERO, nesting_depth)?;
+                    self.evaluate_seac_component(achar, adx, ady, nesting_depth)?;
+                } else {
+                    self.reset_stack();
+                }
                 return Ok(false);
             }
             // Emits a sequence of stem hints
@@ -480,10 +551,195 @@ where
                 let subr_charstring_data = subrs_index.get(biased_index)?;
                 self.evaluate(subr_charstring_data, nesting_depth + 1)?;
             }
+            // The remaining operators are the legacy arithmetic, storage
+            // and conditional operators from the original Type 2 spec which
+            // were removed in CFF2.
+            // Spec: <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=25>
+            // FT: <https://gitlab.freedesktop.org/freetype/freetype/-/blob/80a507a6b8e3d2906ad2c8ba69329bd2fb2a85ef/src/psaux/psintrp.c#L1598>
+            //
+            // Logical operators push 1 for true and 0 for false
+            And | Or | Eq => {
+                let b = self.stack.pop_fixed()?;
+                let a = self.stack.pop_fixed()?;
+                let result = match operator {
+                    And => a != Fixed::ZERO && b != Fixed::ZERO,
+                    Or => a != Fixed::ZERO || b != Fixed::ZERO,
+                    _ => a == b,
+                };
+                self.stack.push(result as i32)?;
+            }
+            Not => {
+                let a = self.stack.pop_fixed()?;
+                self.stack.push((a == Fixed::ZERO) as i32)?;
+            }
+            // Integer operands produce integer results for these so that
+            // computed values remain usable as subroutine indices
+            Abs | Neg => {
+                let result = match self.stack.pop_number()? {
+                    Number::I32(a) if operator == Abs => Number::I32(a.wrapping_abs()),
+                    Number::I32(a) => Number::I32(a.wrapping_neg()),
+                    Number::Fixed(a) if operator == Abs => {
+                        Number::Fixed(Fixed::from_bits(a.to_bits().wrapping_abs()))
+                    }
+                    Number::Fixed(a) => Number::Fixed(Fixed::from_bits(a.to_bits().wrapping_neg())),
+                };
+                self.stack.push(result)?;
+            }
+            Add | Sub => {
+                let b = self.stack.pop_number()?;
+                let a = self.stack.pop_number()?;
+                let result = match (a, b) {
+                    (Number::I32(a), Number::I32(b)) if operator == Add => {
+                        Number::I32(a.wrapping_add(b))
+                    }
+                    (Number::I32(a), Number::I32(b)) => Number::I32(a.wrapping_sub(b)),
+                    _ if operator == Add => {
+                        Number::Fixed(number_to_fixed(a).wrapping_add(number_to_fixed(b)))
+                    }
+                    _ => Number::Fixed(number_to_fixed(a).wrapping_sub(number_to_fixed(b))),
+                };
+                self.stack.push(result)?;
+            }
+            Mul | Div => {
+                let b = self.stack.pop_fixed()?;
+                let a = self.stack.pop_fixed()?;
+                self.stack.push(if operator == Mul {
+                    a * b
+                } else {
+                    fixed_div(a, b)
+                })?;
+            }
+            // FreeType returns 0 for non-positive arguments
+            Sqrt => {
+                let a = self.stack.pop_fixed()?;
+                let result = if a > Fixed::ZERO {
+                    Fixed::from_bits((((a.to_bits() as u64) << 16) as f64).sqrt() as i32)
+                } else {
+                    Fixed::ZERO
+                };
+                self.stack.push(result)?;
+            }
+            // Leaves a pseudo-random number in the range (0, 1]
+            Random => {
+                let value = Fixed::from_bits((self.random & 0xFFFF) as i32 + 1);
+                // 32-bit xorshift, matching FreeType
+                let mut r = self.random;
+                r ^= r << 13;
+                r ^= r >> 17;
+                r ^= r << 5;
+                self.random = r;
+                self.stack.push(value)?;
+            }
+            // Selects s1 if v1 <= v2 or s2 otherwise from s1 s2 v1 v2
+            IfElse => {
+                let v2 = self.stack.pop_fixed()?;
+                let v1 = self.stack.pop_fixed()?;
+                let s2 = self.stack.pop_number()?;
+                let s1 = self.stack.pop_number()?;
+                self.stack.push(if v1 <= v2 { s1 } else { s2 })?;
+            }
+            Drop => {
+                self.stack.pop_number()?;
+            }
+            Dup => {
+                let a = self.stack.pop_number()?;
+                self.stack.push(a)?;
+                self.stack.push(a)?;
+            }
+            Exch => {
+                let b = self.stack.pop_number()?;
+                let a = self.stack.pop_number()?;
+                self.stack.push(b)?;
+                self.stack.push(a)?;
+            }
+            // Copies the element at the given depth to the top of the
+            // stack. Out of range indices are clamped, following FreeType.
+            //
+            // The integer operands of this and the following operators may
+            // be computed by the arithmetic operators so fixed point values
+            // are accepted and truncated.
+            Index => {
+                let depth = number_to_i32(self.stack.pop_number()?);
+                let len = self.stack.len();
+                if len > 0 {
+                    let ix = if depth < 0 {
+                        len - 1
+                    } else if depth as usize >= len {
+                        0
+                    } else {
+                        len - 1 - depth as usize
+                    };
+                    let value = self.stack.get_number(ix)?;
+                    self.stack.push(value)?;
+                }
+            }
+            Roll => {
+                let shift = number_to_i32(self.stack.pop_number()?);
+                let count = number_to_i32(self.stack.pop_number()?);
+                self.stack.roll(count, shift)?;
+            }
+            // Stores and retrieves values from the transient array.
+            // Invalid indices are ignored, following FreeType.
+            Put => {
+                let ix = number_to_i32(self.stack.pop_number()?);
+                let value = self.stack.pop_number()?;
+                if let Some(entry) = usize::try_from(ix)
+                    .ok()
+                    .and_then(|ix| self.transient.get_mut(ix))
+                {
+                    *entry = value;
+                }
+            }
+            Get => {
+                let ix = number_to_i32(self.stack.pop_number()?);
+                if let Some(value) = usize::try_from(ix)
+                    .ok()
+                    .and_then(|ix| self.transient.get(ix))
+                {
+                    self.stack.push(*value)?;
+                }
+            }
         }
         Ok(true)
     }
 
+    /// Evaluates a base or accent component of the implied 'seac'
+    /// operator, offset by the given delta.
+    ///
+    /// The component is selected by a character code in the Standard
+    /// encoding which is mapped to a glyph through the charset.
+    fn evaluate_seac_component(
+        &mut self,
+        code: i32,
+        dx: Fixed,
+        dy: Fixed,
+        nesting_depth: u32,
+    ) -> Result<(), Error> {
+        let seac = self.seac.ok_or(Error::MissingSeacContext)?;
+        let sid = u8::try_from(code)
+            .ok()
+            .map(|code| STANDARD_ENCODING[code as usize])
+            .filter(|sid| *sid != 0)
+            .ok_or(Error::InvalidSeacCode(code))?;
+        let glyph_id = seac
+            .charset
+            .glyph_id(StringId::new(sid as u16))
+            .ok_or(Error::InvalidSeacCode(code))?;
+        let charstring_data = seac.charstrings.get(glyph_id.to_u16() as usize)?;
+        // Components are evaluated with a fresh state, sharing only the
+        // subroutines and the output sink.
+        let mut evaluator = Evaluator::new(
+            self.global_subrs.clone(),
+            self.subrs.clone(),
+            None,
+            Some(seac),
+            &mut *self.sink,
+        );
+        evaluator.x = dx;
+        evaluator.y = dy;
+        evaluator.evaluate(charstring_data, nesting_depth + 1)
+    }
+
     fn coords_remaining(&self) -> usize {
         self.stack.len() - self.stack_ix
     }
@@ -574,6 +830,39 @@ where
     }
 }
 
+fn number_to_fixed(number: Number) -> Fixed {
+    match number {
+        Number::I32(value) => Fixed::from_i32(value),
+        Number::Fixed(value) => value,
+    }
+}
+
+/// Converts a number to an integer, truncating fixed point values toward
+/// zero as FreeType does.
+fn number_to_i32(number: Number) -> i32 {
+    match number {
+        Number::I32(value) => value,
+        Number::Fixed(value) => value.to_bits() / 65536,
+    }
+}
+
+/// Divides two fixed point values with rounding.
+///
+/// This is `FT_DivFix` which, unlike the `Div` impl for [`Fixed`], is
+/// defined for all inputs, including `i32::MIN`.
+fn fixed_div(a: Fixed, b: Fixed) -> Fixed {
+    let (a, b) = (a.to_bits(), b.to_bits());
+    let (ua, ub) = (a.unsigned_abs() as u64, b.unsigned_abs() as u64);
+    let q = ((ua << 16) + (ub >> 1))
+        .checked_div(ub)
+        .unwrap_or(0x7FFFFFFF) as u32 as i32;
+    Fixed::from_bits(if (a < 0) != (b < 0) {
+        q.wrapping_neg()
+    } else {
+        q
+    })
+}
+
 /// Specifies how point coordinates for a curve are computed.
 #[derive(Copy, Clone)]
 enum PointMode {
@@ -589,8 +878,9 @@ enum PointMode {
 /// PostScript charstring operator.
 ///
 /// See <https://learn.microsoft.com/en-us/typography/opentype/spec/cff2charstr#appendix-a-cff2-charstring-command-codes>
-// TODO: This is currently missing legacy math and logical operators.
-// fonttools doesn't even implement these: <https://github.com/fonttools/fonttools/blob/65598197c8afd415781f6667a7fb647c2c987fff/Lib/fontTools/misc/psCharStrings.py#L409>
+/// and "Appendix A Type 2 Charstring Command Codes" at
+/// <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=32>
+/// for the legacy operators.
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 enum Operator {
     HStem,
@@ -622,6 +912,27 @@ enum Operator {
     Flex,
     HFlex1,
     Flex1,
+    // Legacy arithmetic, storage and conditional operators
+    And,
+    Or,
+    Not,
+    Abs,
+    Add,
+    Sub,
+    Div,
+    Neg,
+    Eq,
+    Drop,
+    Put,
+    Get,
+    IfElse,
+    Random,
+    Mul,
+    Sqrt,
+    Dup,
+    Exch,
+    Index,
+    Roll,
 }
 
 impl Operator {
@@ -676,6 +987,26 @@ impl Operator {
     pub fn from_two_byte_opcode(opcode: u8) -> Option<Self> {
         use Operator::*;
         Some(match opcode {
+            3 => And,
+            4 => Or,
+            5 => Not,
+            9 => Abs,
+            10 => Add,
+            11 => Sub,
+            12 => Div,
+            14 => Neg,
+            15 => Eq,
+            18 => Drop,
+            20 => Put,
+            21 => Get,
+            22 => IfElse,
+            23 => Random,
+            24 => Mul,
+            26 => Sqrt,
+            27 => Dup,
+            28 => Exch,
+            29 => Index,
+            30 => Roll,
             34 => HFlex,
             35 => Flex,
             36 => HFlex1,
@@ -1048,4 +1379,206 @@ mod tests {
         ];
         assert_eq!(&commands.0, expected);
     }
+
+    #[test]
+    fn implied_seac() {
+        use Command::*;
+        #[rustfmt::skip]
+        let charstrings_data = [
+            // count, offset size and offsets
+            0, 4, 1, 1, 2, 12, 22, 31,
+            // .notdef: endchar
+            14,
+            // A: 0 0 rmoveto 100 0 rlineto -50 100 rlineto endchar
+            139, 139, 21, 239, 139, 5, 89, 239, 5, 14,
+            // acute: 0 0 rmoveto 20 0 rlineto 0 20 rlineto endchar
+            139, 139, 21, 159, 139, 5, 139, 159, 5, 14,
+            // Aacute: 500 30 120 65 194 endchar
+            248, 136, 169, 247, 12, 204, 247, 86, 14,
+        ];
+        let charstrings = Index::new(&charstrings_data, false).unwrap();
+        // Format 0 charset at offset 4 mapping glyphs to the SIDs for
+        // A, acute and Aacute
+        let charset_data = [0, 0, 0, 0, 0, 0, 34, 0, 125, 0, 174];
+        let charset = Charset::new(FontData::new(&charset_data), 4, 4).unwrap();
+        let seac = SeacContext {
+            charstrings: charstrings.clone(),
+            charset,
+        };
+        let empty_index_bytes = [0u8; 8];
+        let global_subrs = Index::new(&empty_index_bytes, false).unwrap();
+        let mut commands = CaptureCommandSink::default();
+        evaluate_with_seac(
+            charstrings.get(3).unwrap(),
+            global_subrs.clone(),
+            None,
+            None,
+            &seac,
+            &mut commands,
+        )
+        .unwrap();
+        let expected = &[
+            // Base glyph at the origin
+            MoveTo(Fixed::ZERO, Fixed::ZERO),
+            LineTo(Fixed::from_i32(100), Fixed::ZERO),
+            LineTo(Fixed::from_i32(50), Fixed::from_i32(100)),
+            LineTo(Fixed::ZERO, Fixed::ZERO),
+            // Accent offset by (adx, ady)
+            MoveTo(Fixed::from_i32(30), Fixed::from_i32(120)),
+            LineTo(Fixed::from_i32(50), Fixed::from_i32(120)),
+            LineTo(Fixed::from_i32(50), Fixed::from_i32(140)),
+            LineTo(Fixed::from_i32(30), Fixed::from_i32(120)),
+        ];
+        assert_eq!(&commands.0, expected);
+        // Without the context, the components can't be resolved
+        let result = evaluate(
+            charstrings.get(3).unwrap(),
+            global_subrs,
+            None,
+            None,
+            &mut CaptureCommandSink::default(),
+        );
+        assert!(matches!(result, Err(Error::MissingSeacContext)));
+    }
+
+    #[test]
+    fn legacy_arithmetic_ops() {
+        // Each charstring computes two values with the legacy operators and
+        // emits them with rmoveto.
+        #[rustfmt::skip]
+        let cases: &[(&[u8], [Fixed; 2])] = &[
+            // 10 20 add 3 7 sub rmoveto endchar
+            (
+                &[149, 159, 12, 10, 142, 146, 12, 11, 21, 14],
+                [Fixed::from_i32(30), Fixed::from_i32(-4)],
+            ),
+            // 3 4 mul 10 4 div rmoveto endchar
+            (
+                &[142, 143, 12, 24, 149, 143, 12, 12, 21, 14],
+                [Fixed::from_i32(12), Fixed::from_f64(2.5)],
+            ),
+            // -5 abs 5 neg rmoveto endchar
+            (
+                &[134, 12, 9, 144, 12, 14, 21, 14],
+                [Fixed::from_i32(5), Fixed::from_i32(-5)],
+            ),
+            // 16 sqrt 2 sqrt rmoveto endchar
+            (
+                &[155, 12, 26, 141, 12, 26, 21, 14],
+                [Fixed::from_i32(4), Fixed::from_bits(92681)],
+            ),
+            // 1 0 and 1 0 or rmoveto endchar
+            (
+                &[140, 139, 12, 3, 140, 139, 12, 4, 21, 14],
+                [Fixed::ZERO, Fixed::ONE],
+            ),
+            // 0 not 3 3 eq rmoveto endchar
+            (
+                &[139, 12, 5, 142, 142, 12, 15, 21, 14],
+                [Fixed::ONE, Fixed::ONE],
+            ),
+            // 10 20 1 2 ifelse 10 20 2 1 ifelse rmoveto endchar
+            (
+                &[149, 159, 140, 141, 12, 22, 149, 159, 141, 140, 12, 22, 21, 14],
+                [Fixed::from_i32(10), Fixed::from_i32(20)],
+            ),
+            // Division must not overflow for the most negative value
+            // i32::MIN 0 div i32::MIN -1 div rmoveto endchar
+            (
+                &[255, 128, 0, 0, 0, 139, 12, 12, 255, 128, 0, 0, 0, 138, 12, 12, 21, 14],
+                [Fixed::from_bits(-0x7FFFFFFF), Fixed::from_bits(i32::MIN)],
+            ),
+        ];
+        for (charstring, expected) in cases {
+            assert_eq!(
+                eval_move_to(charstring),
+                Command::MoveTo(expected[0], expected[1])
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_stack_ops() {
+        #[rustfmt::skip]
+        let cases: &[(&[u8], [i32; 2])] = &[
+            // 7 8 drop 9 rmoveto endchar
+            (&[146, 147, 12, 18, 148, 21, 14], [7, 9]),
+            // 7 dup rmoveto endchar
+            (&[146, 12, 27, 21, 14], [7, 7]),
+            // 1 2 exch rmoveto endchar
+            (&[140, 141, 12, 28, 21, 14], [2, 1]),
+            // 10 20 1 index add rmoveto endchar
+            (&[149, 159, 140, 12, 29, 12, 10, 21, 14], [10, 30]),
+            // 1 2 3 3 1 roll drop rmoveto endchar
+            (&[140, 141, 142, 142, 140, 12, 30, 12, 18, 21, 14], [3, 1]),
+            // 1 2 3 3 -1 roll drop rmoveto endchar
+            (&[140, 141, 142, 142, 138, 12, 30, 12, 18, 21, 14], [2, 3]),
+            // Fixed point operands are truncated toward zero
+            // 10 20 1.5 index add rmoveto endchar
+            (&[149, 159, 255, 0, 1, 128, 0, 12, 29, 12, 10, 21, 14], [10, 30]),
+            // 1 2 3 3.5 -1.5 roll drop rmoveto endchar
+            (&[140, 141, 142, 255, 0, 3, 128, 0, 255, 255, 254, 128, 0, 12, 30, 12, 18, 21, 14], [2, 3]),
+            // 10 20 2 3 div index add rmoveto endchar
+            (&[149, 159, 141, 142, 12, 12, 12, 29, 12, 10, 21, 14], [10, 40]),
+        ];
+        for (charstring, expected) in cases {
+            assert_eq!(
+                eval_move_to(charstring),
+                Command::MoveTo(Fixed::from_i32(expected[0]), Fixed::from_i32(expected[1]))
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_transient_array() {
+        // 42 3 put 7 1 put 3 get 1 get rmoveto endchar
+        let charstring = &[
+            181, 142, 12, 20, 146, 140, 12, 20, 142, 12, 21, 140, 12, 21, 21, 14,
+        ];
+        assert_eq!(
+            eval_move_to(charstring),
+            Command::MoveTo(Fixed::from_i32(42), Fixed::from_i32(7))
+        );
+        // Fixed point indices are truncated
+        // 42 3.9 put 3.2 get 7 rmoveto endchar
+        let charstring = &[
+            181, 255, 0, 3, 230, 102, 12, 20, 255, 0, 3, 51, 51, 12, 21, 146, 21, 14,
+        ];
+        assert_eq!(
+            eval_move_to(charstring),
+            Command::MoveTo(Fixed::from_i32(42), Fixed::from_i32(7))
+        );
+        // Unset entries are zero and out of range indices are ignored
+        // 100 32 put 5 get 32 get 0 rmoveto endchar
+        let charstring = &[239, 171, 12, 20, 144, 12, 21, 171, 12, 21, 139, 21, 14];
+        assert_eq!(
+            eval_move_to(charstring),
+            Command::MoveTo(Fixed::ZERO, Fixed::ZERO)
+        );
+    }
+
+    #[test]
+    fn legacy_random() {
+        // random random rmoveto endchar
+        let charstring = &[12, 23, 12, 23, 21, 14];
+        let Command::MoveTo(x, y) = eval_move_to(charstring) else {
+            panic!("expected a move command");
+        };
+        for value in [x, y] {
+            assert!(value > Fixed::ZERO && value <= Fixed::ONE);
+        }
+        assert_ne!(x, y);
+        // Evaluation is deterministic
+        assert_eq!(eval_move_to(charstring), Command::MoveTo(x, y));
+    }
+
+    /// Evaluates the charstring and returns the first command which is
+    /// expected to be a move.
+    fn eval_move_to(charstring: &[u8]) -> Command {
+        let empty_index_bytes = [0u8; 8];
+        let global_subrs = Index::new(&empty_index_bytes, false).unwrap();
+        let mut commands = CaptureCommandSink::default();
+        evaluate(charstring, global_subrs, None, None, &mut commands).unwrap();
+        commands.0[0]
+    }
 }
@@ -0,0 +1,282 @@
+//! Parsing for CFF encodings.
+
+use types::GlyphId;
+
+use super::{Charset, EncodingRange1, EncodingSupplement, Error, StringId};
+use crate::{FontData, ReadError};
+
+/// Mapping from character codes to glyph identifiers.
+///
+/// Encodings are only present in name-keyed fonts. Custom encodings map
+/// codes directly to glyphs while the predefined encodings and any
+/// supplements map codes to string identifiers which are then resolved
+/// through the [`Charset`].
+///
+/// See "Encodings" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=18>
+#[derive(Clone)]
+pub struct Encoding<'a> {
+    kind: EncodingKind<'a>,
+    supplements: &'a [EncodingSupplement],
+}
+
+impl<'a> Encoding<'a> {
+    /// Creates a new encoding from the offset in the top DICT.
+    ///
+    /// The `cff_data` parameter should contain the full `CFF` table.
+    pub fn new(cff_data: FontData<'a>, encoding_offset: usize) -> Result<Self, Error> {
+        let kind = match encoding_offset {
+            0 => EncodingKind::Standard,
+            1 => EncodingKind::Expert,
+            _ => {
+                let data = cff_data
+                    .split_off(encoding_offset)
+                    .ok_or(ReadError::OutOfBounds)?;
+                let mut cursor = data.cursor();
+                let format = cursor.read::<u8>()?;
+                // The high bit signifies the presence of supplementary
+                // mappings.
+                let kind = match format & 0x7F {
+                    0 => {
+                        let n_codes = cursor.read::<u8>()?;
+                        EncodingKind::Format0(cursor.read_array(n_codes as usize)?)
+                    }
+                    1 => {
+                        let n_ranges = cursor.read::<u8>()?;
+                        EncodingKind::Format1(cursor.read_array(n_ranges as usize)?)
+                    }
+                    _ => return Err(ReadError::InvalidFormat(format as i64).into()),
+                };
+                if format & 0x80 != 0 {
+                    let n_sups = cursor.read::<u8>()?;
+                    return Ok(Self {
+                        kind,
+                        supplements: cursor.read_array(n_sups as usize)?,
+                    });
+                }
+                kind
+            }
+        };
+        Ok(Self {
+            kind,
+            supplements: &[],
+        })
+    }
+
+    /// Returns true if this is the predefined Standard encoding.
+    pub fn is_standard(&self) -> bool {
+        matches!(self.kind, EncodingKind::Standard)
+    }
+
+    /// Returns true if this is the predefined Expert encoding.
+    pub fn is_expert(&self) -> bool {
+        matches!(self.kind, EncodingKind::Expert)
+    }
+
+    /// Returns the supplementary mappings from codes to string identifiers.
+    pub fn supplements(&self) -> &'a [EncodingSupplement] {
+        self.supplements
+    }
+
+    /// Returns the glyph identifier for the given character code.
+    ///
+    /// The `charset` is used to resolve codes that map to string
+    /// identifiers.
+    pub fn map(&self, code: u8, charset: &Charset) -> Option<GlyphId> {
+        // Supplements are applied after the main encoding so they take
+        // precedence.
+        if let Some(sup) = self.supplements.iter().find(|sup| sup.code() == code) {
+            return charset.glyph_id(StringId::new(sup.glyph()));
+        }
+        let gid = match &self.kind {
+            EncodingKind::Standard | EncodingKind::Expert => {
+                let sid = if self.is_standard() {
+                    STANDARD_ENCODING[code as usize] as u16
+                } else {
+                    EXPERT_ENCODING[code as usize]
+                };
+                if sid == 0 {
+                    return None;
+                }
+                return charset.glyph_id(StringId::new(sid));
+            }
+            // Glyph 0 (.notdef) is never encoded so the first code
+            // corresponds to glyph 1.
+            EncodingKind::Format0(codes) => codes.iter().position(|c| *c == code)? + 1,
+            EncodingKind::Format1(ranges) => {
+                let mut first_gid = 1;
+                let mut gid = None;
+                for range in *ranges {
+                    let count = range.n_left() as usize + 1;
+                    let first = range.first();
+                    if code >= first && ((code - first) as usize) < count {
+                        gid = Some(first_gid + (code - first) as usize);
+                        break;
+                    }
+                    first_gid += count;
+                }
+                gid?
+            }
+        };
+        ((gid as u32) < charset.num_glyphs()).then(|| GlyphId::new(gid as u16))
+    }
+
+    /// Returns an iterator over all pairs of character codes and glyph
+    /// identifiers defined by the encoding.
+    pub fn iter<'b>(
+        &'b self,
+        charset: &'b Charset<'a>,
+    ) -> impl Iterator<Item = (u8, GlyphId)> + 'b {
+        (0..=255u8).filter_map(|code| Some((code, self.map(code, charset)?)))
+    }
+}
+
+#[derive(Clone)]
+enum EncodingKind<'a> {
+    Standard,
+    Expert,
+    Format0(&'a [u8]),
+    Format1(&'a [EncodingRange1]),
+}
+
+/// Predefined Standard encoding, mapping character codes to string
+/// identifiers.
+///
+/// This is primarily used to resolve the components of the implied `seac`
+/// operator in Type 2 charstrings.
+///
+/// See "Appendix B - Predefined Encodings" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=43>
+#[rustfmt::skip]
+pub const STANDARD_ENCODING: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+    17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+    33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48,
+    49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64,
+    65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80,
+    81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110,
+    0, 111, 112, 113, 114, 0, 115, 116, 117, 118, 119, 120, 121, 122, 0, 123,
+    0, 124, 125, 126, 127, 128, 129, 130, 131, 0, 132, 133, 0, 134, 135, 136,
+    137, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 138, 0, 139, 0, 0, 0, 0, 140, 141, 142, 143, 0, 0, 0, 0,
+    0, 144, 0, 0, 0, 145, 0, 0, 146, 147, 148, 149, 0, 0, 0, 0,
+];
+
+/// Predefined Expert encoding, mapping character codes to string
+/// identifiers.
+///
+/// See "Appendix B - Predefined Encodings" at <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5176.CFF.pdf#page=45>
+#[rustfmt::skip]
+const EXPERT_ENCODING: [u16; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    1, 229, 230, 0, 231, 232, 233, 234, 235, 236, 237, 238, 13, 14, 15, 99,
+    239, 240, 241, 242, 243, 244, 245, 246, 247, 248, 27, 28, 249, 250, 251, 252,
+    0, 253, 254, 255, 256, 257, 0, 0, 0, 258, 0, 0, 259, 260, 261, 262,
+    0, 0, 263, 264, 265, 0, 266, 109, 110, 267, 268, 269, 0, 270, 271, 272,
+    273, 274, 275, 276, 277, 278, 279, 280, 281, 282, 283, 284, 285, 286, 287, 288,
+    289, 290, 291, 292, 293, 294, 295, 296, 297, 298, 299, 300, 301, 302, 303, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 304, 305, 306, 0, 0, 307, 308, 309, 310, 311, 0, 312, 0, 0, 313,
+    0, 0, 314, 315, 0, 0, 316, 317, 318, 0, 0, 0, 158, 155, 163, 319,
+    320, 321, 322, 323, 324, 325, 0, 0, 326, 150, 164, 169, 327, 328, 329, 330,
+    331, 332, 333, 334, 335, 336, 337, 338, 339, 340, 341, 342, 343, 344, 345, 346,
+    347, 348, 349, 350, 351, 352, 353, 354, 355, 356, 357, 358, 359, 360, 361, 362,
+    363, 364, 365, 366, 367, 368, 369, 370, 371, 372, 373, 374, 375, 376, 377, 378,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn standard_encoding_names() {
+        let name = |code: u8| {
+            StringId::new(STANDARD_ENCODING[code as usize] as u16)
+                .standard_string()
+                .unwrap()
+        };
+        assert_eq!(name(b'A'), "A");
+        assert_eq!(name(b'z'), "z");
+        assert_eq!(name(b' '), "space");
+        assert_eq!(name(194), "acute");
+        assert_eq!(name(251), "germandbls");
+        assert_eq!(name(0), ".notdef");
+    }
+
+    #[test]
+    fn expert_encoding_names() {
+        let name = |code: u8| {
+            StringId::new(EXPERT_ENCODING[code as usize])
+                .standard_string()
+                .unwrap()
+        };
+        assert_eq!(name(b'!'), "exclamsmall");
+        assert_eq!(name(b'a'), "Asmall");
+        assert_eq!(name(b'~'), "Tildesmall");
+        assert_eq!(name(188), "onequarter");
+        assert_eq!(name(255), "Ydieresissmall");
+    }
+
+    #[test]
+    fn custom_encodings() {
+        // Glyphs 1..=4 are encoded as codes 65, 66, 67, 97 and glyph 5 is
+        // mapped from code 200 with a supplement referencing SID 41.
+        // Each encoding is preceded by 4 bytes of padding since offsets 0
+        // and 1 are reserved for the predefined encodings.
+        let format0 = BeBuffer::new()
+            .push(0u32)
+            .push(0x80u8)
+            .push(4u8)
+            .extend([65u8, 66, 67, 97])
+            .push(1u8)
+            .push(200u8)
+            .push(41u16);
+        let format1 = BeBuffer::new()
+            .push(0u32)
+            .push(0x81u8)
+            .push(2u8)
+            .extend([65u8, 2, 97, 0])
+            .push(1u8)
+            .push(200u8)
+            .push(41u16);
+        // Charset mapping glyphs 1..=5 to SIDs 34, 35, 36, 66, 41
+        let charset_data = BeBuffer::new()
+            .push(0u32)
+            .push(0u8)
+            .extend([34u16, 35, 36, 66, 41]);
+        let charset = Charset::new(charset_data.font_data(), 4, 6).unwrap();
+        for buf in [format0, format1] {
+            let encoding = Encoding::new(buf.font_data(), 4).unwrap();
+            assert_eq!(encoding.supplements().len(), 1);
+            let mapped = encoding.iter(&charset).collect::<Vec<_>>();
+            let expected = [(65, 1), (66, 2), (67, 3), (97, 4), (200, 5)]
+                .map(|(code, gid)| (code, GlyphId::new(gid)));
+            assert_eq!(mapped, expected);
+        }
+    }
+
+    #[test]
+    fn predefined_encodings() {
+        let data = FontData::new(&[]);
+        // The ISOAdobe charset maps glyph ids to identical SIDs
+        let charset = Charset::new(data, 0, 229).unwrap();
+        let standard = Encoding::new(data, 0).unwrap();
+        assert!(standard.is_standard());
+        assert_eq!(standard.map(b'A', &charset), Some(GlyphId::new(34)));
+        assert_eq!(standard.map(251, &charset), Some(GlyphId::new(149)));
+        assert_eq!(standard.map(0, &charset), None);
+        let expert = Encoding::new(data, 1).unwrap();
+        assert!(expert.is_expert());
+        // The Expert charset is required to resolve these codes
+        assert_eq!(expert.map(b'a', &charset), None);
+        let expert_charset = Charset::new(data, 1, 166).unwrap();
+        // "Asmall" is at glyph 55 in the Expert charset
+        assert_eq!(expert.map(b'a', &expert_charset), Some(GlyphId::new(55)));
+    }
+}
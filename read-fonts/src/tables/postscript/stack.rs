@@ -125,6 +125,25 @@ impl Stack {
         })
     }
 
+    /// Returns the number at the given index on the stack, preserving the
+    /// type that was used when it was pushed.
+    pub fn get_number(&self, index: usize) -> Result<Number, Error> {
+        if index >= self.top {
+            return Err(Error::InvalidStackAccess(index));
+        }
+        Ok(Number::from_stack(
+            self.values[index],
+            self.value_is_fixed[index],
+        ))
+    }
+
+    /// Pops a number from the top of the stack, preserving the type that
+    /// was used when it was pushed.
+    pub fn pop_number(&mut self) -> Result<Number, Error> {
+        let i = self.pop()?;
+        Ok(Number::from_stack(self.values[i], self.value_is_fixed[i]))
+    }
+
     /// Pops a 32-bit integer from the top of stack.
     ///
     /// Will return an error if the top value on the stack was not pushed as an
@@ -199,6 +218,31 @@ impl Stack {
             .map(|(value, is_fixed)| Number::from_stack(*value, *is_fixed))
     }
 
+    /// Performs a circular shift of the top `count` elements on the stack by
+    /// `shift` positions.
+    ///
+    /// Positive values of `shift` move elements toward the top of the stack.
+    /// This implements the legacy charstring `roll` operator.
+    ///
+    /// See <https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf#page=28>
+    pub fn roll(&mut self, count: i32, shift: i32) -> Result<(), Error> {
+        if count < 0 {
+            return Err(Error::InvalidStackAccess(count as usize));
+        }
+        let count = count as usize;
+        if count > self.top {
+            return Err(Error::StackUnderflow);
+        }
+        if count < 2 {
+            return Ok(());
+        }
+        let start = self.top - count;
+        let shift = shift.rem_euclid(count as i32) as usize;
+        self.values[start..self.top].rotate_right(shift);
+        self.value_is_fixed[start..self.top].rotate_right(shift);
+        Ok(())
+    }
+
     /// Apply a prefix sum to decode delta-encoded numbers.
     ///
     /// "The second and subsequent numbers in a delta are encoded as the
@@ -368,7 +412,7 @@ impl std::fmt::Display for Number {
 mod tests {
     use types::{F2Dot14, Fixed};
 
-    use super::Stack;
+    use super::{Number, Stack};
     use crate::{
         tables::{postscript::BlendState, variations::ItemVariationStore},
         FontData, FontRead,
@@ -412,6 +456,31 @@ mod tests {
         assert_eq!(stack.pop_fixed().unwrap(), Fixed::from_f64(4.2));
     }
 
+    #[test]
+    fn roll() {
+        let mut stack = Stack::new();
+        for i in 0..5 {
+            stack.push(i).unwrap();
+        }
+        stack.push(Fixed::from_f64(5.5)).unwrap();
+        stack.roll(3, 1).unwrap();
+        let values: Vec<_> = stack.number_values().collect();
+        let expected: [Number; 6] = [
+            0.into(),
+            1.into(),
+            2.into(),
+            Fixed::from_f64(5.5).into(),
+            3.into(),
+            4.into(),
+        ];
+        assert_eq!(&values, &expected);
+        // Rolling in the opposite direction restores the original order
+        stack.roll(3, -1).unwrap();
+        assert_eq!(stack.pop_fixed().unwrap(), Fixed::from_f64(5.5));
+        assert_eq!(stack.pop_i32().unwrap(), 4);
+        assert!(stack.roll(5, 1).is_err());
+    }
+
     #[test]
     fn delta_prefix_sum() {
         let mut stack = Stack::new();
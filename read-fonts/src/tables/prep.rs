@@ -0,0 +1,3 @@
+//! The [prep (Control Value Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/prep) table
+
+include!("../../generated/generated_prep.rs");
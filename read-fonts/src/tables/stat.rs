@@ -2,6 +2,29 @@
 
 include!("../../generated/generated_stat.rs");
 
+impl<'a> AxisValue<'a> {
+    /// Returns the flags for the axis value table.
+    pub fn flags(&self) -> AxisValueTableFlags {
+        match self {
+            Self::Format1(item) => item.flags(),
+            Self::Format2(item) => item.flags(),
+            Self::Format3(item) => item.flags(),
+            Self::Format4(item) => item.flags(),
+        }
+    }
+
+    /// Returns the name ID for the entry in the `name` table that provides
+    /// a display string for this attribute value.
+    pub fn value_name_id(&self) -> NameId {
+        match self {
+            Self::Format1(item) => item.value_name_id(),
+            Self::Format2(item) => item.value_name_id(),
+            Self::Format3(item) => item.value_name_id(),
+            Self::Format4(item) => item.value_name_id(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use types::{Fixed, NameId};
@@ -33,5 +56,9 @@ mod tests {
             assert_eq!(table.value_name_id(), NameId::new(264));
             assert_eq!(table.value(), Fixed::from_f64(700.0));
         }
+        assert_eq!(last.value_name_id(), NameId::new(264));
+        assert!(axis_values[1]
+            .flags()
+            .contains(AxisValueTableFlags::ELIDABLE_AXIS_VALUE_NAME));
     }
 }
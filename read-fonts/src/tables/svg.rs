@@ -0,0 +1,257 @@
+//! The [SVG](https://learn.microsoft.com/en-us/typography/opentype/spec/svg) table
+
+use core::cmp::Ordering;
+
+include!("../../generated/generated_svg.rs");
+
+/// Leading bytes of a gzip compressed document.
+const GZIP_HEADER: [u8; 3] = [0x1F, 0x8B, 0x08];
+
+/// Maximum size in bytes of a decompressed SVG document.
+///
+/// This guards against small compressed documents that expand to
+/// excessive sizes. Real world documents are far smaller than this.
+#[cfg(feature = "gzip")]
+pub const MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+impl<'a> Svg<'a> {
+    /// Returns the raw data of the SVG document that contains the given
+    /// glyph.
+    ///
+    /// The data may be compressed; see [`is_gzip`].
+    pub fn glyph_data(&self, glyph_id: GlyphId) -> Result<Option<&'a [u8]>, ReadError> {
+        let list = self.svg_document_list()?;
+        list.document_index(glyph_id)
+            .map(|index| list.document_data(index))
+            .transpose()
+    }
+}
+
+impl<'a> SvgDocumentList<'a> {
+    /// Returns the index of the document record that covers the given glyph.
+    ///
+    /// Multiple glyphs may share a single document so this is useful for
+    /// caching decoded documents.
+    pub fn document_index(&self, glyph_id: GlyphId) -> Option<usize> {
+        self.document_records()
+            .binary_search_by(|record| {
+                if record.end_glyph_id() < glyph_id {
+                    Ordering::Less
+                } else if record.start_glyph_id() > glyph_id {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()
+    }
+
+    /// Returns the raw data of the document at the given index.
+    ///
+    /// The data may be compressed; see [`is_gzip`].
+    pub fn document_data(&self, index: usize) -> Result<&'a [u8], ReadError> {
+        let record = self
+            .document_records()
+            .get(index)
+            .ok_or(ReadError::OutOfBounds)?;
+        let start = record.svg_doc_offset() as usize;
+        let end = start
+            .checked_add(record.svg_doc_length() as usize)
+            .ok_or(ReadError::OutOfBounds)?;
+        self.offset_data()
+            .as_bytes()
+            .get(start..end)
+            .ok_or(ReadError::OutOfBounds)
+    }
+}
+
+/// Returns true if the document data is compressed with gzip.
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&GZIP_HEADER)
+}
+
+/// Returns the uncompressed document data, decompressing it if it is
+/// compressed with gzip.
+///
+/// The gzip trailer is expected in the final eight bytes of the data and
+/// the CRC-32 and size that it records are checked against the
+/// decompressed document. Documents that would decompress to more than
+/// [`MAX_DECOMPRESSED_SIZE`] bytes produce an error.
+#[cfg(feature = "gzip")]
+pub fn decompress(data: &[u8]) -> Result<std::borrow::Cow<'_, [u8]>, ReadError> {
+    use std::borrow::Cow;
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+    if !is_gzip(data) {
+        return Ok(Cow::Borrowed(data));
+    }
+    let flags = *data.get(3).ok_or(ReadError::OutOfBounds)?;
+    // Skip the fixed size header and any optional fields
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = data.get(pos..pos + 2).ok_or(ReadError::OutOfBounds)?;
+        pos += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let len = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|byte| *byte == 0))
+                .ok_or(ReadError::OutOfBounds)?;
+            pos += len + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    // The trailer contains the CRC-32 and size (modulo 2^32) of the
+    // uncompressed data
+    let trailer_start = data.len().checked_sub(8).ok_or(ReadError::OutOfBounds)?;
+    let deflate_data = data.get(pos..trailer_start).ok_or(ReadError::OutOfBounds)?;
+    let trailer = &data[trailer_start..];
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    let decompressed =
+        miniz_oxide::inflate::decompress_to_vec_with_limit(deflate_data, MAX_DECOMPRESSED_SIZE)
+            .map_err(|e| match e.status {
+                miniz_oxide::inflate::TINFLStatus::HasMoreOutput => {
+                    ReadError::MalformedData("decompressed SVG document exceeds size limit")
+                }
+                _ => ReadError::MalformedData("invalid gzip compressed SVG document"),
+            })?;
+    if decompressed.len() as u32 != size || crc32(&decompressed) != crc {
+        return Err(ReadError::MalformedData(
+            "gzip trailer does not match decompressed SVG document",
+        ));
+    }
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Computes the CRC-32 checksum used by gzip.
+#[cfg(feature = "gzip")]
+fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    0xEDB88320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[i] = crc;
+            i += 1;
+        }
+        table
+    };
+    !data.iter().fold(!0u32, |crc, byte| {
+        TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[rustfmt::skip]
+    static GZIP_DOCUMENT: &[u8] = &[
+        0x1F, 0x8B, 8, 8, 0, 0, 0, 0, 2, 255,
+        // file name
+        b'a', b'.', b's', b'v', b'g', 0,
+        // compressed data and trailer
+        179, 41, 46, 75, 215, 183, 3, 0, 73, 251, 185, 172, 6, 0, 0, 0,
+    ];
+
+    fn svg_data() -> BeBuffer {
+        BeBuffer::new()
+            .push(0u16)
+            .extend([10u32, 0])
+            // document list: 2 records
+            .push(2u16)
+            // glyphs 2..=4 share a plain text document
+            .extend([2u16, 4])
+            .extend([26u32, 6])
+            // glyph 7 has a compressed document
+            .extend([7u16, 7])
+            .extend([32u32, GZIP_DOCUMENT.len() as u32])
+            .extend(*b"<svg/>")
+            .extend(GZIP_DOCUMENT.iter().copied())
+    }
+
+    #[test]
+    fn documents() {
+        let buf = svg_data();
+        let svg = Svg::read(buf.font_data()).unwrap();
+        let list = svg.svg_document_list().unwrap();
+        assert_eq!(list.num_entries(), 2);
+        let indices = (1..9)
+            .map(|gid| list.document_index(GlyphId::new(gid)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            indices,
+            [None, Some(0), Some(0), Some(0), None, None, Some(1), None]
+        );
+        let plain = svg.glyph_data(GlyphId::new(3)).unwrap().unwrap();
+        assert_eq!(plain, b"<svg/>");
+        assert!(!is_gzip(plain));
+        let compressed = svg.glyph_data(GlyphId::new(7)).unwrap().unwrap();
+        assert_eq!(compressed, GZIP_DOCUMENT);
+        assert!(is_gzip(compressed));
+        assert_eq!(svg.glyph_data(GlyphId::new(5)).unwrap(), None);
+        assert!(list.document_data(2).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn decompress_documents() {
+        assert_eq!(decompress(GZIP_DOCUMENT).unwrap().as_ref(), b"<svg/>");
+        assert_eq!(decompress(b"<svg/>").unwrap().as_ref(), b"<svg/>");
+        assert!(decompress(&GZIP_DOCUMENT[..12]).is_err());
+        assert_eq!(crc32(b"<svg/>"), 0xACB9FB49);
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn decompress_checks_trailer() {
+        let len = GZIP_DOCUMENT.len();
+        let mut bad_crc = GZIP_DOCUMENT.to_vec();
+        bad_crc[len - 8] ^= 1;
+        assert!(decompress(&bad_crc).is_err());
+        let mut bad_size = GZIP_DOCUMENT.to_vec();
+        bad_size[len - 4] = 7;
+        assert!(decompress(&bad_size).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn decompress_size_limit() {
+        let mut data = GZIP_HEADER.to_vec();
+        data.extend([0, 0, 0, 0, 0, 0, 255]);
+        // A stream of zeros that decompresses to one byte past the limit
+        let zeros = vec![0u8; MAX_DECOMPRESSED_SIZE + 1];
+        data.extend(miniz_oxide::deflate::compress_to_vec(&zeros, 10));
+        data.extend(crc32(&zeros).to_le_bytes());
+        data.extend((zeros.len() as u32).to_le_bytes());
+        assert!(matches!(
+            decompress(&data),
+            Err(ReadError::MalformedData(
+                "decompressed SVG document exceeds size limit"
+            ))
+        ));
+        // The same stream with one byte less is accepted
+        let zeros = &zeros[..MAX_DECOMPRESSED_SIZE];
+        let mut data = data[..10].to_vec();
+        data.extend(miniz_oxide::deflate::compress_to_vec(zeros, 10));
+        data.extend(crc32(zeros).to_le_bytes());
+        data.extend((zeros.len() as u32).to_le_bytes());
+        assert_eq!(decompress(&data).unwrap().len(), MAX_DECOMPRESSED_SIZE);
+    }
+}
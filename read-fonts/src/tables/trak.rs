@@ -0,0 +1,145 @@
+//! The [trak (Tracking)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6trak.html) table.
+
+include!("../../generated/generated_trak.rs");
+
+impl<'a> Trak<'a> {
+    /// Returns the horizontal tracking adjustment, in font units, for the
+    /// given track and point size.
+    ///
+    /// See [`TrackData::tracking`] for details.
+    pub fn horiz_tracking(&self, track: Fixed, point_size: Fixed) -> Option<i32> {
+        self.horiz()?
+            .ok()?
+            .tracking(self.offset_data(), track, point_size)
+    }
+
+    /// Returns the vertical tracking adjustment, in font units, for the
+    /// given track and point size.
+    ///
+    /// See [`TrackData::tracking`] for details.
+    pub fn vert_tracking(&self, track: Fixed, point_size: Fixed) -> Option<i32> {
+        self.vert()?
+            .ok()?
+            .tracking(self.offset_data(), track, point_size)
+    }
+}
+
+impl<'a> TrackData<'a> {
+    /// Returns the list of point sizes.
+    ///
+    /// The offset to the size table is relative to the start of the `trak`
+    /// table so `offset_data` must be the data for that table.
+    pub fn size_table(
+        &self,
+        offset_data: FontData<'a>,
+    ) -> Result<&'a [BigEndian<Fixed>], ReadError> {
+        let start = self.size_table_offset() as usize;
+        let len = self.n_sizes() as usize * Fixed::RAW_BYTE_LEN;
+        offset_data.read_array(start..start + len)
+    }
+
+    /// Returns the tracking adjustment, in font units, for the given track
+    /// and point size.
+    ///
+    /// Values for point sizes between those in the size table are linearly
+    /// interpolated and sizes outside that range use the nearest value. The
+    /// track value 0 is the normal track while negative values are tighter
+    /// and positive values are looser.
+    ///
+    /// Returns `None` if the track is not present in the table.
+    pub fn tracking(
+        &self,
+        offset_data: FontData<'a>,
+        track: Fixed,
+        point_size: Fixed,
+    ) -> Option<i32> {
+        let entry = self
+            .track_table()
+            .iter()
+            .find(|entry| entry.track() == track)?;
+        let sizes = self.size_table(offset_data).ok()?;
+        let values = entry.per_size_values(offset_data, self.n_sizes()).ok()?;
+        // Find the first size that is greater than or equal to the
+        // requested size
+        let ix = sizes
+            .iter()
+            .position(|size| size.get() >= point_size)
+            .unwrap_or(sizes.len().checked_sub(1)?);
+        if ix == 0 || sizes[ix].get() <= point_size {
+            return values.get(ix).map(|value| value.get() as i32);
+        }
+        let (s0, s1) = (sizes[ix - 1].get().to_f64(), sizes[ix].get().to_f64());
+        let (v0, v1) = (values[ix - 1].get() as f64, values[ix].get() as f64);
+        let t = (point_size.to_f64() - s0) / (s1 - s0);
+        Some((v0 + (v1 - v0) * t).round() as i32)
+    }
+}
+
+impl TrackTableEntry {
+    /// Returns the tracking values for this track, one for each point size.
+    ///
+    /// The offset to the values is relative to the start of the `trak`
+    /// table so `offset_data` must be the data for that table.
+    pub fn per_size_values<'a>(
+        &self,
+        offset_data: FontData<'a>,
+        n_sizes: u16,
+    ) -> Result<&'a [BigEndian<i16>], ReadError> {
+        let start = self.offset() as usize;
+        let len = n_sizes as usize * i16::RAW_BYTE_LEN;
+        offset_data.read_array(start..start + len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn fixed(value: i32) -> Fixed {
+        Fixed::from_i32(value)
+    }
+
+    #[test]
+    fn tracking() {
+        let buf = BeBuffer::new()
+            // version, format, horiz offset, vert offset, reserved
+            .push(MajorMinor::VERSION_1_0)
+            .extend([0u16, 12, 0, 0])
+            // track data: 3 tracks, 2 sizes, size table at 44
+            .extend([3u16, 2])
+            .push(44u32)
+            // track table entries: (track, name index, offset)
+            .push(fixed(-1))
+            .extend([256u16, 52])
+            .push(fixed(0))
+            .extend([257u16, 56])
+            .push(fixed(1))
+            .extend([258u16, 60])
+            // size table
+            .extend([fixed(12), fixed(24)])
+            // per size values
+            .extend([-20i16, -40])
+            .extend([0i16, 0])
+            .extend([50i16, 10]);
+        let trak = Trak::read(buf.font_data()).unwrap();
+        assert!(trak.vert().is_none());
+        let data = trak.horiz().unwrap().unwrap();
+        assert_eq!(data.n_tracks(), 3);
+        let sizes = data.size_table(trak.offset_data()).unwrap();
+        assert_eq!(sizes, &[fixed(12), fixed(24)]);
+        let track = |track, size| trak.horiz_tracking(fixed(track), fixed(size));
+        assert_eq!(track(-1, 12), Some(-20));
+        assert_eq!(track(-1, 24), Some(-40));
+        // clamped
+        assert_eq!(track(-1, 8), Some(-20));
+        assert_eq!(track(-1, 48), Some(-40));
+        // interpolated
+        assert_eq!(track(-1, 18), Some(-30));
+        assert_eq!(track(1, 15), Some(40));
+        assert_eq!(track(0, 15), Some(0));
+        // missing track
+        assert_eq!(track(2, 12), None);
+        assert_eq!(trak.vert_tracking(fixed(0), fixed(12)), None);
+    }
+}
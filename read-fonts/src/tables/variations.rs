@@ -181,6 +181,62 @@ impl<'a> TupleVariationHeader<'a> {
         })
     }
 
+    // transcribed from pinot/moscato
+    /// Compute the scalar for this tuple at a given point in design space,
+    /// using the given peak tuple.
+    ///
+    /// The peak is passed explicitly because in the 'gvar' table it may
+    /// be stored in the shared tuple records rather than in this header.
+    ///
+    /// Returns `None` if this tuple is not applicable at the provided coordinates
+    /// (e.g. if the resulting scalar is zero).
+    pub(crate) fn compute_scalar(
+        &self,
+        peak: Tuple,
+        axis_count: u16,
+        coords: &[F2Dot14],
+    ) -> Option<Fixed> {
+        const ZERO: Fixed = Fixed::ZERO;
+        let mut scalar = Fixed::ONE;
+        let inter_start = self.intermediate_start_tuple();
+        let inter_end = self.intermediate_end_tuple();
+        if peak.len() != axis_count as usize {
+            return None;
+        }
+
+        for i in 0..axis_count {
+            let i = i as usize;
+            let coord = coords.get(i).copied().unwrap_or_default().to_fixed();
+            let peak = peak.get(i).unwrap_or_default().to_fixed();
+            if peak == ZERO || peak == coord {
+                continue;
+            }
+
+            if coord == ZERO {
+                return None;
+            }
+
+            if let (Some(inter_start), Some(inter_end)) = (&inter_start, &inter_end) {
+                let start = inter_start.get(i).unwrap_or_default().to_fixed();
+                let end = inter_end.get(i).unwrap_or_default().to_fixed();
+                if coord <= start || coord >= end {
+                    return None;
+                }
+                if coord < peak {
+                    scalar = scalar.mul_div(coord - start, peak - start);
+                } else {
+                    scalar = scalar.mul_div(end - coord, end - peak);
+                }
+            } else {
+                if coord < peak.min(ZERO) || coord > peak.max(ZERO) {
+                    return None;
+                }
+                scalar = scalar.mul_div(coord, peak);
+            }
+        }
+        Some(scalar)
+    }
+
     /// Compute the actual length of this table in bytes
     fn byte_len(&self, axis_count: u16) -> usize {
         const FIXED_LEN: usize = u16::RAW_BYTE_LEN + TupleIndex::RAW_BYTE_LEN;
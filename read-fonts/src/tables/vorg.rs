@@ -0,0 +1,38 @@
+//! The [VORG (Vertical Origin)](https://docs.microsoft.com/en-us/typography/opentype/spec/vorg) table
+
+include!("../../generated/generated_vorg.rs");
+
+impl<'a> Vorg<'a> {
+    /// Returns the y coordinate of the vertical origin for the specified
+    /// glyph identifier.
+    ///
+    /// Falls back to the default value if the glyph has no explicit entry.
+    pub fn vertical_origin_y(&self, glyph_id: GlyphId) -> i16 {
+        let metrics = self.vert_origin_y_metrics();
+        match metrics.binary_search_by(|rec| rec.glyph_id().cmp(&glyph_id)) {
+            Ok(ix) => metrics[ix].vert_origin_y(),
+            _ => self.default_vert_origin_y(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn vertical_origins() {
+        let buf = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .push(880i16)
+            .push(2u16)
+            .extend([2u16, 900, 5, 920]);
+        let vorg = Vorg::read(buf.font_data()).unwrap();
+        assert_eq!(vorg.num_vert_origin_y_metrics(), 2);
+        let origins = (0..7)
+            .map(|gid| vorg.vertical_origin_y(GlyphId::new(gid)))
+            .collect::<Vec<_>>();
+        assert_eq!(origins, [880, 880, 900, 880, 880, 920, 880]);
+    }
+}
@@ -0,0 +1,10 @@
+#![parse_module(read_fonts::tables::fpgm)]
+
+/// The [fpgm (Font Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/fpgm) table
+#[tag = "fpgm"]
+table Fpgm {
+    /// Instructions. n is the number of uint8 items that fit in the size
+    /// of the table.
+    #[count(..)]
+    instructions: [u8],
+}
@@ -0,0 +1,145 @@
+#![parse_module(read_fonts::tables::kern)]
+
+/// The OpenType [kerning](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table.
+#[tag = "kern"]
+table OtKern {
+    /// Table version number—set to 0.
+    version: u16,
+    /// Number of subtables in the kerning table.
+    n_tables: u16,
+    /// Data for subtables, immediately following the header.
+    #[count(..)]
+    #[traverse_with(traverse_subtables)]
+    subtable_data: [u8],
+}
+
+/// The Apple Advanced Typography [kerning](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kern.html) table.
+#[tag = "kern"]
+table AatKern {
+    /// The version number of the kerning table (0x00010000 for the current version).
+    version: MajorMinor,
+    /// The number of subtables included in the kerning table.
+    n_tables: u32,
+    /// Data for subtables, immediately following the header.
+    #[count(..)]
+    #[traverse_with(traverse_subtables)]
+    subtable_data: [u8],
+}
+
+/// A subtable in an OT `kern` table.
+table OtSubtable {
+    /// Kern subtable version number-set to 0.
+    version: u16,
+    /// The length of this subtable in bytes, including this header.
+    length: u16,
+    /// Circumstances under which this table is used.
+    coverage: u16,
+    /// Subtable specific data.
+    #[count(..)]
+    #[traverse_with(traverse_kind)]
+    data: [u8],
+}
+
+/// A subtable in an AAT `kern` table.
+table AatSubtable {
+    /// The length of this subtable in bytes, including this header.
+    length: u32,
+    /// Circumstances under which this table is used.
+    coverage: u16,
+    /// The tuple index (used for variations fonts). This value specifies which tuple this subtable covers.
+    tuple_index: u16,
+    /// Subtable specific data.
+    #[count(..)]
+    #[traverse_with(traverse_kind)]
+    data: [u8],
+}
+
+/// The type 0 `kern` subtable.
+table Subtable0 {
+    /// The number of kerning pairs in this subtable.
+    n_pairs: u16,
+    /// The largest power of two less than or equal to the value of nPairs, multiplied by the size in bytes of an entry in the subtable.
+    search_range: u16,
+    /// This is calculated as log2 of the largest power of two less than or equal to the value of nPairs.
+    entry_selector: u16,
+    /// The value of nPairs minus the largest power of two less than or equal to nPairs.
+    range_shift: u16,
+    /// Kerning records, sorted by the left and right glyph identifiers.
+    #[count($n_pairs)]
+    pairs: [Subtable0Pair],
+}
+
+/// Kerning pair record for the type 0 `kern` subtable.
+record Subtable0Pair {
+    /// The glyph index for the lefthand glyph in the kerning pair.
+    left: GlyphId,
+    /// The glyph index for the righthand glyph in the kerning pair.
+    right: GlyphId,
+    /// The kerning value in font units for the above pair.
+    value: FWord,
+}
+
+/// The type 1 `kern` subtable.
+///
+/// This is a state machine driven contextual kerning subtable found in
+/// AAT fonts. All offsets are relative to the start of this subtable.
+table Subtable1 {
+    /// The number of classes in the state machine.
+    state_size: u16,
+    /// Offset to the class table.
+    class_table_offset: Offset16<ClassTable>,
+    /// Offset to the state array.
+    state_array_offset: u16,
+    /// Offset to the entry table.
+    entry_table_offset: u16,
+    /// Offset to the array of kerning values.
+    value_table_offset: u16,
+}
+
+/// Class table for the state machine in the type 1 `kern` subtable.
+table ClassTable {
+    /// Glyph index of the first glyph in the class array.
+    first_glyph: GlyphId,
+    /// Number of glyphs in the class array.
+    n_glyphs: u16,
+    /// Class values for each glyph.
+    #[count($n_glyphs)]
+    class_array: [u8],
+}
+
+/// Class table for the type 2 `kern` subtable.
+table Subtable2ClassTable {
+    /// First glyph in class range.
+    first_glyph: GlyphId,
+    /// Number of glyph in class range.
+    n_glyphs: u16,
+    /// The offsets array for all of the glyphs in the range.
+    #[count($n_glyphs)]
+    offsets: [u16],
+}
+
+/// The type 3 `kern` subtable.
+table Subtable3 {
+    /// The number of glyphs in this font.
+    glyph_count: u16,
+    /// The number of kerning values.
+    kern_value_count: u8,
+    /// The number of left-hand classes.
+    left_class_count: u8,
+    /// The number of right-hand classes.
+    right_class_count: u8,
+    /// Set to zero (reserved for future use).
+    flags: u8,
+    /// The kerning values.
+    #[count($kern_value_count)]
+    kern_value: [FWord],
+    /// The left-hand classes.
+    #[count($glyph_count)]
+    left_class: [u8],
+    /// The right-hand classes.
+    #[count($glyph_count)]
+    right_class: [u8],
+    /// The indices into the kernValue array.
+    #[count(multiply($left_class_count, $right_class_count))]
+    kern_index: [u8],
+}
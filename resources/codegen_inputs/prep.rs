@@ -0,0 +1,11 @@
+#![parse_module(read_fonts::tables::prep)]
+
+/// The [prep (Control Value Program)](https://learn.microsoft.com/en-us/typography/opentype/spec/prep) table
+#[tag = "prep"]
+table Prep {
+    /// Set of instructions executed whenever the point size or font
+    /// transformation change. n is the number of uint8 items that fit in
+    /// the size of the table.
+    #[count(..)]
+    instructions: [u8],
+}
@@ -0,0 +1,720 @@
+//! Embedded bitmap glyphs.
+//!
+//! Bitmap glyphs are stored in strikes, where each strike is a set of
+//! images designed for a particular size. The functionality in this module
+//! provides a unified view over the strikes defined in the following tables:
+//!
+//! * [sbix](https://learn.microsoft.com/en-us/typography/opentype/spec/sbix):
+//!   PNG, JPEG or TIFF images
+//! * [CBLC](https://learn.microsoft.com/en-us/typography/opentype/spec/cblc)/[CBDT](https://learn.microsoft.com/en-us/typography/opentype/spec/cbdt):
+//!   PNG images or uncompressed BGRA data
+//! * [EBLC](https://learn.microsoft.com/en-us/typography/opentype/spec/eblc)/[EBDT](https://learn.microsoft.com/en-us/typography/opentype/spec/ebdt):
+//!   monochrome or grayscale masks
+//!
+//! # Example
+//!
+//! ```rust
+//! # use skrifa::prelude::*;
+//! # fn wrapper(font: FontRef) {
+//! let strikes = font.bitmap_strikes();
+//! if let Some(glyph) = strikes.glyph_for_size(Size::new(20.0), GlyphId::new(4)) {
+//!     println!(
+//!         "{}x{} image with top left corner at ({}, {})",
+//!         glyph.width, glyph.height, glyph.bearing_x, glyph.bearing_y
+//!     );
+//! }
+//! # }
+//! ```
+
+use read_fonts::{
+    tables::{
+        bitmap::{
+            BitmapContent, BitmapData as BdtBitmapData, BitmapDataFormat, BitmapLocation,
+            BitmapMetrics, BitmapSize,
+        },
+        cbdt::Cbdt,
+        ebdt::Ebdt,
+        sbix::{GlyphData, Sbix, Strike},
+    },
+    types::{GlyphId, Tag},
+    FontData, TableProvider,
+};
+
+pub use read_fonts::tables::bitmap::BdtComponent;
+
+use super::{
+    instance::{LocationRef, Size},
+    metrics::GlyphMetrics,
+};
+
+/// Source table of a bitmap strike.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BitmapFormat {
+    /// Standard bitmap graphics table.
+    Sbix,
+    /// Color bitmap data table.
+    Cbdt,
+    /// Embedded bitmap data table.
+    Ebdt,
+}
+
+/// Uncompressed single channel image data.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct MaskData<'a> {
+    /// Number of bits per pixel. One of 1, 2, 4 or 8.
+    pub bpp: u8,
+    /// True if the rows of the image are tightly packed. Otherwise, each
+    /// row is padded to a byte boundary.
+    pub is_packed: bool,
+    /// Raw image data with the most significant bits representing the
+    /// leftmost pixels.
+    pub data: &'a [u8],
+}
+
+/// Image payload of a bitmap glyph.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BitmapData<'a> {
+    /// PNG encoded image.
+    Png(&'a [u8]),
+    /// JPEG encoded image.
+    Jpeg(&'a [u8]),
+    /// TIFF encoded image.
+    Tiff(&'a [u8]),
+    /// Uncompressed 32-bit BGRA image with premultiplied alpha.
+    Bgra(&'a [u8]),
+    /// Uncompressed monochrome or grayscale image.
+    Mask(MaskData<'a>),
+    /// Image composed of other glyphs in the same EBDT strike.
+    ///
+    /// Compositing is left to the caller: each component should be
+    /// retrieved from the same [`BitmapStrike`] and drawn with its top left
+    /// corner offset by the given amount from the top left corner of this
+    /// glyph.
+    Composite(&'a [BdtComponent]),
+}
+
+/// Bitmap glyph with metrics.
+///
+/// Bearings and advances are in pixels at the size used to retrieve the
+/// glyph, or in font units when retrieved with an unscaled size. The
+/// image itself is always provided at the size of the strike and the
+/// ratio of the requested size to [`ppem_x`](Self::ppem_x) and
+/// [`ppem_y`](Self::ppem_y) gives the factor needed to scale it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BitmapGlyph<'a> {
+    /// Image payload.
+    pub data: BitmapData<'a>,
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Horizontal pixels per em of the strike containing the image.
+    pub ppem_x: f32,
+    /// Vertical pixels per em of the strike containing the image.
+    pub ppem_y: f32,
+    /// Distance from the origin to the left edge of the image.
+    pub bearing_x: f32,
+    /// Distance from the baseline to the top edge of the image, with
+    /// positive values extending upward.
+    pub bearing_y: f32,
+    /// Horizontal advance width.
+    pub advance: f32,
+}
+
+impl<'a> BitmapGlyph<'a> {
+    /// Scales the metrics from strike pixels to the given size.
+    fn scaled(mut self, ppem: f32) -> Self {
+        if self.ppem_x > 0.0 {
+            let scale_x = ppem / self.ppem_x;
+            self.bearing_x *= scale_x;
+            self.advance *= scale_x;
+        }
+        if self.ppem_y > 0.0 {
+            self.bearing_y *= ppem / self.ppem_y;
+        }
+        self
+    }
+}
+
+/// Set of bitmap glyphs designed for a particular size.
+#[derive(Clone)]
+pub struct BitmapStrike<'a> {
+    kind: StrikeKind<'a>,
+    metrics: GlyphMetrics<'a>,
+    upem: u16,
+}
+
+impl<'a> BitmapStrike<'a> {
+    /// Returns the table that defines the strike.
+    pub fn format(&self) -> BitmapFormat {
+        match &self.kind {
+            StrikeKind::Sbix(_) => BitmapFormat::Sbix,
+            StrikeKind::Bdt(_, tables) => match tables.data {
+                BdtData::Cbdt(_) => BitmapFormat::Cbdt,
+                BdtData::Ebdt(_) => BitmapFormat::Ebdt,
+            },
+        }
+    }
+
+    /// Returns the horizontal and vertical pixels per em for which the
+    /// strike was designed.
+    pub fn ppem(&self) -> (f32, f32) {
+        match &self.kind {
+            StrikeKind::Sbix(strike) => (strike.ppem() as f32, strike.ppem() as f32),
+            StrikeKind::Bdt(size, _) => (size.ppem_x() as f32, size.ppem_y() as f32),
+        }
+    }
+
+    /// Returns the bitmap glyph for the given glyph identifier with metrics
+    /// in pixels at the size of the strike.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<BitmapGlyph<'a>> {
+        match &self.kind {
+            StrikeKind::Sbix(strike) => self.sbix_glyph(strike, glyph_id),
+            StrikeKind::Bdt(size, tables) => bdt_glyph(size, tables, glyph_id),
+        }
+    }
+
+    fn sbix_glyph(&self, strike: &Strike<'a>, glyph_id: GlyphId) -> Option<BitmapGlyph<'a>> {
+        let mut glyph_data = strike.glyph_data(glyph_id).ok()??;
+        if glyph_data.graphic_type() == Tag::new(b"dupe") {
+            // The data is the identifier of another glyph in the same
+            // strike. Only a single level of indirection is followed.
+            let bytes = glyph_data.data();
+            let dupe_id = GlyphId::new(u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?));
+            glyph_data = strike.glyph_data(dupe_id).ok()??;
+        }
+        let ppem = strike.ppem() as f32;
+        let (data, width, height) = sbix_image(&glyph_data)?;
+        // Advances are not stored in the sbix table so we take them from
+        // the horizontal metrics.
+        let advance = self.metrics.advance_width(glyph_id).unwrap_or_default() * ppem
+            / self.upem.max(1) as f32;
+        Some(BitmapGlyph {
+            data,
+            width,
+            height,
+            ppem_x: ppem,
+            ppem_y: ppem,
+            bearing_x: glyph_data.origin_offset_x() as f32,
+            bearing_y: glyph_data.origin_offset_y() as f32 + height as f32,
+            advance,
+        })
+    }
+}
+
+/// Collection of bitmap strikes.
+///
+/// Strikes from all supported tables are included in the order sbix, CBLC
+/// and EBLC.
+#[derive(Clone)]
+pub struct BitmapStrikes<'a> {
+    sbix: Option<Sbix<'a>>,
+    cbdt: Option<BdtTables<'a>>,
+    ebdt: Option<BdtTables<'a>>,
+    metrics: GlyphMetrics<'a>,
+    upem: u16,
+}
+
+impl<'a> BitmapStrikes<'a> {
+    /// Creates a new strike collection from the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        let cbdt = match (font.cblc(), font.cbdt()) {
+            (Ok(cblc), Ok(cbdt)) => Some(BdtTables {
+                location_data: cblc.offset_data(),
+                sizes: cblc.bitmap_sizes(),
+                data: BdtData::Cbdt(cbdt),
+            }),
+            _ => None,
+        };
+        let ebdt = match (font.eblc(), font.ebdt()) {
+            (Ok(eblc), Ok(ebdt)) => Some(BdtTables {
+                location_data: eblc.offset_data(),
+                sizes: eblc.bitmap_sizes(),
+                data: BdtData::Ebdt(ebdt),
+            }),
+            _ => None,
+        };
+        Self {
+            sbix: font.sbix().ok(),
+            cbdt,
+            ebdt,
+            metrics: GlyphMetrics::new(font, Size::unscaled(), LocationRef::default()),
+            upem: font
+                .head()
+                .map(|head| head.units_per_em())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the number of strikes in the collection.
+    pub fn len(&self) -> usize {
+        self.sbix_len() + self.cbdt_len() + self.ebdt.as_ref().map_or(0, |t| t.sizes.len())
+    }
+
+    /// Returns true if the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the strike at the given index.
+    pub fn get(&self, index: usize) -> Option<BitmapStrike<'a>> {
+        let kind = if index < self.sbix_len() {
+            StrikeKind::Sbix(self.sbix.as_ref()?.strikes().get(index).ok()?)
+        } else {
+            let index = index - self.sbix_len();
+            let (index, tables) = if index < self.cbdt_len() {
+                (index, self.cbdt.as_ref()?)
+            } else {
+                (index - self.cbdt_len(), self.ebdt.as_ref()?)
+            };
+            StrikeKind::Bdt(tables.sizes.get(index)?, tables.clone())
+        };
+        Some(BitmapStrike {
+            kind,
+            metrics: self.metrics.clone(),
+            upem: self.upem,
+        })
+    }
+
+    /// Returns an iterator over the strikes in the collection.
+    pub fn iter(&self) -> impl Iterator<Item = BitmapStrike<'a>> + 'a + Clone {
+        let copy = self.clone();
+        (0..self.len()).filter_map(move |i| copy.get(i))
+    }
+
+    /// Returns the bitmap glyph from the strike that best matches the given
+    /// size, with metrics scaled to that size.
+    ///
+    /// The smallest strike that is at least as large as the requested size
+    /// is preferred. Otherwise, the largest available strike is selected.
+    /// Only strikes that contain the glyph are considered and an unscaled
+    /// size selects the largest strike.
+    pub fn glyph_for_size(&self, size: Size, glyph_id: GlyphId) -> Option<BitmapGlyph<'a>> {
+        let requested = size.ppem().unwrap_or(f32::MAX);
+        let mut best: Option<BitmapGlyph<'a>> = None;
+        for glyph in self.iter().filter_map(|strike| strike.get(glyph_id)) {
+            let is_better = match &best {
+                Some(best) => is_better_strike(requested, glyph.ppem_y, best.ppem_y),
+                None => true,
+            };
+            if is_better {
+                best = Some(glyph);
+            }
+        }
+        let target_ppem = size.ppem().unwrap_or(self.upem as f32);
+        best.map(|glyph| glyph.scaled(target_ppem))
+    }
+
+    fn sbix_len(&self) -> usize {
+        self.sbix
+            .as_ref()
+            .map_or(0, |sbix| sbix.num_strikes() as usize)
+    }
+
+    fn cbdt_len(&self) -> usize {
+        self.cbdt.as_ref().map_or(0, |tables| tables.sizes.len())
+    }
+}
+
+/// Returns true if a strike with size `ppem` is a better match for the
+/// requested size than the current best strike.
+///
+/// This matches HarfBuzz's strike selection for sbix and CBDT.
+fn is_better_strike(requested: f32, ppem: f32, best_ppem: f32) -> bool {
+    (requested <= ppem && ppem < best_ppem) || (requested > best_ppem && ppem > best_ppem)
+}
+
+#[derive(Clone)]
+enum StrikeKind<'a> {
+    Sbix(Strike<'a>),
+    Bdt(&'a BitmapSize, BdtTables<'a>),
+}
+
+/// Location and data tables for the CBLC/CBDT and EBLC/EBDT pairs.
+#[derive(Clone)]
+struct BdtTables<'a> {
+    location_data: FontData<'a>,
+    sizes: &'a [BitmapSize],
+    data: BdtData<'a>,
+}
+
+#[derive(Clone)]
+enum BdtData<'a> {
+    Cbdt(Cbdt<'a>),
+    Ebdt(Ebdt<'a>),
+}
+
+fn bdt_glyph<'a>(
+    size: &BitmapSize,
+    tables: &BdtTables<'a>,
+    glyph_id: GlyphId,
+) -> Option<BitmapGlyph<'a>> {
+    let location = size.location(tables.location_data, glyph_id).ok()?;
+    let mut glyph = bdt_image(&tables.data, &location)?;
+    glyph.ppem_x = size.ppem_x() as f32;
+    glyph.ppem_y = size.ppem_y() as f32;
+    Some(glyph)
+}
+
+/// Reads the image at the given location with metrics in strike pixels.
+///
+/// The caller is responsible for setting the pixels per em.
+fn bdt_image<'a>(data: &BdtData<'a>, location: &BitmapLocation) -> Option<BitmapGlyph<'a>> {
+    let BdtBitmapData { metrics, content } = match data {
+        BdtData::Cbdt(cbdt) => cbdt.data(location),
+        BdtData::Ebdt(ebdt) => ebdt.data(location),
+    }
+    .ok()?;
+    let (width, height, bearing_x, bearing_y, advance) = match metrics {
+        BitmapMetrics::Small(m) => (m.width, m.height, m.bearing_x(), m.bearing_y(), m.advance),
+        BitmapMetrics::Big(m) => (
+            m.width,
+            m.height,
+            m.hori_bearing_x(),
+            m.hori_bearing_y(),
+            m.hori_advance,
+        ),
+    };
+    let data = match content {
+        BitmapContent::Data(BitmapDataFormat::Png, data) => BitmapData::Png(data),
+        BitmapContent::Data(_, data) if location.bit_depth == 32 => BitmapData::Bgra(data),
+        BitmapContent::Data(format, data) => BitmapData::Mask(MaskData {
+            bpp: location.bit_depth,
+            is_packed: format == BitmapDataFormat::BitAligned,
+            data,
+        }),
+        BitmapContent::Composite(components) => BitmapData::Composite(components),
+    };
+    Some(BitmapGlyph {
+        data,
+        width: width as u32,
+        height: height as u32,
+        ppem_x: 0.0,
+        ppem_y: 0.0,
+        bearing_x: bearing_x as f32,
+        bearing_y: bearing_y as f32,
+        advance: advance as f32,
+    })
+}
+
+/// Returns the image payload and dimensions of an sbix glyph.
+fn sbix_image<'a>(glyph_data: &GlyphData<'a>) -> Option<(BitmapData<'a>, u32, u32)> {
+    let data = glyph_data.data();
+    match &glyph_data.graphic_type().to_be_bytes() {
+        b"png " => {
+            let (width, height) = png_size(data)?;
+            Some((BitmapData::Png(data), width, height))
+        }
+        b"jpg " => {
+            let (width, height) = jpeg_size(data)?;
+            Some((BitmapData::Jpeg(data), width, height))
+        }
+        b"tiff" => {
+            let (width, height) = tiff_size(data)?;
+            Some((BitmapData::Tiff(data), width, height))
+        }
+        _ => None,
+    }
+}
+
+/// Reads the image dimensions from the frame header of a JPEG image.
+fn jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+    let read_u16 = |offset: usize| {
+        Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) as u32)
+    };
+    // Start of image
+    if data.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+    let mut pos = 2;
+    loop {
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        // Markers may be preceded by any number of fill bytes.
+        while *data.get(pos)? == 0xFF {
+            pos += 1;
+        }
+        let marker = data[pos];
+        pos += 1;
+        match marker {
+            // Standalone markers without a length
+            0x01 | 0xD0..=0xD7 => continue,
+            // Start of frame, excluding the huffman table (C4), JPEG
+            // extension (C8) and arithmetic coding (CC) markers which
+            // share the range. The segment begins with the length and
+            // sample precision, followed by the height and width.
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some((read_u16(pos + 5)?, read_u16(pos + 3)?));
+            }
+            // Start of scan or end of image before any frame header
+            0xD9 | 0xDA => return None,
+            _ => pos += read_u16(pos)? as usize,
+        }
+    }
+}
+
+/// Reads the image dimensions from the first image file directory of a
+/// TIFF image.
+fn tiff_size(data: &[u8]) -> Option<(u32, u32)> {
+    const IMAGE_WIDTH: u16 = 256;
+    const IMAGE_LENGTH: u16 = 257;
+    const SHORT: u16 = 3;
+    const LONG: u16 = 4;
+    let is_big_endian = match data.get(..4)? {
+        b"MM\0*" => true,
+        b"II*\0" => false,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| {
+        let bytes = data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if is_big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let read_u32 = |offset: usize| {
+        let bytes = data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if is_big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    let ifd_offset = read_u32(4)? as usize;
+    let entry_count = read_u16(ifd_offset)? as usize;
+    let (mut width, mut height) = (None, None);
+    for i in 0..entry_count {
+        // Each entry is a tag, a type, a count and a value that is left
+        // aligned in a 4 byte field.
+        let entry = ifd_offset + 2 + i * 12;
+        let value = match read_u16(entry + 2)? {
+            SHORT => read_u16(entry + 8)? as u32,
+            LONG => read_u32(entry + 8)?,
+            _ => continue,
+        };
+        match read_u16(entry)? {
+            IMAGE_WIDTH => width = Some(value),
+            IMAGE_LENGTH => height = Some(value),
+            _ => {}
+        }
+    }
+    Some((width?, height?))
+}
+
+/// Reads the image dimensions from the header of a PNG image.
+fn png_size(data: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    // The first chunk is always the image header which begins with the
+    // width and height.
+    if data.get(..8)? != SIGNATURE || data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let read_u32 = |offset: usize| {
+        Some(u32::from_be_bytes(
+            data.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+    Some((read_u32(16)?, read_u32(20)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider as _;
+    use read_fonts::{FontRead, FontRef};
+
+    #[test]
+    fn strikes() {
+        let font = FontRef::new(font_test_data::EMBEDDED_BITMAPS).unwrap();
+        let strikes = font.bitmap_strikes();
+        let formats = strikes
+            .iter()
+            .map(|strike| (strike.format(), strike.ppem()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            formats,
+            [
+                (BitmapFormat::Cbdt, (109.0, 109.0)),
+                (BitmapFormat::Ebdt, (7.0, 7.0)),
+                (BitmapFormat::Ebdt, (15.0, 15.0)),
+            ]
+        );
+        let glyph = strikes.get(2).unwrap().get(GlyphId::new(3)).unwrap();
+        assert_eq!((glyph.width, glyph.height), (9, 17));
+        assert_eq!(
+            (glyph.bearing_x, glyph.bearing_y, glyph.advance),
+            (0.0, 12.0, 9.0)
+        );
+        let BitmapData::Mask(mask) = glyph.data else {
+            panic!("expected mask data");
+        };
+        assert_eq!((mask.bpp, mask.is_packed, mask.data.len()), (1, true, 20));
+        assert!(strikes.get(1).unwrap().get(GlyphId::new(3)).is_none());
+        assert!(strikes.get(3).is_none());
+    }
+
+    #[test]
+    fn glyph_for_size() {
+        let font = FontRef::new(font_test_data::EMBEDDED_BITMAPS).unwrap();
+        let strikes = font.bitmap_strikes();
+        // Color glyph scaled down from the 109ppem strike.
+        let glyph = strikes
+            .glyph_for_size(Size::new(20.0), GlyphId::new(4))
+            .unwrap();
+        assert_eq!(
+            glyph.data,
+            BitmapData::Png(&[0x89, b'P', b'N', b'G', 13, 10, 26, 10])
+        );
+        assert_eq!((glyph.ppem_x, glyph.ppem_y), (109.0, 109.0));
+        assert_eq!((glyph.width, glyph.height), (136, 128));
+        assert_eq!(glyph.bearing_y, 101.0 * 20.0 / 109.0);
+        assert_eq!(glyph.advance, 136.0 * 20.0 / 109.0);
+        // Mask scaled up from the only strike containing the glyph.
+        let glyph = strikes
+            .glyph_for_size(Size::new(14.0), GlyphId::new(1))
+            .unwrap();
+        assert_eq!(glyph.ppem_y, 7.0);
+        assert_eq!(
+            (glyph.bearing_x, glyph.bearing_y, glyph.advance),
+            (2.0, 12.0, 8.0)
+        );
+        // Unscaled sizes produce metrics in font units.
+        let upem = font.head().unwrap().units_per_em() as f32;
+        let glyph = strikes
+            .glyph_for_size(Size::unscaled(), GlyphId::new(2))
+            .unwrap();
+        assert_eq!(glyph.advance, 4.0 * upem / 7.0);
+        assert!(strikes
+            .glyph_for_size(Size::new(12.0), GlyphId::new(0))
+            .is_none());
+    }
+
+    #[test]
+    fn select_best_strike() {
+        let select = |requested, sizes: &[f32]| {
+            sizes
+                .iter()
+                .copied()
+                .fold(None, |best: Option<f32>, ppem| match best {
+                    Some(best) if !is_better_strike(requested, ppem, best) => Some(best),
+                    _ => Some(ppem),
+                })
+        };
+        let sizes = [20.0, 64.0, 32.0, 109.0];
+        // Exact match
+        assert_eq!(select(32.0, &sizes), Some(32.0));
+        // Smallest strike larger than the requested size
+        assert_eq!(select(21.0, &sizes), Some(32.0));
+        assert_eq!(select(8.0, &sizes), Some(20.0));
+        // Largest strike when all are smaller
+        assert_eq!(select(200.0, &sizes), Some(109.0));
+        assert_eq!(select(f32::MAX, &sizes), Some(109.0));
+    }
+
+    #[test]
+    fn sbix_images() {
+        let png = [
+            0, 2, 0xFF, 0xFE, b'p', b'n', b'g', b' ', 0x89, b'P', b'N', b'G', 13, 10, 26, 10, 0, 0,
+            0, 13, b'I', b'H', b'D', b'R', 0, 0, 0, 20, 0, 0, 0, 30,
+        ];
+        let glyph_data = GlyphData::read(FontData::new(&png)).unwrap();
+        let (data, width, height) = sbix_image(&glyph_data).unwrap();
+        assert_eq!(data, BitmapData::Png(&png[8..]));
+        assert_eq!((width, height), (20, 30));
+        let jpg = [0, 0, 0, 0, b'j', b'p', b'g', b' ', 1, 2, 3];
+        let glyph_data = GlyphData::read(FontData::new(&jpg)).unwrap();
+        assert!(sbix_image(&glyph_data).is_none());
+        let pdf = [0, 0, 0, 0, b'p', b'd', b'f', b' ', 1, 2, 3];
+        let glyph_data = GlyphData::read(FontData::new(&pdf)).unwrap();
+        assert!(sbix_image(&glyph_data).is_none());
+        // Truncated PNG header
+        assert!(png_size(&png[8..20]).is_none());
+    }
+
+    #[test]
+    fn jpeg_and_tiff_sizes() {
+        // SOI, APP0 with a 2 byte payload, fill byte, then SOF2 with a
+        // height of 30 and width of 20.
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0, 4, 1, 2, 0xFF, 0xFF, 0xC2, 0, 11, 8, 0, 30, 0, 20, 1, 1,
+            0x11, 0,
+        ];
+        assert_eq!(jpeg_size(&jpeg), Some((20, 30)));
+        // Huffman table segment is skipped rather than read as a frame.
+        let mut huffman = jpeg;
+        huffman[3] = 0xC4;
+        assert_eq!(jpeg_size(&huffman), Some((20, 30)));
+        // Start of scan before the frame header
+        let mut no_frame = jpeg;
+        no_frame[10] = 0xDA;
+        assert!(jpeg_size(&no_frame).is_none());
+        assert!(jpeg_size(&jpeg[..14]).is_none());
+        // Little endian with a SHORT width and big endian with a LONG
+        // height.
+        let tiff_le = [
+            b'I', b'I', b'*', 0, 8, 0, 0, 0, 3, 0, //
+            0, 1, 3, 0, 1, 0, 0, 0, 20, 0, 0, 0, //
+            3, 1, 3, 0, 1, 0, 0, 0, 1, 0, 0, 0, //
+            1, 1, 4, 0, 1, 0, 0, 0, 30, 0, 0, 0,
+        ];
+        assert_eq!(tiff_size(&tiff_le), Some((20, 30)));
+        let tiff_be = [
+            b'M', b'M', 0, b'*', 0, 0, 0, 8, 0, 2, //
+            1, 0, 0, 3, 0, 0, 0, 1, 0, 20, 0, 0, //
+            1, 1, 0, 4, 0, 0, 0, 1, 0, 0, 0, 30,
+        ];
+        assert_eq!(tiff_size(&tiff_be), Some((20, 30)));
+        // Missing height
+        assert!(tiff_size(&tiff_be[..22]).is_none());
+    }
+
+    #[test]
+    fn sbix_jpeg_bearings() {
+        let mut sbix_glyph = vec![0, 2, 0xFF, 0xFE, b'j', b'p', b'g', b' '];
+        sbix_glyph.extend([
+            0xFF, 0xD8, 0xFF, 0xC0, 0, 11, 8, 0, 30, 0, 20, 1, 1, 0x11, 0,
+        ]);
+        let glyph_data = GlyphData::read(FontData::new(&sbix_glyph)).unwrap();
+        let (data, width, height) = sbix_image(&glyph_data).unwrap();
+        assert!(matches!(data, BitmapData::Jpeg(_)));
+        assert_eq!((width, height), (20, 30));
+        // The top edge is the bottom offset plus the image height.
+        assert_eq!(glyph_data.origin_offset_y() as f32 + height as f32, 28.0);
+    }
+
+    #[test]
+    fn ebdt_composite() {
+        let ebdt_data = [
+            0, 2, 0, 0, // version
+            // Format 8: small metrics (height, width, bearing x, bearing y,
+            // advance), padding and two components
+            10, 12, 1, 9, 13, 0, 0, 2, //
+            0, 5, 0, 0, //
+            0, 6, 4, 0xFE,
+        ];
+        let ebdt = Ebdt::read(FontData::new(&ebdt_data)).unwrap();
+        let location = BitmapLocation {
+            format: 8,
+            data_offset: 4,
+            data_size: None,
+            bit_depth: 1,
+            metrics: None,
+        };
+        let glyph = bdt_image(&BdtData::Ebdt(ebdt), &location).unwrap();
+        assert_eq!((glyph.width, glyph.height), (12, 10));
+        assert_eq!(
+            (glyph.bearing_x, glyph.bearing_y, glyph.advance),
+            (1.0, 9.0, 13.0)
+        );
+        let BitmapData::Composite(components) = glyph.data else {
+            panic!("expected composite data");
+        };
+        let components = components
+            .iter()
+            .map(|c| (c.glyph_id().to_u16(), c.x_offset(), c.y_offset()))
+            .collect::<Vec<_>>();
+        assert_eq!(components, [(5, 0, 0), (6, 4, -2)]);
+    }
+}
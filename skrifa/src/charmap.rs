@@ -12,13 +12,16 @@
 
 use read_fonts::{
     tables::cmap::{
-        self, Cmap, Cmap12, Cmap12Iter, Cmap14, Cmap14Iter, Cmap4, Cmap4Iter, CmapSubtable,
-        EncodingRecord, PlatformId,
+        self, Cmap, Cmap0, Cmap0Iter, Cmap10, Cmap10Iter, Cmap12, Cmap12Iter, Cmap13, Cmap13Iter,
+        Cmap14, Cmap14Iter, Cmap2, Cmap2Iter, Cmap4, Cmap4Iter, Cmap6, Cmap6Iter, Cmap8, Cmap8Iter,
+        CmapSubtable, EncodingRecord, PlatformId,
     },
     types::GlyphId,
     FontData, TableProvider,
 };
 
+use core::ops::RangeInclusive;
+
 pub use read_fonts::tables::cmap::MapVariant;
 
 /// Mapping of characters to nominal glyph identifiers.
@@ -33,14 +36,16 @@ pub use read_fonts::tables::cmap::MapVariant;
 /// coverage:
 ///
 /// * Unicode characters: a symbol mapping subtable is selected if available. Otherwise, subtables supporting
-/// the Unicode full repertoire or Basic Multilingual Plane (BMP) are preferred, in that order. Formats
-/// [4](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-4-segment-mapping-to-delta-values)
-/// and [12](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-12-segmented-coverage) are
-/// supported.
+///   the Unicode full repertoire or Basic Multilingual Plane (BMP) are preferred, in that order. If neither
+///   is present, a Macintosh Roman subtable is used with characters converted to and from that encoding.
+///   Subtables in format
+///   [13](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-13-many-to-one-range-mappings)
+///   map entire ranges to a single glyph and are only selected as a last resort. All subtable formats other
+///   than 14 are supported.
 ///
 /// * Unicode variation sequences: these are provided by a format
-/// [14](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-14-unicode-variation-sequences)
-/// subtable.
+///   [14](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#format-14-unicode-variation-sequences)
+///   subtable.
 ///
 #[derive(Clone, Default)]
 pub struct Charmap<'a> {
@@ -54,13 +59,18 @@ impl<'a> Charmap<'a> {
         let Ok(cmap) = font.cmap() else {
             return Default::default();
         };
-        let selection = MappingSelection::new(&cmap);
+        Self::from_cmap(&cmap)
+    }
+
+    fn from_cmap(cmap: &Cmap<'a>) -> Self {
+        let selection = MappingSelection::new(cmap);
         Self {
             codepoint_subtable: selection
                 .codepoint_subtable
                 .map(|subtable| CodepointSubtable {
                     subtable,
                     is_symbol: selection.mapping_index.codepoint_subtable_is_symbol,
+                    is_mac_roman: selection.mapping_index.codepoint_subtable_is_mac_roman,
                 }),
             variant_subtable: selection.variant_subtable,
         }
@@ -96,13 +106,23 @@ impl<'a> Charmap<'a> {
     pub fn mappings(&self) -> Mappings<'a> {
         self.codepoint_subtable
             .as_ref()
-            .map(|subtable| {
-                Mappings(match &subtable.subtable {
+            .map(|subtable| Mappings {
+                inner: match &subtable.subtable {
+                    SupportedSubtable::Format0(cmap0) => MappingsInner::Format0(cmap0.iter()),
+                    SupportedSubtable::Format2(cmap2) => MappingsInner::Format2(cmap2.iter()),
                     SupportedSubtable::Format4(cmap4) => MappingsInner::Format4(cmap4.iter()),
+                    SupportedSubtable::Format6(cmap6) => MappingsInner::Format6(cmap6.iter()),
+                    SupportedSubtable::Format8(cmap8) => MappingsInner::Format8(cmap8.iter()),
+                    SupportedSubtable::Format10(cmap10) => MappingsInner::Format10(cmap10.iter()),
                     SupportedSubtable::Format12(cmap12) => MappingsInner::Format12(cmap12.iter()),
-                })
+                    SupportedSubtable::Format13(cmap13) => MappingsInner::Format13(cmap13.iter()),
+                },
+                is_mac_roman: subtable.is_mac_roman,
+            })
+            .unwrap_or(Mappings {
+                inner: MappingsInner::None,
+                is_mac_roman: false,
             })
-            .unwrap_or(Mappings(MappingsInner::None))
     }
 
     /// Maps a character and variation selector to a nominal glyph identifier.
@@ -117,6 +137,184 @@ impl<'a> Charmap<'a> {
     pub fn variant_mappings(&self) -> VariantMappings<'a> {
         VariantMappings(self.variant_subtable.clone().map(|cmap14| cmap14.iter()))
     }
+
+    /// Returns the set of all codepoints that are mapped to a glyph in the
+    /// character map.
+    pub fn codepoints(&self) -> CodepointSet {
+        self.mappings().map(|(codepoint, _)| codepoint).collect()
+    }
+
+    /// Returns the set of codepoints that form a supported variation
+    /// sequence with the given variation selector.
+    ///
+    /// This includes sequences that map to the default glyph for the
+    /// codepoint as well as those that map to a variant glyph.
+    pub fn variant_codepoints(&self, selector: impl Into<u32>) -> CodepointSet {
+        let mut set = CodepointSet::new();
+        let Some(cmap14) = self.variant_subtable.as_ref() else {
+            return set;
+        };
+        let selector = selector.into();
+        let records = cmap14.var_selector();
+        let Some(record) = records
+            .binary_search_by_key(&selector, |record| record.var_selector().into())
+            .ok()
+            .and_then(|ix| records.get(ix))
+        else {
+            return set;
+        };
+        let data = cmap14.offset_data();
+        if let Some(Ok(default_uvs)) = record.default_uvs(data) {
+            for range in default_uvs.ranges() {
+                let start: u32 = range.start_unicode_value().into();
+                set.insert_range(start..=start + range.additional_count() as u32);
+            }
+        }
+        if let Some(Ok(non_default_uvs)) = record.non_default_uvs(data) {
+            for mapping in non_default_uvs.uvs_mapping() {
+                set.insert(mapping.unicode_value().into());
+            }
+        }
+        set
+    }
+}
+
+/// Compact set of Unicode codepoints.
+///
+/// The set is stored as a sorted sequence of disjoint ranges which makes
+/// it efficient for the densely packed codepoints found in most character
+/// maps.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct CodepointSet {
+    /// Sorted, disjoint and non-adjacent inclusive ranges.
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CodepointSet {
+    /// Creates a new empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of codepoints in the set.
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|(start, end)| (end - start) as usize + 1)
+            .sum()
+    }
+
+    /// Returns true if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns true if the set contains the given codepoint.
+    pub fn contains(&self, codepoint: impl Into<u32>) -> bool {
+        let codepoint = codepoint.into();
+        let ix = self.ranges.partition_point(|(_, end)| *end < codepoint);
+        self.ranges
+            .get(ix)
+            .map(|(start, _)| *start <= codepoint)
+            .unwrap_or(false)
+    }
+
+    /// Adds a codepoint to the set.
+    pub fn insert(&mut self, codepoint: u32) {
+        self.insert_range(codepoint..=codepoint);
+    }
+
+    /// Adds a range of codepoints to the set.
+    pub fn insert_range(&mut self, range: RangeInclusive<u32>) {
+        let (start, end) = (*range.start(), *range.end());
+        if start > end {
+            return;
+        }
+        // Fast path for codepoints inserted in order
+        if let Some(last) = self.ranges.last_mut() {
+            if start > last.1.saturating_add(1) {
+                self.ranges.push((start, end));
+                return;
+            }
+        }
+        // Find all ranges that overlap or are adjacent to the new range
+        let first = self
+            .ranges
+            .partition_point(|(_, range_end)| range_end.saturating_add(1) < start);
+        let last = self
+            .ranges
+            .partition_point(|(range_start, _)| *range_start <= end.saturating_add(1));
+        if first == last {
+            self.ranges.insert(first, (start, end));
+        } else {
+            let merged = (
+                start.min(self.ranges[first].0),
+                end.max(self.ranges[last - 1].1),
+            );
+            self.ranges.splice(first..last, [merged]);
+        }
+    }
+
+    /// Returns a new set containing the codepoints in either this set or
+    /// the other.
+    pub fn union(&self, other: &CodepointSet) -> CodepointSet {
+        let mut result = self.clone();
+        for (start, end) in &other.ranges {
+            result.insert_range(*start..=*end);
+        }
+        result
+    }
+
+    /// Returns a new set containing the codepoints in both this set and
+    /// the other.
+    pub fn intersection(&self, other: &CodepointSet) -> CodepointSet {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = x.0.max(y.0);
+            let end = x.1.min(y.1);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if x.1 < y.1 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        CodepointSet { ranges }
+    }
+
+    /// Returns an iterator over the ranges of codepoints in the set, in
+    /// ascending order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<u32>> + '_ + Clone {
+        self.ranges.iter().map(|(start, end)| *start..=*end)
+    }
+
+    /// Returns an iterator over the codepoints in the set, in ascending
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ + Clone {
+        self.ranges().flatten()
+    }
+}
+
+impl FromIterator<u32> for CodepointSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<u32> for CodepointSet {
+    fn extend<T: IntoIterator<Item = u32>>(&mut self, iter: T) {
+        for codepoint in iter {
+            self.insert(codepoint);
+        }
+    }
 }
 
 /// Cacheable indices of selected mapping tables for materializing a character
@@ -132,6 +330,8 @@ pub struct MappingIndex {
     codepoint_subtable: Option<u16>,
     /// True if the above is a symbol mapping.
     codepoint_subtable_is_symbol: bool,
+    /// True if the above is a Macintosh Roman mapping.
+    codepoint_subtable_is_mac_roman: bool,
     /// Index of Unicode variation selector subtable.
     variant_subtable: Option<u16>,
 }
@@ -164,6 +364,7 @@ impl MappingIndex {
                 .map(|subtable| CodepointSubtable {
                     subtable,
                     is_symbol: self.codepoint_subtable_is_symbol,
+                    is_mac_roman: self.codepoint_subtable_is_mac_roman,
                 }),
             variant_subtable: self
                 .variant_subtable
@@ -181,16 +382,35 @@ impl MappingIndex {
 ///
 /// This is created with the [`Charmap::mappings`] method.
 #[derive(Clone)]
-pub struct Mappings<'a>(MappingsInner<'a>);
+pub struct Mappings<'a> {
+    inner: MappingsInner<'a>,
+    /// True if character codes should be converted from Macintosh Roman.
+    is_mac_roman: bool,
+}
 
 impl<'a> Iterator for Mappings<'a> {
     type Item = (u32, GlyphId);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match &mut self.0 {
-            MappingsInner::None => None,
-            MappingsInner::Format4(iter) => iter.next(),
-            MappingsInner::Format12(iter) => iter.next(),
+        loop {
+            let (code, glyph_id) = match &mut self.inner {
+                MappingsInner::None => None,
+                MappingsInner::Format0(iter) => iter.next(),
+                MappingsInner::Format2(iter) => iter.next(),
+                MappingsInner::Format4(iter) => iter.next(),
+                MappingsInner::Format6(iter) => iter.next(),
+                MappingsInner::Format8(iter) => iter.next(),
+                MappingsInner::Format10(iter) => iter.next(),
+                MappingsInner::Format12(iter) => iter.next(),
+                MappingsInner::Format13(iter) => iter.next(),
+            }?;
+            if !self.is_mac_roman {
+                return Some((code, glyph_id));
+            }
+            // Skip codes that are not representable in Macintosh Roman
+            if let Some(codepoint) = mac_roman_to_unicode(code) {
+                return Some((codepoint, glyph_id));
+            }
         }
     }
 }
@@ -198,8 +418,14 @@ impl<'a> Iterator for Mappings<'a> {
 #[derive(Clone)]
 enum MappingsInner<'a> {
     None,
+    Format0(Cmap0Iter<'a>),
+    Format2(Cmap2Iter<'a>),
     Format4(Cmap4Iter<'a>),
+    Format6(Cmap6Iter<'a>),
+    Format8(Cmap8Iter<'a>),
+    Format10(Cmap10Iter<'a>),
     Format12(Cmap12Iter<'a>),
+    Format13(Cmap13Iter<'a>),
 }
 
 /// Iterator over all mappings of character and variation selector to
@@ -232,10 +458,15 @@ struct CodepointSubtable<'a> {
     subtable: SupportedSubtable<'a>,
     /// True if the subtable is a symbol mapping.
     is_symbol: bool,
+    /// True if the subtable is a Macintosh Roman mapping.
+    is_mac_roman: bool,
 }
 
 impl<'a> CodepointSubtable<'a> {
     fn map(&self, codepoint: u32) -> Option<GlyphId> {
+        if self.is_mac_roman {
+            return self.map_impl(unicode_to_mac_roman(codepoint)?);
+        }
         self.map_impl(codepoint).or_else(|| {
             if self.is_symbol && codepoint <= 0x00FF {
                 // From HarfBuzz:
@@ -254,27 +485,57 @@ impl<'a> CodepointSubtable<'a> {
 
     fn map_impl(&self, codepoint: u32) -> Option<GlyphId> {
         match &self.subtable {
+            SupportedSubtable::Format0(subtable) => subtable.map_codepoint(codepoint),
+            SupportedSubtable::Format2(subtable) => subtable.map_codepoint(codepoint),
             SupportedSubtable::Format4(subtable) => subtable.map_codepoint(codepoint),
+            SupportedSubtable::Format6(subtable) => subtable.map_codepoint(codepoint),
+            SupportedSubtable::Format8(subtable) => subtable.map_codepoint(codepoint),
+            SupportedSubtable::Format10(subtable) => subtable.map_codepoint(codepoint),
             SupportedSubtable::Format12(subtable) => subtable.map_codepoint(codepoint),
+            SupportedSubtable::Format13(subtable) => subtable.map_codepoint(codepoint),
         }
     }
 }
 
 #[derive(Clone)]
 enum SupportedSubtable<'a> {
+    Format0(Cmap0<'a>),
+    Format2(Cmap2<'a>),
     Format4(Cmap4<'a>),
+    Format6(Cmap6<'a>),
+    Format8(Cmap8<'a>),
+    Format10(Cmap10<'a>),
     Format12(Cmap12<'a>),
+    Format13(Cmap13<'a>),
 }
 
 impl<'a> SupportedSubtable<'a> {
     fn new(subtable: CmapSubtable<'a>) -> Option<Self> {
         Some(match subtable {
+            CmapSubtable::Format0(cmap0) => Self::Format0(cmap0),
+            CmapSubtable::Format2(cmap2) => Self::Format2(cmap2),
             CmapSubtable::Format4(cmap4) => Self::Format4(cmap4),
+            CmapSubtable::Format6(cmap6) => Self::Format6(cmap6),
+            CmapSubtable::Format8(cmap8) => Self::Format8(cmap8),
+            CmapSubtable::Format10(cmap10) => Self::Format10(cmap10),
             CmapSubtable::Format12(cmap12) => Self::Format12(cmap12),
-            _ => return None,
+            CmapSubtable::Format13(cmap13) => Self::Format13(cmap13),
+            CmapSubtable::Format14(_) => return None,
         })
     }
 
+    /// Returns the mapping kind for this subtable when referenced by an
+    /// encoding record of the given kind.
+    ///
+    /// Format 13 subtables map ranges of characters to a single glyph so
+    /// they are only chosen as a last resort.
+    fn kind(&self, record_kind: MappingKind) -> MappingKind {
+        match self {
+            Self::Format13(_) => MappingKind::LastResort,
+            _ => record_kind,
+        }
+    }
+
     fn from_cmap_record(cmap: &Cmap<'a>, record: &cmap::EncodingRecord) -> Option<Self> {
         Self::new(record.subtable(cmap.offset_data()).ok()?)
     }
@@ -287,9 +548,11 @@ impl<'a> SupportedSubtable<'a> {
 #[derive(Copy, Clone, PartialEq, PartialOrd)]
 enum MappingKind {
     None = 0,
-    UnicodeBmp = 1,
-    UnicodeFull = 2,
-    Symbol = 3,
+    LastResort = 1,
+    MacRoman = 2,
+    UnicodeBmp = 3,
+    UnicodeFull = 4,
+    Symbol = 5,
 }
 
 /// The result of searching the cmap table for the "best" available
@@ -317,14 +580,17 @@ impl<'a> MappingSelection<'a> {
         const ENCODING_APPLE_ID_UNICODE_32: u16 = 4;
         const ENCODING_APPLE_ID_VARIANT_SELECTOR: u16 = 5;
         const ENCODING_MS_ID_UCS_4: u16 = 10;
+        const ENCODING_MAC_ROMAN: u16 = 0;
         let mut mapping_index = MappingIndex::default();
         let mut mapping_kind = MappingKind::None;
         let mut codepoint_subtable = None;
         let mut variant_subtable = None;
-        let mut maybe_choose_subtable = |kind, index, subtable| {
+        let mut maybe_choose_subtable = |kind, index, subtable: SupportedSubtable<'a>| {
+            let kind = subtable.kind(kind);
             if kind > mapping_kind {
                 mapping_kind = kind;
                 mapping_index.codepoint_subtable_is_symbol = kind == MappingKind::Symbol;
+                mapping_index.codepoint_subtable_is_mac_roman = kind == MappingKind::MacRoman;
                 mapping_index.codepoint_subtable = Some(index as u16);
                 codepoint_subtable = Some(subtable);
             }
@@ -369,6 +635,12 @@ impl<'a> MappingSelection<'a> {
                         maybe_choose_subtable(MappingKind::UnicodeBmp, i, subtable);
                     }
                 }
+                (PlatformId::Macintosh, ENCODING_MAC_ROMAN) => {
+                    // Macintosh Roman
+                    if let Some(subtable) = SupportedSubtable::from_cmap_record(cmap, record) {
+                        maybe_choose_subtable(MappingKind::MacRoman, i, subtable);
+                    }
+                }
                 _ => {}
             }
         }
@@ -380,11 +652,53 @@ impl<'a> MappingSelection<'a> {
     }
 }
 
+/// Converts a Unicode codepoint to a Macintosh Roman character code.
+fn unicode_to_mac_roman(codepoint: u32) -> Option<u32> {
+    if codepoint < 0x80 {
+        return Some(codepoint);
+    }
+    MAC_ROMAN
+        .iter()
+        .position(|&ch| ch as u32 == codepoint)
+        .map(|ix| ix as u32 + 0x80)
+}
+
+/// Converts a Macintosh Roman character code to a Unicode codepoint.
+fn mac_roman_to_unicode(code: u32) -> Option<u32> {
+    if code < 0x80 {
+        Some(code)
+    } else {
+        MAC_ROMAN.get(code as usize - 0x80).map(|&ch| ch as u32)
+    }
+}
+
+/// Unicode codepoints for the upper half (0x80..=0xFF) of the Macintosh
+/// Roman encoding. The lower half is identical to ASCII.
+#[rustfmt::skip]
+const MAC_ROMAN: [u16; 128] = [
+    0x00C4, 0x00C5, 0x00C7, 0x00C9, 0x00D1, 0x00D6, 0x00DC, 0x00E1,
+    0x00E0, 0x00E2, 0x00E4, 0x00E3, 0x00E5, 0x00E7, 0x00E9, 0x00E8,
+    0x00EA, 0x00EB, 0x00ED, 0x00EC, 0x00EE, 0x00EF, 0x00F1, 0x00F3,
+    0x00F2, 0x00F4, 0x00F6, 0x00F5, 0x00FA, 0x00F9, 0x00FB, 0x00FC,
+    0x2020, 0x00B0, 0x00A2, 0x00A3, 0x00A7, 0x2022, 0x00B6, 0x00DF,
+    0x00AE, 0x00A9, 0x2122, 0x00B4, 0x00A8, 0x2260, 0x00C6, 0x00D8,
+    0x221E, 0x00B1, 0x2264, 0x2265, 0x00A5, 0x00B5, 0x2202, 0x2211,
+    0x220F, 0x03C0, 0x222B, 0x00AA, 0x00BA, 0x03A9, 0x00E6, 0x00F8,
+    0x00BF, 0x00A1, 0x00AC, 0x221A, 0x0192, 0x2248, 0x2206, 0x00AB,
+    0x00BB, 0x2026, 0x00A0, 0x00C0, 0x00C3, 0x00D5, 0x0152, 0x0153,
+    0x2013, 0x2014, 0x201C, 0x201D, 0x2018, 0x2019, 0x00F7, 0x25CA,
+    0x00FF, 0x0178, 0x2044, 0x20AC, 0x2039, 0x203A, 0xFB01, 0xFB02,
+    0x2021, 0x00B7, 0x201A, 0x201E, 0x2030, 0x00C2, 0x00CA, 0x00C1,
+    0x00CB, 0x00C8, 0x00CD, 0x00CE, 0x00CF, 0x00CC, 0x00D3, 0x00D4,
+    0xF8FF, 0x00D2, 0x00DA, 0x00DB, 0x00D9, 0x0131, 0x02C6, 0x02DC,
+    0x00AF, 0x02D8, 0x02D9, 0x02DA, 0x00B8, 0x02DD, 0x02DB, 0x02C7,
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::MetadataProvider;
-    use read_fonts::FontRef;
+    use read_fonts::{FontRead, FontRef};
 
     #[test]
     fn choose_format_12_over_4() {
@@ -491,6 +805,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn codepoints() {
+        for font_data in [
+            font_test_data::VAZIRMATN_VAR,
+            font_test_data::CMAP12_FONT1,
+            font_test_data::SIMPLE_GLYF,
+        ] {
+            let font = FontRef::new(font_data).unwrap();
+            let charmap = font.charmap();
+            let codepoints = charmap.codepoints();
+            assert_eq!(codepoints.len(), charmap.mappings().count());
+            assert!(codepoints.iter().eq(charmap.mappings().map(|(cp, _)| cp)));
+        }
+        let font = FontRef::new(font_test_data::CMAP12_FONT1).unwrap();
+        let codepoints = font.charmap().codepoints();
+        assert!(codepoints.contains(0x101723_u32));
+        assert!(!codepoints.contains(' '));
+    }
+
+    #[test]
+    fn variant_codepoints() {
+        let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
+        let charmap = font.charmap();
+        let selector = 0xE0100_u32;
+        let codepoints = charmap.variant_codepoints(selector);
+        let expected = charmap
+            .variant_mappings()
+            .filter(|(_, vs, _)| *vs == selector)
+            .map(|(cp, ..)| cp)
+            .collect::<CodepointSet>();
+        assert_eq!(codepoints, expected);
+        assert!(codepoints.contains('\u{4e00}'));
+        assert!(!codepoints.contains('a'));
+        assert!(charmap.variant_codepoints(0xFE00_u32).is_empty());
+    }
+
+    #[test]
+    fn codepoint_set_ops() {
+        let mut a = CodepointSet::new();
+        a.insert_range(10..=20);
+        a.insert(30);
+        a.insert(5);
+        // Adjacent ranges are merged
+        a.insert_range(21..=25);
+        assert_eq!(a.ranges().collect::<Vec<_>>(), [5..=5, 10..=25, 30..=30]);
+        assert_eq!(a.len(), 18);
+        // Bridge two ranges
+        a.insert_range(26..=29);
+        assert_eq!(a.ranges().collect::<Vec<_>>(), [5..=5, 10..=30]);
+        let b = [1, 2, 3, 12, 13, 40, u32::MAX]
+            .into_iter()
+            .collect::<CodepointSet>();
+        assert_eq!(
+            a.union(&b).ranges().collect::<Vec<_>>(),
+            [1..=3, 5..=5, 10..=30, 40..=40, u32::MAX..=u32::MAX]
+        );
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [12, 13]);
+        assert!(a.contains(10_u32) && a.contains(30_u32) && !a.contains(31_u32));
+        assert!(!a.contains(0_u32) && !a.contains(9_u32));
+        assert!(CodepointSet::new().is_empty());
+    }
+
     #[test]
     fn variant_mappings() {
         let font = FontRef::new(font_test_data::CMAP14_FONT1).unwrap();
@@ -499,4 +875,123 @@ mod tests {
             assert_eq!(charmap.map_variant(codepoint, selector), Some(variant));
         }
     }
+
+    #[test]
+    fn choose_mac_roman_over_last_resort() {
+        let mut format0 = vec![0u8; 256];
+        format0[b'A' as usize] = 1;
+        // U+00E9 (é) is 0x8E in Macintosh Roman
+        format0[0x8E] = 2;
+        let cmap_data = build_cmap(&[
+            (1, 0, format0_subtable(&format0)),
+            (3, 10, format13_subtable(&[(0, 0x10FFFF, 3)])),
+        ]);
+        let cmap = Cmap::read(FontData::new(&cmap_data)).unwrap();
+        let charmap = Charmap::from_cmap(&cmap);
+        assert!(matches!(
+            charmap.codepoint_subtable.as_ref().unwrap().subtable,
+            SupportedSubtable::Format0(..)
+        ));
+        assert_eq!(charmap.map('A'), Some(GlyphId::new(1)));
+        assert_eq!(charmap.map('é'), Some(GlyphId::new(2)));
+        // Not representable in Macintosh Roman
+        assert_eq!(charmap.map(0x8E_u32), None);
+        let mappings = charmap.mappings().collect::<Vec<_>>();
+        assert_eq!(mappings, [(0x41, GlyphId::new(1)), (0xE9, GlyphId::new(2))]);
+    }
+
+    #[test]
+    fn choose_unicode_over_mac_roman() {
+        let cmap_data = build_cmap(&[
+            (1, 0, format0_subtable(&[0u8; 256])),
+            (3, 1, format6_subtable(0x41, &[1, 2])),
+        ]);
+        let cmap = Cmap::read(FontData::new(&cmap_data)).unwrap();
+        let charmap = Charmap::from_cmap(&cmap);
+        assert!(matches!(
+            charmap.codepoint_subtable.as_ref().unwrap().subtable,
+            SupportedSubtable::Format6(..)
+        ));
+        assert_eq!(charmap.map('A'), Some(GlyphId::new(1)));
+        assert_eq!(charmap.map('B'), Some(GlyphId::new(2)));
+        assert_eq!(charmap.map('C'), None);
+    }
+
+    #[test]
+    fn choose_format_13_as_last_resort() {
+        // Format 13 in a full repertoire record is still chosen last
+        let cmap_data = build_cmap(&[
+            (3, 1, format6_subtable(0x41, &[1, 2])),
+            (3, 10, format13_subtable(&[(0, 0x10FFFF, 3)])),
+        ]);
+        let cmap = Cmap::read(FontData::new(&cmap_data)).unwrap();
+        let charmap = Charmap::from_cmap(&cmap);
+        assert!(matches!(
+            charmap.codepoint_subtable.as_ref().unwrap().subtable,
+            SupportedSubtable::Format6(..)
+        ));
+        // Only a format 13 subtable
+        let cmap_data = build_cmap(&[(3, 10, format13_subtable(&[(0x20, 0x7E, 3)]))]);
+        let cmap = Cmap::read(FontData::new(&cmap_data)).unwrap();
+        let charmap = Charmap::from_cmap(&cmap);
+        assert!(matches!(
+            charmap.codepoint_subtable.as_ref().unwrap().subtable,
+            SupportedSubtable::Format13(..)
+        ));
+        assert_eq!(charmap.map('A'), Some(GlyphId::new(3)));
+        assert_eq!(charmap.map('\u{80}'), None);
+        assert_eq!(charmap.mappings().count(), 0x7E - 0x20 + 1);
+    }
+
+    /// Builds a cmap table from (platform, encoding, subtable) triples.
+    fn build_cmap(records: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend(0u16.to_be_bytes());
+        data.extend((records.len() as u16).to_be_bytes());
+        let mut offset = 4 + records.len() * 8;
+        for (platform_id, encoding_id, subtable) in records {
+            data.extend(platform_id.to_be_bytes());
+            data.extend(encoding_id.to_be_bytes());
+            data.extend((offset as u32).to_be_bytes());
+            offset += subtable.len();
+        }
+        for (_, _, subtable) in records {
+            data.extend(subtable);
+        }
+        data
+    }
+
+    fn format0_subtable(glyph_ids: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        for value in [0u16, 262, 0] {
+            data.extend(value.to_be_bytes());
+        }
+        data.extend(glyph_ids);
+        data
+    }
+
+    fn format6_subtable(first_code: u16, glyph_ids: &[u16]) -> Vec<u8> {
+        let len = 10 + glyph_ids.len() as u16 * 2;
+        [6, len, 0, first_code, glyph_ids.len() as u16]
+            .iter()
+            .chain(glyph_ids)
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    fn format13_subtable(groups: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend(13u16.to_be_bytes());
+        data.extend(0u16.to_be_bytes());
+        let len = 16 + groups.len() as u32 * 12;
+        for value in [len, 0, groups.len() as u32] {
+            data.extend(value.to_be_bytes());
+        }
+        for (start, end, glyph_id) in groups {
+            for value in [start, end, glyph_id] {
+                data.extend(value.to_be_bytes());
+            }
+        }
+        data
+    }
 }
@@ -0,0 +1,825 @@
+//! Drawing color glyphs.
+//!
+//! The functionality in this module walks the paint graphs of color glyphs
+//! defined in the [COLR](https://learn.microsoft.com/en-us/typography/opentype/spec/colr)
+//! table, emitting a sequence of drawing commands to a user supplied
+//! [`ColorPainter`]. Both the layered (version 0) and the paint graph
+//! (version 1) formats are supported.
+//!
+//! All coordinates emitted to the painter are in font units. Glyph clip
+//! regions are resolved to outlines through a user supplied
+//! [`Scaler`](crate::scale::Scaler), so painting requires the `scale`
+//! feature. Colors are
+//! specified as indices into the palettes of the
+//! [CPAL](https://learn.microsoft.com/en-us/typography/opentype/spec/cpal)
+//! table which are available through [`ColorPalettes`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "scale")] {
+//! # use skrifa::prelude::*;
+//! # use skrifa::color::{Brush, ColorPainter, CompositeMode, Transform};
+//! # use skrifa::scale::{Context, Outline};
+//! # use skrifa::raw::types::BoundingBox;
+//! struct Recorder(Vec<String>);
+//!
+//! impl ColorPainter for Recorder {
+//!     fn push_transform(&mut self, transform: Transform) {
+//!         self.0.push(format!("push_transform {transform:?}"));
+//!     }
+//!     fn pop_transform(&mut self) {
+//!         self.0.push("pop_transform".into());
+//!     }
+//!     fn push_clip_glyph(&mut self, glyph_id: GlyphId, outline: &Outline) {
+//!         let n = outline.points().len();
+//!         self.0.push(format!("push_clip_glyph {glyph_id} ({n} points)"));
+//!     }
+//!     fn push_clip_box(&mut self, clip_box: BoundingBox<f32>) {
+//!         self.0.push(format!("push_clip_box {clip_box:?}"));
+//!     }
+//!     fn pop_clip(&mut self) {
+//!         self.0.push("pop_clip".into());
+//!     }
+//!     fn fill(&mut self, brush: Brush) {
+//!         self.0.push(format!("fill {brush:?}"));
+//!     }
+//!     fn push_layer(&mut self, composite_mode: CompositeMode) {
+//!         self.0.push(format!("push_layer {composite_mode:?}"));
+//!     }
+//!     fn pop_layer(&mut self) {
+//!         self.0.push("pop_layer".into());
+//!     }
+//! }
+//!
+//! # fn wrapper(font: FontRef) {
+//! let color_glyphs = font.color_glyphs();
+//! let mut context = Context::new();
+//! // Paint coordinates are in font units so the outlines should be too.
+//! let mut scaler = context.new_scaler().size(Size::unscaled()).build(&font);
+//! let mut recorder = Recorder(vec![]);
+//! if let Some(color_glyph) = color_glyphs.get(GlyphId::new(1)) {
+//!     color_glyph.paint(&mut scaler, &mut recorder).unwrap();
+//! }
+//! # }
+//! # }
+//! ```
+
+mod palette;
+#[cfg(feature = "scale")]
+mod traversal;
+
+use read_fonts::{
+    tables::colr::{Colr, ColrInstance},
+    types::{BoundingBox, GlyphId},
+    ReadError, TableProvider,
+};
+
+use std::fmt;
+
+use super::instance::LocationRef;
+#[cfg(feature = "scale")]
+use crate::scale::{Outline, Scaler};
+
+pub use palette::{Color, ColorPalette, ColorPalettes, PaletteType};
+pub use read_fonts::tables::colr::{CompositeMode, Extend};
+
+/// Palette index that represents the current foreground (text) color.
+pub const FOREGROUND_PALETTE_INDEX: u16 = 0xFFFF;
+
+/// Maximum depth of the paint graph.
+const PAINT_DEPTH_LIMIT: usize = 64;
+
+/// Errors that may occur when drawing color glyphs.
+#[derive(Clone, Debug)]
+pub enum PaintError {
+    /// The requested color glyph was not present in the font.
+    GlyphNotFound(GlyphId),
+    /// A paint was encountered more than once while walking a single path
+    /// through the paint graph.
+    PaintCycleDetected,
+    /// Exceeded the maximum depth of the paint graph.
+    DepthLimitExceeded,
+    /// Error occurred when loading the outline of a clip glyph.
+    #[cfg(feature = "scale")]
+    Scale(crate::scale::Error),
+    /// Error occurred when reading font data.
+    Read(ReadError),
+}
+
+#[cfg(feature = "scale")]
+impl From<crate::scale::Error> for PaintError {
+    fn from(e: crate::scale::Error) -> Self {
+        Self::Scale(e)
+    }
+}
+
+impl From<ReadError> for PaintError {
+    fn from(e: ReadError) -> Self {
+        Self::Read(e)
+    }
+}
+
+impl fmt::Display for PaintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::GlyphNotFound(gid) => {
+                write!(f, "Color glyph {gid} was not found in the given font")
+            }
+            Self::PaintCycleDetected => write!(f, "Cycle detected in the paint graph"),
+            Self::DepthLimitExceeded => write!(
+                f,
+                "Depth limit ({PAINT_DEPTH_LIMIT}) exceeded in the paint graph"
+            ),
+            #[cfg(feature = "scale")]
+            Self::Scale(e) => write!(f, "{e}"),
+            Self::Read(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for PaintError {}
+
+/// Affine transformation matrix.
+///
+/// The components are ordered as in the `Affine2x3` table of the COLR
+/// specification such that a point is mapped with
+/// `x' = xx * x + xy * y + dx` and `y' = yx * x + yy * y + dy`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Transform {
+    pub xx: f32,
+    pub yx: f32,
+    pub xy: f32,
+    pub yy: f32,
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Transform {
+    /// The identity transform.
+    pub const IDENTITY: Self = Self {
+        xx: 1.0,
+        yx: 0.0,
+        xy: 0.0,
+        yy: 1.0,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    /// Returns a transform that applies `self` followed by `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            xx: other.xx * self.xx + other.xy * self.yx,
+            yx: other.yx * self.xx + other.yy * self.yx,
+            xy: other.xx * self.xy + other.xy * self.yy,
+            yy: other.yx * self.xy + other.yy * self.yy,
+            dx: other.xx * self.dx + other.xy * self.dy + other.dx,
+            dy: other.yx * self.dx + other.yy * self.dy + other.dy,
+        }
+    }
+
+    /// Maps the given point through the transform.
+    pub fn map_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.xx * x + self.xy * y + self.dx,
+            self.yx * x + self.yy * y + self.dy,
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Color stop of a gradient with variation deltas applied.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorStop {
+    /// Position of the stop along the color line.
+    pub offset: f32,
+    /// Index of the color in the current palette.
+    pub palette_index: u16,
+    /// Alpha multiplier in the range 0.0 to 1.0.
+    pub alpha: f32,
+}
+
+/// Source of color for a fill operation.
+///
+/// Color stops are sorted by offset.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Brush<'a> {
+    /// Solid color.
+    Solid {
+        /// Index of the color in the current palette or
+        /// [`FOREGROUND_PALETTE_INDEX`].
+        palette_index: u16,
+        /// Alpha multiplier in the range 0.0 to 1.0.
+        alpha: f32,
+    },
+    /// Gradient along the line from `p0` to `p1`.
+    ///
+    /// The rotation point (`p2`) of the COLR table is resolved so that the
+    /// color line is always perpendicular to the isolines of the gradient.
+    LinearGradient {
+        p0: (f32, f32),
+        p1: (f32, f32),
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+    /// Gradient between two circles.
+    RadialGradient {
+        c0: (f32, f32),
+        r0: f32,
+        c1: (f32, f32),
+        r1: f32,
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+    /// Gradient around a center point.
+    ///
+    /// Angles are in degrees, counter-clockwise from the positive x axis.
+    SweepGradient {
+        c0: (f32, f32),
+        start_angle: f32,
+        end_angle: f32,
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+}
+
+/// Interface for receiving the drawing commands of a color glyph.
+///
+/// When painting succeeds, transforms, clips and layers are always balanced
+/// by the corresponding pop operation.
+#[cfg(feature = "scale")]
+pub trait ColorPainter {
+    /// Pushes a transform that applies to all subsequent commands until the
+    /// matching [`pop_transform`](Self::pop_transform). The transform should
+    /// be combined with any existing transforms.
+    fn push_transform(&mut self, transform: Transform);
+
+    /// Removes the most recently pushed transform.
+    fn pop_transform(&mut self);
+
+    /// Pushes a clip region defined by the outline of the given glyph.
+    ///
+    /// The outline is loaded by the scaler that was passed to
+    /// [`ColorGlyph::paint`] and is only valid for the duration of the call.
+    fn push_clip_glyph(&mut self, glyph_id: GlyphId, outline: &Outline);
+
+    /// Pushes a rectangular clip region.
+    fn push_clip_box(&mut self, clip_box: BoundingBox<f32>);
+
+    /// Removes the most recently pushed clip region.
+    fn pop_clip(&mut self);
+
+    /// Fills the current clip region with the given brush.
+    fn fill(&mut self, brush: Brush);
+
+    /// Pushes a new layer that is composited with the content below it
+    /// using the given mode when popped.
+    fn push_layer(&mut self, composite_mode: CompositeMode);
+
+    /// Composites the most recently pushed layer.
+    fn pop_layer(&mut self);
+}
+
+/// Format of a color glyph.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorGlyphFormat {
+    /// Layers of glyph outlines filled with solid colors (COLRv0).
+    ColrV0,
+    /// Directed acyclic graph of paint operations (COLRv1).
+    ColrV1,
+}
+
+/// A color glyph.
+#[derive(Clone)]
+pub struct ColorGlyph<'a> {
+    colr: Colr<'a>,
+    glyph_id: GlyphId,
+    format: ColorGlyphFormat,
+}
+
+impl<'a> ColorGlyph<'a> {
+    /// Returns the glyph identifier.
+    pub fn glyph_id(&self) -> GlyphId {
+        self.glyph_id
+    }
+
+    /// Returns the format of the color glyph.
+    pub fn format(&self) -> ColorGlyphFormat {
+        self.format
+    }
+
+    /// Returns the clip box of the glyph at the given location in
+    /// normalized variation space.
+    ///
+    /// This is only available for COLRv1 glyphs that specify an explicit
+    /// clip box.
+    pub fn clip_box(&self, location: impl Into<LocationRef<'a>>) -> Option<BoundingBox<f32>> {
+        if self.format != ColorGlyphFormat::ColrV1 {
+            return None;
+        }
+        let instance = instance(&self.colr, location.into());
+        clip_box(&instance, self.glyph_id)
+    }
+
+    /// Walks the paint graph of the glyph, emitting commands to the painter.
+    ///
+    /// The variation location is taken from the normalized coordinates of
+    /// the scaler which is also used to load the outlines of glyph clip
+    /// regions. Since all other coordinates are in font units, the scaler
+    /// should be built with [`Size::unscaled`](crate::instance::Size::unscaled).
+    #[cfg(feature = "scale")]
+    pub fn paint(
+        &self,
+        scaler: &mut Scaler,
+        painter: &mut impl ColorPainter,
+    ) -> Result<(), PaintError> {
+        let instance = instance(&self.colr, LocationRef::new(scaler.normalized_coords()));
+        match self.format {
+            ColorGlyphFormat::ColrV0 => {
+                traversal::paint_v0(&instance, self.glyph_id, scaler, painter)
+            }
+            ColorGlyphFormat::ColrV1 => {
+                traversal::paint_v1(&instance, self.glyph_id, scaler, painter)
+            }
+        }
+    }
+}
+
+fn instance<'a>(colr: &Colr<'a>, location: LocationRef<'a>) -> ColrInstance<'a> {
+    ColrInstance::new(colr.clone(), location.coords())
+}
+
+/// Returns the resolved COLRv1 clip box for the given glyph.
+fn clip_box(instance: &ColrInstance, glyph_id: GlyphId) -> Option<BoundingBox<f32>> {
+    let clip_box = instance.v1_clip_box(glyph_id).ok()??.resolve(instance);
+    Some(BoundingBox {
+        x_min: clip_box.x_min.to_f32(),
+        y_min: clip_box.y_min.to_f32(),
+        x_max: clip_box.x_max.to_f32(),
+        y_max: clip_box.y_max.to_f32(),
+    })
+}
+
+/// Collection of color glyphs.
+#[derive(Clone, Default)]
+pub struct ColorGlyphCollection<'a> {
+    colr: Option<Colr<'a>>,
+}
+
+impl<'a> ColorGlyphCollection<'a> {
+    /// Creates a new collection of color glyphs from the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        Self {
+            colr: font.colr().ok(),
+        }
+    }
+
+    /// Returns the color glyph for the given glyph identifier.
+    ///
+    /// If the glyph is available in both formats, the COLRv1 version is
+    /// preferred.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<ColorGlyph<'a>> {
+        self.get_with_format(glyph_id, ColorGlyphFormat::ColrV1)
+            .or_else(|| self.get_with_format(glyph_id, ColorGlyphFormat::ColrV0))
+    }
+
+    /// Returns the color glyph in the requested format for the given glyph
+    /// identifier.
+    pub fn get_with_format(
+        &self,
+        glyph_id: GlyphId,
+        format: ColorGlyphFormat,
+    ) -> Option<ColorGlyph<'a>> {
+        let colr = self.colr.clone()?;
+        let exists = match format {
+            ColorGlyphFormat::ColrV0 => colr.v0_base_glyph(glyph_id).ok()?.is_some(),
+            ColorGlyphFormat::ColrV1 => colr.v1_base_glyph(glyph_id).ok()?.is_some(),
+        };
+        exists.then_some(ColorGlyph {
+            colr,
+            glyph_id,
+            format,
+        })
+    }
+}
+
+/// Color glyph format that may be present in a font.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ColorFormat {
+    /// Layered glyphs in the COLR table (version 0).
+    ColrV0,
+    /// Paint graphs in the COLR table (version 1).
+    ColrV1,
+    /// SVG documents in the `SVG ` table.
+    Svg,
+    /// Color bitmaps in the CBLC and CBDT tables.
+    Cbdt,
+    /// Bitmaps in the sbix table.
+    Sbix,
+}
+
+impl ColorFormat {
+    const ALL: [Self; 5] = [
+        Self::ColrV0,
+        Self::ColrV1,
+        Self::Svg,
+        Self::Cbdt,
+        Self::Sbix,
+    ];
+
+    fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Set of color glyph formats present in a font.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct ColorFormats(u8);
+
+impl ColorFormats {
+    /// Determines the set of color formats present in the given font.
+    pub fn new<'a>(font: &impl TableProvider<'a>) -> Self {
+        let mut formats = Self::default();
+        if let Ok(colr) = font.colr() {
+            if colr.num_base_glyph_records() != 0 {
+                formats.insert(ColorFormat::ColrV0);
+            }
+            if let Some(Ok(list)) = colr.base_glyph_list() {
+                if list.num_base_glyph_paint_records() != 0 {
+                    formats.insert(ColorFormat::ColrV1);
+                }
+            }
+        }
+        if let Ok(list) = font.svg().and_then(|svg| svg.svg_document_list()) {
+            if list.num_entries() != 0 {
+                formats.insert(ColorFormat::Svg);
+            }
+        }
+        if font.cblc().is_ok() && font.cbdt().is_ok() {
+            formats.insert(ColorFormat::Cbdt);
+        }
+        if let Ok(sbix) = font.sbix() {
+            if sbix.num_strikes() != 0 {
+                formats.insert(ColorFormat::Sbix);
+            }
+        }
+        formats
+    }
+
+    /// Returns true if the given format is present.
+    pub fn contains(self, format: ColorFormat) -> bool {
+        self.0 & format.mask() != 0
+    }
+
+    /// Returns true if the font does not contain any color glyphs.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns an iterator over the formats that are present.
+    pub fn iter(self) -> impl Iterator<Item = ColorFormat> + Clone {
+        ColorFormat::ALL
+            .into_iter()
+            .filter(move |format| self.contains(*format))
+    }
+
+    fn insert(&mut self, format: ColorFormat) {
+        self.0 |= format.mask();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider;
+    use read_fonts::FontRef;
+
+    #[test]
+    fn clip_box() {
+        let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+        let color_glyph = font.color_glyphs().get(GlyphId::new(2)).unwrap();
+        assert_eq!(
+            color_glyph.clip_box(LocationRef::default()),
+            Some(BoundingBox {
+                x_min: 20.0,
+                y_min: 60.0,
+                x_max: 80.0,
+                y_max: 80.0
+            })
+        );
+    }
+
+    #[test]
+    fn missing_color_glyphs() {
+        let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+        let color_glyphs = font.color_glyphs();
+        for gid in [0, 1, 3, 100] {
+            assert!(color_glyphs.get(GlyphId::new(gid)).is_none());
+        }
+        assert!(color_glyphs
+            .get_with_format(GlyphId::new(2), ColorGlyphFormat::ColrV0)
+            .is_none());
+        // No COLR table at all.
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        assert!(font.color_glyphs().get(GlyphId::new(1)).is_none());
+    }
+
+    #[test]
+    fn color_formats() {
+        let formats = |data| FontRef::new(data).unwrap().color_formats();
+        let colr = formats(font_test_data::COLR_GRADIENT_RECT);
+        assert!(colr.contains(ColorFormat::ColrV1));
+        assert!(!colr.contains(ColorFormat::Svg));
+        let bitmaps = formats(font_test_data::EMBEDDED_BITMAPS);
+        assert_eq!(bitmaps.iter().collect::<Vec<_>>(), [ColorFormat::Cbdt]);
+        assert!(formats(font_test_data::VAZIRMATN_VAR).is_empty());
+    }
+
+    #[test]
+    fn transform_then() {
+        let scale = Transform {
+            xx: 2.0,
+            yy: 3.0,
+            ..Transform::IDENTITY
+        };
+        let translate = Transform {
+            dx: 10.0,
+            dy: -5.0,
+            ..Transform::IDENTITY
+        };
+        assert_eq!(scale.then(&translate).map_point(1.0, 1.0), (12.0, -2.0));
+        assert_eq!(translate.then(&scale).map_point(1.0, 1.0), (22.0, -12.0));
+    }
+
+    #[cfg(feature = "scale")]
+    mod paint {
+        use super::*;
+        use crate::{instance::Size, scale::Context};
+        use read_fonts::{FontData, FontRead};
+
+        #[derive(Default)]
+        struct Recorder {
+            commands: Vec<String>,
+            /// Outlines received by `push_clip_glyph`.
+            clips: Vec<Outline>,
+        }
+
+        impl ColorPainter for Recorder {
+            fn push_transform(&mut self, transform: Transform) {
+                self.commands.push(format!("push_transform {transform:?}"));
+            }
+            fn pop_transform(&mut self) {
+                self.commands.push("pop_transform".into());
+            }
+            fn push_clip_glyph(&mut self, glyph_id: GlyphId, outline: &Outline) {
+                self.commands.push(format!("push_clip_glyph {glyph_id}"));
+                self.clips.push(outline.clone());
+            }
+            fn push_clip_box(&mut self, clip_box: BoundingBox<f32>) {
+                self.commands.push(format!("push_clip_box {clip_box:?}"));
+            }
+            fn pop_clip(&mut self) {
+                self.commands.push("pop_clip".into());
+            }
+            fn fill(&mut self, brush: Brush) {
+                self.commands.push(format!("fill {brush:?}"));
+            }
+            fn push_layer(&mut self, composite_mode: CompositeMode) {
+                self.commands.push(format!("push_layer {composite_mode:?}"));
+            }
+            fn pop_layer(&mut self) {
+                self.commands.push("pop_layer".into());
+            }
+        }
+
+        #[test]
+        fn paint_gradient_rect() {
+            let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+            let color_glyph = font.color_glyphs().get(GlyphId::new(2)).unwrap();
+            assert_eq!(color_glyph.format(), ColorGlyphFormat::ColrV1);
+            // The test font has no outlines so borrow them from another font.
+            let outline_font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+            let mut cx = Context::new();
+            let mut scaler = cx.new_scaler().size(Size::unscaled()).build(&outline_font);
+            let mut recorder = Recorder::default();
+            color_glyph.paint(&mut scaler, &mut recorder).unwrap();
+            let expected = [
+                "push_clip_box BoundingBox { x_min: 20.0, y_min: 60.0, x_max: 80.0, y_max: 80.0 }",
+                "push_clip_glyph GID_3",
+                "fill LinearGradient { p0: (20.0, 80.0), p1: (80.0, 80.0), color_stops: [\
+                 ColorStop { offset: 0.099975586, palette_index: 0, alpha: 1.0 }, \
+                 ColorStop { offset: 0.9000244, palette_index: 1, alpha: 0.7999878 }], \
+                 extend: Pad }",
+                "pop_clip",
+                "pop_clip",
+            ];
+            assert_eq!(recorder.commands, expected);
+            assert_eq!(recorder.clips, [outline(&outline_font, 3)]);
+        }
+
+        fn outline(font: &FontRef, gid: u16) -> Outline {
+            let mut cx = Context::new();
+            let mut scaler = cx.new_scaler().size(Size::unscaled()).build(font);
+            let mut outline = Outline::new();
+            scaler.outline(GlyphId::new(gid), &mut outline).unwrap();
+            outline
+        }
+
+        /// Paints the given glyph from a synthetic COLR table, loading clip
+        /// outlines from a font without color glyphs.
+        fn paint_synthetic(
+            glyph_id: u16,
+            format: ColorGlyphFormat,
+        ) -> Result<Recorder, PaintError> {
+            let colr_data = synthetic_colr();
+            let colr = Colr::read(FontData::new(&colr_data)).unwrap();
+            let color_glyph = ColorGlyph {
+                colr,
+                glyph_id: GlyphId::new(glyph_id),
+                format,
+            };
+            let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+            let mut cx = Context::new();
+            let mut scaler = cx.new_scaler().size(Size::unscaled()).build(&font);
+            let mut recorder = Recorder::default();
+            color_glyph.paint(&mut scaler, &mut recorder)?;
+            Ok(recorder)
+        }
+
+        /// Builds a COLR table with the following glyphs:
+        ///
+        /// * 10: COLRv0 layers of glyph 1 (palette 0) and glyph 2 (palette 1)
+        /// * 20 and 21: PaintColrGlyph referencing each other
+        /// * 22: PaintColrGlyph referencing 23
+        /// * 23: glyph 1 filled with palette 2
+        /// * 24: composite of glyph 2 (source) over glyph 1 (backdrop)
+        /// * 25: translate, then scale glyph 3
+        fn synthetic_colr() -> Vec<u8> {
+            // Each paint is immediately followed by its children so the
+            // offset of the first child is the size of the parent.
+            fn solid(palette_index: u16) -> Vec<u8> {
+                let mut buf = vec![2];
+                buf.extend(palette_index.to_be_bytes());
+                buf.extend(0x4000u16.to_be_bytes());
+                buf
+            }
+            fn offset24(offset: usize) -> [u8; 3] {
+                let [_, a, b, c] = (offset as u32).to_be_bytes();
+                [a, b, c]
+            }
+            fn glyph(glyph_id: u16, child: Vec<u8>) -> Vec<u8> {
+                let mut buf = vec![10];
+                buf.extend(offset24(6));
+                buf.extend(glyph_id.to_be_bytes());
+                buf.extend(child);
+                buf
+            }
+            fn colr_glyph(glyph_id: u16) -> Vec<u8> {
+                let mut buf = vec![11];
+                buf.extend(glyph_id.to_be_bytes());
+                buf
+            }
+            fn translate(dx: i16, dy: i16, child: Vec<u8>) -> Vec<u8> {
+                let mut buf = vec![14];
+                buf.extend(offset24(8));
+                buf.extend(dx.to_be_bytes());
+                buf.extend(dy.to_be_bytes());
+                buf.extend(child);
+                buf
+            }
+            fn scale(sx: f32, sy: f32, child: Vec<u8>) -> Vec<u8> {
+                let mut buf = vec![16];
+                buf.extend(offset24(8));
+                buf.extend(((sx * 16384.0) as i16).to_be_bytes());
+                buf.extend(((sy * 16384.0) as i16).to_be_bytes());
+                buf.extend(child);
+                buf
+            }
+            fn composite(source: Vec<u8>, mode: u8, backdrop: Vec<u8>) -> Vec<u8> {
+                let mut buf = vec![32];
+                buf.extend(offset24(8));
+                buf.push(mode);
+                buf.extend(offset24(8 + source.len()));
+                buf.extend(source);
+                buf.extend(backdrop);
+                buf
+            }
+            let base_glyphs = [
+                (20, colr_glyph(21)),
+                (21, colr_glyph(20)),
+                (22, colr_glyph(23)),
+                (23, glyph(1, solid(2))),
+                (24, composite(glyph(2, solid(0)), 5, glyph(1, solid(1)))),
+                (25, translate(10, 20, scale(1.5, 0.5, glyph(3, solid(0))))),
+            ];
+            const HEADER_LEN: u32 = 34;
+            let v0_base_glyphs_offset = HEADER_LEN;
+            let v0_layers_offset = v0_base_glyphs_offset + 6;
+            let base_glyph_list_offset = v0_layers_offset + 8;
+            let mut buf = vec![];
+            buf.extend(1u16.to_be_bytes());
+            // COLRv0 base glyph and layer records.
+            buf.extend(1u16.to_be_bytes());
+            buf.extend(v0_base_glyphs_offset.to_be_bytes());
+            buf.extend(v0_layers_offset.to_be_bytes());
+            buf.extend(2u16.to_be_bytes());
+            // Base glyph list, layer list, clip list, variation index map and
+            // item variation store.
+            buf.extend(base_glyph_list_offset.to_be_bytes());
+            buf.extend([0; 16]);
+            assert_eq!(buf.len() as u32, HEADER_LEN);
+            for value in [10u16, 0, 2, 1, 0, 2, 1] {
+                buf.extend(value.to_be_bytes());
+            }
+            buf.extend((base_glyphs.len() as u32).to_be_bytes());
+            let mut paint_offset = 4 + 6 * base_glyphs.len();
+            for (glyph_id, paint) in &base_glyphs {
+                buf.extend((*glyph_id as u16).to_be_bytes());
+                buf.extend((paint_offset as u32).to_be_bytes());
+                paint_offset += paint.len();
+            }
+            for (_, paint) in base_glyphs {
+                buf.extend(paint);
+            }
+            buf
+        }
+
+        #[test]
+        fn paint_colr_v0_layers() {
+            let recorder = paint_synthetic(10, ColorGlyphFormat::ColrV0).unwrap();
+            let expected = [
+                "push_clip_glyph GID_1",
+                "fill Solid { palette_index: 0, alpha: 1.0 }",
+                "pop_clip",
+                "push_clip_glyph GID_2",
+                "fill Solid { palette_index: 1, alpha: 1.0 }",
+                "pop_clip",
+            ];
+            assert_eq!(recorder.commands, expected);
+            let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+            assert_eq!(recorder.clips, [outline(&font, 1), outline(&font, 2)]);
+            assert!(recorder.clips.iter().all(|outline| !outline.is_empty()));
+        }
+
+        #[test]
+        fn paint_colr_glyph() {
+            let recorder = paint_synthetic(22, ColorGlyphFormat::ColrV1).unwrap();
+            let expected = [
+                "push_clip_glyph GID_1",
+                "fill Solid { palette_index: 2, alpha: 1.0 }",
+                "pop_clip",
+            ];
+            assert_eq!(recorder.commands, expected);
+        }
+
+        #[test]
+        fn paint_cycle_detected() {
+            for glyph_id in [20, 21] {
+                assert!(matches!(
+                    paint_synthetic(glyph_id, ColorGlyphFormat::ColrV1),
+                    Err(PaintError::PaintCycleDetected)
+                ));
+            }
+        }
+
+        #[test]
+        fn paint_composite() {
+            let recorder = paint_synthetic(24, ColorGlyphFormat::ColrV1).unwrap();
+            // The backdrop is painted first, followed by the source in a
+            // separate layer.
+            let expected = [
+                "push_layer SrcOver",
+                "push_clip_glyph GID_1",
+                "fill Solid { palette_index: 1, alpha: 1.0 }",
+                "pop_clip",
+                "push_layer SrcIn",
+                "push_clip_glyph GID_2",
+                "fill Solid { palette_index: 0, alpha: 1.0 }",
+                "pop_clip",
+                "pop_layer",
+                "pop_layer",
+            ];
+            assert_eq!(recorder.commands, expected);
+        }
+
+        #[test]
+        fn paint_nested_transforms() {
+            let recorder = paint_synthetic(25, ColorGlyphFormat::ColrV1).unwrap();
+            let expected = [
+                "push_transform Transform { xx: 1.0, yx: 0.0, xy: 0.0, yy: 1.0, dx: 10.0, dy: 20.0 }",
+                "push_transform Transform { xx: 1.5, yx: 0.0, xy: 0.0, yy: 0.5, dx: 0.0, dy: 0.0 }",
+                "push_clip_glyph GID_3",
+                "fill Solid { palette_index: 0, alpha: 1.0 }",
+                "pop_clip",
+                "pop_transform",
+                "pop_transform",
+            ];
+            assert_eq!(recorder.commands, expected);
+        }
+    }
+}
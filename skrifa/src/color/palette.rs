@@ -0,0 +1,284 @@
+//! Color palettes.
+
+use read_fonts::{
+    tables::cpal::{ColorRecord, Cpal},
+    types::BigEndian,
+    TableProvider,
+};
+
+use crate::string::StringId;
+
+pub use read_fonts::tables::cpal::PaletteType;
+
+/// Value used in the label arrays to indicate that no name is provided.
+const NO_NAME_ID: u16 = 0xFFFF;
+
+/// Color with 8-bit red, green, blue and alpha components.
+///
+/// Components are not premultiplied by alpha.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl From<&ColorRecord> for Color {
+    fn from(record: &ColorRecord) -> Self {
+        Self {
+            red: record.red(),
+            green: record.green(),
+            blue: record.blue(),
+            alpha: record.alpha(),
+        }
+    }
+}
+
+/// Palette of colors used to render color glyphs.
+///
+/// Indices into the palette are provided by the color glyph painting
+/// commands in [`Brush`](super::Brush).
+#[derive(Clone)]
+pub struct ColorPalette<'a> {
+    index: u16,
+    colors: &'a [ColorRecord],
+    palette_type: PaletteType,
+    label: Option<StringId>,
+}
+
+impl<'a> ColorPalette<'a> {
+    /// Returns the index of the palette in the font.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the flags that describe the intended usage of the palette.
+    pub fn palette_type(&self) -> PaletteType {
+        self.palette_type
+    }
+
+    /// Returns true if the palette is appropriate for use on a light
+    /// background.
+    pub fn is_usable_with_light_background(&self) -> bool {
+        self.palette_type
+            .contains(PaletteType::USABLE_WITH_LIGHT_BACKGROUND)
+    }
+
+    /// Returns true if the palette is appropriate for use on a dark
+    /// background.
+    pub fn is_usable_with_dark_background(&self) -> bool {
+        self.palette_type
+            .contains(PaletteType::USABLE_WITH_DARK_BACKGROUND)
+    }
+
+    /// Returns the string identifier for the name of the palette.
+    ///
+    /// The name can be retrieved with
+    /// [`localized_strings`](crate::MetadataProvider::localized_strings).
+    pub fn label(&self) -> Option<StringId> {
+        self.label
+    }
+
+    /// Returns the number of colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Returns true if the palette contains no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the color for the given palette entry index.
+    pub fn get(&self, entry_index: u16) -> Option<Color> {
+        self.colors.get(entry_index as usize).map(Color::from)
+    }
+
+    /// Returns an iterator over the colors in the palette.
+    pub fn colors(&self) -> impl Iterator<Item = Color> + 'a + Clone {
+        self.colors.iter().map(Color::from)
+    }
+}
+
+/// Collection of color palettes.
+///
+/// The palettes are defined by the
+/// [CPAL](https://learn.microsoft.com/en-us/typography/opentype/spec/cpal)
+/// table.
+#[derive(Clone, Default)]
+pub struct ColorPalettes<'a> {
+    cpal: Option<Cpal<'a>>,
+}
+
+impl<'a> ColorPalettes<'a> {
+    /// Creates a new palette collection from the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        Self {
+            cpal: font.cpal().ok(),
+        }
+    }
+
+    /// Returns the number of palettes in the collection.
+    pub fn len(&self) -> usize {
+        self.cpal
+            .as_ref()
+            .map(|cpal| cpal.num_palettes() as usize)
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of color entries in each palette.
+    pub fn entry_count(&self) -> u16 {
+        self.cpal
+            .as_ref()
+            .map(|cpal| cpal.num_palette_entries())
+            .unwrap_or(0)
+    }
+
+    /// Returns the string identifier for the name of the palette entry at
+    /// the given index.
+    ///
+    /// Entry labels are shared by all palettes and describe the role of the
+    /// color, such as "Outline" or "Fill".
+    pub fn entry_label(&self, entry_index: u16) -> Option<StringId> {
+        let labels = self.cpal.as_ref()?.palette_entry_labels_array()?.ok()?;
+        labels
+            .get(entry_index as usize)
+            .map(|id| id.get())
+            .filter(|id| id.to_u16() != NO_NAME_ID)
+    }
+
+    /// Returns the palette at the given index.
+    pub fn get(&self, index: usize) -> Option<ColorPalette<'a>> {
+        let cpal = self.cpal.as_ref()?;
+        let start = cpal.color_record_indices().get(index)?.get() as usize;
+        let end = start.checked_add(cpal.num_palette_entries() as usize)?;
+        let colors = cpal.color_records_array()?.ok()?.get(start..end)?;
+        let palette_type = cpal
+            .palette_types_array()
+            .and_then(|types| types.ok()?.get(index).map(BigEndian::get))
+            .unwrap_or_default();
+        let label = cpal
+            .palette_labels_array()
+            .and_then(|labels| labels.ok()?.get(index).map(BigEndian::get))
+            .filter(|id| *id != NO_NAME_ID)
+            .map(StringId::new);
+        Some(ColorPalette {
+            index: index as u16,
+            colors,
+            palette_type,
+            label,
+        })
+    }
+
+    /// Returns the first palette that is appropriate for use on a dark
+    /// background, if any.
+    pub fn dark(&self) -> Option<ColorPalette<'a>> {
+        self.iter()
+            .find(|palette| palette.is_usable_with_dark_background())
+    }
+
+    /// Returns the first palette that is appropriate for use on a light
+    /// background, if any.
+    pub fn light(&self) -> Option<ColorPalette<'a>> {
+        self.iter()
+            .find(|palette| palette.is_usable_with_light_background())
+    }
+
+    /// Returns an iterator over the palettes in the collection.
+    pub fn iter(&self) -> impl Iterator<Item = ColorPalette<'a>> + 'a + Clone {
+        let copy = self.clone();
+        (0..self.len()).filter_map(move |i| copy.get(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider as _;
+    use read_fonts::{FontData, FontRead, FontRef};
+
+    #[test]
+    fn palettes() {
+        let font = FontRef::new(font_test_data::COLR_GRADIENT_RECT).unwrap();
+        let palettes = font.palettes();
+        assert_eq!(palettes.len(), 2);
+        assert_eq!(palettes.entry_count(), 2);
+        // Version 0 tables have no types or labels.
+        assert_eq!(palettes.entry_label(0), None);
+        assert!(palettes.dark().is_none());
+        let colors = palettes
+            .iter()
+            .map(|palette| {
+                assert_eq!(palette.palette_type(), PaletteType::empty());
+                assert_eq!(palette.label(), None);
+                palette.colors().collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let rgb = |red, green, blue| Color {
+            red,
+            green,
+            blue,
+            alpha: 0xFF,
+        };
+        assert_eq!(
+            colors,
+            [
+                [rgb(0x00, 0x00, 0xFF), rgb(0x00, 0xFF, 0xFF)],
+                [rgb(0xAA, 0x00, 0xFF), rgb(0xAA, 0xFF, 0xFF)],
+            ]
+        );
+        assert!(palettes.get(2).is_none());
+        assert!(palettes.get(0).unwrap().get(2).is_none());
+    }
+
+    #[test]
+    fn palette_types_and_labels() {
+        #[rustfmt::skip]
+        let data = [
+            // version, entries, palettes, color records
+            0, 1, 0, 2, 0, 2, 0, 4,
+            // color records offset
+            0, 0, 0, 28,
+            // color record indices
+            0, 0, 0, 2,
+            // types, labels and entry labels offsets
+            0, 0, 0, 44, 0, 0, 0, 52, 0, 0, 0, 56,
+            // color records (BGRA)
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+            // palette types
+            0, 0, 0, 1, 0, 0, 0, 2,
+            // palette labels
+            1, 0, 0xFF, 0xFF,
+            // entry labels
+            0xFF, 0xFF, 1, 1,
+        ];
+        let cpal = Cpal::read(FontData::new(&data)).unwrap();
+        let palettes = ColorPalettes { cpal: Some(cpal) };
+        assert_eq!(palettes.entry_label(0), None);
+        assert_eq!(palettes.entry_label(1), Some(StringId::new(257)));
+        assert_eq!(palettes.entry_label(2), None);
+        let light = palettes.light().unwrap();
+        assert_eq!(light.index(), 0);
+        assert!(!light.is_usable_with_dark_background());
+        assert_eq!(light.label(), Some(StringId::new(256)));
+        let dark = palettes.dark().unwrap();
+        assert_eq!(dark.index(), 1);
+        assert!(!dark.is_usable_with_light_background());
+        assert_eq!(dark.label(), None);
+        assert_eq!(
+            dark.get(1),
+            Some(Color {
+                red: 15,
+                green: 14,
+                blue: 13,
+                alpha: 16
+            })
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Walking the paint graph of a color glyph.
+
+use read_fonts::{
+    tables::colr::{ColorStops, ColrInstance, CompositeMode, Paint, PaintId, ResolvedPaint},
+    types::{Fixed, GlyphId, Point},
+};
+
+use super::{clip_box, Brush, ColorPainter, ColorStop, PaintError, Transform, PAINT_DEPTH_LIMIT};
+use crate::scale::{Outline, Scaler};
+
+/// Emits the layers of a COLRv0 glyph.
+pub(super) fn paint_v0(
+    instance: &ColrInstance,
+    glyph_id: GlyphId,
+    scaler: &mut Scaler,
+    painter: &mut impl ColorPainter,
+) -> Result<(), PaintError> {
+    let layers = instance
+        .v0_base_glyph(glyph_id)?
+        .ok_or(PaintError::GlyphNotFound(glyph_id))?;
+    let mut outline = Outline::new();
+    for layer_ix in layers {
+        let (layer_glyph_id, palette_index) = instance.v0_layer(layer_ix)?;
+        push_clip_glyph(scaler, &mut outline, layer_glyph_id, painter)?;
+        painter.fill(Brush::Solid {
+            palette_index,
+            alpha: 1.0,
+        });
+        painter.pop_clip();
+    }
+    Ok(())
+}
+
+/// Walks the paint graph of a COLRv1 glyph.
+pub(super) fn paint_v1(
+    instance: &ColrInstance,
+    glyph_id: GlyphId,
+    scaler: &mut Scaler,
+    painter: &mut impl ColorPainter,
+) -> Result<(), PaintError> {
+    let mut walker = Walker {
+        instance,
+        scaler,
+        outline: Outline::new(),
+        painter,
+        visited: vec![],
+    };
+    walker.colr_glyph(glyph_id, 0)
+}
+
+/// Loads the outline of the given glyph and pushes it as a clip region.
+fn push_clip_glyph(
+    scaler: &mut Scaler,
+    outline: &mut Outline,
+    glyph_id: GlyphId,
+    painter: &mut impl ColorPainter,
+) -> Result<(), PaintError> {
+    outline.clear();
+    scaler.outline(glyph_id, outline)?;
+    painter.push_clip_glyph(glyph_id, outline);
+    Ok(())
+}
+
+struct Walker<'a, 'b, 's, P> {
+    instance: &'b ColrInstance<'a>,
+    /// Source of outlines for glyph clip regions.
+    scaler: &'b mut Scaler<'s>,
+    /// Buffer for the outline of the current clip glyph.
+    outline: Outline,
+    painter: &'b mut P,
+    /// Identifiers of the layers and base glyphs on the current path through
+    /// the graph.
+    ///
+    /// These are the only edges that can introduce a cycle since all other
+    /// paints are reached through forward offsets.
+    visited: Vec<PaintId>,
+}
+
+impl<'a, 'b, 's, P: ColorPainter> Walker<'a, 'b, 's, P> {
+    /// Emits the base glyph paint for the given glyph, clipped to the
+    /// associated clip box if present.
+    fn colr_glyph(&mut self, glyph_id: GlyphId, depth: usize) -> Result<(), PaintError> {
+        let (paint, paint_id) = self
+            .instance
+            .v1_base_glyph(glyph_id)?
+            .ok_or(PaintError::GlyphNotFound(glyph_id))?;
+        let clip_box = clip_box(self.instance, glyph_id);
+        if let Some(clip_box) = clip_box {
+            self.painter.push_clip_box(clip_box);
+        }
+        self.paint_with_id(&paint, paint_id, depth)?;
+        if clip_box.is_some() {
+            self.painter.pop_clip();
+        }
+        Ok(())
+    }
+
+    fn paint_with_id(
+        &mut self,
+        paint: &Paint<'a>,
+        paint_id: PaintId,
+        depth: usize,
+    ) -> Result<(), PaintError> {
+        if self.visited.contains(&paint_id) {
+            return Err(PaintError::PaintCycleDetected);
+        }
+        self.visited.push(paint_id);
+        self.paint(paint, depth)?;
+        self.visited.pop();
+        Ok(())
+    }
+
+    fn paint(&mut self, paint: &Paint<'a>, depth: usize) -> Result<(), PaintError> {
+        if depth > PAINT_DEPTH_LIMIT {
+            return Err(PaintError::DepthLimitExceeded);
+        }
+        let depth = depth + 1;
+        match paint.resolve(self.instance)? {
+            ResolvedPaint::ColrLayers { range } => {
+                for layer_ix in range {
+                    let (layer, layer_id) = self.instance.v1_layer(layer_ix)?;
+                    self.paint_with_id(&layer, layer_id, depth)?;
+                }
+            }
+            ResolvedPaint::Solid {
+                palette_index,
+                alpha,
+            } => self.painter.fill(Brush::Solid {
+                palette_index,
+                alpha: alpha.to_f32(),
+            }),
+            ResolvedPaint::LinearGradient {
+                x0,
+                y0,
+                x1,
+                y1,
+                x2,
+                y2,
+                color_stops,
+                extend,
+            } => {
+                let color_stops = self.color_stops(&color_stops);
+                let p0 = point(x0, y0);
+                let p1 = resolve_linear_rotation(p0, point(x1, y1), point(x2, y2));
+                self.painter.fill(Brush::LinearGradient {
+                    p0,
+                    p1,
+                    color_stops: &color_stops,
+                    extend,
+                });
+            }
+            ResolvedPaint::RadialGradient {
+                x0,
+                y0,
+                radius0,
+                x1,
+                y1,
+                radius1,
+                color_stops,
+                extend,
+            } => {
+                let color_stops = self.color_stops(&color_stops);
+                self.painter.fill(Brush::RadialGradient {
+                    c0: point(x0, y0),
+                    r0: radius0.to_f32(),
+                    c1: point(x1, y1),
+                    r1: radius1.to_f32(),
+                    color_stops: &color_stops,
+                    extend,
+                });
+            }
+            ResolvedPaint::SweepGradient {
+                center_x,
+                center_y,
+                start_angle,
+                end_angle,
+                color_stops,
+                extend,
+            } => {
+                let color_stops = self.color_stops(&color_stops);
+                // Angles are encoded in units of 180 degrees.
+                self.painter.fill(Brush::SweepGradient {
+                    c0: point(center_x, center_y),
+                    start_angle: start_angle.to_f32() * 180.0,
+                    end_angle: end_angle.to_f32() * 180.0,
+                    color_stops: &color_stops,
+                    extend,
+                });
+            }
+            ResolvedPaint::Glyph { glyph_id, paint } => {
+                push_clip_glyph(self.scaler, &mut self.outline, glyph_id, self.painter)?;
+                self.paint(&paint, depth)?;
+                self.painter.pop_clip();
+            }
+            ResolvedPaint::ColrGlyph { glyph_id } => self.colr_glyph(glyph_id, depth)?,
+            ResolvedPaint::Transform {
+                xx,
+                yx,
+                xy,
+                yy,
+                dx,
+                dy,
+                paint,
+            } => {
+                let transform = Transform {
+                    xx: xx.to_f32(),
+                    yx: yx.to_f32(),
+                    xy: xy.to_f32(),
+                    yy: yy.to_f32(),
+                    dx: dx.to_f32(),
+                    dy: dy.to_f32(),
+                };
+                self.transformed(transform, &paint, depth)?;
+            }
+            ResolvedPaint::Translate { dx, dy, paint } => {
+                let transform = Transform {
+                    dx: dx.to_f32(),
+                    dy: dy.to_f32(),
+                    ..Transform::IDENTITY
+                };
+                self.transformed(transform, &paint, depth)?;
+            }
+            ResolvedPaint::Scale {
+                scale_x,
+                scale_y,
+                around_center,
+                paint,
+            } => {
+                let transform = Transform {
+                    xx: scale_x.to_f32(),
+                    yy: scale_y.to_f32(),
+                    ..Transform::IDENTITY
+                };
+                self.transformed(around(transform, around_center), &paint, depth)?;
+            }
+            ResolvedPaint::Rotate {
+                angle,
+                around_center,
+                paint,
+            } => {
+                let (sin, cos) = (angle.to_f32() * core::f32::consts::PI).sin_cos();
+                let transform = Transform {
+                    xx: cos,
+                    yx: sin,
+                    xy: -sin,
+                    yy: cos,
+                    ..Transform::IDENTITY
+                };
+                self.transformed(around(transform, around_center), &paint, depth)?;
+            }
+            ResolvedPaint::Skew {
+                x_skew_angle,
+                y_skew_angle,
+                around_center,
+                paint,
+            } => {
+                let transform = Transform {
+                    yx: (y_skew_angle.to_f32() * core::f32::consts::PI).tan(),
+                    xy: -(x_skew_angle.to_f32() * core::f32::consts::PI).tan(),
+                    ..Transform::IDENTITY
+                };
+                self.transformed(around(transform, around_center), &paint, depth)?;
+            }
+            ResolvedPaint::Composite {
+                source_paint,
+                mode,
+                backdrop_paint,
+            } => {
+                self.painter.push_layer(CompositeMode::SrcOver);
+                self.paint(&backdrop_paint, depth)?;
+                self.painter.push_layer(mode);
+                self.paint(&source_paint, depth)?;
+                self.painter.pop_layer();
+                self.painter.pop_layer();
+            }
+        }
+        Ok(())
+    }
+
+    fn transformed(
+        &mut self,
+        transform: Transform,
+        paint: &Paint<'a>,
+        depth: usize,
+    ) -> Result<(), PaintError> {
+        self.painter.push_transform(transform);
+        self.paint(paint, depth)?;
+        self.painter.pop_transform();
+        Ok(())
+    }
+
+    /// Resolves the color stops of a gradient, sorted by offset.
+    fn color_stops(&self, color_stops: &ColorStops) -> Vec<ColorStop> {
+        let mut stops = color_stops
+            .resolve(self.instance)
+            .map(|stop| ColorStop {
+                offset: stop.offset.to_f32(),
+                palette_index: stop.palette_index,
+                alpha: stop.alpha.to_f32(),
+            })
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        stops
+    }
+}
+
+fn point(x: Fixed, y: Fixed) -> (f32, f32) {
+    (x.to_f32(), y.to_f32())
+}
+
+/// Applies the transform around the given center point.
+fn around(transform: Transform, center: Option<Point<Fixed>>) -> Transform {
+    let Some(center) = center else {
+        return transform;
+    };
+    let (cx, cy) = point(center.x, center.y);
+    let (x, y) = transform.map_point(cx, cy);
+    Transform {
+        dx: transform.dx + cx - x,
+        dy: transform.dy + cy - y,
+        ..transform
+    }
+}
+
+/// Projects `p1` onto the line through `p0` that is perpendicular to the
+/// line from `p0` to `p2`.
+///
+/// This converts the three point linear gradient of the COLR table to the
+/// two point form used by most graphics libraries.
+fn resolve_linear_rotation(p0: (f32, f32), p1: (f32, f32), p2: (f32, f32)) -> (f32, f32) {
+    let (p0p1_x, p0p1_y) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (perp_x, perp_y) = (p2.1 - p0.1, p0.0 - p2.0);
+    let perp_len_sq = perp_x * perp_x + perp_y * perp_y;
+    if perp_len_sq == 0.0 {
+        return p1;
+    }
+    let scale = (p0p1_x * perp_x + p0p1_y * perp_y) / perp_len_sq;
+    (p0.0 + perp_x * scale, p0.1 + perp_y * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_around_center() {
+        let rotate = Transform {
+            xx: 0.0,
+            yx: 1.0,
+            xy: -1.0,
+            yy: 0.0,
+            ..Transform::IDENTITY
+        };
+        let center = Point::new(Fixed::from_i32(10), Fixed::from_i32(20));
+        let transform = around(rotate, Some(center));
+        assert_eq!(transform.map_point(10.0, 20.0), (10.0, 20.0));
+        assert_eq!(transform.map_point(11.0, 20.0), (10.0, 21.0));
+        assert_eq!(around(rotate, None), rotate);
+    }
+
+    #[test]
+    fn linear_gradient_rotation() {
+        // Rotation point perpendicular to the color line has no effect.
+        assert_eq!(
+            resolve_linear_rotation((0.0, 0.0), (100.0, 0.0), (0.0, 100.0)),
+            (100.0, 0.0)
+        );
+        // Skewed rotation point projects p1 onto the perpendicular.
+        assert_eq!(
+            resolve_linear_rotation((0.0, 0.0), (100.0, 0.0), (100.0, 100.0)),
+            (50.0, -50.0)
+        );
+        // Degenerate rotation point.
+        assert_eq!(
+            resolve_linear_rotation((0.0, 0.0), (100.0, 0.0), (0.0, 0.0)),
+            (100.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Estimation of writing system support from character coverage.
+//!
+//! This provides a report of the Unicode scripts and orthographies (the
+//! character repertoire used to write a language) that are supported by the
+//! character map of a font. This is useful for font fallback and for
+//! filtering fonts by language.
+//!
+//! Script coverage is computed over the primary Unicode blocks for each
+//! script. Orthography coverage is computed against an embedded table of
+//! exemplar characters derived from the
+//! [CLDR](https://cldr.unicode.org/translation/core-data/exemplars) main
+//! exemplar sets for a selection of common languages.
+
+use read_fonts::{types::Tag, TableProvider};
+
+use crate::charmap::{Charmap, CodepointSet};
+
+/// Coverage of a Unicode script.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ScriptCoverage {
+    /// ISO 15924 tag for the script, such as `Latn` or `Cyrl`.
+    pub script: Tag,
+    /// Number of codepoints in the script blocks that are mapped by the
+    /// font.
+    pub covered: u32,
+    /// Total number of codepoints in the script blocks.
+    ///
+    /// This includes unassigned codepoints so is only useful as a rough
+    /// measure of completeness.
+    pub total: u32,
+}
+
+/// Coverage of the exemplar characters for a language.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct OrthographyCoverage {
+    /// BCP 47 language tag, such as `en` or `sr-Cyrl`.
+    pub language: &'static str,
+    /// ISO 15924 tag for the script of the orthography.
+    pub script: Tag,
+    /// Number of exemplar characters mapped by the font.
+    pub covered: u32,
+    /// Total number of exemplar characters.
+    pub total: u32,
+}
+
+impl OrthographyCoverage {
+    /// Returns true if the font maps all exemplar characters for the
+    /// language.
+    pub fn is_supported(&self) -> bool {
+        self.covered == self.total
+    }
+}
+
+/// Report of the scripts and orthographies supported by a font.
+#[derive(Clone, Default, Debug)]
+pub struct Coverage {
+    codepoints: CodepointSet,
+}
+
+impl Coverage {
+    /// Creates a new coverage report for the given font.
+    pub fn new<'a>(font: &impl TableProvider<'a>) -> Self {
+        Self::from_codepoints(Charmap::new(font).codepoints())
+    }
+
+    /// Creates a new coverage report for the given set of codepoints.
+    pub fn from_codepoints(codepoints: CodepointSet) -> Self {
+        Self { codepoints }
+    }
+
+    /// Returns the set of codepoints mapped by the font.
+    pub fn codepoints(&self) -> &CodepointSet {
+        &self.codepoints
+    }
+
+    /// Returns the coverage for the script with the given ISO 15924 tag.
+    ///
+    /// Returns `None` if the script is not present in the embedded table.
+    pub fn script(&self, script: Tag) -> Option<ScriptCoverage> {
+        SCRIPTS
+            .iter()
+            .find(|(tag, _)| Tag::new(tag) == script)
+            .map(|entry| self.script_coverage(entry))
+    }
+
+    /// Returns an iterator over the scripts that have at least one codepoint
+    /// mapped by the font.
+    pub fn scripts(&self) -> impl Iterator<Item = ScriptCoverage> + '_ {
+        SCRIPTS
+            .iter()
+            .map(|entry| self.script_coverage(entry))
+            .filter(|coverage| coverage.covered != 0)
+    }
+
+    /// Returns the coverage for the orthography of the given language.
+    ///
+    /// Returns `None` if the language is not present in the embedded table.
+    pub fn orthography(&self, language: &str) -> Option<OrthographyCoverage> {
+        ORTHOGRAPHIES
+            .iter()
+            .find(|(lang, ..)| *lang == language)
+            .map(|entry| self.orthography_coverage(entry))
+    }
+
+    /// Returns an iterator over the coverage of all orthographies in the
+    /// embedded table.
+    pub fn orthographies(&self) -> impl Iterator<Item = OrthographyCoverage> + '_ {
+        ORTHOGRAPHIES
+            .iter()
+            .map(|entry| self.orthography_coverage(entry))
+    }
+
+    /// Returns an iterator over the languages with orthographies that are
+    /// fully supported by the font.
+    pub fn supported_languages(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.orthographies()
+            .filter(|coverage| coverage.is_supported())
+            .map(|coverage| coverage.language)
+    }
+
+    fn script_coverage(&self, (tag, ranges): &ScriptEntry) -> ScriptCoverage {
+        let mut script_set = CodepointSet::new();
+        for (start, end) in ranges.iter() {
+            script_set.insert_range(*start..=*end);
+        }
+        ScriptCoverage {
+            script: Tag::new(tag),
+            covered: self.codepoints.intersection(&script_set).len() as u32,
+            total: script_set.len() as u32,
+        }
+    }
+
+    fn orthography_coverage(
+        &self,
+        (language, script, exemplars): &OrthographyEntry,
+    ) -> OrthographyCoverage {
+        let script = Tag::new(script);
+        let mut exemplar_set = exemplars.chars().map(u32::from).collect::<CodepointSet>();
+        if BICAMERAL_SCRIPTS.contains(&script) {
+            // Only the lowercase forms are listed for bicameral scripts
+            for ch in exemplars.chars() {
+                let mut upper = ch.to_uppercase();
+                if let (Some(upper), None) = (upper.next(), upper.next()) {
+                    exemplar_set.insert(upper as u32);
+                }
+            }
+        }
+        OrthographyCoverage {
+            language,
+            script,
+            covered: self.codepoints.intersection(&exemplar_set).len() as u32,
+            total: exemplar_set.len() as u32,
+        }
+    }
+}
+
+/// Scripts with distinct upper and lowercase letters.
+const BICAMERAL_SCRIPTS: &[Tag] = &[
+    Tag::new(b"Latn"),
+    Tag::new(b"Grek"),
+    Tag::new(b"Cyrl"),
+    Tag::new(b"Armn"),
+];
+
+/// ISO 15924 script tag and inclusive codepoint ranges.
+type ScriptEntry = (&'static [u8; 4], &'static [(u32, u32)]);
+
+/// Language tag, ISO 15924 script tag and exemplar characters.
+type OrthographyEntry = (&'static str, &'static [u8; 4], &'static str);
+
+/// Primary Unicode blocks for each script.
+#[rustfmt::skip]
+const SCRIPTS: &[ScriptEntry] = &[
+    (b"Latn", &[(0x0041, 0x005A), (0x0061, 0x007A), (0x00C0, 0x00D6), (0x00D8, 0x00F6), (0x00F8, 0x024F), (0x1E00, 0x1EFF)]),
+    (b"Grek", &[(0x0370, 0x03FF), (0x1F00, 0x1FFF)]),
+    (b"Cyrl", &[(0x0400, 0x052F)]),
+    (b"Armn", &[(0x0530, 0x058F)]),
+    (b"Hebr", &[(0x0590, 0x05FF)]),
+    (b"Arab", &[(0x0600, 0x06FF), (0x0750, 0x077F)]),
+    (b"Deva", &[(0x0900, 0x097F)]),
+    (b"Beng", &[(0x0980, 0x09FF)]),
+    (b"Guru", &[(0x0A00, 0x0A7F)]),
+    (b"Gujr", &[(0x0A80, 0x0AFF)]),
+    (b"Taml", &[(0x0B80, 0x0BFF)]),
+    (b"Telu", &[(0x0C00, 0x0C7F)]),
+    (b"Knda", &[(0x0C80, 0x0CFF)]),
+    (b"Mlym", &[(0x0D00, 0x0D7F)]),
+    (b"Thai", &[(0x0E00, 0x0E7F)]),
+    (b"Laoo", &[(0x0E80, 0x0EFF)]),
+    (b"Tibt", &[(0x0F00, 0x0FFF)]),
+    (b"Mymr", &[(0x1000, 0x109F)]),
+    (b"Geor", &[(0x10A0, 0x10FF)]),
+    (b"Hang", &[(0x1100, 0x11FF), (0xAC00, 0xD7AF)]),
+    (b"Ethi", &[(0x1200, 0x137F)]),
+    (b"Khmr", &[(0x1780, 0x17FF)]),
+    (b"Hira", &[(0x3040, 0x309F)]),
+    (b"Kana", &[(0x30A0, 0x30FF)]),
+    (b"Hani", &[(0x4E00, 0x9FFF)]),
+];
+
+/// Exemplar characters for each language.
+///
+/// For bicameral scripts, only the lowercase forms are listed.
+const ORTHOGRAPHIES: &[OrthographyEntry] = &[
+    ("en", b"Latn", "abcdefghijklmnopqrstuvwxyz"),
+    ("fr", b"Latn", "abcdefghijklmnopqrstuvwxyzàâæçéèêëîïôœùûüÿ"),
+    ("de", b"Latn", "abcdefghijklmnopqrstuvwxyzäößü"),
+    ("es", b"Latn", "abcdefghijklmnopqrstuvwxyzáéíñóúü"),
+    ("it", b"Latn", "abcdefghijklmnopqrstuvwxyzàéèìóòù"),
+    ("pt", b"Latn", "abcdefghijklmnopqrstuvwxyzáàâãçéêíóôõú"),
+    ("nl", b"Latn", "abcdefghijklmnopqrstuvwxyzáäéëíïóöúü"),
+    ("sv", b"Latn", "abcdefghijklmnopqrstuvwxyzåäöéà"),
+    ("da", b"Latn", "abcdefghijklmnopqrstuvwxyzæøå"),
+    ("nb", b"Latn", "abcdefghijklmnopqrstuvwxyzæøåéóòôà"),
+    ("fi", b"Latn", "abcdefghijklmnopqrstuvwxyzåäö"),
+    ("pl", b"Latn", "aąbcćdeęfghijklłmnńoóprsśtuwyzźż"),
+    ("cs", b"Latn", "aábcčdďeéěfghiíjklmnňoópqrřsštťuúůvwxyýzž"),
+    ("sk", b"Latn", "aáäbcčdďeéfghiíjklĺľmnňoóôpqrŕsštťuúvwxyýzž"),
+    ("hu", b"Latn", "aábcdeéfghiíjklmnoóöőpqrstuúüűvwxyz"),
+    ("ro", b"Latn", "aăâbcdefghiîjklmnopqrsștțuvwxyz"),
+    ("tr", b"Latn", "abcçdefgğhıiİjklmnoöprsştuüvyz"),
+    (
+        "vi",
+        b"Latn",
+        "aàảãáạăằẳẵắặâầẩẫấậbcdđeèẻẽéẹêềểễếệfghiìỉĩíịjklmnoòỏõóọôồổỗốộơờởỡớợpqrstuùủũúụưừửữứựvwxyỳỷỹýỵz",
+    ),
+    ("el", b"Grek", "αάβγδεέζηήθιίϊΐκλμνξοόπρσςτυύϋΰφχψωώ"),
+    ("ru", b"Cyrl", "абвгдеёжзийклмнопрстуфхцчшщъыьэюя"),
+    ("uk", b"Cyrl", "абвгґдеєжзиіїйклмнопрстуфхцчшщьюя"),
+    ("bg", b"Cyrl", "абвгдежзийклмнопрстуфхцчшщъьюя"),
+    ("sr-Cyrl", b"Cyrl", "абвгдђежзијклљмнњопрстћуфхцчџш"),
+    ("hy", b"Armn", "աբգդեզէըթժիլխծկհձղճմյնշոչպջռսվտրցւփքօֆ"),
+    ("ka", b"Geor", "აბგდევზთიკლმნოპჟრსტუფქღყშჩცძწჭხჯჰ"),
+    ("he", b"Hebr", "אבגדהוזחטיכךלמםנןסעפףצץקרשת"),
+    (
+        "ar",
+        b"Arab",
+        "ءآأؤإئابةتثجحخدذرزسشصضطظعغفقكلمنهوىي\u{64B}\u{64C}\u{64D}\u{64E}\u{64F}\u{650}\u{651}\u{652}",
+    ),
+    ("fa", b"Arab", "آاءأؤئبپتثجچحخدذرزژسشصضطظعغفقکگلمنوهی"),
+    (
+        "hi",
+        b"Deva",
+        "\u{901}\u{902}\u{903}अआइईउऊऋएऐऑओऔकखगघङचछजझञटठडढणतथदधनपफबभमयरलवशषसह\u{93C}ऽ\u{93E}\u{93F}\u{940}\u{941}\u{942}\u{943}\u{945}\u{947}\u{948}\u{949}\u{94B}\u{94C}\u{94D}",
+    ),
+    (
+        "bn",
+        b"Beng",
+        "\u{981}\u{982}\u{983}অআইঈউঊঋএঐওঔকখগঘঙচছজঝঞটঠডঢণতথদধনপফবভমযরলশষসহ\u{9BC}\u{9BE}\u{9BF}\u{9C0}\u{9C1}\u{9C2}\u{9C3}\u{9C7}\u{9C8}\u{9CB}\u{9CC}\u{9CD}ৎ",
+    ),
+    (
+        "ta",
+        b"Taml",
+        "அஆஇஈஉஊஎஏஐஒஓஔ\u{B83}கஙசஞடணதநபமயரலவழளறனஜஷஸஹ\u{BBE}\u{BBF}\u{BC0}\u{BC1}\u{BC2}\u{BC6}\u{BC7}\u{BC8}\u{BCA}\u{BCB}\u{BCC}\u{BCD}",
+    ),
+    (
+        "th",
+        b"Thai",
+        "กขฃคฅฆงจฉชซฌญฎฏฐฑฒณดตถทธนบปผฝพฟภมยรฤลฦวศษสหฬอฮฯะ\u{E31}าำ\u{E34}\u{E35}\u{E36}\u{E37}\u{E38}\u{E39}\u{E3A}เแโใไๅๆ\u{E47}\u{E48}\u{E49}\u{E4A}\u{E4B}\u{E4C}\u{E4D}",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider as _;
+    use read_fonts::FontRef;
+
+    fn coverage(chars: &str) -> Coverage {
+        Coverage::from_codepoints(chars.chars().map(u32::from).collect())
+    }
+
+    #[test]
+    fn orthographies() {
+        let coverage = coverage("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZäöüÄÖÜß");
+        let en = coverage.orthography("en").unwrap();
+        assert_eq!((en.covered, en.total), (52, 52));
+        assert!(en.is_supported());
+        // Uppercase eszett is not required
+        assert!(coverage.orthography("de").unwrap().is_supported());
+        let fr = coverage.orthography("fr").unwrap();
+        assert!(!fr.is_supported());
+        assert_eq!(fr.covered, 54);
+        assert!(coverage.orthography("xx").is_none());
+        let supported = coverage.supported_languages().collect::<Vec<_>>();
+        assert_eq!(supported, ["en", "de"]);
+    }
+
+    #[test]
+    fn scripts() {
+        let coverage = coverage("abcабв");
+        let scripts = coverage.scripts().collect::<Vec<_>>();
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].script, Tag::new(b"Latn"));
+        assert_eq!(scripts[0].covered, 3);
+        assert_eq!(scripts[1].script, Tag::new(b"Cyrl"));
+        assert_eq!(scripts[1].covered, 3);
+        assert_eq!(scripts[1].total, 0x130);
+        assert_eq!(coverage.script(Tag::new(b"Grek")).unwrap().covered, 0);
+    }
+
+    #[test]
+    fn font_coverage() {
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let coverage = font.coverage();
+        assert_eq!(coverage.codepoints(), &font.charmap().codepoints());
+        let latn = coverage.script(Tag::new(b"Latn")).unwrap();
+        // Maps 'A' and 'À'
+        assert_eq!(latn.covered, 2);
+        assert_eq!(coverage.supported_languages().count(), 0);
+    }
+}
@@ -7,7 +7,7 @@ use read_fonts::{
     ReadError,
 };
 
-use super::PHANTOM_POINT_COUNT;
+use crate::GLYF_PHANTOM_POINT_COUNT as PHANTOM_POINT_COUNT;
 
 pub type Delta = Point<Fixed>;
 
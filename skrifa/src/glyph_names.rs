@@ -0,0 +1,431 @@
+//! Support for glyph names.
+//!
+//! Names are read from the [post](https://learn.microsoft.com/en-us/typography/opentype/spec/post)
+//! table or from the charset of a [CFF](https://learn.microsoft.com/en-us/typography/opentype/spec/cff)
+//! table. Glyphs that are not named by the font are assigned synthesized
+//! names based on the character map.
+
+use core::{cell::OnceCell, fmt, ops::Deref};
+use std::borrow::Cow;
+
+use read_fonts::{
+    tables::{
+        cff::Cff,
+        post::{Post, DEFAULT_GLYPH_NAMES},
+        postscript::Charset,
+    },
+    types::{GlyphId, Version16Dot16},
+    FontData, TableProvider, TopLevelTable,
+};
+
+use crate::charmap::Charmap;
+
+/// Source of a glyph name.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GlyphNameSource {
+    /// The name was read from the font.
+    File,
+    /// The name was synthesized from the character map or glyph identifier.
+    Synthesized,
+}
+
+/// Name of a glyph.
+///
+/// This dereferences to `str`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GlyphName<'a> {
+    name: Cow<'a, str>,
+    source: GlyphNameSource,
+}
+
+impl<'a> GlyphName<'a> {
+    /// Returns the name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the source of the name.
+    pub fn source(&self) -> GlyphNameSource {
+        self.source
+    }
+
+    /// Returns true if the name was synthesized.
+    pub fn is_synthesized(&self) -> bool {
+        self.source == GlyphNameSource::Synthesized
+    }
+
+    /// Consumes the glyph name and returns the underlying string.
+    pub fn into_inner(self) -> Cow<'a, str> {
+        self.name
+    }
+}
+
+impl Deref for GlyphName<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.name
+    }
+}
+
+impl PartialEq<&str> for GlyphName<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.name == *other
+    }
+}
+
+impl fmt::Display for GlyphName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Mapping between glyph identifiers and names.
+///
+/// Names are selected from the following sources, in order:
+///
+/// 1. The `post` table, versions 1.0, 2.0 and 2.5.
+/// 2. The charset of the `CFF` table. CID-keyed fonts have no glyph names.
+/// 3. Synthesized names. These are `uniXXXX` (or `uXXXXX` for characters
+///    outside of the Basic Multilingual Plane) for glyphs that are mapped
+///    from a character and `glyphN` for all others, where `N` is the glyph
+///    identifier. Glyph 0 is always named `.notdef`.
+///
+/// The synthesized names are also used for individual glyphs that have
+/// no name, or an empty name, in the font.
+#[derive(Clone)]
+pub struct GlyphNames<'a> {
+    names: Names<'a>,
+    num_glyphs: u32,
+    charmap: Charmap<'a>,
+    /// Lowest mapped character for each glyph, sorted by glyph identifier.
+    ///
+    /// This is built lazily on the first request for a synthesized name.
+    codepoints: OnceCell<Vec<(GlyphId, u32)>>,
+    /// Names paired with glyph identifiers, sorted by name.
+    ///
+    /// This is built lazily on the first reverse lookup.
+    glyph_ids: OnceCell<Vec<(Cow<'a, str>, GlyphId)>>,
+}
+
+impl<'a> GlyphNames<'a> {
+    /// Creates a new glyph name mapping for the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        let num_glyphs = font
+            .maxp()
+            .map(|maxp| maxp.num_glyphs() as u32)
+            .unwrap_or_default();
+        Self::with_names(Names::new(font), num_glyphs, Charmap::new(font))
+    }
+
+    fn with_names(names: Names<'a>, num_glyphs: u32, charmap: Charmap<'a>) -> Self {
+        Self {
+            names,
+            num_glyphs,
+            charmap,
+            codepoints: OnceCell::new(),
+            glyph_ids: OnceCell::new(),
+        }
+    }
+
+    /// Returns the number of glyphs in the font.
+    pub fn num_glyphs(&self) -> u32 {
+        self.num_glyphs
+    }
+
+    /// Returns true if the font contains glyph names.
+    ///
+    /// When this is false, all names are synthesized.
+    pub fn has_names(&self) -> bool {
+        !matches!(self.names, Names::None)
+    }
+
+    /// Returns the name for the given glyph identifier.
+    ///
+    /// Returns `None` if the glyph identifier is out of range.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<GlyphName<'a>> {
+        if glyph_id.to_u16() as u32 >= self.num_glyphs {
+            return None;
+        }
+        if let Some(name) = self.names.get(glyph_id).filter(|name| !name.is_empty()) {
+            return Some(GlyphName {
+                name,
+                source: GlyphNameSource::File,
+            });
+        }
+        Some(GlyphName {
+            name: Cow::Owned(self.synthesize(glyph_id)),
+            source: GlyphNameSource::Synthesized,
+        })
+    }
+
+    /// Returns the glyph identifier for the given name.
+    ///
+    /// Synthesized names are also recognized for glyphs that are not
+    /// named by the font.
+    ///
+    /// If multiple glyphs share a name, the lowest glyph identifier is
+    /// returned.
+    ///
+    /// The first call builds an index of all names, after which lookups
+    /// are a binary search.
+    pub fn glyph_id(&self, name: &str) -> Option<GlyphId> {
+        let glyph_ids = self.glyph_ids.get_or_init(|| {
+            let mut glyph_ids = self
+                .iter()
+                .map(|(glyph_id, name)| (name.into_inner(), glyph_id))
+                .collect::<Vec<_>>();
+            glyph_ids.sort_unstable();
+            glyph_ids
+        });
+        let ix = glyph_ids.partition_point(|(glyph_name, _)| glyph_name.as_ref() < name);
+        glyph_ids
+            .get(ix)
+            .filter(|(glyph_name, _)| glyph_name == name)
+            .map(|(_, glyph_id)| *glyph_id)
+    }
+
+    /// Returns an iterator over all pairs of glyph identifiers and names.
+    pub fn iter(&self) -> impl Iterator<Item = (GlyphId, GlyphName<'a>)> + '_ + Clone {
+        (0..self.num_glyphs.min(u16::MAX as u32 + 1)).filter_map(|gid| {
+            let glyph_id = GlyphId::new(gid as u16);
+            Some((glyph_id, self.get(glyph_id)?))
+        })
+    }
+
+    fn synthesize(&self, glyph_id: GlyphId) -> String {
+        if glyph_id == GlyphId::NOTDEF {
+            return ".notdef".into();
+        }
+        let codepoints = self.codepoints.get_or_init(|| {
+            let mut codepoints = self
+                .charmap
+                .mappings()
+                .map(|(ch, glyph_id)| (glyph_id, ch))
+                .collect::<Vec<_>>();
+            codepoints.sort_unstable();
+            codepoints.dedup_by_key(|(glyph_id, _)| *glyph_id);
+            codepoints
+        });
+        match codepoints.binary_search_by_key(&glyph_id, |(glyph_id, _)| *glyph_id) {
+            Ok(ix) if codepoints[ix].1 <= 0xFFFF => format!("uni{:04X}", codepoints[ix].1),
+            Ok(ix) => format!("u{:05X}", codepoints[ix].1),
+            Err(_) => format!("glyph{}", glyph_id.to_u16()),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Names<'a> {
+    None,
+    Post(Post<'a>),
+    /// Version 2.5 tables are not supported by the `post` table parser so
+    /// we read the offsets directly from the raw data.
+    PostV2_5(FontData<'a>),
+    Cff(Cff<'a>, Charset<'a>),
+}
+
+impl<'a> Names<'a> {
+    fn new(font: &impl TableProvider<'a>) -> Self {
+        // The version is read from the raw header since the `post` table
+        // parser may accept a version 2.5 table without exposing its
+        // offsets.
+        if let Some(data) = font.data_for_tag(Post::TAG) {
+            match data.read_at::<Version16Dot16>(0).ok() {
+                Some(Version16Dot16::VERSION_2_5) => return Self::PostV2_5(data),
+                Some(Version16Dot16::VERSION_1_0 | Version16Dot16::VERSION_2_0) => {
+                    if let Ok(post) = font.post() {
+                        return Self::Post(post);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Ok(cff) = font.cff() {
+            if let Ok(charset) = cff.charset(0) {
+                if !charset.is_cid() {
+                    return Self::Cff(cff, charset);
+                }
+            }
+        }
+        Self::None
+    }
+
+    fn get(&self, glyph_id: GlyphId) -> Option<Cow<'a, str>> {
+        match self {
+            Self::None => None,
+            Self::Post(post) => post.glyph_name(glyph_id).map(Cow::Borrowed),
+            Self::PostV2_5(data) => post_v2_5_name(*data, glyph_id).map(Cow::Borrowed),
+            Self::Cff(cff, charset) => {
+                let name = cff.string(charset.string_id(glyph_id)?)?;
+                Some(Cow::Owned(name.chars().collect()))
+            }
+        }
+    }
+}
+
+/// Offset of the glyph count in a version 2.5 `post` table.
+const POST_V2_5_NUM_GLYPHS_OFFSET: usize = 32;
+
+/// Returns the name of the given glyph from a version 2.5 `post` table.
+///
+/// This version stores a signed offset for each glyph into the standard
+/// Macintosh ordering.
+fn post_v2_5_name(data: FontData, glyph_id: GlyphId) -> Option<&'static str> {
+    let num_glyphs = data.read_at::<u16>(POST_V2_5_NUM_GLYPHS_OFFSET).ok()?;
+    let gid = glyph_id.to_u16();
+    if gid >= num_glyphs {
+        return None;
+    }
+    let offset = data
+        .read_at::<i8>(POST_V2_5_NUM_GLYPHS_OFFSET + 2 + gid as usize)
+        .ok()?;
+    let ix = (gid as usize).checked_add_signed(offset as isize)?;
+    DEFAULT_GLYPH_NAMES.get(ix).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider as _;
+    use read_fonts::{types::Tag, FontRead, FontRef};
+
+    /// Builds a font file containing the given tables.
+    fn font_with_tables(tables: &[(Tag, &[u8])]) -> Vec<u8> {
+        let mut buf = vec![];
+        buf.extend(0x00010000u32.to_be_bytes());
+        buf.extend((tables.len() as u16).to_be_bytes());
+        // Search range, entry selector and range shift are unused.
+        buf.extend([0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, data) in tables {
+            buf.extend(tag.to_be_bytes());
+            buf.extend(0u32.to_be_bytes());
+            buf.extend((offset as u32).to_be_bytes());
+            buf.extend((data.len() as u32).to_be_bytes());
+            offset += (data.len() + 3) & !3;
+        }
+        for (_, data) in tables {
+            buf.extend(*data);
+            buf.resize((buf.len() + 3) & !3, 0);
+        }
+        buf
+    }
+
+    #[test]
+    fn post_names() {
+        let post = Post::read(font_test_data::post::SIMPLE.into()).unwrap();
+        let names = GlyphNames::with_names(Names::Post(post), 11, Charmap::default());
+        assert!(names.has_names());
+        let expected = [
+            ".notdef",
+            ".notdef",
+            "space",
+            "exclam",
+            "numbersign",
+            "dollar",
+            "percent",
+            "hello",
+            "hi",
+            "hola",
+        ];
+        for (i, expected) in expected.iter().enumerate() {
+            let name = names.get(GlyphId::new(i as u16)).unwrap();
+            assert_eq!(name, *expected);
+            assert_eq!(name.source(), GlyphNameSource::File);
+        }
+        // Glyph beyond the end of the post table
+        let name = names.get(GlyphId::new(10)).unwrap();
+        assert_eq!(name, "glyph10");
+        assert!(name.is_synthesized());
+        assert!(names.get(GlyphId::new(11)).is_none());
+        assert_eq!(names.glyph_id("hi"), Some(GlyphId::new(8)));
+        assert_eq!(names.glyph_id("glyph10"), Some(GlyphId::new(10)));
+        assert_eq!(names.glyph_id("hey"), None);
+        // Duplicate names resolve to the first glyph
+        assert_eq!(names.glyph_id(".notdef"), Some(GlyphId::NOTDEF));
+    }
+
+    #[test]
+    fn post_v2_5_names() {
+        let data = FontData::new(font_test_data::post::VERSION_2_5);
+        let names = GlyphNames::with_names(Names::PostV2_5(data), 4, Charmap::default());
+        assert_eq!(
+            names.iter().map(|(_, name)| name).collect::<Vec<_>>(),
+            [".notdef", "space", "A", "B"]
+        );
+        assert_eq!(post_v2_5_name(data, GlyphId::new(4)), None);
+        assert_eq!(names.glyph_id("A"), Some(GlyphId::new(2)));
+    }
+
+    #[test]
+    fn post_v2_5_names_from_font() {
+        // Version 0.5 maxp with 5 glyphs
+        let maxp = [0, 0, 0x50, 0, 0, 5];
+        let font_data = font_with_tables(&[
+            (Tag::new(b"maxp"), &maxp),
+            (Tag::new(b"post"), font_test_data::post::VERSION_2_5),
+        ]);
+        let font = FontRef::new(&font_data).unwrap();
+        let names = font.glyph_names();
+        assert!(names.has_names());
+        let names = names
+            .iter()
+            .map(|(_, name)| (name.to_string(), name.source()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                (".notdef".into(), GlyphNameSource::File),
+                ("space".into(), GlyphNameSource::File),
+                ("A".into(), GlyphNameSource::File),
+                ("B".into(), GlyphNameSource::File),
+                ("glyph4".into(), GlyphNameSource::Synthesized),
+            ]
+        );
+    }
+
+    #[test]
+    fn cff_names() {
+        let font = FontRef::new(font_test_data::NOTO_SERIF_DISPLAY_TRIMMED).unwrap();
+        let names = font.glyph_names();
+        assert!(names.has_names());
+        assert_eq!(
+            names
+                .iter()
+                .map(|(_, name)| {
+                    assert!(!name.is_synthesized());
+                    name.to_string()
+                })
+                .collect::<Vec<_>>(),
+            [".notdef", "i", "j", "k", "l"]
+        );
+        assert_eq!(names.glyph_id("k"), Some(GlyphId::new(3)));
+    }
+
+    #[test]
+    fn synthesized_names() {
+        let font = FontRef::new(font_test_data::TTHINT_SUBSET).unwrap();
+        let names = font.glyph_names();
+        assert!(!names.has_names());
+        assert_eq!(names.get(GlyphId::NOTDEF).unwrap(), ".notdef");
+        let charmap = font.charmap();
+        let mut mapped = 0;
+        for (glyph_id, name) in names.iter().skip(1) {
+            assert!(name.is_synthesized());
+            let ch = charmap
+                .mappings()
+                .filter(|(_, gid)| *gid == glyph_id)
+                .map(|(ch, _)| ch)
+                .min();
+            if let Some(ch) = ch {
+                assert_eq!(name.as_str(), format!("uni{ch:04X}"));
+                assert_eq!(names.glyph_id(&name), Some(glyph_id));
+                mapped += 1;
+            } else {
+                assert_eq!(name.as_str(), format!("glyph{}", glyph_id.to_u16()));
+            }
+        }
+        assert!(mapped > 0);
+    }
+}
@@ -0,0 +1,422 @@
+//! Pair kerning.
+//!
+//! This provides simple pairwise adjustments of glyph advances for
+//! clients that do not use a full shaping engine. Contextual kerning and
+//! other positioning features require a shaper.
+//!
+//! Kerning values are read from the pair adjustment lookups that are
+//! referenced by the `kern` feature of the
+//! [GPOS](https://learn.microsoft.com/en-us/typography/opentype/spec/gpos)
+//! table. If the font does not have any such lookups, the legacy
+//! [kern](https://learn.microsoft.com/en-us/typography/opentype/spec/kern)
+//! table is used instead.
+
+use read_fonts::{
+    tables::{
+        gpos::{
+            ExtensionSubtable, FeatureList, PairPos, PairPosFormat1, PairPosFormat2,
+            PositionLookup, PositionLookupList, ValueRecord,
+        },
+        kern::Kern,
+    },
+    types::{GlyphId, Tag},
+    TableProvider,
+};
+
+use crate::{
+    instance::{LocationRef, Size},
+    layout_value::LayoutValues,
+};
+
+/// Tag for the kerning feature.
+const KERN_FEATURE: Tag = Tag::new(b"kern");
+
+/// Source of kerning adjustments.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum KerningSource {
+    /// Pair adjustment lookups in the `GPOS` table.
+    Gpos,
+    /// The legacy `kern` table.
+    Kern,
+}
+
+/// Pairwise kerning adjustments for a specific size and location in
+/// variation space.
+#[derive(Clone)]
+pub struct Kerning<'a> {
+    gpos: Option<GposKerning<'a>>,
+    kern: Option<Kern<'a>>,
+    values: LayoutValues<'a>,
+}
+
+impl<'a> Kerning<'a> {
+    /// Creates new kerning adjustments from the given font, size and
+    /// location in normalized variation space.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: Size,
+        location: impl Into<LocationRef<'a>>,
+    ) -> Self {
+        let upem = font
+            .head()
+            .map(|head| head.units_per_em())
+            .unwrap_or_default();
+        let gpos = font.gpos().ok().and_then(|gpos| {
+            GposKerning::new(gpos.lookup_list().ok()?, &gpos.feature_list().ok()?)
+        });
+        // Variation indices in the GPOS table refer to the item variation
+        // store in GDEF
+        let var_store = font
+            .gdef()
+            .ok()
+            .and_then(|gdef| gdef.item_var_store()?.ok());
+        let kern = if gpos.is_none() {
+            font.kern().ok()
+        } else {
+            None
+        };
+        Self {
+            gpos,
+            kern,
+            values: LayoutValues::new(var_store, size, upem, location.into()),
+        }
+    }
+
+    /// Returns the source of the kerning adjustments or `None` if the font
+    /// does not support kerning.
+    pub fn source(&self) -> Option<KerningSource> {
+        if self.gpos.is_some() {
+            Some(KerningSource::Gpos)
+        } else if self.kern.is_some() {
+            Some(KerningSource::Kern)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the adjustment to the advance of the `left` glyph when it is
+    /// followed by the `right` glyph.
+    ///
+    /// Negative values move the glyphs closer together. Returns `None` if
+    /// the font does not define kerning for the pair.
+    pub fn kern(&self, left: GlyphId, right: GlyphId) -> Option<f32> {
+        if let Some(gpos) = &self.gpos {
+            gpos.kern(left, right, self)
+        } else {
+            Some(kern_table_value(self.kern.as_ref()?, left, right)? as f32 * self.values.scale)
+        }
+    }
+}
+
+/// Returns the accumulated adjustment, in font units, from the horizontal
+/// pair kerning subtables of the legacy `kern` table.
+///
+/// Returns `None` if no subtable contains the pair.
+fn kern_table_value(kern: &Kern, left: GlyphId, right: GlyphId) -> Option<i32> {
+    let mut result = None;
+    for subtable in kern.subtables() {
+        let Ok(subtable) = subtable else {
+            break;
+        };
+        if !subtable.is_horizontal()
+            || subtable.is_cross_stream()
+            || subtable.is_minimum()
+            || subtable.is_variable()
+        {
+            continue;
+        }
+        let Some(value) = subtable
+            .kind()
+            .ok()
+            .and_then(|kind| kind.kerning(left, right))
+        else {
+            continue;
+        };
+        result = Some(if subtable.is_override() {
+            value
+        } else {
+            result.unwrap_or(0) + value
+        });
+    }
+    result
+}
+
+/// Pair adjustment lookups referenced by the `kern` feature.
+#[derive(Clone)]
+struct GposKerning<'a> {
+    lookup_list: PositionLookupList<'a>,
+    lookup_indices: Vec<u16>,
+}
+
+impl<'a> GposKerning<'a> {
+    fn new(lookup_list: PositionLookupList<'a>, feature_list: &FeatureList<'a>) -> Option<Self> {
+        let mut lookup_indices = feature_list
+            .feature_records()
+            .iter()
+            .filter(|record| record.feature_tag() == KERN_FEATURE)
+            .filter_map(|record| record.feature(feature_list.offset_data()).ok())
+            .flat_map(|feature| feature.lookup_list_indices().iter().map(|ix| ix.get()))
+            .collect::<Vec<_>>();
+        lookup_indices.sort_unstable();
+        lookup_indices.dedup();
+        (!lookup_indices.is_empty()).then_some(Self {
+            lookup_list,
+            lookup_indices,
+        })
+    }
+
+    /// Sums the adjustments of all lookups that contain the pair.
+    ///
+    /// Within a single lookup, only the first subtable that contains the
+    /// pair is applied.
+    fn kern(&self, left: GlyphId, right: GlyphId, kerning: &Kerning) -> Option<f32> {
+        let mut result = None;
+        let lookups = self.lookup_list.lookups();
+        for lookup_ix in &self.lookup_indices {
+            let Ok(lookup) = lookups.get(*lookup_ix as usize) else {
+                continue;
+            };
+            let value = match lookup {
+                PositionLookup::Pair(lookup) => lookup
+                    .subtables()
+                    .iter()
+                    .filter_map(|subtable| subtable.ok())
+                    .find_map(|subtable| self.pair_value(&subtable, left, right, kerning)),
+                PositionLookup::Extension(lookup) => lookup
+                    .subtables()
+                    .iter()
+                    .filter_map(|subtable| match subtable.ok()? {
+                        ExtensionSubtable::Pair(extension) => extension.extension().ok(),
+                        _ => None,
+                    })
+                    .find_map(|subtable| self.pair_value(&subtable, left, right, kerning)),
+                _ => None,
+            };
+            if let Some(value) = value {
+                result = Some(result.unwrap_or(0.0) + value);
+            }
+        }
+        result
+    }
+
+    fn pair_value(
+        &self,
+        subtable: &PairPos<'a>,
+        left: GlyphId,
+        right: GlyphId,
+        kerning: &Kerning,
+    ) -> Option<f32> {
+        let (record, data) = match subtable {
+            PairPos::Format1(subtable) => {
+                (pair_format1(subtable, left, right)?, subtable.offset_data())
+            }
+            PairPos::Format2(subtable) => {
+                (pair_format2(subtable, left, right)?, subtable.offset_data())
+            }
+        };
+        let value = record.x_advance().unwrap_or_default() as i32;
+        Some(kerning.values.resolve(value, record.x_advance_device(data)))
+    }
+}
+
+/// Returns the value record for the first glyph of the pair from the sorted
+/// pair set associated with the left glyph.
+fn pair_format1(subtable: &PairPosFormat1, left: GlyphId, right: GlyphId) -> Option<ValueRecord> {
+    let coverage_ix = subtable.coverage().ok()?.get(left)?;
+    let pair_set = subtable.pair_sets().get(coverage_ix as usize).ok()?;
+    let records = pair_set.pair_value_records();
+    let (mut lo, mut hi) = (0, records.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let record = records.get(mid).ok()?;
+        match record.second_glyph().cmp(&right) {
+            core::cmp::Ordering::Less => lo = mid + 1,
+            core::cmp::Ordering::Greater => hi = mid,
+            core::cmp::Ordering::Equal => return Some(record.value_record1),
+        }
+    }
+    None
+}
+
+/// Returns the value record for the first glyph of the pair from the class
+/// based adjustment matrix.
+fn pair_format2(subtable: &PairPosFormat2, left: GlyphId, right: GlyphId) -> Option<ValueRecord> {
+    subtable.coverage().ok()?.get(left)?;
+    let class1 = subtable.class_def1().ok()?.get(left);
+    let class2 = subtable.class_def2().ok()?.get(right);
+    let class1_record = subtable.class1_records().get(class1 as usize).ok()?;
+    let class2_record = class1_record.class2_records().get(class2 as usize).ok()?;
+    Some(class2_record.value_record1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instance::NormalizedCoord, MetadataProvider as _};
+    use read_fonts::{
+        tables::{gdef::Gdef, gpos::Gpos},
+        types::F2Dot14,
+        FontData, FontRead, FontRef,
+    };
+
+    #[rustfmt::skip]
+    static GPOS: &[u8] = &[
+        // version, script list, feature list, lookup list
+        0, 1, 0, 0, 0, 10, 0, 12, 0, 28,
+        // script list
+        0, 0,
+        // feature list with a single kern feature
+        0, 1, b'k', b'e', b'r', b'n', 0, 8,
+        0, 0, 0, 2, 0, 0, 0, 1,
+        // lookup list
+        0, 2, 0, 6, 0, 60,
+        // lookup 0: pair adjustment
+        0, 2, 0, 0, 0, 1, 0, 8,
+        // pair adjustment format 1 with x advance and device
+        0, 1, 0, 26, 0, 0x44, 0, 0, 0, 1, 0, 12,
+        // pair set: 5 -> 6 (device), 5 -> 8 (variation index)
+        0, 2, 0, 6, 0xFF, 0xCE, 0, 32, 0, 8, 0xFF, 0xEC, 0, 40,
+        // coverage
+        0, 1, 0, 1, 0, 5,
+        // device: sizes 12..=13, 4-bit deltas +2, -1
+        0, 12, 0, 13, 0, 2, 0x2F, 0,
+        // variation index
+        0, 0, 0, 0, 0x80, 0,
+        // lookup 1: extension
+        0, 9, 0, 0, 0, 1, 0, 8,
+        0, 1, 0, 2, 0, 0, 0, 8,
+        // pair adjustment format 2
+        0, 2, 0, 24, 0, 4, 0, 0, 0, 32, 0, 40, 0, 2, 0, 2,
+        0, 0, 0xFF, 0xE2, 0, 0, 0xFF, 0xD8,
+        // coverage
+        0, 1, 0, 2, 0, 7, 0, 9,
+        // class def 1: glyph 9 -> 1
+        0, 1, 0, 9, 0, 1, 0, 1,
+        // class def 2 (format 2): glyphs 10..=12 -> 1
+        0, 2, 0, 1, 0, 10, 0, 12, 0, 1,
+    ];
+
+    #[rustfmt::skip]
+    static GDEF: &[u8] = &[
+        // version 1.3 with only an item variation store
+        0, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 18,
+        // item variation store
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22,
+        // region list: single axis, peak at 1.0
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0,
+        // item variation data with a single delta of -10
+        0, 1, 0, 0, 0, 1, 0, 0, 0xF6,
+    ];
+
+    fn kerning(ppem: Option<u16>, coords: &[NormalizedCoord]) -> Kerning<'_> {
+        let gpos = Gpos::read(FontData::new(GPOS)).unwrap();
+        let gdef = Gdef::read(FontData::new(GDEF)).unwrap();
+        let gpos = GposKerning::new(gpos.lookup_list().unwrap(), &gpos.feature_list().unwrap());
+        Kerning {
+            gpos,
+            kern: None,
+            values: LayoutValues {
+                var_store: gdef.item_var_store().transpose().unwrap(),
+                scale: 0.5,
+                ppem,
+                coords,
+            },
+        }
+    }
+
+    #[test]
+    fn gpos_pairs() {
+        let kerning = kerning(None, &[]);
+        assert_eq!(kerning.source(), Some(KerningSource::Gpos));
+        let gid = GlyphId::new;
+        assert_eq!(kerning.kern(gid(5), gid(6)), Some(-25.0));
+        assert_eq!(kerning.kern(gid(5), gid(8)), Some(-10.0));
+        assert_eq!(kerning.kern(gid(7), gid(10)), Some(-15.0));
+        assert_eq!(kerning.kern(gid(9), gid(10)), Some(-20.0));
+        // Glyphs within a class range
+        assert_eq!(kerning.kern(gid(9), gid(11)), Some(-20.0));
+        assert_eq!(kerning.kern(gid(7), gid(12)), Some(-15.0));
+        // Class 0 of the second glyph
+        assert_eq!(kerning.kern(gid(9), gid(13)), Some(0.0));
+        assert_eq!(kerning.kern(gid(5), gid(7)), None);
+        assert_eq!(kerning.kern(gid(6), gid(5)), None);
+    }
+
+    #[test]
+    fn gpos_device_deltas() {
+        let gid = GlyphId::new;
+        assert_eq!(kerning(Some(12), &[]).kern(gid(5), gid(6)), Some(-23.0));
+        assert_eq!(kerning(Some(13), &[]).kern(gid(5), gid(6)), Some(-26.0));
+        assert_eq!(kerning(Some(14), &[]).kern(gid(5), gid(6)), Some(-25.0));
+    }
+
+    #[test]
+    fn gpos_variation_deltas() {
+        let gid = GlyphId::new;
+        let coords = [F2Dot14::from_f32(1.0)];
+        assert_eq!(kerning(None, &coords).kern(gid(5), gid(8)), Some(-15.0));
+        let coords = [F2Dot14::from_f32(0.5)];
+        assert_eq!(kerning(None, &coords).kern(gid(5), gid(8)), Some(-12.5));
+    }
+
+    #[test]
+    fn kern_table_skips_cross_stream_and_accumulates() {
+        #[rustfmt::skip]
+        let data = [
+            // version, nTables
+            0, 0, 0, 4,
+            // horizontal
+            0, 0, 0, 20, 0, 1,
+            0, 1, 0, 6, 0, 0, 0, 0,
+            0, 1, 0, 2, 0, 10,
+            // cross stream
+            0, 0, 0, 20, 0, 5,
+            0, 1, 0, 6, 0, 0, 0, 0,
+            0, 1, 0, 2, 0, 20,
+            // horizontal
+            0, 0, 0, 20, 0, 1,
+            0, 1, 0, 6, 0, 0, 0, 0,
+            0, 1, 0, 2, 0, 30,
+            // horizontal, format 3
+            0, 0, 0, 26, 3, 1,
+            0, 3, 2, 2, 2, 0,
+            0, 0, 0xFF, 0xF6,
+            0, 1, 0, 0, 0, 1,
+            0, 0, 1, 0,
+        ];
+        let kerning = Kerning {
+            gpos: None,
+            kern: Kern::read(FontData::new(&data)).ok(),
+            values: LayoutValues {
+                var_store: None,
+                scale: 0.5,
+                ppem: None,
+                coords: &[],
+            },
+        };
+        assert_eq!(kerning.source(), Some(KerningSource::Kern));
+        let gid = GlyphId::new;
+        // Sum of the two horizontal format 0 subtables and a zero value
+        // from the format 3 subtable
+        assert_eq!(kerning.kern(gid(1), gid(2)), Some(20.0));
+        assert_eq!(kerning.kern(gid(1), gid(1)), Some(-5.0));
+        assert_eq!(kerning.kern(gid(0), gid(0)), Some(0.0));
+        // Glyph outside of the format 3 class arrays
+        assert_eq!(kerning.kern(gid(3), gid(1)), None);
+    }
+
+    #[test]
+    fn font_kerning() {
+        let font = FontRef::new(font_test_data::NOTO_SERIF_DISPLAY_TRIMMED).unwrap();
+        let kerning = font.kerning(Size::new(16.0), LocationRef::default());
+        assert_eq!(kerning.source(), Some(KerningSource::Gpos));
+        let gid = GlyphId::new;
+        // k followed by i; the values in this font are all zero
+        assert_eq!(kerning.kern(gid(3), gid(1)), Some(0.0));
+        assert_eq!(kerning.kern(gid(1), gid(3)), None);
+        let font = FontRef::new(font_test_data::TTHINT_SUBSET).unwrap();
+        let kerning = font.kerning(Size::new(16.0), LocationRef::default());
+        assert_eq!(kerning.source(), None);
+        assert_eq!(kerning.kern(gid(1), gid(2)), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Resolution of values from layout tables.
+//!
+//! Values in the BASE, GDEF, GPOS and MATH tables may have an associated
+//! device table or variation index. The former provides hinting adjustments
+//! in pixels for specific sizes while the latter selects deltas from an
+//! item variation store.
+
+use read_fonts::{
+    tables::{
+        layout::{DeltaFormat, Device, DeviceOrVariationIndex},
+        variations::{DeltaSetIndex, ItemVariationStore},
+    },
+    ReadError,
+};
+
+use crate::instance::{LocationRef, NormalizedCoord, Size};
+
+/// State for computing scaled layout values at a specific size and location
+/// in variation space.
+#[derive(Clone)]
+pub(crate) struct LayoutValues<'a> {
+    pub var_store: Option<ItemVariationStore<'a>>,
+    pub scale: f32,
+    pub ppem: Option<u16>,
+    pub coords: &'a [NormalizedCoord],
+}
+
+impl<'a> LayoutValues<'a> {
+    pub fn new(
+        var_store: Option<ItemVariationStore<'a>>,
+        size: Size,
+        upem: u16,
+        location: LocationRef<'a>,
+    ) -> Self {
+        Self {
+            var_store,
+            scale: size.linear_scale(upem),
+            ppem: size.ppem().map(|ppem| ppem.round() as u16),
+            coords: location.coords(),
+        }
+    }
+
+    /// Computes the scaled value, including variation and device deltas.
+    ///
+    /// Variation deltas are applied in font units before scaling while
+    /// device adjustments are added in pixels afterward. Device tables are
+    /// ignored when the size does not have a pixels per em value.
+    pub fn resolve(
+        &self,
+        value: i32,
+        device: Option<Result<DeviceOrVariationIndex, ReadError>>,
+    ) -> f32 {
+        let mut value = value;
+        let mut device_pixels = 0;
+        match device {
+            Some(Ok(DeviceOrVariationIndex::VariationIndex(var_index))) => {
+                if let Some(var_store) = &self.var_store {
+                    let index = DeltaSetIndex {
+                        outer: var_index.delta_set_outer_index(),
+                        inner: var_index.delta_set_inner_index(),
+                    };
+                    value += var_store
+                        .compute_delta(index, self.coords)
+                        .unwrap_or_default();
+                }
+            }
+            Some(Ok(DeviceOrVariationIndex::Device(device))) => {
+                if let Some(ppem) = self.ppem {
+                    device_pixels = device_delta(&device, ppem);
+                }
+            }
+            _ => {}
+        }
+        value as f32 * self.scale + device_pixels as f32
+    }
+}
+
+/// Returns the adjustment, in pixels, from a device table for the given
+/// size.
+fn device_delta(device: &Device, ppem: u16) -> i32 {
+    let start_size = device.start_size();
+    if ppem < start_size || ppem > device.end_size() {
+        return 0;
+    }
+    let bits = match device.delta_format() {
+        DeltaFormat::Local2BitDeltas => 2,
+        DeltaFormat::Local4BitDeltas => 4,
+        DeltaFormat::Local8BitDeltas => 8,
+        _ => return 0,
+    };
+    let ix = (ppem - start_size) as usize;
+    let per_word = 16 / bits;
+    let Some(word) = device.delta_value().get(ix / per_word) else {
+        return 0;
+    };
+    let shift = 16 - bits * (ix % per_word + 1);
+    let raw = ((word.get() >> shift) as i32) & ((1 << bits) - 1);
+    // Sign extend
+    (raw << (32 - bits)) >> (32 - bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_fonts::{FontData, FontRead};
+
+    #[rustfmt::skip]
+    static DEVICE: &[u8] = &[
+        // sizes 10..=13, 2-bit deltas 1, -1, 0, -2
+        0, 10, 0, 13, 0, 1, 0b0111_0010, 0,
+    ];
+
+    #[rustfmt::skip]
+    static VAR_STORE: &[u8] = &[
+        // item variation store
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22,
+        // region list: single axis, peak at 1.0
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0,
+        // item variation data with a single delta of -10
+        0, 1, 0, 0, 0, 1, 0, 0, 0xF6,
+    ];
+
+    fn values(ppem: Option<u16>, coords: &[NormalizedCoord]) -> LayoutValues<'_> {
+        LayoutValues {
+            var_store: ItemVariationStore::read(FontData::new(VAR_STORE)).ok(),
+            scale: 0.5,
+            ppem,
+            coords,
+        }
+    }
+
+    #[test]
+    fn device_deltas() {
+        let device = Device::read(FontData::new(DEVICE)).unwrap();
+        let deltas = (9..=14)
+            .map(|ppem| device_delta(&device, ppem))
+            .collect::<Vec<_>>();
+        assert_eq!(deltas, [0, 1, -1, 0, -2, 0]);
+    }
+
+    #[test]
+    fn resolve_device() {
+        let device = || Some(DeviceOrVariationIndex::read(FontData::new(DEVICE)));
+        assert_eq!(values(None, &[]).resolve(100, device()), 50.0);
+        assert_eq!(values(Some(10), &[]).resolve(100, device()), 51.0);
+        assert_eq!(values(Some(13), &[]).resolve(100, device()), 48.0);
+        assert_eq!(values(Some(14), &[]).resolve(100, device()), 50.0);
+    }
+
+    #[test]
+    fn resolve_variation_index() {
+        // delta set outer index 0, inner index 0, format 0x8000
+        let data = [0, 0, 0, 0, 0x80, 0];
+        let index = || Some(DeviceOrVariationIndex::read(FontData::new(&data)));
+        let coords = [NormalizedCoord::from_f32(1.0)];
+        assert_eq!(values(Some(10), &[]).resolve(100, index()), 50.0);
+        assert_eq!(values(None, &coords).resolve(100, index()), 45.0);
+        let coords = [NormalizedCoord::from_f32(0.5)];
+        assert_eq!(values(None, &coords).resolve(100, index()), 47.5);
+        assert_eq!(values(None, &coords).resolve(100, None), 50.0);
+    }
+}
@@ -15,15 +15,24 @@
 pub extern crate read_fonts as raw;
 
 pub mod attribute;
+pub mod bitmap;
 pub mod charmap;
+pub mod color;
+pub mod coverage;
 pub mod font;
+pub mod glyph_names;
 pub mod instance;
+pub mod kerning;
+pub mod math;
 pub mod metrics;
 #[cfg(feature = "scale")]
 pub mod scale;
 pub mod setting;
 pub mod string;
+pub mod style_name;
 
+mod glyf_deltas;
+mod layout_value;
 mod provider;
 mod small_array;
 mod variation;
@@ -47,3 +56,7 @@ pub use provider::MetadataProvider;
 
 /// Limit for recursion when loading TrueType composite glyphs.
 const GLYF_COMPOSITE_RECURSION_LIMIT: usize = 32;
+
+/// Number of phantom points appended to TrueType outlines to carry
+/// variations of the glyph metrics.
+const GLYF_PHANTOM_POINT_COUNT: usize = 4;
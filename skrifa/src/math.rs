@@ -0,0 +1,760 @@
+//! Mathematical typesetting.
+//!
+//! This provides the font specific data needed to lay out mathematical
+//! formulas: global layout constants, per glyph italics correction, accent
+//! attachment and kerning, along with size variants and assemblies for
+//! glyphs that can stretch to cover a requested size.
+//!
+//! All values are read from the
+//! [MATH](https://learn.microsoft.com/en-us/typography/opentype/spec/math)
+//! table and are scaled for a specific size and location in variation
+//! space.
+
+use read_fonts::{
+    tables::math::{self, MathGlyphConstruction, MathGlyphInfo, MathValueRecord},
+    types::GlyphId,
+    FontData, TableProvider,
+};
+
+use crate::{
+    instance::{LocationRef, Size},
+    layout_value::LayoutValues,
+};
+
+/// Upper limit on the number of parts in a glyph assembly.
+const MAX_ASSEMBLY_PARTS: usize = 1024;
+
+/// Global constants for mathematical layout.
+///
+/// With the exception of the percentages, all constants are distances that
+/// are scaled to the requested size.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MathConstant {
+    /// Percentage of scaling down for level 1 superscripts and subscripts.
+    ScriptPercentScaleDown,
+    /// Percentage of scaling down for level 2 superscripts and subscripts.
+    ScriptScriptPercentScaleDown,
+    /// Minimum height required for a delimited expression to be treated as
+    /// a sub-formula.
+    DelimitedSubFormulaMinHeight,
+    /// Minimum height of n-ary operators for formulas in display mode.
+    DisplayOperatorMinHeight,
+    /// White space to be left between math formulas to ensure proper line
+    /// spacing.
+    MathLeading,
+    /// Axis height of the font.
+    AxisHeight,
+    /// Maximum height of accent base that does not require raising the
+    /// accents.
+    AccentBaseHeight,
+    /// Maximum height of accent base that does not require flattening the
+    /// accents.
+    FlattenedAccentBaseHeight,
+    /// Standard shift down applied to subscript elements.
+    SubscriptShiftDown,
+    /// Maximum allowed height of the top of subscripts that does not
+    /// require moving subscripts further down.
+    SubscriptTopMax,
+    /// Minimum allowed drop of the baseline of subscripts relative to the
+    /// bottom of the base.
+    SubscriptBaselineDropMin,
+    /// Standard shift up applied to superscript elements.
+    SuperscriptShiftUp,
+    /// Standard shift of superscripts relative to the base, in cramped
+    /// style.
+    SuperscriptShiftUpCramped,
+    /// Minimum allowed height of the bottom of superscripts that does not
+    /// require moving superscripts further up.
+    SuperscriptBottomMin,
+    /// Maximum allowed drop of the baseline of superscripts relative to the
+    /// top of the base.
+    SuperscriptBaselineDropMax,
+    /// Minimum gap between the superscript and subscript ink.
+    SubSuperscriptGapMin,
+    /// Maximum level to which the bottom of superscript can be pushed to
+    /// increase the gap between superscript and subscript.
+    SuperscriptBottomMaxWithSubscript,
+    /// Extra white space to be added after each subscript and superscript.
+    SpaceAfterScript,
+    /// Minimum gap between the bottom of the upper limit and the top of the
+    /// base operator.
+    UpperLimitGapMin,
+    /// Minimum distance between baseline of upper limit and top of the base
+    /// operator.
+    UpperLimitBaselineRiseMin,
+    /// Minimum gap between top of the lower limit and bottom of the base
+    /// operator.
+    LowerLimitGapMin,
+    /// Minimum distance between baseline of the lower limit and bottom of
+    /// the base operator.
+    LowerLimitBaselineDropMin,
+    /// Standard shift up applied to the top element of a stack.
+    StackTopShiftUp,
+    /// Standard shift up applied to the top element of a stack in display
+    /// style.
+    StackTopDisplayStyleShiftUp,
+    /// Standard shift down applied to the bottom element of a stack.
+    StackBottomShiftDown,
+    /// Standard shift down applied to the bottom element of a stack in
+    /// display style.
+    StackBottomDisplayStyleShiftDown,
+    /// Minimum gap between bottom of the top element of a stack and the top
+    /// of the bottom element.
+    StackGapMin,
+    /// Minimum gap between bottom of the top element of a stack and the top
+    /// of the bottom element in display style.
+    StackDisplayStyleGapMin,
+    /// Standard shift up applied to the top element of the stretch stack.
+    StretchStackTopShiftUp,
+    /// Standard shift down applied to the bottom element of the stretch
+    /// stack.
+    StretchStackBottomShiftDown,
+    /// Minimum gap between the stretched element and the bottom of the
+    /// element above.
+    StretchStackGapAboveMin,
+    /// Minimum gap between the stretched element and the top of the element
+    /// below.
+    StretchStackGapBelowMin,
+    /// Standard shift up applied to the numerator.
+    FractionNumeratorShiftUp,
+    /// Standard shift up applied to the numerator in display style.
+    FractionNumeratorDisplayStyleShiftUp,
+    /// Standard shift down applied to the denominator.
+    FractionDenominatorShiftDown,
+    /// Standard shift down applied to the denominator in display style.
+    FractionDenominatorDisplayStyleShiftDown,
+    /// Minimum gap between the bottom of the numerator and the fraction
+    /// bar.
+    FractionNumeratorGapMin,
+    /// Minimum gap between the bottom of the numerator and the fraction bar
+    /// in display style.
+    FractionNumDisplayStyleGapMin,
+    /// Thickness of the fraction bar.
+    FractionRuleThickness,
+    /// Minimum gap between the top of the denominator and the fraction bar.
+    FractionDenominatorGapMin,
+    /// Minimum gap between the top of the denominator and the fraction bar
+    /// in display style.
+    FractionDenomDisplayStyleGapMin,
+    /// Horizontal distance between the top and bottom elements of a skewed
+    /// fraction.
+    SkewedFractionHorizontalGap,
+    /// Vertical distance between the top and bottom elements of a skewed
+    /// fraction.
+    SkewedFractionVerticalGap,
+    /// Distance between the overbar and the top of the base.
+    OverbarVerticalGap,
+    /// Thickness of the overbar.
+    OverbarRuleThickness,
+    /// Extra white space reserved above the overbar.
+    OverbarExtraAscender,
+    /// Distance between the underbar and the bottom of the base.
+    UnderbarVerticalGap,
+    /// Thickness of the underbar.
+    UnderbarRuleThickness,
+    /// Extra white space reserved below the underbar.
+    UnderbarExtraDescender,
+    /// Space between the top of the expression and the bar over it.
+    RadicalVerticalGap,
+    /// Space between the top of the expression and the bar over it in
+    /// display style.
+    RadicalDisplayStyleVerticalGap,
+    /// Thickness of the radical rule.
+    RadicalRuleThickness,
+    /// Extra white space reserved above the radical.
+    RadicalExtraAscender,
+    /// Extra horizontal kern before the degree of a radical.
+    RadicalKernBeforeDegree,
+    /// Negative kern after the degree of a radical.
+    RadicalKernAfterDegree,
+    /// Height of the bottom of the radical degree, as a percentage of the
+    /// height of the radical sign.
+    RadicalDegreeBottomRaisePercent,
+}
+
+/// Direction in which a glyph is stretched.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum StretchDirection {
+    /// Growing along the vertical axis.
+    Vertical,
+    /// Growing along the horizontal axis.
+    Horizontal,
+}
+
+/// Corner of a glyph for math kerning.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MathKernCorner {
+    /// Top right corner, used for superscripts.
+    TopRight,
+    /// Top left corner, used for pre-superscripts.
+    TopLeft,
+    /// Bottom right corner, used for subscripts.
+    BottomRight,
+    /// Bottom left corner, used for pre-subscripts.
+    BottomLeft,
+}
+
+/// Pre-designed size variant of a glyph.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GlyphVariant {
+    /// Identifier of the variant glyph.
+    pub glyph_id: GlyphId,
+    /// Advance of the variant in the direction of stretching.
+    pub advance: f32,
+}
+
+/// Single glyph in an assembly.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AssemblyPart {
+    /// Identifier of the glyph.
+    pub glyph_id: GlyphId,
+    /// Position of the part along the direction of stretching, relative to
+    /// the start of the assembly.
+    pub offset: f32,
+    /// Full advance of the part in the direction of stretching.
+    pub advance: f32,
+}
+
+/// Glyph constructed from a sequence of parts to cover a requested size.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct GlyphAssembly {
+    /// Parts from left to right for horizontal assemblies or from bottom to
+    /// top for vertical assemblies.
+    pub parts: Vec<AssemblyPart>,
+    /// Total size of the assembly in the direction of stretching.
+    pub size: f32,
+    /// Italics correction of the assembly.
+    pub italics_correction: f32,
+}
+
+/// Mathematical layout data for a specific size and location in variation
+/// space.
+#[derive(Clone)]
+pub struct MathLayout<'a> {
+    math: Option<math::Math<'a>>,
+    values: LayoutValues<'a>,
+}
+
+impl<'a> MathLayout<'a> {
+    /// Creates new mathematical layout data from the given font, size and
+    /// location in normalized variation space.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: Size,
+        location: impl Into<LocationRef<'a>>,
+    ) -> Self {
+        let upem = font
+            .head()
+            .map(|head| head.units_per_em())
+            .unwrap_or_default();
+        // Variation indices in the MATH table refer to the item variation
+        // store in GDEF
+        let var_store = font
+            .gdef()
+            .ok()
+            .and_then(|gdef| gdef.item_var_store()?.ok());
+        Self {
+            math: font.math().ok(),
+            values: LayoutValues::new(var_store, size, upem, location.into()),
+        }
+    }
+
+    /// Returns true if the font contains mathematical layout data.
+    pub fn is_present(&self) -> bool {
+        self.math.is_some()
+    }
+
+    /// Returns the value of the given layout constant.
+    ///
+    /// Percentages are returned as is while all other values are scaled
+    /// distances. Returns `None` if the font does not contain the
+    /// constants table.
+    pub fn constant(&self, constant: MathConstant) -> Option<f32> {
+        use MathConstant::*;
+        let constants = self.math.as_ref()?.math_constants().ok()?;
+        let record = match constant {
+            ScriptPercentScaleDown => return Some(constants.script_percent_scale_down() as f32),
+            ScriptScriptPercentScaleDown => {
+                return Some(constants.script_script_percent_scale_down() as f32)
+            }
+            RadicalDegreeBottomRaisePercent => {
+                return Some(constants.radical_degree_bottom_raise_percent() as f32)
+            }
+            DelimitedSubFormulaMinHeight => {
+                let value = constants.delimited_sub_formula_min_height().to_u16();
+                return Some(value as f32 * self.values.scale);
+            }
+            DisplayOperatorMinHeight => {
+                let value = constants.display_operator_min_height().to_u16();
+                return Some(value as f32 * self.values.scale);
+            }
+            MathLeading => constants.math_leading(),
+            AxisHeight => constants.axis_height(),
+            AccentBaseHeight => constants.accent_base_height(),
+            FlattenedAccentBaseHeight => constants.flattened_accent_base_height(),
+            SubscriptShiftDown => constants.subscript_shift_down(),
+            SubscriptTopMax => constants.subscript_top_max(),
+            SubscriptBaselineDropMin => constants.subscript_baseline_drop_min(),
+            SuperscriptShiftUp => constants.superscript_shift_up(),
+            SuperscriptShiftUpCramped => constants.superscript_shift_up_cramped(),
+            SuperscriptBottomMin => constants.superscript_bottom_min(),
+            SuperscriptBaselineDropMax => constants.superscript_baseline_drop_max(),
+            SubSuperscriptGapMin => constants.sub_superscript_gap_min(),
+            SuperscriptBottomMaxWithSubscript => constants.superscript_bottom_max_with_subscript(),
+            SpaceAfterScript => constants.space_after_script(),
+            UpperLimitGapMin => constants.upper_limit_gap_min(),
+            UpperLimitBaselineRiseMin => constants.upper_limit_baseline_rise_min(),
+            LowerLimitGapMin => constants.lower_limit_gap_min(),
+            LowerLimitBaselineDropMin => constants.lower_limit_baseline_drop_min(),
+            StackTopShiftUp => constants.stack_top_shift_up(),
+            StackTopDisplayStyleShiftUp => constants.stack_top_display_style_shift_up(),
+            StackBottomShiftDown => constants.stack_bottom_shift_down(),
+            StackBottomDisplayStyleShiftDown => constants.stack_bottom_display_style_shift_down(),
+            StackGapMin => constants.stack_gap_min(),
+            StackDisplayStyleGapMin => constants.stack_display_style_gap_min(),
+            StretchStackTopShiftUp => constants.stretch_stack_top_shift_up(),
+            StretchStackBottomShiftDown => constants.stretch_stack_bottom_shift_down(),
+            StretchStackGapAboveMin => constants.stretch_stack_gap_above_min(),
+            StretchStackGapBelowMin => constants.stretch_stack_gap_below_min(),
+            FractionNumeratorShiftUp => constants.fraction_numerator_shift_up(),
+            FractionNumeratorDisplayStyleShiftUp => {
+                constants.fraction_numerator_display_style_shift_up()
+            }
+            FractionDenominatorShiftDown => constants.fraction_denominator_shift_down(),
+            FractionDenominatorDisplayStyleShiftDown => {
+                constants.fraction_denominator_display_style_shift_down()
+            }
+            FractionNumeratorGapMin => constants.fraction_numerator_gap_min(),
+            FractionNumDisplayStyleGapMin => constants.fraction_num_display_style_gap_min(),
+            FractionRuleThickness => constants.fraction_rule_thickness(),
+            FractionDenominatorGapMin => constants.fraction_denominator_gap_min(),
+            FractionDenomDisplayStyleGapMin => constants.fraction_denom_display_style_gap_min(),
+            SkewedFractionHorizontalGap => constants.skewed_fraction_horizontal_gap(),
+            SkewedFractionVerticalGap => constants.skewed_fraction_vertical_gap(),
+            OverbarVerticalGap => constants.overbar_vertical_gap(),
+            OverbarRuleThickness => constants.overbar_rule_thickness(),
+            OverbarExtraAscender => constants.overbar_extra_ascender(),
+            UnderbarVerticalGap => constants.underbar_vertical_gap(),
+            UnderbarRuleThickness => constants.underbar_rule_thickness(),
+            UnderbarExtraDescender => constants.underbar_extra_descender(),
+            RadicalVerticalGap => constants.radical_vertical_gap(),
+            RadicalDisplayStyleVerticalGap => constants.radical_display_style_vertical_gap(),
+            RadicalRuleThickness => constants.radical_rule_thickness(),
+            RadicalExtraAscender => constants.radical_extra_ascender(),
+            RadicalKernBeforeDegree => constants.radical_kern_before_degree(),
+            RadicalKernAfterDegree => constants.radical_kern_after_degree(),
+        };
+        Some(self.value(record, constants.offset_data()))
+    }
+
+    /// Returns the italics correction for the given glyph.
+    pub fn italics_correction(&self, glyph_id: GlyphId) -> Option<f32> {
+        let info = self.glyph_info()?.math_italics_correction_info()?.ok()?;
+        Some(self.value(&info.get(glyph_id)?, info.offset_data()))
+    }
+
+    /// Returns the horizontal position of the attachment point for top
+    /// accents on the given glyph.
+    pub fn top_accent_attachment(&self, glyph_id: GlyphId) -> Option<f32> {
+        let attachment = self.glyph_info()?.math_top_accent_attachment()?.ok()?;
+        Some(self.value(&attachment.get(glyph_id)?, attachment.offset_data()))
+    }
+
+    /// Returns true if the given glyph is an extended shape.
+    ///
+    /// The ink box of extended shapes should be used for positioning
+    /// scripts rather than the default positions defined by the layout
+    /// constants.
+    pub fn is_extended_shape(&self, glyph_id: GlyphId) -> bool {
+        self.glyph_info()
+            .map(|info| info.is_extended_shape(glyph_id))
+            .unwrap_or_default()
+    }
+
+    /// Returns the kerning adjustment for the given corner of the glyph at
+    /// the specified height.
+    ///
+    /// Negative values move the glyphs closer together. Returns `None` if
+    /// the font does not define kerning for the glyph corner.
+    pub fn kern(&self, glyph_id: GlyphId, corner: MathKernCorner, height: f32) -> Option<f32> {
+        let info = self.glyph_info()?.math_kern_info()?.ok()?;
+        let record = info.get(glyph_id)?;
+        let data = info.offset_data();
+        let kern = match corner {
+            MathKernCorner::TopRight => record.top_right_math_kern(data),
+            MathKernCorner::TopLeft => record.top_left_math_kern(data),
+            MathKernCorner::BottomRight => record.bottom_right_math_kern(data),
+            MathKernCorner::BottomLeft => record.bottom_left_math_kern(data),
+        }?
+        .ok()?;
+        let data = kern.offset_data();
+        // Heights are sorted and each one marks the inclusive upper limit
+        // of the range for the kern value with the same index
+        let ix = kern
+            .correction_height()
+            .iter()
+            .take_while(|correction_height| self.value(correction_height, data) < height)
+            .count();
+        Some(self.value(kern.kern_values().get(ix)?, data))
+    }
+
+    /// Returns the minimum overlap of connecting parts in glyph assemblies.
+    pub fn min_connector_overlap(&self) -> f32 {
+        self.math
+            .as_ref()
+            .and_then(|math| math.math_variants().ok())
+            .map(|variants| variants.min_connector_overlap().to_u16() as f32 * self.values.scale)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over the pre-designed size variants of the glyph
+    /// in the given direction.
+    ///
+    /// Variants are ordered from smallest to largest and the first is
+    /// usually the glyph itself.
+    pub fn variants(
+        &self,
+        glyph_id: GlyphId,
+        direction: StretchDirection,
+    ) -> impl Iterator<Item = GlyphVariant> + 'a {
+        let scale = self.values.scale;
+        self.construction(glyph_id, direction)
+            .into_iter()
+            .flat_map(move |construction| {
+                construction
+                    .math_glyph_variant_records()
+                    .iter()
+                    .map(move |record| GlyphVariant {
+                        glyph_id: record.variant_glyph(),
+                        advance: record.advance_measurement().to_u16() as f32 * scale,
+                    })
+            })
+    }
+
+    /// Constructs an assembly of parts for the glyph that covers the target
+    /// size in the given direction.
+    ///
+    /// Extender parts are repeated the minimum number of times needed to
+    /// reach the target size and the overlap between connecting parts is
+    /// then distributed evenly so that the assembly matches the target as
+    /// closely as the connectors allow.
+    ///
+    /// Clients should generally prefer the first [variant](Self::variants)
+    /// that is large enough and only fall back to an assembly when none
+    /// exists.
+    pub fn assembly(
+        &self,
+        glyph_id: GlyphId,
+        direction: StretchDirection,
+        target_size: f32,
+    ) -> Option<GlyphAssembly> {
+        let assembly = self
+            .construction(glyph_id, direction)?
+            .glyph_assembly()?
+            .ok()?;
+        let records = assembly.part_records();
+        let scale = self.values.scale;
+        let min_overlap = self.min_connector_overlap();
+        let (mut base_count, mut base_advance) = (0usize, 0.0);
+        let (mut ext_count, mut ext_advance) = (0usize, 0.0);
+        for record in records {
+            let advance = record.full_advance().to_u16() as f32 * scale;
+            if record.is_extender() {
+                ext_count += 1;
+                ext_advance += advance;
+            } else {
+                base_count += 1;
+                base_advance += advance;
+            }
+        }
+        // Size of the assembly without extenders at minimum overlap and the
+        // amount it grows for each repetition of the extenders
+        let base_size = base_advance - min_overlap * (base_count as f32 - 1.0);
+        let ext_growth = ext_advance - min_overlap * ext_count as f32;
+        let mut repeats = 0;
+        if let Some(max_repeats) = MAX_ASSEMBLY_PARTS
+            .saturating_sub(base_count)
+            .checked_div(ext_count)
+        {
+            if target_size > base_size && ext_growth > 0.0 {
+                repeats = ((target_size - base_size) / ext_growth).ceil() as usize;
+            }
+            if base_count == 0 {
+                repeats = repeats.max(1);
+            }
+            repeats = repeats.min(max_repeats);
+        }
+        let mut parts = Vec::with_capacity(base_count + ext_count * repeats);
+        let mut connectors = Vec::with_capacity(parts.capacity());
+        for record in records {
+            let count = if record.is_extender() { repeats } else { 1 };
+            for _ in 0..count {
+                parts.push(AssemblyPart {
+                    glyph_id: record.glyph_id(),
+                    offset: 0.0,
+                    advance: record.full_advance().to_u16() as f32 * scale,
+                });
+                connectors.push((
+                    record.start_connector_length().to_u16() as f32 * scale,
+                    record.end_connector_length().to_u16() as f32 * scale,
+                ));
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        // Use the same overlap for all connections, bounded below by the
+        // minimum overlap and above by the shortest pair of connectors
+        let mut overlap = 0.0;
+        if parts.len() > 1 {
+            let total_advance: f32 = parts.iter().map(|part| part.advance).sum();
+            let max_overlap = connectors
+                .windows(2)
+                .map(|pair| pair[0].1.min(pair[1].0))
+                .fold(f32::INFINITY, f32::min);
+            overlap = ((total_advance - target_size) / (parts.len() - 1) as f32)
+                .min(max_overlap)
+                .max(min_overlap);
+        }
+        let mut offset = 0.0;
+        for part in &mut parts {
+            part.offset = offset;
+            offset += part.advance - overlap;
+        }
+        Some(GlyphAssembly {
+            parts,
+            size: offset + overlap,
+            italics_correction: self.value(assembly.italics_correction(), assembly.offset_data()),
+        })
+    }
+
+    fn glyph_info(&self) -> Option<MathGlyphInfo<'a>> {
+        self.math.as_ref()?.math_glyph_info().ok()
+    }
+
+    fn construction(
+        &self,
+        glyph_id: GlyphId,
+        direction: StretchDirection,
+    ) -> Option<MathGlyphConstruction<'a>> {
+        let variants = self.math.as_ref()?.math_variants().ok()?;
+        match direction {
+            StretchDirection::Vertical => variants.vert_glyph_construction(glyph_id),
+            StretchDirection::Horizontal => variants.horiz_glyph_construction(glyph_id),
+        }?
+        .ok()
+    }
+
+    /// Computes the scaled value of the record, including variation and
+    /// device deltas.
+    fn value(&self, record: &MathValueRecord, data: FontData<'a>) -> f32 {
+        self.values
+            .resolve(record.value().to_i16() as i32, record.device(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instance::NormalizedCoord, MetadataProvider as _};
+    use read_fonts::{tables::variations::ItemVariationStore, types::F2Dot14, FontRead, FontRef};
+
+    #[rustfmt::skip]
+    static VAR_STORE: &[u8] = &[
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22,
+        // region list: single axis, peak at 1.0
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0,
+        // item variation data with a single delta of -10
+        0, 1, 0, 0, 0, 1, 0, 0, 0xF6,
+    ];
+
+    fn math_data() -> Vec<u8> {
+        let mut words: Vec<i16> = vec![
+            // version, constants, glyph info, variants
+            1, 0, 10, 238, 300, // percent scale down, min heights
+            80, 60, 1500, 1300,
+            // math leading with a device table, axis height with a
+            // variation index
+            150, 214, 250, 222,
+        ];
+        words.extend((2..51).flat_map(|i| [i * 10, 0]));
+        words.extend([
+            // radical degree bottom raise percent
+            60, // device: size 12, 2-bit delta of 1
+            12, 12, 1, 0x4000, // variation index: outer 0, inner 0
+            0, 0, -0x8000, // glyph info: italics correction, kern info
+            8, 0, 0, 22, // italics correction: glyph 3
+            8, 1, 50, 0, 1, 1, 3, // kern info: top right kern for glyph 3
+            12, 1, 18, 0, 0, 0, 1, 1, 3, // math kern: heights 100, 200
+            2, 100, 0, 200, 0, -10, 0, -20, 0, -30, 0,
+            // variants: min overlap, vert coverage, horiz coverage,
+            // counts, construction for glyph 5
+            10, 12, 18, 1, 0, 22, 1, 1, 5, 1, 0, // construction: assembly, 2 variants
+            12, 2, 6, 500, 8, 1000, // assembly: italics correction, 3 parts
+            5, 0, 3, 10, 0, 100, 300, 0, 11, 100, 100, 200, 1, 12, 100, 0, 300, 0,
+        ]);
+        words.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+
+    fn math<'a>(
+        data: &'a [u8],
+        scale: f32,
+        ppem: Option<u16>,
+        coords: &'a [NormalizedCoord],
+    ) -> MathLayout<'a> {
+        MathLayout {
+            math: math::Math::read(FontData::new(data)).ok(),
+            values: LayoutValues {
+                var_store: ItemVariationStore::read(FontData::new(VAR_STORE)).ok(),
+                scale,
+                ppem,
+                coords,
+            },
+        }
+    }
+
+    #[test]
+    fn constants() {
+        let data = math_data();
+        let math = math(&data, 0.5, None, &[]);
+        assert!(math.is_present());
+        let constant = |constant| math.constant(constant).unwrap();
+        // Percentages are not scaled
+        assert_eq!(constant(MathConstant::ScriptPercentScaleDown), 80.0);
+        assert_eq!(constant(MathConstant::ScriptScriptPercentScaleDown), 60.0);
+        assert_eq!(
+            constant(MathConstant::RadicalDegreeBottomRaisePercent),
+            60.0
+        );
+        assert_eq!(constant(MathConstant::DelimitedSubFormulaMinHeight), 750.0);
+        assert_eq!(constant(MathConstant::DisplayOperatorMinHeight), 650.0);
+        assert_eq!(constant(MathConstant::MathLeading), 75.0);
+        assert_eq!(constant(MathConstant::AxisHeight), 125.0);
+        assert_eq!(constant(MathConstant::AccentBaseHeight), 10.0);
+        assert_eq!(constant(MathConstant::RadicalKernAfterDegree), 250.0);
+    }
+
+    #[test]
+    fn constant_deltas() {
+        let data = math_data();
+        let leading = |ppem| {
+            math(&data, 0.5, ppem, &[])
+                .constant(MathConstant::MathLeading)
+                .unwrap()
+        };
+        assert_eq!(leading(Some(11)), 75.0);
+        assert_eq!(leading(Some(12)), 76.0);
+        let axis_height = |coord| {
+            let coords = [F2Dot14::from_f32(coord)];
+            math(&data, 0.5, None, &coords)
+                .constant(MathConstant::AxisHeight)
+                .unwrap()
+        };
+        assert_eq!(axis_height(1.0), 120.0);
+        assert_eq!(axis_height(0.5), 122.5);
+    }
+
+    #[test]
+    fn glyph_info() {
+        let data = math_data();
+        let math = math(&data, 0.5, None, &[]);
+        let gid = GlyphId::new;
+        assert_eq!(math.italics_correction(gid(3)), Some(25.0));
+        assert_eq!(math.italics_correction(gid(4)), None);
+        assert_eq!(math.top_accent_attachment(gid(3)), None);
+        assert!(!math.is_extended_shape(gid(3)));
+        let kern = |height| math.kern(gid(3), MathKernCorner::TopRight, height);
+        assert_eq!(kern(40.0), Some(-5.0));
+        assert_eq!(kern(75.0), Some(-10.0));
+        assert_eq!(kern(150.0), Some(-15.0));
+        // Heights exactly at a correction height belong to the lower range
+        assert_eq!(kern(50.0), Some(-5.0));
+        assert_eq!(kern(50.5), Some(-10.0));
+        assert_eq!(kern(100.0), Some(-10.0));
+        assert_eq!(kern(100.5), Some(-15.0));
+        assert_eq!(math.kern(gid(3), MathKernCorner::TopLeft, 0.0), None);
+        assert_eq!(math.kern(gid(4), MathKernCorner::TopRight, 0.0), None);
+    }
+
+    #[test]
+    fn variants() {
+        let data = math_data();
+        let math = math(&data, 0.5, None, &[]);
+        let gid = GlyphId::new;
+        let variants = math
+            .variants(gid(5), StretchDirection::Vertical)
+            .map(|variant| (variant.glyph_id.to_u16(), variant.advance))
+            .collect::<Vec<_>>();
+        assert_eq!(variants, [(6, 250.0), (8, 500.0)]);
+        assert_eq!(
+            math.variants(gid(5), StretchDirection::Horizontal).count(),
+            0
+        );
+        assert_eq!(math.min_connector_overlap(), 5.0);
+    }
+
+    #[test]
+    fn assembly() {
+        let data = math_data();
+        let math = math(&data, 1.0, None, &[]);
+        let gid = GlyphId::new;
+        let assembly = |size| {
+            let assembly = math
+                .assembly(gid(5), StretchDirection::Vertical, size)
+                .unwrap();
+            let parts = assembly
+                .parts
+                .iter()
+                .map(|part| (part.glyph_id.to_u16(), part.offset))
+                .collect::<Vec<_>>();
+            (parts, assembly.size)
+        };
+        // No extenders needed
+        assert_eq!(assembly(500.0), (vec![(10, 0.0), (12, 200.0)], 500.0));
+        // Three extenders with an overlap of 50
+        assert_eq!(
+            assembly(1000.0),
+            (
+                vec![
+                    (10, 0.0),
+                    (11, 250.0),
+                    (11, 400.0),
+                    (11, 550.0),
+                    (12, 700.0)
+                ],
+                1000.0
+            )
+        );
+        // Overlap limited by the connector lengths
+        assert_eq!(assembly(300.0), (vec![(10, 0.0), (12, 200.0)], 500.0));
+        // Largest size with a single extender uses the minimum overlap
+        let (parts, size) = assembly(780.0);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(size, 780.0);
+        let (parts, size) = assembly(785.0);
+        assert_eq!(parts.len(), 4);
+        assert!((size - 785.0).abs() < 0.01);
+        let (parts, size) = assembly(2000.0);
+        assert_eq!(parts.len(), 10);
+        assert!((size - 2000.0).abs() < 0.01);
+        let assembly = math
+            .assembly(gid(5), StretchDirection::Vertical, 0.0)
+            .unwrap();
+        assert_eq!(assembly.italics_correction, 5.0);
+        assert!(math
+            .assembly(gid(5), StretchDirection::Horizontal, 1000.0)
+            .is_none());
+        assert!(math
+            .assembly(gid(4), StretchDirection::Vertical, 1000.0)
+            .is_none());
+    }
+
+    #[test]
+    fn font_without_math() {
+        let font = FontRef::new(font_test_data::NOTO_SERIF_DISPLAY_TRIMMED).unwrap();
+        let math = font.math_layout(Size::new(16.0), LocationRef::default());
+        assert!(!math.is_present());
+        assert_eq!(math.constant(MathConstant::AxisHeight), None);
+        assert_eq!(math.min_connector_overlap(), 0.0);
+    }
+}
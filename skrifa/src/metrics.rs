@@ -10,6 +10,8 @@
 //!
 //! * Glyph metrics: these apply to single glyphs. For example, the advance
 //! width value describes the distance between two consecutive glyphs on a line.
+//!   For vertical layout, the advance height and vertical origin serve the same
+//!   purpose for glyphs in a column.
 //!
 //! ### Selecting an "instance"
 //! Both global and glyph specific metrics accept two additional pieces of information
@@ -22,20 +24,30 @@
 //! as an argument for this parameter.
 //!
 
+use core::cell::OnceCell;
+
 use read_fonts::{
     tables::{
-        glyf::{CompositeGlyphFlags, Glyf, Glyph},
+        base::{Axis, Base, BaseCoord},
+        gdef::{CaretValue, LigCaretList},
+        glyf::{Anchor, CompositeGlyphFlags, Glyf, Glyph, PointFlags},
         gvar::Gvar,
         hmtx::LongMetric,
         hvar::Hvar,
         loca::Loca,
         os2::SelectionFlags,
+        vorg::Vorg,
+        vvar::Vvar,
     },
-    types::{BigEndian, Fixed, GlyphId},
-    TableProvider,
+    types::{BigEndian, F2Dot14, Fixed, GlyphId, Point, Tag},
+    ReadError, TableProvider,
 };
 
-use super::instance::{LocationRef, NormalizedCoord, Size};
+use super::{
+    glyf_deltas,
+    instance::{LocationRef, NormalizedCoord, Size},
+    layout_value::LayoutValues,
+};
 
 /// Type for a bounding box with single precision floating point coordinates.
 pub type BoundingBox = read_fonts::types::BoundingBox<f32>;
@@ -52,6 +64,24 @@ pub struct Decoration {
     pub thickness: f32,
 }
 
+/// Metrics for subscript or superscript glyphs.
+///
+/// This represents the suggested size and position of glyphs that are
+/// synthesized for a subscript or superscript.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct ScriptMetrics {
+    /// Horizontal size of the glyphs.
+    pub x_size: f32,
+    /// Vertical size of the glyphs.
+    pub y_size: f32,
+    /// Horizontal offset of the glyphs relative to the preceding glyph.
+    pub x_offset: f32,
+    /// Vertical offset of the glyphs from the baseline. This is positive
+    /// for both subscripts (which are moved down) and superscripts (which
+    /// are moved up).
+    pub y_offset: f32,
+}
+
 /// Metrics that apply to all glyphs in a font.
 ///
 /// These are retrieved for a specific position in the design space.
@@ -61,7 +91,7 @@ pub struct Decoration {
 /// * [maxp](https://learn.microsoft.com/en-us/typography/opentype/spec/maxp): `glyph_count`
 /// * [post](https://learn.microsoft.com/en-us/typography/opentype/spec/post): `is_monospace`, `italic_angle`, `underline`
 /// * [OS/2](https://learn.microsoft.com/en-us/typography/opentype/spec/os2): `average_width`, `cap_height`,
-/// `x_height`, `strikeout`, as well as the line metrics: `ascent`, `descent`, `leading` if the `USE_TYPOGRAPHIC_METRICS`
+/// `x_height`, `strikeout`, `subscript`, `superscript`, as well as the line metrics: `ascent`, `descent`, `leading` if the `USE_TYPOGRAPHIC_METRICS`
 /// flag is set or the `hhea` line metrics are zero (the Windows metrics are used as a last resort).
 /// * [hhea](https://learn.microsoft.com/en-us/typography/opentype/spec/hhea): `max_width`, as well as the line metrics:
 /// `ascent`, `descent`, `leading` if they are non-zero and the `USE_TYPOGRAHIC_METRICS` flag is not set in the OS/2 table
@@ -98,6 +128,10 @@ pub struct Metrics {
     pub underline: Option<Decoration>,
     /// Metrics for a strikeout decoration.
     pub strikeout: Option<Decoration>,
+    /// Metrics for synthesized subscript glyphs.
+    pub subscript: Option<ScriptMetrics>,
+    /// Metrics for synthesized superscript glyphs.
+    pub superscript: Option<ScriptMetrics>,
     /// Union of minimum and maximum extents for all glyphs in the font.
     pub bounds: Option<BoundingBox>,
 }
@@ -171,6 +205,18 @@ impl Metrics {
                 offset: os2.y_strikeout_position() as f32 * scale,
                 thickness: os2.y_strikeout_size() as f32 * scale,
             });
+            metrics.subscript = Some(ScriptMetrics {
+                x_size: os2.y_subscript_x_size() as f32 * scale,
+                y_size: os2.y_subscript_y_size() as f32 * scale,
+                x_offset: os2.y_subscript_x_offset() as f32 * scale,
+                y_offset: os2.y_subscript_y_offset() as f32 * scale,
+            });
+            metrics.superscript = Some(ScriptMetrics {
+                x_size: os2.y_superscript_x_size() as f32 * scale,
+                y_size: os2.y_superscript_y_size() as f32 * scale,
+                x_offset: os2.y_superscript_x_offset() as f32 * scale,
+                y_offset: os2.y_superscript_y_offset() as f32 * scale,
+            });
         }
         if !used_typo_metrics {
             if let Ok(hhea) = font.hhea() {
@@ -214,6 +260,18 @@ impl Metrics {
                 strikeout.offset += metric_delta(STRO);
                 strikeout.thickness += metric_delta(STRS);
             }
+            if let Some(subscript) = &mut metrics.subscript {
+                subscript.x_size += metric_delta(SBXS);
+                subscript.y_size += metric_delta(SBYS);
+                subscript.x_offset += metric_delta(SBXO);
+                subscript.y_offset += metric_delta(SBYO);
+            }
+            if let Some(superscript) = &mut metrics.superscript {
+                superscript.x_size += metric_delta(SPXS);
+                superscript.y_size += metric_delta(SPYS);
+                superscript.x_offset += metric_delta(SPXO);
+                superscript.y_offset += metric_delta(SPYO);
+            }
         }
         metrics
     }
@@ -231,6 +289,14 @@ pub struct GlyphMetrics<'a> {
     gvar: Option<Gvar<'a>>,
     loca_glyf: Option<(Loca<'a>, Glyf<'a>)>,
     coords: &'a [NormalizedCoord],
+    v_metrics: &'a [LongMetric],
+    default_advance_height: u16,
+    tsbs: &'a [BigEndian<i16>],
+    vvar: Option<Vvar<'a>>,
+    vorg: Option<Vorg<'a>>,
+    /// Ascender and descender used to synthesize vertical metrics.
+    ascender: i16,
+    descender: i16,
 }
 
 impl<'a> GlyphMetrics<'a> {
@@ -267,6 +333,27 @@ impl<'a> GlyphMetrics<'a> {
         } else {
             None
         };
+        let (v_metrics, default_advance_height, tsbs) = font
+            .vmtx()
+            .map(|vmtx| {
+                let v_metrics = vmtx.v_metrics();
+                let default_advance_height = v_metrics.last().map(|m| m.advance.get()).unwrap_or(0);
+                let tsbs = vmtx.top_side_bearings();
+                (v_metrics, default_advance_height, tsbs)
+            })
+            .unwrap_or_default();
+        let vvar = font.vvar().ok();
+        let vorg = font.vorg().ok();
+        // Match FreeType and prefer the typographic metrics for synthesized
+        // vertical metrics.
+        // See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/80a507a6b8e3d2906ad2c8ba69329bd2fb2a85ef/src/truetype/ttgload.c#L1539>
+        let (ascender, descender) = if let Ok(os2) = font.os2() {
+            (os2.s_typo_ascender(), os2.s_typo_descender())
+        } else if let Ok(hhea) = font.hhea() {
+            (hhea.ascender().to_i16(), hhea.descender().to_i16())
+        } else {
+            (0, 0)
+        };
         Self {
             glyph_count,
             fixed_scale,
@@ -277,6 +364,13 @@ impl<'a> GlyphMetrics<'a> {
             gvar,
             loca_glyf,
             coords,
+            v_metrics,
+            default_advance_height,
+            tsbs,
+            vvar,
+            vorg,
+            ascender,
+            descender,
         }
     }
 
@@ -306,7 +400,7 @@ impl<'a> GlyphMetrics<'a> {
                 .map(|delta| delta.to_f64() as i32)
                 .unwrap_or(0);
         } else if self.gvar.is_some() {
-            advance += self.metric_deltas_from_gvar(glyph_id)[1];
+            advance += self.metric_deltas_from_gvar(glyph_id).advance_width;
         }
         Some(self.fixed_scale.apply(advance))
     }
@@ -338,15 +432,103 @@ impl<'a> GlyphMetrics<'a> {
                 .map(|delta| delta.to_f64() as i32)
                 .unwrap_or(0);
         } else if self.gvar.is_some() {
-            lsb += self.metric_deltas_from_gvar(glyph_id)[0];
+            lsb += self.metric_deltas_from_gvar(glyph_id).lsb;
         }
         Some(self.fixed_scale.apply(lsb))
     }
 
+    /// Returns the advance height for the specified glyph.
+    ///
+    /// If normalized coordinates were provided when constructing glyph metrics and
+    /// a `VVAR` table is present, applies the appropriate delta. Otherwise,
+    /// deltas are computed from the `gvar` phantom points.
+    ///
+    /// Fonts without a `vmtx` table use the distance between the ascender
+    /// and descender for all glyphs.
+    pub fn advance_height(&self, glyph_id: GlyphId) -> Option<f32> {
+        if glyph_id.to_u16() >= self.glyph_count {
+            return None;
+        }
+        if self.v_metrics.is_empty() {
+            let advance = (self.ascender as i32 - self.descender as i32).abs();
+            return Some(self.fixed_scale.apply(advance));
+        }
+        let mut advance = self
+            .v_metrics
+            .get(glyph_id.to_u16() as usize)
+            .map(|metric| metric.advance())
+            .unwrap_or(self.default_advance_height) as i32;
+        if let Some(vvar) = &self.vvar {
+            advance += vvar
+                .advance_height_delta(glyph_id, self.coords)
+                .map(|delta| delta.to_f64() as i32)
+                .unwrap_or(0);
+        } else if self.gvar.is_some() {
+            advance += self.metric_deltas_from_gvar(glyph_id).advance_height;
+        }
+        Some(self.fixed_scale.apply(advance))
+    }
+
+    /// Returns the top side bearing for the specified glyph.
+    ///
+    /// If normalized coordinates were provided when constructing glyph metrics and
+    /// a `VVAR` table is present, applies the appropriate delta. Otherwise,
+    /// deltas are computed from the `gvar` phantom points.
+    ///
+    /// Fonts without a `vmtx` table use the distance between the ascender
+    /// and the top of the glyph.
+    ///
+    /// At non-default locations without a `VVAR` table, the glyph outline
+    /// is loaded and varied to find the top of the glyph, so this is
+    /// considerably more expensive than the other metrics.
+    pub fn top_side_bearing(&self, glyph_id: GlyphId) -> Option<f32> {
+        self.vertical_metrics_unscaled(glyph_id, false)
+            .map(|(tsb, _)| self.fixed_scale.apply(tsb))
+    }
+
+    /// Returns the y coordinate of the vertical origin for the specified
+    /// glyph.
+    ///
+    /// This is the point that is aligned with the vertical baseline when
+    /// laying out text vertically. It is selected as follows:
+    ///
+    /// 1. The `VORG` table, with deltas from `VVAR` if present.
+    /// 2. The top side bearing added to the top of the glyph bounding box
+    ///    if a `vmtx` table is present.
+    /// 3. The ascender.
+    ///
+    /// As with [`top_side_bearing`](Self::top_side_bearing), the second case
+    /// loads the glyph outline at non-default locations.
+    pub fn vertical_origin(&self, glyph_id: GlyphId) -> Option<f32> {
+        if glyph_id.to_u16() >= self.glyph_count {
+            return None;
+        }
+        let origin = if let Some(vorg) = &self.vorg {
+            let mut origin = vorg.vertical_origin_y(glyph_id) as i32;
+            if let Some(vvar) = &self.vvar {
+                origin += vvar
+                    .v_org_delta(glyph_id, self.coords)
+                    .map(|delta| delta.to_f64() as i32)
+                    .unwrap_or(0);
+            }
+            origin
+        } else if let Some((tsb, Some(y_max))) = (!self.v_metrics.is_empty())
+            .then(|| self.vertical_metrics_unscaled(glyph_id, true))
+            .flatten()
+        {
+            tsb + y_max
+        } else {
+            self.ascender as i32
+        };
+        Some(self.fixed_scale.apply(origin))
+    }
+
     /// Returns the bounding box for the specified glyph.
     ///
     /// Note that variations are not reflected in the bounding box returned by
-    /// this method.
+    /// this method and that bounds are only available for glyphs in the `glyf`
+    /// table. Use [`Scaler::bounds`](crate::scale::Scaler::bounds) to compute
+    /// the bounding box from the outline for any glyph format and location.
     pub fn bounds(&self, glyph_id: GlyphId) -> Option<BoundingBox> {
         let (loca, glyf) = self.loca_glyf.as_ref()?;
         Some(match loca.get_glyf(glyph_id, glyf).ok()? {
@@ -363,11 +545,269 @@ impl<'a> GlyphMetrics<'a> {
 }
 
 impl<'a> GlyphMetrics<'a> {
-    fn metric_deltas_from_gvar(&self, glyph_id: GlyphId) -> [i32; 2] {
+    fn metric_deltas_from_gvar(&self, glyph_id: GlyphId) -> MetricDeltas {
         GvarMetricDeltas::new(self)
             .and_then(|metric_deltas| metric_deltas.compute_deltas(glyph_id))
             .unwrap_or_default()
     }
+
+    /// Returns the unscaled top side bearing and, if requested, the maximum
+    /// y coordinate of the glyph including any variations.
+    ///
+    /// The outline variations are only computed once when both values
+    /// depend on them.
+    fn vertical_metrics_unscaled(
+        &self,
+        glyph_id: GlyphId,
+        with_y_max: bool,
+    ) -> Option<(i32, Option<i32>)> {
+        if glyph_id.to_u16() >= self.glyph_count {
+            return None;
+        }
+        let y_max_delta = OnceCell::new();
+        let y_max_delta = || *y_max_delta.get_or_init(|| self.y_max_delta_from_gvar(glyph_id));
+        let y_max = || Some(self.default_y_max(glyph_id)? + y_max_delta());
+        if self.v_metrics.is_empty() {
+            let y_max = y_max();
+            let tsb = self.ascender as i32 - y_max.unwrap_or(self.ascender as i32);
+            return Some((tsb, y_max.filter(|_| with_y_max)));
+        }
+        let gid_index = glyph_id.to_u16() as usize;
+        let mut tsb = self
+            .v_metrics
+            .get(gid_index)
+            .map(|metric| metric.side_bearing())
+            .unwrap_or_else(|| {
+                self.tsbs
+                    .get(gid_index.saturating_sub(self.v_metrics.len()))
+                    .map(|tsb| tsb.get())
+                    .unwrap_or_default()
+            }) as i32;
+        if let Some(vvar) = &self.vvar {
+            tsb += vvar
+                .tsb_delta(glyph_id, self.coords)
+                .map(|delta| delta.to_f64() as i32)
+                .unwrap_or(0);
+        } else if self.gvar.is_some() {
+            // The bearing is measured from the top phantom point to the top
+            // of the outline and both may move.
+            tsb += self.metric_deltas_from_gvar(glyph_id).top - y_max_delta();
+        }
+        Some((tsb, if with_y_max { y_max() } else { None }))
+    }
+
+    /// Returns the maximum y coordinate of the glyph from the `glyf` table
+    /// header.
+    fn default_y_max(&self, glyph_id: GlyphId) -> Option<i32> {
+        let (loca, glyf) = self.loca_glyf.as_ref()?;
+        Some(
+            loca.get_glyf(glyph_id, glyf)
+                .ok()?
+                .map(|glyph| glyph.y_max() as i32)
+                .unwrap_or_default(),
+        )
+    }
+
+    fn y_max_delta_from_gvar(&self, glyph_id: GlyphId) -> i32 {
+        if self
+            .coords
+            .iter()
+            .all(|coord| *coord == NormalizedCoord::ZERO)
+        {
+            return 0;
+        }
+        GvarMetricDeltas::new(self)
+            .and_then(|metric_deltas| metric_deltas.y_max_delta(glyph_id))
+            .unwrap_or_default()
+    }
+}
+
+/// Metric deltas computed from phantom points.
+#[derive(Copy, Clone, Default, Debug)]
+struct MetricDeltas {
+    lsb: i32,
+    advance_width: i32,
+    /// Delta of the top phantom point.
+    top: i32,
+    advance_height: i32,
+}
+
+/// Baseline positions for a single script.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct ScriptBaselines {
+    /// Tag of the baseline that the script is aligned to by default.
+    pub default_baseline: Option<Tag>,
+    /// Pairs of baseline tags and positions along the layout axis.
+    pub baselines: Vec<(Tag, f32)>,
+}
+
+impl ScriptBaselines {
+    /// Returns the position of the baseline with the given tag.
+    pub fn get(&self, baseline: Tag) -> Option<f32> {
+        self.baselines
+            .iter()
+            .find(|(tag, _)| *tag == baseline)
+            .map(|(_, position)| *position)
+    }
+}
+
+/// Per script baseline positions.
+///
+/// These are retrieved for a specific position in the design space and are
+/// derived from the [BASE](https://learn.microsoft.com/en-us/typography/opentype/spec/base)
+/// table.
+#[derive(Clone)]
+pub struct Baselines<'a> {
+    base: Option<Base<'a>>,
+    values: LayoutValues<'a>,
+}
+
+impl<'a> Baselines<'a> {
+    /// Creates new baselines for the given font, size, and location in
+    /// normalized variation space.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: Size,
+        location: impl Into<LocationRef<'a>>,
+    ) -> Self {
+        let upem = font.head().map(|head| head.units_per_em()).unwrap_or(0);
+        let base = font.base().ok();
+        let var_store = base.as_ref().and_then(|base| base.item_var_store()?.ok());
+        Self {
+            base,
+            values: LayoutValues::new(var_store, size, upem, location.into()),
+        }
+    }
+
+    /// Returns the baselines for the given script in horizontal layout.
+    ///
+    /// Falls back to the `DFLT` script if the font does not contain
+    /// baselines for the requested script.
+    pub fn horizontal(&self, script: Tag) -> Option<ScriptBaselines> {
+        self.script_baselines(self.base.as_ref()?.horiz_axis()?.ok()?, script)
+    }
+
+    /// Returns the baselines for the given script in vertical layout.
+    ///
+    /// Falls back to the `DFLT` script if the font does not contain
+    /// baselines for the requested script.
+    pub fn vertical(&self, script: Tag) -> Option<ScriptBaselines> {
+        self.script_baselines(self.base.as_ref()?.vert_axis()?.ok()?, script)
+    }
+
+    fn script_baselines(&self, axis: Axis<'a>, script: Tag) -> Option<ScriptBaselines> {
+        const DFLT: Tag = Tag::new(b"DFLT");
+        let tags = axis.base_tag_list()?.ok()?.baseline_tags();
+        let script_list = axis.base_script_list().ok()?;
+        let records = script_list.base_script_records();
+        let record = records
+            .iter()
+            .find(|record| record.base_script_tag() == script)
+            .or_else(|| {
+                records
+                    .iter()
+                    .find(|record| record.base_script_tag() == DFLT)
+            })?;
+        let values = record
+            .base_script(script_list.offset_data())
+            .ok()?
+            .base_values()?
+            .ok()?;
+        let baselines = tags
+            .iter()
+            .zip(values.base_coords().iter())
+            .filter_map(|(tag, coord)| Some((tag.get(), self.coordinate(coord.ok()?))))
+            .collect();
+        Some(ScriptBaselines {
+            default_baseline: tags
+                .get(values.default_baseline_index() as usize)
+                .map(|tag| tag.get()),
+            baselines,
+        })
+    }
+
+    fn coordinate(&self, coord: BaseCoord) -> f32 {
+        let (value, device) = match coord {
+            BaseCoord::Format1(coord) => (coord.coordinate(), None),
+            BaseCoord::Format2(coord) => (coord.coordinate(), None),
+            BaseCoord::Format3(coord) => (coord.coordinate(), coord.device()),
+        };
+        self.values.resolve(value as i32, device)
+    }
+}
+
+/// Position of a caret between components of a ligature glyph.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Caret {
+    /// Position along the layout axis.
+    Coordinate(f32),
+    /// Index of a point in the glyph outline that defines the position of
+    /// the caret.
+    ContourPoint(u16),
+}
+
+/// Caret positions for ligature glyphs.
+///
+/// These are retrieved for a specific position in the design space and are
+/// derived from the ligature caret list in the
+/// [GDEF](https://learn.microsoft.com/en-us/typography/opentype/spec/gdef)
+/// table.
+#[derive(Clone)]
+pub struct LigatureCarets<'a> {
+    lig_caret_list: Option<LigCaretList<'a>>,
+    values: LayoutValues<'a>,
+}
+
+impl<'a> LigatureCarets<'a> {
+    /// Creates new ligature carets for the given font, size, and location in
+    /// normalized variation space.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: Size,
+        location: impl Into<LocationRef<'a>>,
+    ) -> Self {
+        let upem = font.head().map(|head| head.units_per_em()).unwrap_or(0);
+        let gdef = font.gdef().ok();
+        let lig_caret_list = gdef.as_ref().and_then(|gdef| gdef.lig_caret_list()?.ok());
+        let var_store = gdef.and_then(|gdef| gdef.item_var_store()?.ok());
+        Self {
+            lig_caret_list,
+            values: LayoutValues::new(var_store, size, upem, location.into()),
+        }
+    }
+
+    /// Returns an iterator over the caret positions for the specified
+    /// ligature glyph, in increasing order.
+    ///
+    /// The iterator is empty if the glyph is not a ligature with caret
+    /// positions.
+    pub fn get(&self, glyph_id: GlyphId) -> impl Iterator<Item = Caret> + '_ {
+        self.lig_glyph_carets(glyph_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|caret| Some(self.caret(caret.ok()?)))
+    }
+
+    fn lig_glyph_carets(
+        &self,
+        glyph_id: GlyphId,
+    ) -> Option<impl Iterator<Item = Result<CaretValue<'a>, ReadError>> + 'a> {
+        let lig_caret_list = self.lig_caret_list.as_ref()?;
+        let index = lig_caret_list.coverage().ok()?.get(glyph_id)?;
+        let lig_glyph = lig_caret_list.lig_glyphs().get(index as usize).ok()?;
+        Some((0..lig_glyph.caret_count() as usize).map(move |i| lig_glyph.caret_values().get(i)))
+    }
+
+    fn caret(&self, caret: CaretValue) -> Caret {
+        let (value, device) = match caret {
+            CaretValue::Format1(caret) => (caret.coordinate(), None),
+            CaretValue::Format2(caret) => {
+                return Caret::ContourPoint(caret.caret_value_point_index())
+            }
+            CaretValue::Format3(caret) => (caret.coordinate(), Some(caret.device())),
+        };
+        Caret::Coordinate(self.values.resolve(value as i32, device))
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -384,6 +824,19 @@ impl FixedScaleFactor {
     }
 }
 
+/// Buffers that are reused while loading the points of a glyph and its
+/// components.
+#[derive(Default)]
+struct LoadBuffers {
+    /// Varied points of all loaded outlines.
+    points: Vec<Point<i32>>,
+    unscaled: Vec<Point<i32>>,
+    flags: Vec<PointFlags>,
+    contours: Vec<u16>,
+    iup: Vec<Point<Fixed>>,
+    deltas: Vec<Point<Fixed>>,
+}
+
 struct GvarMetricDeltas<'a> {
     loca: Loca<'a>,
     glyf: Glyf<'a>,
@@ -403,8 +856,7 @@ impl<'a> GvarMetricDeltas<'a> {
         })
     }
 
-    /// Returns [lsb_delta, advance_delta]
-    fn compute_deltas(&self, glyph_id: GlyphId) -> Option<[i32; 2]> {
+    fn compute_deltas(&self, glyph_id: GlyphId) -> Option<MetricDeltas> {
         // For any given glyph, there's only one outline that contributes to
         // metrics deltas (via "phantom points"). For simple glyphs, that is
         // the glyph itself. For composite glyphs, it is the first component
@@ -416,9 +868,9 @@ impl<'a> GvarMetricDeltas<'a> {
         // count), so that we know where the deltas for phantom points start
         // in the variation data.
         let (glyph_id, point_count) = self.find_glyph_and_point_count(glyph_id, 0)?;
-        // [left_extent_delta, right_extent_delta]
-        let mut metric_deltas = [Fixed::ZERO; 2];
-        let phantom_range = point_count..point_count + 2;
+        // [left_extent_delta, right_extent_delta, top_extent_delta, bottom_extent_delta]
+        let mut metric_deltas = [Fixed::ZERO; 4];
+        let phantom_range = point_count..point_count + 4;
         let var_data = self.gvar.glyph_variation_data(glyph_id).ok()?;
         // Note that phantom points can never belong to a contour so we don't have
         // to handle the IUP case here.
@@ -426,12 +878,151 @@ impl<'a> GvarMetricDeltas<'a> {
             for tuple_delta in tuple.deltas() {
                 let ix = tuple_delta.position as usize;
                 if phantom_range.contains(&ix) {
-                    metric_deltas[ix - phantom_range.start] += tuple_delta.apply_scalar(scalar).x;
+                    let delta = tuple_delta.apply_scalar(scalar);
+                    let phantom_ix = ix - phantom_range.start;
+                    // The first two phantom points define the horizontal
+                    // extents and the last two the vertical extents.
+                    metric_deltas[phantom_ix] += if phantom_ix < 2 { delta.x } else { delta.y };
                 }
             }
         }
-        metric_deltas[1] -= metric_deltas[0];
-        Some(metric_deltas.map(|x| x.to_i32()))
+        let [left, right, top, bottom] = metric_deltas.map(|x| x.to_i32());
+        Some(MetricDeltas {
+            lsb: left,
+            advance_width: right - left,
+            top,
+            advance_height: top - bottom,
+        })
+    }
+
+    /// Returns the change in the maximum y coordinate of the outline of the
+    /// given glyph at the current location, relative to the maximum
+    /// recorded in the `glyf` table.
+    ///
+    /// This loads the full outline, including all components.
+    fn y_max_delta(&self, glyph_id: GlyphId) -> Option<i32> {
+        let Some(glyph) = self.loca.get_glyf(glyph_id, &self.glyf).ok()? else {
+            return Some(0);
+        };
+        let mut buffers = LoadBuffers::default();
+        self.load_points(glyph_id, 0, &mut buffers)?;
+        let varied_y_max = buffers.points.iter().map(|point| point.y).max();
+        Some(varied_y_max.unwrap_or_default() - glyph.y_max() as i32)
+    }
+
+    /// Appends the unscaled points of the outline of the given glyph with
+    /// variations applied.
+    ///
+    /// This follows the FreeType glyph loader with unscaled output, which
+    /// rounds deltas and transformed coordinates to integers. The rarely
+    /// used scaled component offsets are not supported.
+    fn load_points(
+        &self,
+        glyph_id: GlyphId,
+        recurse_depth: usize,
+        buffers: &mut LoadBuffers,
+    ) -> Option<()> {
+        if recurse_depth > crate::GLYF_COMPOSITE_RECURSION_LIMIT {
+            return None;
+        }
+        let Some(glyph) = self.loca.get_glyf(glyph_id, &self.glyf).ok()? else {
+            return Some(());
+        };
+        match glyph {
+            Glyph::Simple(simple) => {
+                let point_count = simple.num_points();
+                let len = point_count + crate::GLYF_PHANTOM_POINT_COUNT;
+                let LoadBuffers {
+                    points,
+                    unscaled,
+                    flags,
+                    contours,
+                    iup,
+                    deltas,
+                } = buffers;
+                unscaled.clear();
+                unscaled.resize(len, Point::default());
+                flags.clear();
+                flags.resize(len, PointFlags::default());
+                simple
+                    .read_points_fast(&mut unscaled[..point_count], &mut flags[..point_count])
+                    .ok()?;
+                contours.clear();
+                contours.extend(simple.end_pts_of_contours().iter().map(|pt| pt.get()));
+                iup.clear();
+                iup.resize(len, Point::default());
+                deltas.clear();
+                deltas.resize(len, Point::default());
+                let glyph = glyf_deltas::SimpleGlyph {
+                    points: unscaled,
+                    flags,
+                    contours,
+                };
+                glyf_deltas::simple_glyph(
+                    &self.gvar,
+                    glyph_id,
+                    self.coords,
+                    true,
+                    glyph,
+                    iup,
+                    deltas,
+                )
+                .ok()?;
+                points.extend(
+                    unscaled[..point_count]
+                        .iter()
+                        .zip(deltas.iter())
+                        .map(|(point, delta)| *point + delta.map(Fixed::to_i32)),
+                );
+            }
+            Glyph::Composite(composite) => {
+                let component_count = composite.components().count();
+                let mut deltas =
+                    vec![Point::default(); component_count + crate::GLYF_PHANTOM_POINT_COUNT];
+                glyf_deltas::composite_glyph(&self.gvar, glyph_id, self.coords, &mut deltas)
+                    .ok()?;
+                let base = buffers.points.len();
+                for (component, delta) in composite.components().zip(&deltas) {
+                    let start = buffers.points.len();
+                    self.load_points(component.glyph, recurse_depth + 1, buffers)?;
+                    let points = &mut buffers.points;
+                    if component.flags.intersects(
+                        CompositeGlyphFlags::WE_HAVE_A_SCALE
+                            | CompositeGlyphFlags::WE_HAVE_AN_X_AND_Y_SCALE
+                            | CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO,
+                    ) {
+                        // Each product is rounded to match FreeType's
+                        // FT_MulFix.
+                        let mul = |value: i32, factor: F2Dot14| {
+                            (Fixed::from_bits(value) * factor.to_fixed()).to_bits()
+                        };
+                        let xform = &component.transform;
+                        for point in &mut points[start..] {
+                            *point = Point::new(
+                                mul(point.x, xform.xx) + mul(point.y, xform.xy),
+                                mul(point.x, xform.yx) + mul(point.y, xform.yy),
+                            );
+                        }
+                    }
+                    let offset = match component.anchor {
+                        Anchor::Offset { x, y } => {
+                            Point::new(x as i32, y as i32) + delta.map(Fixed::to_i32)
+                        }
+                        Anchor::Point {
+                            base: base_ix,
+                            component: component_ix,
+                        } => {
+                            *points.get(base + base_ix as usize)?
+                                - *points.get(start + component_ix as usize)?
+                        }
+                    };
+                    for point in &mut points[start..] {
+                        *point += offset;
+                    }
+                }
+            }
+        }
+        Some(())
     }
 
     /// Returns the glyph id and associated point count that determines the
@@ -475,7 +1066,7 @@ impl<'a> GvarMetricDeltas<'a> {
 mod tests {
     use super::*;
     use crate::MetadataProvider as _;
-    use font_test_data::{SIMPLE_GLYF, VAZIRMATN_VAR};
+    use font_test_data::{SIMPLE_GLYF, VAZIRMATN_VAR, VAZIRMATN_VAR_VERTICAL};
     use read_fonts::FontRef;
 
     #[test]
@@ -505,6 +1096,18 @@ mod tests {
                 offset: 307.0,
                 thickness: 51.0,
             }),
+            subscript: Some(ScriptMetrics {
+                x_size: 666.0,
+                y_size: 614.0,
+                x_offset: 0.0,
+                y_offset: 77.0,
+            }),
+            superscript: Some(ScriptMetrics {
+                x_size: 666.0,
+                y_size: 614.0,
+                x_offset: 0.0,
+                y_offset: 358.0,
+            }),
         };
         assert_eq!(metrics, expected);
     }
@@ -533,6 +1136,8 @@ mod tests {
             leading: 0.0,
             underline: None,
             strikeout: None,
+            subscript: None,
+            superscript: None,
         };
         assert_eq!(metrics, expected);
     }
@@ -631,6 +1236,249 @@ mod tests {
         }
     }
 
+    #[test]
+    fn vertical_metrics_missing_vmtx() {
+        let font = FontRef::new(VAZIRMATN_VAR).unwrap();
+        let glyph_metrics = font.glyph_metrics(Size::unscaled(), LocationRef::default());
+        // No OS/2 table so we fall back to hhea ascender (2100) and
+        // descender (-1100)
+        for gid in 0..glyph_metrics.glyph_count() {
+            let gid = GlyphId::new(gid);
+            let y_max = glyph_metrics.bounds(gid).unwrap().y_max;
+            assert_eq!(glyph_metrics.advance_height(gid), Some(3200.0));
+            assert_eq!(glyph_metrics.top_side_bearing(gid), Some(2100.0 - y_max));
+            assert_eq!(glyph_metrics.vertical_origin(gid), Some(2100.0));
+        }
+        assert_eq!(glyph_metrics.advance_height(GlyphId::new(4)), None);
+    }
+
+    /// Hides the given tables of a font.
+    struct WithoutTables<'a>(FontRef<'a>, &'a [Tag]);
+
+    impl<'a> TableProvider<'a> for WithoutTables<'a> {
+        fn data_for_tag(&self, tag: Tag) -> Option<read_fonts::FontData<'a>> {
+            if self.1.contains(&tag) {
+                None
+            } else {
+                self.0.data_for_tag(tag)
+            }
+        }
+    }
+
+    /// Returns (advance height, top side bearing, vertical origin) for each
+    /// glyph.
+    fn vertical_metrics<'a>(
+        font: &impl TableProvider<'a>,
+        coords: &'a [NormalizedCoord],
+    ) -> Vec<(f32, f32, f32)> {
+        let glyph_metrics = GlyphMetrics::new(font, Size::unscaled(), LocationRef::new(coords));
+        (0..glyph_metrics.glyph_count())
+            .map(|gid| {
+                let gid = GlyphId::new(gid);
+                (
+                    glyph_metrics.advance_height(gid).unwrap(),
+                    glyph_metrics.top_side_bearing(gid).unwrap(),
+                    glyph_metrics.vertical_origin(gid).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vertical_metrics_vmtx_vorg_and_vvar() {
+        let font = FontRef::new(VAZIRMATN_VAR_VERTICAL).unwrap();
+        // Glyphs 2 and 3 use the last advance from vmtx and the origin
+        // for all glyphs but 3 is the VORG default.
+        assert_eq!(
+            vertical_metrics(&font, &[]),
+            [
+                (2200.0, 100.0, 1800.0),
+                (2048.0, 300.0, 1800.0),
+                (2048.0, 120.0, 1800.0),
+                (2048.0, 400.0, 1900.0),
+            ]
+        );
+        // VVAR deltas at wght max, and at half of wght max where the
+        // fractional deltas are rounded.
+        assert_eq!(
+            vertical_metrics(&font, &[NormalizedCoord::from_f32(1.0)]),
+            [
+                (2200.0, 100.0, 1800.0),
+                (2108.0, 270.0, 1800.0),
+                (2108.0, 105.0, 1800.0),
+                (2078.0, 405.0, 1936.0),
+            ]
+        );
+        assert_eq!(
+            vertical_metrics(&font, &[NormalizedCoord::from_f32(0.5)]),
+            [
+                (2200.0, 100.0, 1800.0),
+                (2078.0, 285.0, 1800.0),
+                (2078.0, 113.0, 1800.0),
+                (2063.0, 403.0, 1918.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn vertical_metrics_gvar() {
+        // Without VVAR, deltas are taken from the phantom points. Without
+        // VORG, the origin is the top side bearing added to the top of the
+        // glyph.
+        let font = WithoutTables(
+            FontRef::new(VAZIRMATN_VAR_VERTICAL).unwrap(),
+            &[Tag::new(b"VVAR"), Tag::new(b"VORG")],
+        );
+        // Values from FreeType 2.12.1. The top side bearing of the composite
+        // glyph 2 is offset by 211 units since FreeType takes the default
+        // vertical metrics of a composite from the USE_MY_METRICS component.
+        let expected: [(&[NormalizedCoord], _); 3] = [
+            (
+                &[],
+                [
+                    (2200.0, 100.0, 100.0),
+                    (2048.0, 300.0, 1756.0),
+                    (2048.0, -91.0 + 211.0, 1967.0),
+                    (2048.0, 400.0, 1936.0),
+                ],
+            ),
+            (
+                // The top phantom point of glyph 1 moves down by 40 while
+                // the top of the composite glyph 2 moves up by 8.
+                &[NormalizedCoord::from_f32(-1.0)],
+                [
+                    (2200.0, 100.0, 100.0),
+                    (2008.0, 260.0, 1716.0),
+                    (2008.0, -139.0 + 211.0, 1927.0),
+                    (2048.0, 400.0, 1936.0),
+                ],
+            ),
+            (
+                // The top of glyph 3 is moved up by 60 through interpolated
+                // deltas.
+                &[NormalizedCoord::from_f32(1.0)],
+                [
+                    (2200.0, 100.0, 100.0),
+                    (2118.0, 320.0, 1806.0),
+                    (2118.0, -100.0 + 211.0, 2017.0),
+                    (2058.0, 350.0, 1946.0),
+                ],
+            ),
+        ];
+        for (coords, expected) in expected {
+            assert_eq!(vertical_metrics(&font, coords), expected, "{coords:?}");
+        }
+    }
+
+    #[rustfmt::skip]
+    static BASE: &[u8] = &[
+        // version 1.0, horizontal axis, no vertical axis
+        0, 1, 0, 0, 0, 8, 0, 0,
+        // axis: base tag list, base script list
+        0, 4, 0, 14,
+        // base tag list
+        0, 2, b'i', b'd', b'e', b'o', b'r', b'o', b'm', b'n',
+        // base script list
+        0, 1, b'l', b'a', b't', b'n', 0, 8,
+        // base script: base values, no min/max or language systems
+        0, 6, 0, 0, 0, 0,
+        // base values: default baseline index, coord count, offsets
+        0, 1, 0, 2, 0, 8, 0, 12,
+        // base coord format 1: -120
+        0, 1, 0xFF, 0x88,
+        // base coord format 3: 0 with device table
+        0, 3, 0, 0, 0, 6,
+        // device table: sizes 10..=13, 2-bit deltas 1, -1, 0, -2
+        0, 10, 0, 13, 0, 1, 0b0111_0010, 0,
+    ];
+
+    #[test]
+    fn baselines() {
+        use read_fonts::{tables::base::Base, FontData, FontRead};
+        let baselines_at = |ppem| Baselines {
+            base: Some(Base::read(FontData::new(BASE)).unwrap()),
+            values: LayoutValues {
+                var_store: None,
+                scale: 0.5,
+                ppem,
+                coords: &[],
+            },
+        };
+        let baselines = baselines_at(None);
+        let latn = baselines.horizontal(Tag::new(b"latn")).unwrap();
+        assert_eq!(latn.default_baseline, Some(Tag::new(b"romn")));
+        assert_eq!(
+            latn.baselines,
+            [(Tag::new(b"ideo"), -60.0), (Tag::new(b"romn"), 0.0)]
+        );
+        assert_eq!(latn.get(Tag::new(b"ideo")), Some(-60.0));
+        assert_eq!(latn.get(Tag::new(b"hang")), None);
+        // No DFLT script to fall back to
+        assert!(baselines.horizontal(Tag::new(b"cyrl")).is_none());
+        assert!(baselines.vertical(Tag::new(b"latn")).is_none());
+        // Device adjustments only apply with a matching ppem
+        for (ppem, romn) in [(10, 1.0), (11, -1.0), (13, -2.0), (14, 0.0)] {
+            let latn = baselines_at(Some(ppem)).horizontal(Tag::new(b"latn"));
+            assert_eq!(latn.unwrap().get(Tag::new(b"romn")), Some(romn), "{ppem}");
+        }
+    }
+
+    #[rustfmt::skip]
+    static GDEF: &[u8] = &[
+        // version 1.3 with a ligature caret list and item variation store
+        0, 1, 0, 3, 0, 0, 0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 58,
+        // ligature caret list: coverage, lig glyph count, offsets
+        0, 6, 0, 1, 0, 12,
+        // coverage: glyph 3
+        0, 1, 0, 1, 0, 3,
+        // lig glyph: caret count, offsets
+        0, 3, 0, 8, 0, 12, 0, 16,
+        // caret format 1: 300
+        0, 1, 0x01, 0x2C,
+        // caret format 2: contour point 5
+        0, 2, 0, 5,
+        // caret format 3: 600 with variation index
+        0, 3, 0x02, 0x58, 0, 6,
+        0, 0, 0, 0, 0x80, 0,
+        // item variation store
+        0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22,
+        // region list: single axis, peak at 1.0
+        0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0,
+        // item variation data with a single delta of -10
+        0, 1, 0, 0, 0, 1, 0, 0, 0xF6,
+    ];
+
+    #[test]
+    fn ligature_carets() {
+        use read_fonts::{tables::gdef::Gdef, FontData, FontRead};
+        let gdef = Gdef::read(FontData::new(GDEF)).unwrap();
+        let carets = |coords| LigatureCarets {
+            lig_caret_list: gdef.lig_caret_list().transpose().unwrap(),
+            values: LayoutValues {
+                var_store: gdef.item_var_store().transpose().unwrap(),
+                scale: 0.5,
+                ppem: None,
+                coords,
+            },
+        };
+        let default_carets = carets(&[]);
+        assert_eq!(
+            default_carets.get(GlyphId::new(3)).collect::<Vec<_>>(),
+            [
+                Caret::Coordinate(150.0),
+                Caret::ContourPoint(5),
+                Caret::Coordinate(300.0)
+            ]
+        );
+        assert_eq!(default_carets.get(GlyphId::new(4)).count(), 0);
+        let coords = &[NormalizedCoord::from_f32(1.0)];
+        let var_carets = carets(coords);
+        assert_eq!(
+            var_carets.get(GlyphId::new(3)).last(),
+            Some(Caret::Coordinate(295.0))
+        );
+    }
+
     /// Ensure our fixed point scaling code matches FreeType for advances.
     ///
     /// <https://github.com/googlefonts/fontations/issues/590>
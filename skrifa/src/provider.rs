@@ -1,9 +1,16 @@
 use super::{
     attribute::Attributes,
+    bitmap::BitmapStrikes,
     charmap::Charmap,
+    color::{ColorFormats, ColorGlyphCollection, ColorPalettes},
+    coverage::Coverage,
+    glyph_names::GlyphNames,
     instance::{LocationRef, Size},
-    metrics::{GlyphMetrics, Metrics},
+    kerning::Kerning,
+    math::MathLayout,
+    metrics::{Baselines, GlyphMetrics, LigatureCarets, Metrics},
     string::{LocalizedStrings, StringId},
+    style_name::AxisValueCollection,
     variation::{AxisCollection, NamedInstanceCollection},
 };
 
@@ -25,6 +32,12 @@ pub trait MetadataProvider<'a>: raw::TableProvider<'a> + Sized {
         NamedInstanceCollection::new(self)
     }
 
+    /// Returns the collection of named axis values used for composing style
+    /// names.
+    fn axis_values(&self) -> AxisValueCollection<'a> {
+        AxisValueCollection::new(self)
+    }
+
     /// Returns an iterator over the collection of localized strings for the
     /// given informational string identifier.
     fn localized_strings(&self, id: StringId) -> LocalizedStrings<'a> {
@@ -43,10 +56,69 @@ pub trait MetadataProvider<'a>: raw::TableProvider<'a> + Sized {
         GlyphMetrics::new(self, size, location)
     }
 
+    /// Returns the per script baseline positions for the specified size and
+    /// location in normalized variation space.
+    fn baselines(&self, size: Size, location: impl Into<LocationRef<'a>>) -> Baselines<'a> {
+        Baselines::new(self, size, location)
+    }
+
+    /// Returns the ligature caret positions for the specified size and
+    /// location in normalized variation space.
+    fn ligature_carets(
+        &self,
+        size: Size,
+        location: impl Into<LocationRef<'a>>,
+    ) -> LigatureCarets<'a> {
+        LigatureCarets::new(self, size, location)
+    }
+
     /// Returns the character to nominal glyph identifier mapping.
     fn charmap(&self) -> Charmap<'a> {
         Charmap::new(self)
     }
+
+    /// Returns the report of scripts and orthographies supported by the
+    /// character map.
+    fn coverage(&self) -> Coverage {
+        Coverage::new(self)
+    }
+
+    /// Returns the collection of color glyphs.
+    fn color_glyphs(&self) -> ColorGlyphCollection<'a> {
+        ColorGlyphCollection::new(self)
+    }
+
+    /// Returns the set of color glyph formats present in the font.
+    fn color_formats(&self) -> ColorFormats {
+        ColorFormats::new(self)
+    }
+
+    /// Returns the collection of color palettes.
+    fn palettes(&self) -> ColorPalettes<'a> {
+        ColorPalettes::new(self)
+    }
+
+    /// Returns the collection of bitmap strikes.
+    fn bitmap_strikes(&self) -> BitmapStrikes<'a> {
+        BitmapStrikes::new(self)
+    }
+
+    /// Returns the mapping between glyph identifiers and names.
+    fn glyph_names(&self) -> GlyphNames<'a> {
+        GlyphNames::new(self)
+    }
+
+    /// Returns the pair kerning adjustments for the specified size and
+    /// location in normalized variation space.
+    fn kerning(&self, size: Size, location: impl Into<LocationRef<'a>>) -> Kerning<'a> {
+        Kerning::new(self, size, location)
+    }
+
+    /// Returns the mathematical layout data for the specified size and
+    /// location in normalized variation space.
+    fn math_layout(&self, size: Size, location: impl Into<LocationRef<'a>>) -> MathLayout<'a> {
+        MathLayout::new(self, size, location)
+    }
 }
 
 /// Blanket implementation of `MetadataProvider` for any type that implements
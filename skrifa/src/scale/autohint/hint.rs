@@ -0,0 +1,421 @@
+//! Edge detection and grid fitting for the vertical direction.
+//!
+//! This is a simplified version of FreeType's light auto-hinting mode:
+//! horizontal segments are collected from the outline, grouped into edges
+//! and linked into stems. Edges are then aligned to blue zones and the
+//! pixel grid and the remaining points are interpolated.
+//!
+//! See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/autofit/aflatin.c>
+
+use super::{super::outline::Outline, metrics::Metrics};
+
+/// A segment is considered horizontal when its horizontal extent is at
+/// least this many times larger than its vertical extent.
+const FLAT_RATIO: f32 = 14.0;
+
+/// Maximum distance between segments that are merged into a single edge,
+/// in pixels.
+const EDGE_THRESHOLD: f32 = 0.25;
+
+/// Applies vertical hinting to the outline.
+pub fn hint_outline(metrics: &Metrics, outline: &mut Outline) {
+    if metrics.y_scale != 1.0 {
+        for point in &mut outline.points {
+            point.y *= metrics.y_scale;
+        }
+    }
+    let mut edges = compute_edges(metrics, outline);
+    if edges.is_empty() {
+        return;
+    }
+    align_blue_edges(metrics, &mut edges);
+    align_stem_edges(&mut edges);
+    align_remaining_edges(&mut edges);
+    // Interpolate all points between the fitted edges and then move the
+    // points that define each edge to the fitted position.
+    let fitted = edges
+        .iter()
+        .map(|edge| (edge.pos, edge.fitted))
+        .collect::<Vec<_>>();
+    for point in &mut outline.points {
+        point.y = interpolate(&fitted, point.y);
+    }
+    for edge in &edges {
+        for &ix in &edge.points {
+            outline.points[ix].y = edge.fitted;
+        }
+    }
+}
+
+/// A run of nearly horizontal outline points.
+#[derive(Clone, Debug)]
+struct Segment {
+    y: f32,
+    min_x: f32,
+    max_x: f32,
+    is_top: bool,
+    is_round: bool,
+    /// Indices of the points in the outline.
+    points: Vec<usize>,
+}
+
+/// A set of segments sharing the same vertical position.
+#[derive(Clone, Default, Debug)]
+struct Edge {
+    /// Original position.
+    pos: f32,
+    /// Fitted position, valid when `is_fixed` is true.
+    fitted: f32,
+    is_fixed: bool,
+    is_top: bool,
+    is_round: bool,
+    /// Index of the opposite edge of a stem.
+    link: Option<usize>,
+    /// Horizontal extents of the segments.
+    extents: Vec<(f32, f32)>,
+    /// Indices of the points of all segments.
+    points: Vec<usize>,
+}
+
+impl Edge {
+    fn overlaps(&self, other: &Edge) -> bool {
+        self.extents.iter().any(|(min0, max0)| {
+            other
+                .extents
+                .iter()
+                .any(|(min1, max1)| min0 < max1 && min1 < max0)
+        })
+    }
+}
+
+fn compute_edges(metrics: &Metrics, outline: &Outline) -> Vec<Edge> {
+    // The orientation of the outline determines which side of a segment
+    // is filled.
+    let mut area = 0.0;
+    for contour in outline.contours() {
+        let points = &outline.points[contour];
+        for (i, p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            area += (p1.x - p0.x) * (p1.y + p0.y);
+        }
+    }
+    // Positive for clockwise contours (TrueType), negative for counter
+    // clockwise (PostScript).
+    let is_clockwise = area > 0.0;
+    let min_length = metrics.size / 50.0;
+    let mut segments = vec![];
+    for contour in outline.contours() {
+        let base = contour.start;
+        let points = &outline.points[contour.clone()];
+        let on_curve = &outline.on_curve[contour];
+        let len = points.len();
+        // Start at a non horizontal step so that runs are not split at
+        // the beginning of the contour.
+        let is_flat = |i: usize| {
+            let (p0, p1) = (points[i], points[(i + 1) % len]);
+            let (dx, dy) = (p1.x - p0.x, p1.y - p0.y);
+            (dx != 0.0 && dy.abs() * FLAT_RATIO <= dx.abs()).then_some(dx > 0.0)
+        };
+        let Some(start) = (0..len).find(|&i| is_flat(i).is_none()) else {
+            continue;
+        };
+        let mut run: Option<(bool, Segment)> = None;
+        for step in 1..=len {
+            let i = (start + step) % len;
+            let next = (i + 1) % len;
+            let dir = is_flat(i);
+            if let Some((run_dir, segment)) = run.as_mut() {
+                if dir == Some(*run_dir) {
+                    let p = points[next];
+                    segment.min_x = segment.min_x.min(p.x);
+                    segment.max_x = segment.max_x.max(p.x);
+                    segment.is_round |= !on_curve[next];
+                    segment.points.push(base + next);
+                    continue;
+                }
+                if let Some((_, mut segment)) = run.take() {
+                    segment.y = segment
+                        .points
+                        .iter()
+                        .map(|&ix| outline.points[ix].y)
+                        .sum::<f32>()
+                        / segment.points.len() as f32;
+                    if segment.max_x - segment.min_x >= min_length {
+                        segments.push(segment);
+                    }
+                }
+            }
+            if let Some(dir) = dir {
+                let (p0, p1) = (points[i], points[next]);
+                run = Some((
+                    dir,
+                    Segment {
+                        y: 0.0,
+                        min_x: p0.x.min(p1.x),
+                        max_x: p0.x.max(p1.x),
+                        is_top: dir == is_clockwise,
+                        is_round: !on_curve[i] || !on_curve[next],
+                        points: vec![base + i, base + next],
+                    },
+                ));
+            }
+        }
+    }
+    segments.sort_by(|a, b| a.y.total_cmp(&b.y));
+    // Group segments with the same orientation into edges.
+    let mut edges: Vec<Edge> = vec![];
+    for segment in &segments {
+        let length = segment.max_x - segment.min_x;
+        let existing = edges.iter_mut().rev().find(|edge| {
+            edge.is_top == segment.is_top && (segment.y - edge.pos).abs() <= EDGE_THRESHOLD
+        });
+        if let Some(edge) = existing {
+            // Keep a length weighted average of the segment positions.
+            let total: f32 = edge.extents.iter().map(|(min, max)| max - min).sum();
+            edge.pos = (edge.pos * total + segment.y * length) / (total + length);
+            edge.is_round &= segment.is_round;
+            edge.extents.push((segment.min_x, segment.max_x));
+            edge.points.extend_from_slice(&segment.points);
+        } else {
+            edges.push(Edge {
+                pos: segment.y,
+                is_top: segment.is_top,
+                is_round: segment.is_round,
+                extents: vec![(segment.min_x, segment.max_x)],
+                points: segment.points.clone(),
+                ..Default::default()
+            });
+        }
+    }
+    edges.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    link_stems(metrics, &mut edges);
+    edges
+}
+
+/// Pairs bottom edges with the nearest overlapping top edges above them.
+fn link_stems(metrics: &Metrics, edges: &mut [Edge]) {
+    let max_distance = metrics.size / 4.0;
+    let mut candidates = vec![];
+    for (i, bottom) in edges.iter().enumerate().filter(|(_, edge)| !edge.is_top) {
+        for (j, top) in edges.iter().enumerate().skip(i + 1) {
+            let distance = top.pos - bottom.pos;
+            if distance > max_distance {
+                break;
+            }
+            if top.is_top && distance > 0.0 && bottom.overlaps(top) {
+                candidates.push((distance, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    for (_, i, j) in candidates {
+        if edges[i].link.is_none() && edges[j].link.is_none() {
+            edges[i].link = Some(j);
+            edges[j].link = Some(i);
+        }
+    }
+}
+
+/// Snaps edges that fall within blue zones.
+fn align_blue_edges(metrics: &Metrics, edges: &mut [Edge]) {
+    let max_distance = (metrics.size / 40.0).min(0.5);
+    for edge in edges {
+        let mut best_distance = max_distance;
+        for blue in metrics
+            .blues
+            .iter()
+            .filter(|blue| blue.is_top == edge.is_top)
+        {
+            // Edges on the far side of the reference are compared against
+            // the overshoot.
+            let is_overshoot = if blue.is_top {
+                edge.pos > blue.reference
+            } else {
+                edge.pos < blue.reference
+            };
+            let candidates = [
+                (blue.reference, blue.fitted_reference),
+                (blue.overshoot, blue.fitted_overshoot),
+            ];
+            for (pos, fitted) in candidates.iter().take(1 + is_overshoot as usize) {
+                let distance = (edge.pos - pos).abs();
+                if distance < best_distance {
+                    best_distance = distance;
+                    edge.fitted = *fitted;
+                    edge.is_fixed = true;
+                }
+            }
+        }
+    }
+}
+
+/// Fits stems, preserving the position of edges already aligned to blue
+/// zones.
+fn align_stem_edges(edges: &mut [Edge]) {
+    // Delta of the first fitted stem, used to position subsequent stems.
+    let mut anchor: Option<f32> = None;
+    for i in 0..edges.len() {
+        let Some(j) = edges[i].link.filter(|&j| j > i) else {
+            continue;
+        };
+        let (bottom, top) = (&edges[i], &edges[j]);
+        let distance = top.pos - bottom.pos;
+        let width = stem_width(distance, bottom.is_round || top.is_round);
+        match (bottom.is_fixed, top.is_fixed) {
+            (true, true) => continue,
+            (true, false) => {
+                edges[j].fitted = edges[i].fitted + width;
+                edges[j].is_fixed = true;
+            }
+            (false, true) => {
+                edges[i].fitted = edges[j].fitted - width;
+                edges[i].is_fixed = true;
+            }
+            (false, false) => {
+                let pos = bottom.pos + anchor.unwrap_or_default();
+                let center = pos + distance * 0.5;
+                let fitted = if width <= 1.5 {
+                    // Choose the pixel boundary that best preserves the
+                    // center of thin stems.
+                    let (up, down) = if width <= 1.0 {
+                        (0.5, 0.5)
+                    } else {
+                        (38.0 / 64.0, 26.0 / 64.0)
+                    };
+                    let rounded = center.round();
+                    let center =
+                        if (center - (rounded - up)).abs() < (center - (rounded + down)).abs() {
+                            rounded - up
+                        } else {
+                            rounded + down
+                        };
+                    center - width * 0.5
+                } else {
+                    (center - width * 0.5).round()
+                };
+                anchor.get_or_insert(fitted - bottom.pos);
+                edges[i].fitted = fitted;
+                edges[j].fitted = fitted + width;
+                edges[i].is_fixed = true;
+                edges[j].is_fixed = true;
+            }
+        }
+    }
+}
+
+/// Positions edges that are neither part of a stem nor aligned to a blue
+/// zone by interpolating between their fixed neighbors.
+fn align_remaining_edges(edges: &mut [Edge]) {
+    let fixed = edges
+        .iter()
+        .filter(|edge| edge.is_fixed)
+        .map(|edge| (edge.pos, edge.fitted))
+        .collect::<Vec<_>>();
+    for edge in edges.iter_mut().filter(|edge| !edge.is_fixed) {
+        edge.fitted = if fixed.is_empty() {
+            edge.pos.round()
+        } else {
+            interpolate(&fixed, edge.pos)
+        };
+        edge.is_fixed = true;
+    }
+    // Preserve the original ordering of the edges.
+    for i in 1..edges.len() {
+        if edges[i].fitted < edges[i - 1].fitted {
+            edges[i].fitted = edges[i - 1].fitted;
+        }
+    }
+}
+
+/// Quantizes the height of a horizontal stem.
+///
+/// This is the "smooth" branch of FreeType's `af_latin_compute_stem_width`
+/// computed in 26.6 units.
+fn stem_width(distance: f32, is_round: bool) -> f32 {
+    let mut dist = (distance * 64.0) as i32;
+    if is_round {
+        if dist < 80 {
+            dist = 64;
+        }
+    } else if dist < 56 {
+        dist = 56;
+    }
+    if dist < 3 * 64 {
+        let delta = dist & 63;
+        dist &= !63;
+        dist += if delta < 10 {
+            delta
+        } else if delta < 32 {
+            10
+        } else if delta < 54 {
+            54
+        } else {
+            delta
+        };
+    } else {
+        dist = (dist + 32) & !63;
+    }
+    dist as f32 / 64.0
+}
+
+/// Maps a position through a sorted list of `(original, fitted)` edge
+/// positions.
+fn interpolate(fixed: &[(f32, f32)], pos: f32) -> f32 {
+    let mut prev: Option<(f32, f32)> = None;
+    for &(org, fitted) in fixed {
+        if pos <= org {
+            return match prev {
+                Some((prev_org, prev_fitted)) if org > prev_org => {
+                    let t = (pos - prev_org) / (org - prev_org);
+                    prev_fitted + t * (fitted - prev_fitted)
+                }
+                _ => pos + fitted - org,
+            };
+        }
+        prev = Some((org, fitted));
+    }
+    prev.map(|(org, fitted)| pos + fitted - org).unwrap_or(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::{Context, Hinting, Size};
+    use super::*;
+    use crate::charmap::Charmap;
+    use read_fonts::FontRef;
+
+    #[test]
+    fn align_to_blue_zones() {
+        let font = FontRef::new(font_test_data::TTHINT_SUBSET).unwrap();
+        let charmap = Charmap::new(&font);
+        let mut cx = Context::new();
+        let mut scaler = cx
+            .new_scaler()
+            .size(Size::new(16.0))
+            .hint(Some(Hinting::Auto))
+            .build(&font);
+        let mut outline = Outline::default();
+        // Baseline, cap height and x-height
+        for (ch, top) in [('H', 12.0), ('x', 9.0), ('o', 9.0)] {
+            outline.clear();
+            scaler
+                .outline(charmap.map(ch).unwrap(), &mut outline)
+                .unwrap();
+            let ys = || outline.points.iter().map(|point| point.y);
+            assert_eq!(ys().fold(f32::MIN, f32::max), top);
+            assert_eq!(ys().fold(f32::MAX, f32::min), 0.0);
+        }
+    }
+
+    #[test]
+    fn stem_widths() {
+        // Thin stems are expanded to a full pixel.
+        assert_eq!(stem_width(0.5, true), 1.0);
+        assert_eq!(stem_width(0.5, false), 0.875);
+        // Fractions are lightly quantized.
+        assert_eq!(stem_width(1.25, false), 1.15625);
+        assert_eq!(stem_width(1.75, false), 1.84375);
+        // Wide stems are rounded.
+        assert_eq!(stem_width(3.4, false), 3.0);
+    }
+}
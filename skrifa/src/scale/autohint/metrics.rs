@@ -0,0 +1,287 @@
+//! Blue zones and scaling metrics for the auto-hinter.
+//!
+//! Loosely based on FreeType's Latin writing system metrics.
+//!
+//! See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/autofit/aflatin.c>
+
+use read_fonts::types::GlyphId;
+
+use super::super::outline::Outline;
+use crate::charmap::Charmap;
+
+/// Reference characters used to compute the metrics for a script.
+struct ScriptClass {
+    /// Character that must be mapped by the font to select this script.
+    standard_char: char,
+    /// Sets of characters that define each blue zone.
+    blues: &'static [(&'static str, u8)],
+}
+
+/// The blue zone is at the top of the glyphs.
+const TOP: u8 = 1;
+/// The blue zone defines the x-height which is used to adjust the vertical
+/// scale.
+const X_HEIGHT: u8 = 2;
+
+/// Supported scripts in order of preference.
+///
+/// Blue strings are taken from FreeType's `afblue.dat`.
+const SCRIPT_CLASSES: &[ScriptClass] = &[
+    // Latin
+    ScriptClass {
+        standard_char: 'o',
+        blues: &[
+            ("THEZOCQS", TOP),
+            ("HEZLOCUS", 0),
+            ("fijkdbh", TOP),
+            ("xzroesc", TOP | X_HEIGHT),
+            ("xzroesc", 0),
+            ("pqgjy", 0),
+        ],
+    },
+    // Cyrillic
+    ScriptClass {
+        standard_char: 'о',
+        blues: &[
+            ("БВЕПЗОСЭ", TOP),
+            ("БВЕШЗОСЭ", 0),
+            ("хпншезос", TOP | X_HEIGHT),
+            ("хпншезос", 0),
+            ("руф", 0),
+        ],
+    },
+    // Greek
+    ScriptClass {
+        standard_char: 'ο',
+        blues: &[
+            ("ΓΒΕΖΘΟΩ", TOP),
+            ("ΒΔΖΞΘΟ", 0),
+            ("βθδζλξ", TOP),
+            ("αειοπστω", TOP | X_HEIGHT),
+            ("αειοπστω", 0),
+            ("βγημρφχψ", 0),
+        ],
+    },
+];
+
+/// Maximum height of the overshoot region for an active blue zone, in
+/// pixels.
+const MAX_OVERSHOOT: f32 = 0.75;
+
+/// A blue zone with positions in pixels.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct BlueZone {
+    /// Position of flat features.
+    pub reference: f32,
+    /// Position of round features that overshoot the reference.
+    pub overshoot: f32,
+    /// Grid fitted reference position.
+    pub fitted_reference: f32,
+    /// Grid fitted overshoot position.
+    pub fitted_overshoot: f32,
+    /// True if the zone is at the top of the glyphs.
+    pub is_top: bool,
+}
+
+/// Metrics used by the auto-hinter for a font at a particular size and
+/// location in variation space.
+#[derive(Clone, Default, Debug)]
+pub struct Metrics {
+    /// Size in pixels per em.
+    pub size: f32,
+    /// Factor applied to vertical coordinates that aligns the x-height to
+    /// the pixel grid.
+    pub y_scale: f32,
+    /// Blue zones with positions adjusted by `y_scale`.
+    pub blues: Vec<BlueZone>,
+}
+
+impl Metrics {
+    /// Computes metrics for the given size.
+    ///
+    /// The `load` function is used to fetch outlines for the reference
+    /// characters at the same size and should return false if the glyph
+    /// cannot be loaded.
+    pub fn new(
+        size: f32,
+        charmap: &Charmap,
+        mut load: impl FnMut(GlyphId, &mut Outline) -> bool,
+    ) -> Self {
+        let mut metrics = Self {
+            size,
+            y_scale: 1.0,
+            blues: vec![],
+        };
+        let Some(class) = SCRIPT_CLASSES
+            .iter()
+            .find(|class| charmap.map(class.standard_char).is_some())
+        else {
+            return metrics;
+        };
+        let mut outline = Outline::default();
+        let mut x_height = None;
+        for (chars, flags) in class.blues {
+            let is_top = flags & TOP != 0;
+            let mut flats = Extrema::default();
+            let mut rounds = Extrema::default();
+            for glyph_id in chars.chars().filter_map(|ch| charmap.map(ch)) {
+                outline.clear();
+                if !load(glyph_id, &mut outline) {
+                    continue;
+                }
+                if let Some((y, is_flat)) = extremum(&outline, is_top) {
+                    if is_flat {
+                        flats.add(y);
+                    } else {
+                        rounds.add(y);
+                    }
+                }
+            }
+            let (reference, overshoot) = match (flats.average(), rounds.average()) {
+                (Some(flat), Some(round)) => (flat, round),
+                (Some(flat), None) => (flat, flat),
+                (None, Some(round)) => (round, round),
+                (None, None) => continue,
+            };
+            // Overshoots must extend away from the glyph body.
+            let overshoot = if is_top {
+                overshoot.max(reference)
+            } else {
+                overshoot.min(reference)
+            };
+            if flags & X_HEIGHT != 0 {
+                x_height = Some(reference);
+            }
+            metrics.blues.push(BlueZone {
+                reference,
+                overshoot,
+                is_top,
+                ..Default::default()
+            });
+        }
+        if let Some(x_height) = x_height.filter(|x_height| *x_height > 0.0) {
+            // Round the x-height with a bias towards larger values. This
+            // matches FreeType's `(scaled + 40) & ~63`.
+            let fitted = (x_height + 40.0 / 64.0).floor();
+            if fitted > 0.0 {
+                metrics.y_scale = fitted / x_height;
+            }
+        }
+        let y_scale = metrics.y_scale;
+        metrics.blues.retain_mut(|blue| {
+            blue.reference *= y_scale;
+            blue.overshoot *= y_scale;
+            let delta = blue.overshoot - blue.reference;
+            // Ignore zones with overshoots that are too large to be
+            // meaningful at this size.
+            if delta.abs() > MAX_OVERSHOOT {
+                return false;
+            }
+            blue.fitted_reference = blue.reference.round();
+            blue.fitted_overshoot = blue.fitted_reference + fit_overshoot(delta);
+            true
+        });
+        metrics
+    }
+}
+
+/// Rounds the distance between the reference and overshoot positions of
+/// a blue zone.
+///
+/// Overshoots are suppressed below half a pixel and rounded to half pixel
+/// increments below one pixel.
+fn fit_overshoot(delta: f32) -> f32 {
+    let dist = delta.abs();
+    let fitted = if dist < 0.5 {
+        0.0
+    } else if dist < 1.0 {
+        0.5 + ((dist - 0.5) * 2.0 + 0.5).floor() * 0.5
+    } else {
+        dist.round()
+    };
+    fitted.copysign(delta)
+}
+
+/// Finds the vertical extremum of the outline, returning the position and
+/// true if the extremum lies on a flat (horizontal line) segment.
+fn extremum(outline: &Outline, is_top: bool) -> Option<(f32, bool)> {
+    let better = |a: f32, b: f32| if is_top { a > b } else { a < b };
+    let mut best: Option<(f32, bool)> = None;
+    for contour in outline.contours() {
+        let points = &outline.points[contour.clone()];
+        let on_curve = &outline.on_curve[contour];
+        for (i, point) in points.iter().enumerate() {
+            let y = point.y;
+            if !on_curve[i] {
+                // Off curve points are only considered to detect round
+                // extrema, in which case the curve itself is bounded by
+                // the neighboring on curve points.
+                continue;
+            }
+            let next = (i + 1) % points.len();
+            let prev = (i + points.len() - 1) % points.len();
+            let is_flat = [prev, next].iter().any(|&j| {
+                on_curve[j] && (points[j].y - y).abs() < 0.01 && (points[j].x - point.x).abs() > 0.0
+            });
+            // Prefer flat points when the extrema are equal.
+            let is_better = match best {
+                Some((best_y, _)) => better(y, best_y) || (y == best_y && is_flat),
+                None => true,
+            };
+            if is_better {
+                best = Some((y, is_flat));
+            }
+        }
+    }
+    best
+}
+
+/// Accumulator for computing the average of a set of extrema.
+#[derive(Copy, Clone, Default)]
+struct Extrema {
+    sum: f32,
+    count: u32,
+}
+
+impl Extrema {
+    fn add(&mut self, value: f32) {
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn average(&self) -> Option<f32> {
+        (self.count != 0).then(|| self.sum / self.count as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::{Context, Size};
+    use super::*;
+    use read_fonts::FontRef;
+
+    #[test]
+    fn latin_blue_zones() {
+        let font = FontRef::new(font_test_data::TTHINT_SUBSET).unwrap();
+        let charmap = Charmap::new(&font);
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().size(Size::new(16.0)).build(&font);
+        let metrics = Metrics::new(16.0, &charmap, |glyph_id, outline| {
+            scaler.outline(glyph_id, outline).is_ok()
+        });
+        // The x-height is fitted to the pixel grid.
+        assert!(metrics
+            .blues
+            .iter()
+            .any(|blue| blue.is_top && (blue.reference - 9.0).abs() < 1e-3));
+        for blue in &metrics.blues {
+            assert_eq!(blue.fitted_reference.fract(), 0.0);
+        }
+        let baseline = metrics
+            .blues
+            .iter()
+            .find(|blue| !blue.is_top && blue.reference.abs() < 0.5)
+            .unwrap();
+        assert_eq!(baseline.fitted_reference, 0.0);
+    }
+}
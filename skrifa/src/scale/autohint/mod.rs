@@ -0,0 +1,11 @@
+//! Automatic hinting.
+//!
+//! Generates hinted outlines from the shapes of the glyphs rather than
+//! from font specific instructions. Only the vertical direction is
+//! adjusted, similar to FreeType's light auto-hinting mode.
+
+mod hint;
+mod metrics;
+
+pub use hint::hint_outline;
+pub use metrics::Metrics;
@@ -0,0 +1,242 @@
+//! Bounding box computation for scaled outlines.
+
+use super::Pen;
+use crate::metrics::BoundingBox;
+
+/// Method for computing the bounding box of an outline.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum BoundsMode {
+    /// Bounds of all points in the outline, including off-curve control
+    /// points.
+    ///
+    /// This is cheaper to compute but may be larger than the area covered
+    /// by the outline.
+    ControlBox,
+    /// Exact bounds of the outline, computed from the extrema of each
+    /// curve segment.
+    #[default]
+    Tight,
+}
+
+/// Pen that accumulates the bounding box of an outline.
+pub(super) struct BoundsPen {
+    mode: BoundsMode,
+    /// Current point, used as the start of the next segment.
+    current: (f32, f32),
+    /// Accumulated bounds as [x_min, y_min, x_max, y_max].
+    bounds: Option<[f32; 4]>,
+}
+
+impl BoundsPen {
+    pub fn new(mode: BoundsMode) -> Self {
+        Self {
+            mode,
+            current: (0.0, 0.0),
+            bounds: None,
+        }
+    }
+
+    /// Returns the accumulated bounding box.
+    ///
+    /// Empty outlines produce an empty bounding box at the origin.
+    pub fn bounds(&self) -> BoundingBox {
+        match self.bounds {
+            Some([x_min, y_min, x_max, y_max]) => BoundingBox {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            },
+            None => BoundingBox::default(),
+        }
+    }
+
+    fn add_point(&mut self, x: f32, y: f32) {
+        self.bounds = Some(match self.bounds {
+            Some([x_min, y_min, x_max, y_max]) => {
+                [x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y)]
+            }
+            None => [x, y, x, y],
+        });
+    }
+
+    /// Returns true if the given point lies within the current bounds.
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.bounds
+            .map(|[x_min, y_min, x_max, y_max]| {
+                x >= x_min && x <= x_max && y >= y_min && y <= y_max
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl Pen for BoundsPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add_point(x, y);
+        self.current = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.add_point(x, y);
+        self.current = (x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let (x0, y0) = self.current;
+        self.add_point(x, y);
+        self.current = (x, y);
+        if self.mode == BoundsMode::ControlBox {
+            self.add_point(cx0, cy0);
+            return;
+        }
+        // The curve is contained in the hull of its control points so
+        // there are no new extrema if the control point is already inside
+        // the bounds
+        if self.contains(cx0, cy0) {
+            return;
+        }
+        let (px, py) = (quad_extrema(x0, cx0, x), quad_extrema(y0, cy0, y));
+        for t in [px, py].into_iter().flatten() {
+            let (ex, ey) = (quad_eval(x0, cx0, x, t), quad_eval(y0, cy0, y, t));
+            self.add_point(ex, ey);
+        }
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let (x0, y0) = self.current;
+        self.add_point(x, y);
+        self.current = (x, y);
+        if self.mode == BoundsMode::ControlBox {
+            self.add_point(cx0, cy0);
+            self.add_point(cx1, cy1);
+            return;
+        }
+        if self.contains(cx0, cy0) && self.contains(cx1, cy1) {
+            return;
+        }
+        let roots_x = cubic_extrema(x0, cx0, cx1, x);
+        let roots_y = cubic_extrema(y0, cy0, cy1, y);
+        for t in roots_x.into_iter().chain(roots_y).flatten() {
+            let ex = cubic_eval(x0, cx0, cx1, x, t);
+            let ey = cubic_eval(y0, cy0, cy1, y, t);
+            self.add_point(ex, ey);
+        }
+    }
+
+    fn close(&mut self) {}
+}
+
+/// Returns the parameter of the extremum of a quadratic bezier in one
+/// dimension if it lies strictly within the curve.
+fn quad_extrema(p0: f32, p1: f32, p2: f32) -> Option<f32> {
+    let denom = p0 - 2.0 * p1 + p2;
+    if denom == 0.0 {
+        return None;
+    }
+    let t = (p0 - p1) / denom;
+    (t > 0.0 && t < 1.0).then_some(t)
+}
+
+fn quad_eval(p0: f32, p1: f32, p2: f32, t: f32) -> f32 {
+    let mt = 1.0 - t;
+    mt * mt * p0 + 2.0 * mt * t * p1 + t * t * p2
+}
+
+/// Returns the parameters of the extrema of a cubic bezier in one
+/// dimension that lie strictly within the curve.
+fn cubic_extrema(p0: f32, p1: f32, p2: f32, p3: f32) -> [Option<f32>; 2] {
+    // Coefficients of the derivative (divided by 3): a*t^2 + b*t + c
+    let a = -p0 + 3.0 * (p1 - p2) + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+    let in_range = |t: f32| (t > 0.0 && t < 1.0).then_some(t);
+    if a.abs() < 1e-6 {
+        // Derivative is linear
+        if b == 0.0 {
+            return [None, None];
+        }
+        return [in_range(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let sqrt = discriminant.sqrt();
+    [
+        in_range((-b + sqrt) / (2.0 * a)),
+        in_range((-b - sqrt) / (2.0 * a)),
+    ]
+}
+
+fn cubic_eval(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let mt = 1.0 - t;
+    mt * mt * mt * p0 + 3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t * p3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_outline() {
+        let pen = BoundsPen::new(BoundsMode::Tight);
+        assert_eq!(pen.bounds(), BoundingBox::default());
+    }
+
+    #[test]
+    fn quad_bounds() {
+        let bounds = |mode| {
+            let mut pen = BoundsPen::new(mode);
+            pen.move_to(0.0, 0.0);
+            pen.quad_to(50.0, 100.0, 100.0, 0.0);
+            pen.close();
+            pen.bounds()
+        };
+        assert_eq!(
+            bounds(BoundsMode::ControlBox),
+            BoundingBox {
+                x_min: 0.0,
+                y_min: 0.0,
+                x_max: 100.0,
+                y_max: 100.0,
+            }
+        );
+        // The peak of the curve is at t = 0.5
+        assert_eq!(
+            bounds(BoundsMode::Tight),
+            BoundingBox {
+                x_min: 0.0,
+                y_min: 0.0,
+                x_max: 100.0,
+                y_max: 50.0,
+            }
+        );
+    }
+
+    #[test]
+    fn cubic_bounds() {
+        let bounds = |mode| {
+            let mut pen = BoundsPen::new(mode);
+            pen.move_to(0.0, 0.0);
+            pen.curve_to(0.0, 100.0, 100.0, -100.0, 100.0, 0.0);
+            pen.close();
+            pen.bounds()
+        };
+        assert_eq!(
+            bounds(BoundsMode::ControlBox),
+            BoundingBox {
+                x_min: 0.0,
+                y_min: -100.0,
+                x_max: 100.0,
+                y_max: 100.0,
+            }
+        );
+        let tight = bounds(BoundsMode::Tight);
+        assert_eq!((tight.x_min, tight.x_max), (0.0, 100.0));
+        // y(t) = 300t(1 - t)(1 - 2t) with extrema at t = (3 -/+ sqrt(3)) / 6
+        let t = (3.0 - 3f32.sqrt()) / 6.0;
+        let extremum = 300.0 * t * (1.0 - t) * (1.0 - 2.0 * t);
+        assert!((tight.y_max - extremum).abs() < 1e-3);
+        assert!((tight.y_min + extremum).abs() < 1e-3);
+    }
+}
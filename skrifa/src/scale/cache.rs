@@ -0,0 +1,168 @@
+//! Cache of hinting state.
+
+use read_fonts::types::F2Dot14;
+
+use super::Hinting;
+use crate::font::UniqueId;
+
+/// Maximum number of entries retained by the cache.
+const MAX_ENTRIES: usize = 8;
+
+/// Bounded least recently used cache of hinting state.
+///
+/// Each entry holds state (such as the result of executing the TrueType
+/// font and control value programs) for a particular font, size, location
+/// in variation space and hinting mode.
+///
+/// Entries are keyed by the requested size rather than the rounded ppem.
+/// The hinting state depends on both: the ppem is visible to the TrueType
+/// programs, but control values (and the autohinter's metrics) are scaled
+/// by the unrounded size, so two sizes that share a ppem produce different
+/// state.
+#[derive(Clone, Debug)]
+pub struct InstanceCache<T> {
+    entries: Vec<Entry<T>>,
+    /// Instance used for fonts without an identifier. This never displaces
+    /// a cached entry.
+    scratch: T,
+    serial: u64,
+}
+
+impl<T: Default> Default for InstanceCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            scratch: T::default(),
+            serial: 0,
+        }
+    }
+}
+
+impl<T: Default> InstanceCache<T> {
+    /// Returns an instance for the given configuration along with a flag
+    /// that is true if the instance was found in the cache.
+    ///
+    /// When the flag is false, the instance must be reconfigured by the
+    /// caller before use. A `None` identifier bypasses the cache and always
+    /// returns a scratch instance that requires reconfiguration.
+    pub fn instance(
+        &mut self,
+        id: Option<UniqueId>,
+        size: f32,
+        coords: &[F2Dot14],
+        mode: Hinting,
+    ) -> (bool, &mut T) {
+        let Some(id) = id else {
+            return (false, &mut self.scratch);
+        };
+        self.serial = self.serial.wrapping_add(1);
+        let serial = self.serial;
+        if let Some(ix) = self
+            .entries
+            .iter()
+            .position(|entry| entry.matches(id, size, coords, mode))
+        {
+            let entry = &mut self.entries[ix];
+            entry.serial = serial;
+            return (true, &mut entry.instance);
+        }
+        let ix = if self.entries.len() < MAX_ENTRIES {
+            self.entries.push(Entry::default());
+            self.entries.len() - 1
+        } else {
+            // Evict the least recently used entry.
+            self.entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| entry.serial)
+                .map(|(ix, _)| ix)
+                .unwrap_or_default()
+        };
+        let entry = &mut self.entries[ix];
+        entry.serial = serial;
+        entry.id = Some(id);
+        entry.size = size;
+        entry.mode = mode;
+        entry.coords.clear();
+        entry.coords.extend_from_slice(coords);
+        (false, &mut entry.instance)
+    }
+}
+
+#[derive(Clone, Default, Debug)]
+struct Entry<T> {
+    id: Option<UniqueId>,
+    size: f32,
+    coords: Vec<F2Dot14>,
+    mode: Hinting,
+    serial: u64,
+    instance: T,
+}
+
+impl<T> Entry<T> {
+    fn matches(&self, id: UniqueId, size: f32, coords: &[F2Dot14], mode: Hinting) -> bool {
+        self.id == Some(id) && self.size == size && self.mode == mode && self.coords == coords
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuse_matching_entries() {
+        let mut cache = InstanceCache::<()>::default();
+        let id = UniqueId::new(1, 0);
+        assert!(!cache.instance(Some(id), 16.0, &[], Hinting::Full).0);
+        assert!(cache.instance(Some(id), 16.0, &[], Hinting::Full).0);
+        // Any difference in configuration is a miss.
+        assert!(!cache.instance(Some(id), 16.0, &[], Hinting::Light).0);
+        assert!(!cache.instance(Some(id), 17.0, &[], Hinting::Full).0);
+        // Sizes sharing a ppem still scale control values differently.
+        assert!(!cache.instance(Some(id), 16.25, &[], Hinting::Full).0);
+        let coords = [F2Dot14::from_f32(0.5)];
+        assert!(!cache.instance(Some(id), 16.0, &coords, Hinting::Full).0);
+        assert!(
+            !cache
+                .instance(Some(UniqueId::new(2, 0)), 16.0, &[], Hinting::Full)
+                .0
+        );
+    }
+
+    #[test]
+    fn missing_id_uses_scratch() {
+        let mut cache = InstanceCache::<u32>::default();
+        let id = UniqueId::new(1, 0);
+        for size in 0..MAX_ENTRIES {
+            *cache.instance(Some(id), size as f32, &[], Hinting::Full).1 = size as u32;
+        }
+        // Missing identifiers are never cached and never evict an entry.
+        for _ in 0..2 {
+            let (is_cached, instance) = cache.instance(None, 0.0, &[], Hinting::Full);
+            assert!(!is_cached);
+            *instance = 100;
+        }
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        for size in 0..MAX_ENTRIES {
+            let (is_cached, instance) = cache.instance(Some(id), size as f32, &[], Hinting::Full);
+            assert!(is_cached);
+            assert_eq!(*instance, size as u32);
+        }
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = InstanceCache::<()>::default();
+        let id = UniqueId::new(1, 0);
+        for size in 0..MAX_ENTRIES {
+            cache.instance(Some(id), size as f32, &[], Hinting::Full);
+        }
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        // Touch the first entry so that the second becomes the oldest.
+        assert!(cache.instance(Some(id), 0.0, &[], Hinting::Full).0);
+        assert!(!cache.instance(Some(id), 100.0, &[], Hinting::Full).0);
+        assert_eq!(cache.entries.len(), MAX_ENTRIES);
+        assert!(cache.instance(Some(id), 0.0, &[], Hinting::Full).0);
+        assert!(!cache.instance(Some(id), 1.0, &[], Hinting::Full).0);
+    }
+}
@@ -7,7 +7,7 @@ use read_fonts::{
         cff::Cff,
         cff2::Cff2,
         postscript::{
-            charstring::{self, CommandSink},
+            charstring::{self, CommandSink, SeacContext},
             dict, BlendState, Error, FdSelect, Index,
         },
         variations::ItemVariationStore,
@@ -38,6 +38,9 @@ use super::hint::{HintParams, HintState};
 pub(crate) struct Scaler<'a> {
     version: Version<'a>,
     top_dict: TopDict<'a>,
+    /// Glyph data for resolving the components of the implied seac
+    /// operator.
+    seac: Option<SeacContext<'a>>,
     units_per_em: u16,
 }
 
@@ -63,9 +66,20 @@ impl<'a> Scaler<'a> {
     fn from_cff(cff1: Cff<'a>, top_dict_index: usize, units_per_em: u16) -> Result<Self, Error> {
         let top_dict_data = cff1.top_dicts().get(top_dict_index)?;
         let top_dict = TopDict::new(cff1.offset_data().as_bytes(), top_dict_data, false)?;
+        // The implied seac operator is not available in CID-keyed fonts
+        let seac = top_dict
+            .charstrings
+            .clone()
+            .zip(cff1.charset(top_dict_index).ok())
+            .filter(|(_, charset)| !charset.is_cid())
+            .map(|(charstrings, charset)| SeacContext {
+                charstrings,
+                charset,
+            });
         Ok(Self {
             version: Version::Version1(cff1),
             top_dict,
+            seac,
             units_per_em,
         })
     }
@@ -76,6 +90,7 @@ impl<'a> Scaler<'a> {
         Ok(Self {
             version: Version::Version2(cff2),
             top_dict,
+            seac: None,
             units_per_em,
         })
     }
@@ -197,13 +212,24 @@ impl<'a> Scaler<'a> {
         let mut pen_sink = charstring::PenSink::new(pen);
         let mut simplifying_adapter = NopFilteringSink::new(&mut pen_sink);
         let mut scaling_adapter = ScalingSink26Dot6::new(&mut simplifying_adapter, subfont.scale);
-        charstring::evaluate(
-            charstring_data,
-            self.global_subrs(),
-            subrs,
-            blend_state,
-            &mut scaling_adapter,
-        )?;
+        if let Some(seac) = &self.seac {
+            charstring::evaluate_with_seac(
+                charstring_data,
+                self.global_subrs(),
+                subrs,
+                blend_state,
+                seac,
+                &mut scaling_adapter,
+            )?;
+        } else {
+            charstring::evaluate(
+                charstring_data,
+                self.global_subrs(),
+                subrs,
+                blend_state,
+                &mut scaling_adapter,
+            )?;
+        }
         simplifying_adapter.finish();
         Ok(())
     }
@@ -7,7 +7,7 @@ use read_fonts::{
     types::{F26Dot6, Fixed, GlyphId, Pen, Point},
 };
 
-use super::ScalerMemory;
+use super::{ScalerMemory, PHANTOM_POINT_COUNT};
 
 /// Represents the information necessary to scale a glyph.
 ///
@@ -89,6 +89,8 @@ pub struct ScalerOutline<'a> {
     pub points: &'a mut [Point<F26Dot6>],
     pub flags: &'a mut [PointFlags],
     pub contours: &'a mut [u16],
+    /// The final positions of the phantom points.
+    pub phantom_points: [Point<F26Dot6>; PHANTOM_POINT_COUNT],
 }
 
 impl<'a> ScalerOutline<'a> {
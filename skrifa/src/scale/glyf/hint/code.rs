@@ -0,0 +1,241 @@
+//! Bytecode decoding, definitions and the call stack.
+
+use super::error::HintErrorKind;
+
+/// Identifies one of the three TrueType programs.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Program {
+    /// The font program (`fpgm`).
+    #[default]
+    Font = 0,
+    /// The control value program (`prep`).
+    ControlValue = 1,
+    /// Instructions for an individual glyph.
+    Glyph = 2,
+}
+
+/// Decoded instruction.
+#[derive(Copy, Clone, Debug)]
+pub struct Instruction<'a> {
+    pub opcode: u8,
+    /// Inline data for push instructions.
+    pub inline_data: &'a [u8],
+    /// Offset of the instruction in the program.
+    pub pc: usize,
+    /// Total size of the instruction in bytes including inline data.
+    pub size: usize,
+}
+
+impl<'a> Instruction<'a> {
+    /// Returns true if the inline data consists of 16-bit words.
+    pub fn is_word_push(&self) -> bool {
+        self.opcode == 0x41 || (0xB8..=0xBF).contains(&self.opcode)
+    }
+
+    /// Returns the number of values pushed by a push instruction.
+    pub fn push_count(&self) -> usize {
+        if self.is_word_push() {
+            self.inline_data.len() / 2
+        } else {
+            self.inline_data.len()
+        }
+    }
+
+    /// Returns an iterator over the values pushed by a push instruction.
+    pub fn push_values(&self) -> impl Iterator<Item = i32> + 'a {
+        let is_word = self.is_word_push();
+        let data = self.inline_data;
+        let step = if is_word { 2 } else { 1 };
+        data.chunks_exact(step).map(move |chunk| {
+            if is_word {
+                i16::from_be_bytes([chunk[0], chunk[1]]) as i32
+            } else {
+                chunk[0] as i32
+            }
+        })
+    }
+}
+
+/// Decodes a single instruction at the given offset.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L1128>
+pub fn decode(bytecode: &[u8], pc: usize) -> Result<Instruction<'_>, HintErrorKind> {
+    let opcode = *bytecode.get(pc).ok_or(HintErrorKind::CodeOverflow)?;
+    let (data_start, data_len) = match opcode {
+        // NPUSHB
+        0x40 => (
+            pc + 2,
+            *bytecode.get(pc + 1).ok_or(HintErrorKind::CodeOverflow)? as usize,
+        ),
+        // NPUSHW
+        0x41 => (
+            pc + 2,
+            *bytecode.get(pc + 1).ok_or(HintErrorKind::CodeOverflow)? as usize * 2,
+        ),
+        // PUSHB[abc]
+        0xB0..=0xB7 => (pc + 1, (opcode - 0xB0) as usize + 1),
+        // PUSHW[abc]
+        0xB8..=0xBF => (pc + 1, ((opcode - 0xB8) as usize + 1) * 2),
+        _ => (pc + 1, 0),
+    };
+    let end = data_start + data_len;
+    let inline_data = bytecode
+        .get(data_start..end)
+        .ok_or(HintErrorKind::CodeOverflow)?;
+    Ok(Instruction {
+        opcode,
+        inline_data,
+        pc,
+        size: end - pc,
+    })
+}
+
+/// A function or instruction definition.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Definition {
+    pub program: Program,
+    /// Function number or opcode.
+    pub key: u32,
+    pub start: usize,
+    pub end: usize,
+    pub is_active: bool,
+}
+
+/// Table of function or instruction definitions.
+///
+/// This follows FreeType in allowing arbitrary keys up to the capacity
+/// specified by the `maxp` table while providing fast lookup for the
+/// common case of sequentially numbered functions.
+pub struct DefinitionMap<'a> {
+    defs: &'a mut Vec<Definition>,
+    capacity: usize,
+}
+
+impl<'a> DefinitionMap<'a> {
+    pub fn new(defs: &'a mut Vec<Definition>, capacity: usize) -> Self {
+        Self { defs, capacity }
+    }
+
+    /// Returns the definition for the given key.
+    pub fn get(&self, key: u32) -> Option<&Definition> {
+        match self.defs.get(key as usize) {
+            Some(def) if def.key == key => Some(def),
+            _ => self.defs.iter().find(|def| def.key == key),
+        }
+    }
+
+    /// Returns a mutable reference to the definition for the given key,
+    /// allocating a new entry if necessary.
+    pub fn allocate(&mut self, key: u32) -> Result<&mut Definition, HintErrorKind> {
+        let index = match self.defs.iter().position(|def| def.key == key) {
+            Some(index) => index,
+            None => {
+                if self.defs.len() >= self.capacity {
+                    return Err(HintErrorKind::TooManyDefinitions);
+                }
+                self.defs.push(Definition {
+                    key,
+                    ..Default::default()
+                });
+                self.defs.len() - 1
+            }
+        };
+        Ok(&mut self.defs[index])
+    }
+
+    /// Returns the active definition for the given instruction opcode.
+    pub fn find_active(&self, key: u32) -> Option<&Definition> {
+        self.defs.iter().find(|def| def.key == key && def.is_active)
+    }
+}
+
+/// Maximum depth of the call stack.
+///
+/// Matches FreeType's `callSize`.
+pub const MAX_CALL_DEPTH: usize = 32;
+
+/// Record for an active function call.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct CallRecord {
+    pub caller_program: Program,
+    pub return_pc: usize,
+    /// Remaining iterations for `LOOPCALL`.
+    pub count: u32,
+    pub definition: Definition,
+}
+
+/// Fixed size call stack.
+#[derive(Default, Debug)]
+pub struct CallStack {
+    records: [CallRecord; MAX_CALL_DEPTH],
+    len: usize,
+}
+
+impl CallStack {
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn push(&mut self, record: CallRecord) -> Result<(), HintErrorKind> {
+        let slot = self
+            .records
+            .get_mut(self.len)
+            .ok_or(HintErrorKind::CallStackOverflow)?;
+        *slot = record;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn peek(&self) -> Option<&CallRecord> {
+        self.len.checked_sub(1).map(|ix| &self.records[ix])
+    }
+
+    pub fn pop(&mut self) -> Option<CallRecord> {
+        let record = *self.peek()?;
+        self.len -= 1;
+        Some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_pushes() {
+        // NPUSHB 2 1 2, PUSHW[1] 0xFFFF 0x0100, SVTCA[0]
+        let code = [0x40, 2, 1, 2, 0xB9, 0xFF, 0xFF, 0x01, 0x00, 0x00];
+        let ins = decode(&code, 0).unwrap();
+        assert_eq!(ins.size, 4);
+        assert_eq!(ins.push_values().collect::<Vec<_>>(), [1, 2]);
+        let ins = decode(&code, 4).unwrap();
+        assert_eq!(ins.size, 5);
+        assert_eq!(ins.push_values().collect::<Vec<_>>(), [-1, 256]);
+        let ins = decode(&code, 9).unwrap();
+        assert_eq!((ins.opcode, ins.size), (0, 1));
+        assert!(decode(&code, 10).is_err());
+        // Truncated push
+        assert!(decode(&code[..3], 0).is_err());
+    }
+
+    #[test]
+    fn sparse_definitions() {
+        let mut defs = vec![];
+        let mut map = DefinitionMap::new(&mut defs, 3);
+        for key in [0, 7, 3] {
+            let def = map.allocate(key).unwrap();
+            def.start = key as usize * 10;
+            def.is_active = true;
+        }
+        assert!(map.allocate(9).is_err());
+        // Redefinition reuses the existing entry
+        assert!(map.allocate(7).is_ok());
+        assert_eq!(map.get(7).unwrap().start, 70);
+        assert_eq!(map.get(3).unwrap().start, 30);
+        assert!(map.get(4).is_none());
+    }
+}
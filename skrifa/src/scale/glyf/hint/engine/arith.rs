@@ -0,0 +1,146 @@
+//! Arithmetic, logical and rounding instructions.
+//!
+//! Implements 27 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#arithmetic-and-math-instructions>
+//! and <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#logical-functions>
+
+use super::{super::math, Engine, HintErrorKind, OpResult};
+
+impl<'a> Engine<'a> {
+    /// LT[] (0x50)
+    pub(super) fn op_lt(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a < b) as i32))
+    }
+
+    /// LTEQ[] (0x51)
+    pub(super) fn op_lteq(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a <= b) as i32))
+    }
+
+    /// GT[] (0x52)
+    pub(super) fn op_gt(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a > b) as i32))
+    }
+
+    /// GTEQ[] (0x53)
+    pub(super) fn op_gteq(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a >= b) as i32))
+    }
+
+    /// EQ[] (0x54)
+    pub(super) fn op_eq(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a == b) as i32))
+    }
+
+    /// NEQ[] (0x55)
+    pub(super) fn op_neq(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok((a != b) as i32))
+    }
+
+    /// ODD[] (0x56)
+    ///
+    /// The value is rounded according to the current round state before
+    /// testing.
+    pub(super) fn op_odd(&mut self) -> OpResult {
+        let round_state = self.graphics.round_state;
+        self.value_stack
+            .apply_unary(|a| Ok((round_state.round(a) & 127 == 64) as i32))
+    }
+
+    /// EVEN[] (0x57)
+    pub(super) fn op_even(&mut self) -> OpResult {
+        let round_state = self.graphics.round_state;
+        self.value_stack
+            .apply_unary(|a| Ok((round_state.round(a) & 127 == 0) as i32))
+    }
+
+    /// AND[] (0x5A)
+    pub(super) fn op_and(&mut self) -> OpResult {
+        self.value_stack
+            .apply_binary(|a, b| Ok((a != 0 && b != 0) as i32))
+    }
+
+    /// OR[] (0x5B)
+    pub(super) fn op_or(&mut self) -> OpResult {
+        self.value_stack
+            .apply_binary(|a, b| Ok((a != 0 || b != 0) as i32))
+    }
+
+    /// NOT[] (0x5C)
+    pub(super) fn op_not(&mut self) -> OpResult {
+        self.value_stack.apply_unary(|a| Ok((a == 0) as i32))
+    }
+
+    /// ADD[] (0x60)
+    pub(super) fn op_add(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok(a.wrapping_add(b)))
+    }
+
+    /// SUB[] (0x61)
+    pub(super) fn op_sub(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok(a.wrapping_sub(b)))
+    }
+
+    /// DIV[] (0x62)
+    pub(super) fn op_div(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| {
+            if b == 0 {
+                Err(HintErrorKind::DivideByZero)
+            } else {
+                Ok(math::mul_div_no_round(a, 64, b))
+            }
+        })
+    }
+
+    /// MUL[] (0x63)
+    pub(super) fn op_mul(&mut self) -> OpResult {
+        self.value_stack
+            .apply_binary(|a, b| Ok(math::mul_div(a, b, 64)))
+    }
+
+    /// ABS[] (0x64)
+    pub(super) fn op_abs(&mut self) -> OpResult {
+        self.value_stack.apply_unary(|a| Ok(a.wrapping_abs()))
+    }
+
+    /// NEG[] (0x65)
+    pub(super) fn op_neg(&mut self) -> OpResult {
+        self.value_stack.apply_unary(|a| Ok(a.wrapping_neg()))
+    }
+
+    /// FLOOR[] (0x66)
+    pub(super) fn op_floor(&mut self) -> OpResult {
+        self.value_stack.apply_unary(|a| Ok(a & !63))
+    }
+
+    /// CEILING[] (0x67)
+    pub(super) fn op_ceiling(&mut self) -> OpResult {
+        self.value_stack
+            .apply_unary(|a| Ok(a.wrapping_add(63) & !63))
+    }
+
+    /// MAX[] (0x8B)
+    pub(super) fn op_max(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok(a.max(b)))
+    }
+
+    /// MIN[] (0x8C)
+    pub(super) fn op_min(&mut self) -> OpResult {
+        self.value_stack.apply_binary(|a, b| Ok(a.min(b)))
+    }
+
+    /// ROUND[ab] (0x68-0x6B)
+    pub(super) fn op_round(&mut self) -> OpResult {
+        let round_state = self.graphics.round_state;
+        self.value_stack.apply_unary(|a| Ok(round_state.round(a)))
+    }
+
+    /// NROUND[ab] (0x6C-0x6F)
+    ///
+    /// Engine compensation is always zero so this leaves the value
+    /// unchanged.
+    pub(super) fn op_nround(&mut self) -> OpResult {
+        Ok(())
+    }
+}
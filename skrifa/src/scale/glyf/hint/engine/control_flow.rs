@@ -0,0 +1,285 @@
+//! Control flow and definition instructions.
+//!
+//! Implements 13 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#managing-the-flow-of-control>
+//! and <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#defining-and-using-functions-and-instructions>
+
+use super::{
+    super::code::{decode, CallRecord, Definition, Instruction, Program, MAX_CALL_DEPTH},
+    Engine, HintErrorKind, OpResult,
+};
+
+/// Opcodes that affect the scanning of definitions and conditionals.
+const IF: u8 = 0x58;
+const ELSE: u8 = 0x1B;
+const EIF: u8 = 0x59;
+const FDEF: u8 = 0x2C;
+const ENDF: u8 = 0x2D;
+const IDEF: u8 = 0x89;
+
+impl<'a> Engine<'a> {
+    /// If test.
+    ///
+    /// IF[] (0x58)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3355>
+    pub(super) fn op_if(&mut self) -> OpResult {
+        if self.value_stack.pop()? != 0 {
+            return Ok(());
+        }
+        // Skip to the matching ELSE or EIF.
+        let mut nesting = 1;
+        let mut ins = self.current_instruction()?;
+        loop {
+            ins = self.skip_instruction(&ins)?;
+            match ins.opcode {
+                IF => nesting += 1,
+                ELSE if nesting == 1 => break,
+                EIF => {
+                    nesting -= 1;
+                    if nesting == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.next_pc = ins.pc + ins.size;
+        Ok(())
+    }
+
+    /// Else.
+    ///
+    /// ELSE[] (0x1B)
+    ///
+    /// This is only executed when the preceding IF condition was true so
+    /// skip to the matching EIF.
+    pub(super) fn op_else(&mut self) -> OpResult {
+        let mut nesting = 1;
+        let mut ins = self.current_instruction()?;
+        while nesting != 0 {
+            ins = self.skip_instruction(&ins)?;
+            match ins.opcode {
+                IF => nesting += 1,
+                EIF => nesting -= 1,
+                _ => {}
+            }
+        }
+        self.next_pc = ins.pc + ins.size;
+        Ok(())
+    }
+
+    /// End if.
+    ///
+    /// EIF[] (0x59)
+    pub(super) fn op_eif(&mut self) -> OpResult {
+        Ok(())
+    }
+
+    /// Jump relative on true.
+    ///
+    /// JROT[] (0x78)
+    pub(super) fn op_jrot(&mut self) -> OpResult {
+        let condition = self.value_stack.pop()?;
+        let offset = self.value_stack.pop()?;
+        if condition != 0 {
+            self.jump(offset)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Jump.
+    ///
+    /// JMPR[] (0x1C)
+    pub(super) fn op_jmpr(&mut self) -> OpResult {
+        let offset = self.value_stack.pop()?;
+        self.jump(offset)
+    }
+
+    /// Jump relative on false.
+    ///
+    /// JROF[] (0x79)
+    pub(super) fn op_jrof(&mut self) -> OpResult {
+        let condition = self.value_stack.pop()?;
+        let offset = self.value_stack.pop()?;
+        if condition == 0 {
+            self.jump(offset)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3314>
+    fn jump(&mut self, offset: i32) -> OpResult {
+        // A zero offset with nothing else on the stack is an infinite loop.
+        if offset == 0 && self.value_stack.is_empty() {
+            return Err(HintErrorKind::InvalidArgument);
+        }
+        let target = self.pc as i64 + offset as i64;
+        if target < 0
+            || self
+                .call_stack
+                .peek()
+                .map(|record| target > record.definition.end as i64)
+                .unwrap_or_default()
+        {
+            return Err(HintErrorKind::InvalidArgument);
+        }
+        self.next_pc = target as usize;
+        if offset < 0 {
+            self.loop_budget.add_backward_jump()?;
+        }
+        Ok(())
+    }
+
+    /// Function definition.
+    ///
+    /// FDEF[] (0x2C)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3393>
+    pub(super) fn op_fdef(&mut self) -> OpResult {
+        if self.initial_program == Program::Glyph {
+            return Err(HintErrorKind::DefinitionInGlyphProgram);
+        }
+        let key = self.value_stack.pop()? as u32;
+        if key > 0xFFFF {
+            return Err(HintErrorKind::TooManyDefinitions);
+        }
+        let definition = self.read_definition(key)?;
+        *self.functions.allocate(key)? = definition;
+        Ok(())
+    }
+
+    /// End function definition.
+    ///
+    /// ENDF[] (0x2D)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3462>
+    pub(super) fn op_endf(&mut self) -> OpResult {
+        let mut record = self
+            .call_stack
+            .pop()
+            .ok_or(HintErrorKind::UnexpectedEndOfFunction)?;
+        record.count -= 1;
+        if record.count > 0 {
+            // Loop through the current function.
+            self.next_pc = record.definition.start;
+            self.call_stack.push(record)?;
+        } else {
+            self.program = record.caller_program;
+            self.next_pc = record.return_pc;
+        }
+        Ok(())
+    }
+
+    /// Call function.
+    ///
+    /// CALL[] (0x2B)
+    pub(super) fn op_call(&mut self) -> OpResult {
+        let key = self.value_stack.pop()? as u32;
+        let definition = self.function(key)?;
+        self.enter_definition(definition, 1)
+    }
+
+    /// Loop and call function.
+    ///
+    /// LOOPCALL[] (0x2A)
+    pub(super) fn op_loopcall(&mut self) -> OpResult {
+        let key = self.value_stack.pop()? as u32;
+        let count = self.value_stack.pop()?;
+        let definition = self.function(key)?;
+        if self.call_stack.len() >= MAX_CALL_DEPTH {
+            return Err(HintErrorKind::CallStackOverflow);
+        }
+        if count > 0 {
+            self.enter_definition(definition, count as u32)?;
+            self.loop_budget.add_loop_calls(count as usize)?;
+        }
+        Ok(())
+    }
+
+    /// Instruction definition.
+    ///
+    /// IDEF[] (0x89)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3598>
+    pub(super) fn op_idef(&mut self) -> OpResult {
+        if self.initial_program == Program::Glyph {
+            return Err(HintErrorKind::DefinitionInGlyphProgram);
+        }
+        let opcode = self.value_stack.pop()?;
+        if !(0..=255).contains(&opcode) {
+            return Err(HintErrorKind::TooManyDefinitions);
+        }
+        let definition = self.read_definition(opcode as u32)?;
+        *self.instructions.allocate(opcode as u32)? = definition;
+        Ok(())
+    }
+
+    /// Executes a user defined instruction or fails if the current opcode
+    /// is undefined.
+    pub(super) fn op_unknown(&mut self) -> OpResult {
+        let opcode = self.opcode;
+        let definition = *self
+            .instructions
+            .find_active(opcode as u32)
+            .ok_or(HintErrorKind::InvalidOpcode(opcode))?;
+        self.enter_definition(definition, 1)
+    }
+
+    fn function(&self, key: u32) -> Result<Definition, HintErrorKind> {
+        self.functions
+            .get(key)
+            .filter(|def| def.is_active)
+            .copied()
+            .ok_or(HintErrorKind::InvalidDefinition(key))
+    }
+
+    fn enter_definition(&mut self, definition: Definition, count: u32) -> OpResult {
+        self.call_stack.push(CallRecord {
+            caller_program: self.program,
+            return_pc: self.pc + 1,
+            count,
+            definition,
+        })?;
+        if definition.start > self.programs[definition.program as usize].len() {
+            return Err(HintErrorKind::CodeOverflow);
+        }
+        self.program = definition.program;
+        self.next_pc = definition.start;
+        Ok(())
+    }
+
+    /// Scans the body of a function or instruction definition that begins
+    /// at the current instruction and sets the next instruction to the one
+    /// following the matching ENDF.
+    fn read_definition(&mut self, key: u32) -> Result<Definition, HintErrorKind> {
+        let mut ins = self.current_instruction()?;
+        loop {
+            ins = self.skip_instruction(&ins)?;
+            match ins.opcode {
+                IDEF | FDEF => return Err(HintErrorKind::NestedDefinition),
+                ENDF => break,
+                _ => {}
+            }
+        }
+        self.next_pc = ins.pc + ins.size;
+        Ok(Definition {
+            program: self.program,
+            key,
+            start: self.pc + 1,
+            end: ins.pc,
+            is_active: true,
+        })
+    }
+
+    fn current_instruction(&self) -> Result<Instruction<'a>, HintErrorKind> {
+        decode(self.programs[self.program as usize], self.pc)
+    }
+
+    fn skip_instruction(&self, ins: &Instruction) -> Result<Instruction<'a>, HintErrorKind> {
+        decode(self.programs[self.program as usize], ins.pc + ins.size)
+    }
+}
@@ -0,0 +1,107 @@
+//! Exceptions.
+//!
+//! Implements 6 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#managing-exceptions>
+
+use read_fonts::tables::glyf::PointMarker;
+
+use super::{Engine, OpResult};
+
+impl<'a> Engine<'a> {
+    /// Delta exception P1, P2 and P3.
+    ///
+    /// DELTAP1[] (0x5D), DELTAP2[] (0x71), DELTAP3[] (0x72)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6457>
+    pub(super) fn op_deltap(&mut self) -> OpResult {
+        let count = self.value_stack.pop_usize()?;
+        let bias = self.delta_bias();
+        let zp0 = self.graphics.zp0;
+        for _ in 0..count {
+            if self.value_stack.len() < 2 {
+                // Stop and clear the stack when we run out of pairs.
+                self.value_stack.clear();
+                return self.invalid_reference();
+            }
+            let point = self.value_stack.pop_usize()?;
+            let arg = self.value_stack.pop()?;
+            if point >= self.zone_len(zp0) {
+                self.invalid_reference()?;
+                continue;
+            }
+            let Some(delta) = self.delta_amount(arg, bias) else {
+                continue;
+            };
+            let gs = &mut self.graphics;
+            if gs.backward_compatibility {
+                // Allow delta moves on the y axis until IUP has been
+                // called on both axes, but only for points already touched
+                // in y or for any point in a composite glyph.
+                let is_touched_y = gs.zone(zp0).flags[point].has_marker(PointMarker::TOUCHED_Y);
+                if !(gs.did_iup_x && gs.did_iup_y)
+                    && ((gs.is_composite && gs.freedom_vector.1 != 0) || is_touched_y)
+                {
+                    gs.move_point(zp0, point, delta);
+                }
+            } else {
+                gs.move_point(zp0, point, delta);
+            }
+        }
+        Ok(())
+    }
+
+    /// Delta exception C1, C2 and C3.
+    ///
+    /// DELTAC1[] (0x73), DELTAC2[] (0x74), DELTAC3[] (0x75)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6575>
+    pub(super) fn op_deltac(&mut self) -> OpResult {
+        let count = self.value_stack.pop_usize()?;
+        let bias = self.delta_bias();
+        for _ in 0..count {
+            if self.value_stack.len() < 2 {
+                self.value_stack.clear();
+                return self.invalid_reference();
+            }
+            let cvt_index = self.value_stack.pop_usize()?;
+            let arg = self.value_stack.pop()?;
+            if cvt_index >= self.cvt.len() {
+                self.invalid_reference()?;
+                continue;
+            }
+            if let Some(delta) = self.delta_amount(arg, bias) {
+                let value = &mut self.cvt[cvt_index];
+                *value = value.wrapping_add(delta);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ppem offset selected by the current delta opcode.
+    fn delta_bias(&self) -> i32 {
+        match self.opcode {
+            0x71 | 0x74 => 16,
+            0x72 | 0x75 => 32,
+            _ => 0,
+        }
+    }
+
+    /// Decodes the delta exception argument and returns the distance to
+    /// move if it applies to the current ppem.
+    fn delta_amount(&self, arg: i32, bias: i32) -> Option<i32> {
+        let gs = &self.graphics;
+        let ppem = ((arg & 0xF0) >> 4)
+            .wrapping_add(bias)
+            .wrapping_add(gs.delta_base as i32);
+        if ppem != self.ppem {
+            return None;
+        }
+        // Map the low 4 bits to the range [-8, -1] and [1, 8].
+        let mut steps = (arg & 0xF) - 8;
+        if steps >= 0 {
+            steps += 1;
+        }
+        Some(steps * (1 << (6 - gs.delta_shift as i32)))
+    }
+}
@@ -0,0 +1,533 @@
+//! Managing the graphics state.
+//!
+//! Implements 45 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#managing-the-graphics-state>
+
+use super::{
+    super::{
+        code::Program,
+        graphics_state::{RoundMode, UnitVector, X_AXIS, Y_AXIS},
+        math,
+        zone::ZonePointer,
+    },
+    Engine, HintErrorKind, OpResult,
+};
+
+impl<'a> Engine<'a> {
+    /// Set freedom and projection vectors to coordinate axis.
+    ///
+    /// SVTCA[a] (0x00-0x01)
+    pub(super) fn op_svtca(&mut self) -> OpResult {
+        let axis = self.opcode_axis();
+        let gs = &mut self.graphics;
+        gs.freedom_vector = axis;
+        gs.proj_vector = axis;
+        gs.dual_proj_vector = axis;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set projection vector to coordinate axis.
+    ///
+    /// SPVTCA[a] (0x02-0x03)
+    pub(super) fn op_spvtca(&mut self) -> OpResult {
+        let axis = self.opcode_axis();
+        let gs = &mut self.graphics;
+        gs.proj_vector = axis;
+        gs.dual_proj_vector = axis;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set freedom vector to coordinate axis.
+    ///
+    /// SFVTCA[a] (0x04-0x05)
+    pub(super) fn op_sfvtca(&mut self) -> OpResult {
+        let axis = self.opcode_axis();
+        let gs = &mut self.graphics;
+        gs.freedom_vector = axis;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set projection vector to line.
+    ///
+    /// SPVTL[a] (0x06-0x07)
+    pub(super) fn op_spvtl(&mut self) -> OpResult {
+        if let Some(vector) = self.line_vector()? {
+            let gs = &mut self.graphics;
+            gs.proj_vector = vector;
+            gs.dual_proj_vector = vector;
+            gs.update_projection_state();
+        }
+        Ok(())
+    }
+
+    /// Set freedom vector to line.
+    ///
+    /// SFVTL[a] (0x08-0x09)
+    pub(super) fn op_sfvtl(&mut self) -> OpResult {
+        if let Some(vector) = self.line_vector()? {
+            let gs = &mut self.graphics;
+            gs.freedom_vector = vector;
+            gs.update_projection_state();
+        }
+        Ok(())
+    }
+
+    /// Set dual projection vector to line.
+    ///
+    /// SDPVTL[a] (0x86-0x87)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5364>
+    pub(super) fn op_sdpvtl(&mut self) -> OpResult {
+        let p2 = self.value_stack.pop_usize()?;
+        let p1 = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp1, zp2) = (gs.zone(gs.zp1), gs.zone(gs.zp2));
+        let (Some(org1), Some(org2), Some(cur1), Some(cur2)) = (
+            zp1.original(p1),
+            zp2.original(p2),
+            zp1.point(p1),
+            zp2.point(p2),
+        ) else {
+            return self.invalid_reference();
+        };
+        let mut rotate = self.opcode & 1 != 0;
+        let dual = line_direction(org1, org2, &mut rotate);
+        let proj = line_direction(cur1, cur2, &mut rotate);
+        let gs = &mut self.graphics;
+        gs.dual_proj_vector = dual;
+        gs.proj_vector = proj;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set projection vector from stack.
+    ///
+    /// SPVFS[] (0x0A)
+    pub(super) fn op_spvfs(&mut self) -> OpResult {
+        let y = self.value_stack.pop()? as i16 as i32;
+        let x = self.value_stack.pop()? as i16 as i32;
+        let gs = &mut self.graphics;
+        let vector = normalize(x, y, gs.proj_vector);
+        gs.proj_vector = vector;
+        gs.dual_proj_vector = vector;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set freedom vector from stack.
+    ///
+    /// SFVFS[] (0x0B)
+    pub(super) fn op_sfvfs(&mut self) -> OpResult {
+        let y = self.value_stack.pop()? as i16 as i32;
+        let x = self.value_stack.pop()? as i16 as i32;
+        let gs = &mut self.graphics;
+        gs.freedom_vector = normalize(x, y, gs.freedom_vector);
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Get projection vector.
+    ///
+    /// GPV[] (0x0C)
+    pub(super) fn op_gpv(&mut self) -> OpResult {
+        let (x, y) = self.graphics.proj_vector;
+        self.value_stack.push(x)?;
+        self.value_stack.push(y)
+    }
+
+    /// Get freedom vector.
+    ///
+    /// GFV[] (0x0D)
+    pub(super) fn op_gfv(&mut self) -> OpResult {
+        let (x, y) = self.graphics.freedom_vector;
+        self.value_stack.push(x)?;
+        self.value_stack.push(y)
+    }
+
+    /// Set freedom vector to projection vector.
+    ///
+    /// SFVTPV[] (0x0E)
+    pub(super) fn op_sfvtpv(&mut self) -> OpResult {
+        let gs = &mut self.graphics;
+        gs.freedom_vector = gs.proj_vector;
+        gs.update_projection_state();
+        Ok(())
+    }
+
+    /// Set reference point 0.
+    ///
+    /// SRP0[] (0x10)
+    pub(super) fn op_srp0(&mut self) -> OpResult {
+        self.graphics.rp0 = self.value_stack.pop_usize()?;
+        Ok(())
+    }
+
+    /// Set reference point 1.
+    ///
+    /// SRP1[] (0x11)
+    pub(super) fn op_srp1(&mut self) -> OpResult {
+        self.graphics.rp1 = self.value_stack.pop_usize()?;
+        Ok(())
+    }
+
+    /// Set reference point 2.
+    ///
+    /// SRP2[] (0x12)
+    pub(super) fn op_srp2(&mut self) -> OpResult {
+        self.graphics.rp2 = self.value_stack.pop_usize()?;
+        Ok(())
+    }
+
+    /// Set zone pointer 0.
+    ///
+    /// SZP0[] (0x13)
+    pub(super) fn op_szp0(&mut self) -> OpResult {
+        if let Some(zone) = self.pop_zone()? {
+            self.graphics.zp0 = zone;
+        }
+        Ok(())
+    }
+
+    /// Set zone pointer 1.
+    ///
+    /// SZP1[] (0x14)
+    pub(super) fn op_szp1(&mut self) -> OpResult {
+        if let Some(zone) = self.pop_zone()? {
+            self.graphics.zp1 = zone;
+        }
+        Ok(())
+    }
+
+    /// Set zone pointer 2.
+    ///
+    /// SZP2[] (0x15)
+    pub(super) fn op_szp2(&mut self) -> OpResult {
+        if let Some(zone) = self.pop_zone()? {
+            self.graphics.zp2 = zone;
+        }
+        Ok(())
+    }
+
+    /// Set all zone pointers.
+    ///
+    /// SZPS[] (0x16)
+    pub(super) fn op_szps(&mut self) -> OpResult {
+        if let Some(zone) = self.pop_zone()? {
+            let gs = &mut self.graphics;
+            gs.zp0 = zone;
+            gs.zp1 = zone;
+            gs.zp2 = zone;
+        }
+        Ok(())
+    }
+
+    /// Set loop variable.
+    ///
+    /// SLOOP[] (0x17)
+    pub(super) fn op_sloop(&mut self) -> OpResult {
+        let count = self.value_stack.pop()?;
+        if count < 0 {
+            return Err(HintErrorKind::InvalidArgument);
+        }
+        // We heuristically limit the number of loops to 16 bits.
+        self.graphics.loop_counter = (count as u32).min(0xFFFF);
+        Ok(())
+    }
+
+    /// Round to grid.
+    ///
+    /// RTG[] (0x18)
+    pub(super) fn op_rtg(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::Grid;
+        Ok(())
+    }
+
+    /// Round to half grid.
+    ///
+    /// RTHG[] (0x19)
+    pub(super) fn op_rthg(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::HalfGrid;
+        Ok(())
+    }
+
+    /// Round to double grid.
+    ///
+    /// RTDG[] (0x3D)
+    pub(super) fn op_rtdg(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::DoubleGrid;
+        Ok(())
+    }
+
+    /// Round down to grid.
+    ///
+    /// RDTG[] (0x7D)
+    pub(super) fn op_rdtg(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::DownToGrid;
+        Ok(())
+    }
+
+    /// Round up to grid.
+    ///
+    /// RUTG[] (0x7C)
+    pub(super) fn op_rutg(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::UpToGrid;
+        Ok(())
+    }
+
+    /// Round off.
+    ///
+    /// ROFF[] (0x7A)
+    pub(super) fn op_roff(&mut self) -> OpResult {
+        self.graphics.round_state.mode = RoundMode::Off;
+        Ok(())
+    }
+
+    /// Super round.
+    ///
+    /// SROUND[] (0x76)
+    pub(super) fn op_sround(&mut self) -> OpResult {
+        let selector = self.value_stack.pop()?;
+        let round_state = &mut self.graphics.round_state;
+        round_state.set_super(0x4000, selector);
+        round_state.mode = RoundMode::Super;
+        Ok(())
+    }
+
+    /// Super round 45 degrees.
+    ///
+    /// S45ROUND[] (0x77)
+    pub(super) fn op_s45round(&mut self) -> OpResult {
+        let selector = self.value_stack.pop()?;
+        let round_state = &mut self.graphics.round_state;
+        round_state.set_super(0x2D41, selector);
+        round_state.mode = RoundMode::Super45;
+        Ok(())
+    }
+
+    /// Set minimum distance.
+    ///
+    /// SMD[] (0x1A)
+    pub(super) fn op_smd(&mut self) -> OpResult {
+        self.graphics.min_distance = self.value_stack.pop()?;
+        Ok(())
+    }
+
+    /// Set control value table cut-in.
+    ///
+    /// SCVTCI[] (0x1D)
+    pub(super) fn op_scvtci(&mut self) -> OpResult {
+        self.graphics.control_value_cutin = self.value_stack.pop()?;
+        Ok(())
+    }
+
+    /// Set single width cut-in.
+    ///
+    /// SSWCI[] (0x1E)
+    pub(super) fn op_sswci(&mut self) -> OpResult {
+        self.graphics.single_width_cutin = self.value_stack.pop()?;
+        Ok(())
+    }
+
+    /// Set single width.
+    ///
+    /// SSW[] (0x1F)
+    ///
+    /// The value is specified in font units.
+    pub(super) fn op_ssw(&mut self) -> OpResult {
+        let width = self.value_stack.pop()?;
+        self.graphics.single_width = math::mul(width, self.scale);
+        Ok(())
+    }
+
+    /// Set auto flip boolean to on.
+    ///
+    /// FLIPON[] (0x4D)
+    pub(super) fn op_flipon(&mut self) -> OpResult {
+        self.graphics.auto_flip = true;
+        Ok(())
+    }
+
+    /// Set auto flip boolean to off.
+    ///
+    /// FLIPOFF[] (0x4E)
+    pub(super) fn op_flipoff(&mut self) -> OpResult {
+        self.graphics.auto_flip = false;
+        Ok(())
+    }
+
+    /// Set angle weight.
+    ///
+    /// SANGW[] (0x7E)
+    ///
+    /// This instruction is obsolete and ignored.
+    pub(super) fn op_sangw(&mut self) -> OpResult {
+        self.value_stack.pop()?;
+        Ok(())
+    }
+
+    /// Set delta base in the graphics state.
+    ///
+    /// SDB[] (0x5E)
+    pub(super) fn op_sdb(&mut self) -> OpResult {
+        self.graphics.delta_base = self.value_stack.pop()? as u16;
+        Ok(())
+    }
+
+    /// Set delta shift in the graphics state.
+    ///
+    /// SDS[] (0x5F)
+    pub(super) fn op_sds(&mut self) -> OpResult {
+        let shift = self.value_stack.pop()?;
+        if shift as u32 > 6 {
+            return Err(HintErrorKind::InvalidArgument);
+        }
+        self.graphics.delta_shift = shift as u16;
+        Ok(())
+    }
+
+    /// Scan conversion control.
+    ///
+    /// SCANCTRL[] (0x85)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L4814>
+    pub(super) fn op_scanctrl(&mut self) -> OpResult {
+        let flags = self.value_stack.pop()?;
+        let threshold = flags & 0xFF;
+        let gs = &mut self.graphics;
+        if threshold == 0xFF {
+            gs.scan_control = true;
+        } else if threshold == 0 {
+            gs.scan_control = false;
+        } else {
+            // Rotation and stretching are never applied so we only
+            // need to check the ppem conditions.
+            if flags & 0x100 != 0 && self.ppem <= threshold {
+                gs.scan_control = true;
+            }
+            if flags & 0x800 != 0 && self.ppem > threshold {
+                gs.scan_control = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Scan type.
+    ///
+    /// SCANTYPE[] (0x8D)
+    pub(super) fn op_scantype(&mut self) -> OpResult {
+        let scan_type = self.value_stack.pop()?;
+        if scan_type >= 0 {
+            self.graphics.scan_type = scan_type & 0xFFFF;
+        }
+        Ok(())
+    }
+
+    /// Instruction execution control.
+    ///
+    /// INSTCTRL[] (0x8E)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L4767>
+    pub(super) fn op_instctrl(&mut self) -> OpResult {
+        let selector = self.value_stack.pop()? as u32;
+        let value = self.value_stack.pop()? as u32;
+        // Selectors are indices starting with 1, not flags.
+        if !(1..=3).contains(&selector) {
+            return self.invalid_reference();
+        }
+        let flag = 1 << (selector - 1);
+        if value != 0 && value != flag {
+            return self.invalid_reference();
+        }
+        let gs = &mut self.graphics;
+        if self.initial_program == Program::ControlValue {
+            gs.instruct_control &= !(flag as u8);
+            gs.instruct_control |= value as u8;
+        } else if self.initial_program == Program::Glyph && selector == 3 {
+            // Native ClearType fonts may temporarily disable backward
+            // compatibility for a single glyph.
+            gs.backward_compatibility = value != 4;
+        } else if self.is_pedantic {
+            return Err(HintErrorKind::InvalidReference);
+        }
+        Ok(())
+    }
+
+    /// Returns the coordinate axis selected by the low bit of the current
+    /// opcode.
+    fn opcode_axis(&self) -> UnitVector {
+        if self.opcode & 1 != 0 {
+            X_AXIS
+        } else {
+            Y_AXIS
+        }
+    }
+
+    /// Pops two points and computes the (possibly rotated) unit vector of
+    /// the line between them for SPVTL and SFVTL.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L4450>
+    fn line_vector(&mut self) -> Result<Option<UnitVector>, HintErrorKind> {
+        let p2 = self.value_stack.pop_usize()?;
+        let p1 = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (Some(a), Some(b)) = (gs.zone(gs.zp1).point(p1), gs.zone(gs.zp2).point(p2)) else {
+            self.invalid_reference()?;
+            return Ok(None);
+        };
+        let mut rotate = self.opcode & 1 != 0;
+        Ok(Some(line_direction(a, b, &mut rotate)))
+    }
+
+    /// Pops a zone pointer value, returning `None` (or an error in
+    /// pedantic mode) for invalid values.
+    fn pop_zone(&mut self) -> Result<Option<ZonePointer>, HintErrorKind> {
+        let value = self.value_stack.pop()?;
+        match ZonePointer::try_from(value) {
+            Ok(zone) => Ok(Some(zone)),
+            Err(_) => self.invalid_reference().map(|_| None),
+        }
+    }
+
+    /// Returns an error in pedantic mode or silently ignores the invalid
+    /// reference otherwise.
+    pub(super) fn invalid_reference(&self) -> OpResult {
+        if self.is_pedantic {
+            Err(HintErrorKind::InvalidReference)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Computes the unit vector pointing from `b` to `a`, optionally rotated
+/// 90 degrees counter-clockwise.
+///
+/// If the points are coincident, the result is the x axis and rotation is
+/// disabled (for this and subsequent calls sharing the flag).
+fn line_direction(a: (i32, i32), b: (i32, i32), rotate: &mut bool) -> UnitVector {
+    let mut dx = a.0.wrapping_sub(b.0);
+    let mut dy = a.1.wrapping_sub(b.1);
+    if dx == 0 && dy == 0 {
+        dx = 0x4000;
+        *rotate = false;
+    }
+    if *rotate {
+        (dx, dy) = (dy.wrapping_neg(), dx);
+    }
+    math::normalize14(dx, dy)
+}
+
+/// Normalizes a vector to 2.14 unit length.
+///
+/// Like FreeType, attempting to normalize the zero vector retains the
+/// current value.
+fn normalize(x: i32, y: i32, current: UnitVector) -> UnitVector {
+    if x == 0 && y == 0 {
+        return current;
+    }
+    math::normalize14(x, y)
+}
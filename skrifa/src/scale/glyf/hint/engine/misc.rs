@@ -0,0 +1,128 @@
+//! Miscellaneous instructions.
+//!
+//! Implements 7 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#miscellaneous-instructions>
+
+use super::{Engine, HintErrorKind, OpResult};
+
+impl<'a> Engine<'a> {
+    /// Get information.
+    ///
+    /// GETINFO[] (0x88)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6689>
+    pub(super) fn op_getinfo(&mut self) -> OpResult {
+        use getinfo::*;
+        let selector = self.value_stack.pop()?;
+        let gs = &self.graphics;
+        let mut result = 0;
+        if selector & VERSION_SELECTOR_BIT != 0 {
+            result = 40;
+        }
+        // Glyphs are never rotated or stretched.
+        if selector & VARIATIONS_SELECTOR_BIT != 0 && self.axis_count != 0 {
+            result |= VARIATIONS_RESULT_BIT;
+        }
+        // FreeType's v40 interpreter never reports grayscale rendering.
+        if gs.is_subpixel {
+            if selector & HINTING_FOR_CLEARTYPE_SELECTOR_BIT != 0 {
+                result |= HINTING_FOR_CLEARTYPE_RESULT_BIT;
+            }
+            if selector & LCD_VERTICAL_SELECTOR_BIT != 0 && gs.is_vertical_lcd {
+                result |= LCD_VERTICAL_RESULT_BIT;
+            }
+            if selector & SUBPIXEL_POSITIONED_SELECTOR_BIT != 0 {
+                result |= SUBPIXEL_POSITIONED_RESULT_BIT;
+            }
+            if selector & SYMMETRICAL_SMOOTHING_SELECTOR_BIT != 0 {
+                result |= SYMMETRICAL_SMOOTHING_RESULT_BIT;
+            }
+            if selector & GRAYSCALE_CLEARTYPE_SELECTOR_BIT != 0 && gs.is_grayscale_cleartype {
+                result |= GRAYSCALE_CLEARTYPE_RESULT_BIT;
+            }
+        }
+        self.value_stack.push(result)
+    }
+
+    /// Get variation.
+    ///
+    /// GETVARIATION[] (0x91)
+    ///
+    /// Pushes the normalized coordinate for each axis in 2.14 format.
+    pub(super) fn op_getvariation(&mut self) -> OpResult {
+        let axis_count = self.axis_count as usize;
+        let coords = self.coords;
+        self.value_stack.push_all(
+            axis_count,
+            (0..axis_count).map(|i| {
+                coords
+                    .get(i)
+                    .map(|coord| coord.to_bits() as i32)
+                    .unwrap_or_default()
+            }),
+        )
+    }
+
+    /// Get data.
+    ///
+    /// GETDATA[] (0x92)
+    ///
+    /// This is an undocumented instruction that always returns 17.
+    pub(super) fn op_getdata(&mut self) -> OpResult {
+        self.value_stack.push(17)
+    }
+
+    /// Measure pixels per em.
+    ///
+    /// MPPEM[] (0x4B)
+    pub(super) fn op_mppem(&mut self) -> OpResult {
+        self.value_stack.push(self.ppem)
+    }
+
+    /// Measure point size.
+    ///
+    /// MPS[] (0x4C)
+    pub(super) fn op_mps(&mut self) -> OpResult {
+        self.value_stack.push(self.point_size)
+    }
+
+    /// Debug call.
+    ///
+    /// DEBUG[] (0x4F)
+    ///
+    /// This is not supported outside of a debugger.
+    pub(super) fn op_debug(&mut self) -> OpResult {
+        self.value_stack.pop()?;
+        Err(HintErrorKind::DebugOpcode)
+    }
+
+    /// Adjust angle.
+    ///
+    /// AA[] (0x7F)
+    ///
+    /// Obsolete instruction that pops its argument and does nothing.
+    pub(super) fn op_aa(&mut self) -> OpResult {
+        self.value_stack.pop()?;
+        Ok(())
+    }
+}
+
+/// Constants for the GETINFO instruction.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#get-information>
+mod getinfo {
+    pub const VERSION_SELECTOR_BIT: i32 = 1 << 0;
+    pub const VARIATIONS_SELECTOR_BIT: i32 = 1 << 3;
+    pub const VARIATIONS_RESULT_BIT: i32 = 1 << 10;
+    pub const HINTING_FOR_CLEARTYPE_SELECTOR_BIT: i32 = 1 << 6;
+    pub const HINTING_FOR_CLEARTYPE_RESULT_BIT: i32 = 1 << 13;
+    pub const LCD_VERTICAL_SELECTOR_BIT: i32 = 1 << 8;
+    pub const LCD_VERTICAL_RESULT_BIT: i32 = 1 << 15;
+    pub const SUBPIXEL_POSITIONED_SELECTOR_BIT: i32 = 1 << 10;
+    pub const SUBPIXEL_POSITIONED_RESULT_BIT: i32 = 1 << 17;
+    pub const SYMMETRICAL_SMOOTHING_SELECTOR_BIT: i32 = 1 << 11;
+    pub const SYMMETRICAL_SMOOTHING_RESULT_BIT: i32 = 1 << 18;
+    pub const GRAYSCALE_CLEARTYPE_SELECTOR_BIT: i32 = 1 << 12;
+    pub const GRAYSCALE_CLEARTYPE_RESULT_BIT: i32 = 1 << 19;
+}
@@ -0,0 +1,462 @@
+//! TrueType bytecode interpreter.
+//!
+//! The instruction implementations are grouped into modules following the
+//! categories of the [instruction set reference](https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions).
+//! Behavior closely follows the FreeType v40 interpreter so that results
+//! match exactly.
+
+mod arith;
+mod control_flow;
+mod delta;
+mod graphics;
+mod misc;
+mod outline;
+mod stack;
+mod storage;
+
+use read_fonts::types::F2Dot14;
+
+use super::{
+    code::{decode, CallStack, DefinitionMap, Instruction, Program},
+    error::{HintError, HintErrorKind},
+    graphics_state::GraphicsState,
+    zone::ZonePointer,
+};
+
+pub use stack::ValueStack;
+
+pub type OpResult = Result<(), HintErrorKind>;
+
+/// Maximum number of instructions executed in a single program.
+///
+/// Matches FreeType's `TT_CONFIG_OPTION_MAX_RUNNABLE_OPCODES`.
+const MAX_RUN_INSTRUCTIONS: usize = 1_000_000;
+
+/// Heuristic limits on loops to avoid slow execution of malformed
+/// bytecode.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6997>
+#[derive(Copy, Clone, Default, Debug)]
+pub struct LoopBudget {
+    limit: usize,
+    loop_calls: usize,
+    backward_jumps: usize,
+}
+
+impl LoopBudget {
+    pub fn new(glyph_point_count: usize, cvt_len: usize, glyph_count: usize) -> Self {
+        let limit = if glyph_point_count != 0 {
+            (glyph_point_count * 10).max(50) + (cvt_len / 10).max(50)
+        } else {
+            300 + 22 * cvt_len
+        };
+        // As a protection against an unreasonable number of CVT entries
+        // we assume at most 100 control values per glyph for the counter.
+        let limit = limit.min(100 * glyph_count);
+        Self {
+            limit,
+            loop_calls: 0,
+            backward_jumps: 0,
+        }
+    }
+
+    fn add_loop_calls(&mut self, count: usize) -> OpResult {
+        self.loop_calls += count;
+        if self.loop_calls > self.limit {
+            return Err(HintErrorKind::ExecutionTooLong);
+        }
+        Ok(())
+    }
+
+    fn add_backward_jump(&mut self) -> OpResult {
+        self.backward_jumps += 1;
+        if self.backward_jumps > self.limit {
+            return Err(HintErrorKind::ExecutionTooLong);
+        }
+        Ok(())
+    }
+}
+
+/// TrueType bytecode interpreter.
+pub struct Engine<'a> {
+    graphics: GraphicsState<'a>,
+    cvt: &'a mut [i32],
+    storage: &'a mut [i32],
+    value_stack: ValueStack<'a>,
+    functions: DefinitionMap<'a>,
+    instructions: DefinitionMap<'a>,
+    call_stack: CallStack,
+    programs: [&'a [u8]; 3],
+    program: Program,
+    initial_program: Program,
+    /// Offset of the current instruction.
+    pc: usize,
+    /// Offset of the next instruction. Control flow instructions modify
+    /// this to perform jumps.
+    next_pc: usize,
+    /// Current opcode. Many instructions encode flags in the low bits.
+    opcode: u8,
+    ppem: i32,
+    /// Size in points, in 26.6 format.
+    point_size: i32,
+    /// Scale factor from font units to 26.6 pixels in 16.16 format.
+    scale: i32,
+    coords: &'a [F2Dot14],
+    axis_count: u16,
+    is_pedantic: bool,
+    loop_budget: LoopBudget,
+}
+
+impl<'a> Engine<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mut graphics: GraphicsState<'a>,
+        cvt: &'a mut [i32],
+        storage: &'a mut [i32],
+        value_stack: &'a mut [i32],
+        functions: DefinitionMap<'a>,
+        instructions: DefinitionMap<'a>,
+        programs: [&'a [u8]; 3],
+        loop_budget: LoopBudget,
+    ) -> Self {
+        // We restrict the number of twilight points to a reasonable,
+        // heuristic value to avoid slow execution of malformed bytecode.
+        let max_twilight_points = (2 * (graphics.zones[1].len() + cvt.len())).max(30);
+        let twilight = &mut graphics.zones[0];
+        if twilight.len() > max_twilight_points {
+            twilight.truncate(max_twilight_points);
+        }
+        Self {
+            graphics,
+            cvt,
+            storage,
+            value_stack: ValueStack::new(value_stack),
+            functions,
+            instructions,
+            call_stack: CallStack::default(),
+            programs,
+            program: Program::Font,
+            initial_program: Program::Font,
+            pc: 0,
+            next_pc: 0,
+            opcode: 0,
+            ppem: 0,
+            point_size: 0,
+            scale: 0,
+            coords: &[],
+            axis_count: 0,
+            is_pedantic: false,
+            loop_budget,
+        }
+    }
+
+    /// Sets the size parameters for the current instance.
+    ///
+    /// The scale is a 16.16 factor for converting font units to 26.6
+    /// pixels.
+    pub fn set_size(&mut self, ppem: i32, point_size: i32, scale: i32) {
+        self.ppem = ppem;
+        self.point_size = point_size;
+        self.scale = scale;
+    }
+
+    /// Sets the normalized variation coordinates and the number of axes
+    /// in the font.
+    pub fn set_coords(&mut self, coords: &'a [F2Dot14], axis_count: u16) {
+        self.coords = coords;
+        self.axis_count = axis_count;
+    }
+
+    /// Returns the graphics state.
+    pub fn graphics(&self) -> &GraphicsState<'a> {
+        &self.graphics
+    }
+
+    /// Returns the mutable graphics state.
+    pub fn graphics_mut(&mut self) -> &mut GraphicsState<'a> {
+        &mut self.graphics
+    }
+
+    /// Consumes the engine and returns the graphics state.
+    pub fn into_graphics(self) -> GraphicsState<'a> {
+        self.graphics
+    }
+
+    /// Executes the given program.
+    pub fn run_program(&mut self, program: Program, is_pedantic: bool) -> Result<(), HintError> {
+        self.program = program;
+        self.initial_program = program;
+        self.pc = 0;
+        self.is_pedantic = is_pedantic;
+        self.value_stack.clear();
+        self.call_stack.clear();
+        // Toggle backward compatibility according to what the font wants,
+        // except in monochrome mode where rendering is completely up to the
+        // font's programming.
+        let gs = &mut self.graphics;
+        gs.backward_compatibility = gs.is_subpixel && gs.instruct_control & 4 == 0;
+        gs.did_iup_x = false;
+        gs.did_iup_y = false;
+        gs.update_projection_state();
+        self.run()
+    }
+
+    fn run(&mut self) -> Result<(), HintError> {
+        let mut count = 0;
+        loop {
+            let code = self.programs[self.program as usize];
+            let (program, pc) = (self.program, self.pc);
+            let err = |kind| HintError { program, pc, kind };
+            if pc >= code.len() {
+                if self.call_stack.len() != 0 {
+                    return Err(err(HintErrorKind::CodeOverflow));
+                }
+                return Ok(());
+            }
+            let ins = decode(code, pc).map_err(err)?;
+            self.next_pc = pc + ins.size;
+            self.dispatch(&ins).map_err(err)?;
+            self.pc = self.next_pc;
+            count += 1;
+            if count > MAX_RUN_INSTRUCTIONS {
+                return Err(err(HintErrorKind::ExecutionTooLong));
+            }
+        }
+    }
+
+    fn dispatch(&mut self, ins: &Instruction) -> OpResult {
+        let opcode = ins.opcode;
+        self.opcode = opcode;
+        self.value_stack
+            .ensure(pop_count(opcode), self.is_pedantic)?;
+        match opcode {
+            0x00..=0x01 => self.op_svtca(),
+            0x02..=0x03 => self.op_spvtca(),
+            0x04..=0x05 => self.op_sfvtca(),
+            0x06..=0x07 => self.op_spvtl(),
+            0x08..=0x09 => self.op_sfvtl(),
+            0x0A => self.op_spvfs(),
+            0x0B => self.op_sfvfs(),
+            0x0C => self.op_gpv(),
+            0x0D => self.op_gfv(),
+            0x0E => self.op_sfvtpv(),
+            0x0F => self.op_isect(),
+            0x10 => self.op_srp0(),
+            0x11 => self.op_srp1(),
+            0x12 => self.op_srp2(),
+            0x13 => self.op_szp0(),
+            0x14 => self.op_szp1(),
+            0x15 => self.op_szp2(),
+            0x16 => self.op_szps(),
+            0x17 => self.op_sloop(),
+            0x18 => self.op_rtg(),
+            0x19 => self.op_rthg(),
+            0x1A => self.op_smd(),
+            0x1B => self.op_else(),
+            0x1C => self.op_jmpr(),
+            0x1D => self.op_scvtci(),
+            0x1E => self.op_sswci(),
+            0x1F => self.op_ssw(),
+            0x20 => self.op_dup(),
+            0x21 => self.op_pop(),
+            0x22 => self.op_clear(),
+            0x23 => self.op_swap(),
+            0x24 => self.op_depth(),
+            0x25 => self.op_cindex(),
+            0x26 => self.op_mindex(),
+            0x27 => self.op_alignpts(),
+            0x29 => self.op_utp(),
+            0x2A => self.op_loopcall(),
+            0x2B => self.op_call(),
+            0x2C => self.op_fdef(),
+            0x2D => self.op_endf(),
+            0x2E..=0x2F => self.op_mdap(),
+            0x30..=0x31 => self.op_iup(),
+            0x32..=0x33 => self.op_shp(),
+            0x34..=0x35 => self.op_shc(),
+            0x36..=0x37 => self.op_shz(),
+            0x38 => self.op_shpix(),
+            0x39 => self.op_ip(),
+            0x3A..=0x3B => self.op_msirp(),
+            0x3C => self.op_alignrp(),
+            0x3D => self.op_rtdg(),
+            0x3E..=0x3F => self.op_miap(),
+            0x40..=0x41 | 0xB0..=0xBF => self.op_push(ins.push_count(), ins.push_values()),
+            0x42 => self.op_ws(),
+            0x43 => self.op_rs(),
+            0x44 => self.op_wcvtp(),
+            0x45 => self.op_rcvt(),
+            0x46..=0x47 => self.op_gc(),
+            0x48 => self.op_scfs(),
+            0x49..=0x4A => self.op_md(),
+            0x4B => self.op_mppem(),
+            0x4C => self.op_mps(),
+            0x4D => self.op_flipon(),
+            0x4E => self.op_flipoff(),
+            0x4F => self.op_debug(),
+            0x50 => self.op_lt(),
+            0x51 => self.op_lteq(),
+            0x52 => self.op_gt(),
+            0x53 => self.op_gteq(),
+            0x54 => self.op_eq(),
+            0x55 => self.op_neq(),
+            0x56 => self.op_odd(),
+            0x57 => self.op_even(),
+            0x58 => self.op_if(),
+            0x59 => self.op_eif(),
+            0x5A => self.op_and(),
+            0x5B => self.op_or(),
+            0x5C => self.op_not(),
+            0x5D | 0x71..=0x72 => self.op_deltap(),
+            0x5E => self.op_sdb(),
+            0x5F => self.op_sds(),
+            0x60 => self.op_add(),
+            0x61 => self.op_sub(),
+            0x62 => self.op_div(),
+            0x63 => self.op_mul(),
+            0x64 => self.op_abs(),
+            0x65 => self.op_neg(),
+            0x66 => self.op_floor(),
+            0x67 => self.op_ceiling(),
+            0x68..=0x6B => self.op_round(),
+            0x6C..=0x6F => self.op_nround(),
+            0x70 => self.op_wcvtf(),
+            0x73..=0x75 => self.op_deltac(),
+            0x76 => self.op_sround(),
+            0x77 => self.op_s45round(),
+            0x78 => self.op_jrot(),
+            0x79 => self.op_jrof(),
+            0x7A => self.op_roff(),
+            0x7C => self.op_rutg(),
+            0x7D => self.op_rdtg(),
+            0x7E => self.op_sangw(),
+            0x7F => self.op_aa(),
+            0x80 => self.op_flippt(),
+            0x81 => self.op_fliprgon(),
+            0x82 => self.op_fliprgoff(),
+            0x85 => self.op_scanctrl(),
+            0x86..=0x87 => self.op_sdpvtl(),
+            0x88 => self.op_getinfo(),
+            0x89 => self.op_idef(),
+            0x8A => self.op_roll(),
+            0x8B => self.op_max(),
+            0x8C => self.op_min(),
+            0x8D => self.op_scantype(),
+            0x8E => self.op_instctrl(),
+            0x91 if self.axis_count != 0 => self.op_getvariation(),
+            0x92 if self.axis_count != 0 => self.op_getdata(),
+            0xC0..=0xDF => self.op_mdrp(),
+            0xE0..=0xFF => self.op_mirp(),
+            _ => self.op_unknown(),
+        }
+    }
+
+    /// Returns the scale factor for unscaled distances.
+    ///
+    /// The "unscaled" points for composite glyphs are already scaled, so
+    /// this returns 1.0 in that case.
+    fn unscaled_scale(&self) -> i32 {
+        if self.graphics.is_composite {
+            0x10000
+        } else {
+            self.scale
+        }
+    }
+
+    /// Returns the number of points in the zone referenced by the given
+    /// pointer.
+    fn zone_len(&self, pointer: ZonePointer) -> usize {
+        self.graphics.zone(pointer).len()
+    }
+}
+
+/// Returns the number of fixed arguments popped by the given opcode.
+///
+/// Instructions that take a variable number of arguments (such as those
+/// that respect the loop counter) only include the fixed portion.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L1140>
+fn pop_count(opcode: u8) -> usize {
+    match opcode {
+        // SPVTL, SFVTL, SPVFS, SFVFS
+        0x06..=0x0B => 2,
+        // ISECT
+        0x0F => 5,
+        // SRPx, SZPx, SZPS, SLOOP
+        0x10..=0x17 => 1,
+        // SMD
+        0x1A => 1,
+        // JMPR, SCVTCI, SSWCI, SSW, DUP, POP
+        0x1C..=0x21 => 1,
+        // SWAP
+        0x23 => 2,
+        // CINDEX, MINDEX
+        0x25..=0x26 => 1,
+        // ALIGNPTS
+        0x27 => 2,
+        // UTP
+        0x29 => 1,
+        // LOOPCALL
+        0x2A => 2,
+        // CALL, FDEF
+        0x2B..=0x2C => 1,
+        // MDAP
+        0x2E..=0x2F => 1,
+        // SHC, SHZ, SHPIX
+        0x34..=0x38 => 1,
+        // MSIRP
+        0x3A..=0x3B => 2,
+        // MIAP
+        0x3E..=0x3F => 2,
+        // WS
+        0x42 => 2,
+        // RS, WCVTP, RCVT, GC
+        0x43 | 0x45..=0x47 => 1,
+        0x44 => 2,
+        // SCFS, MD
+        0x48..=0x4A => 2,
+        // DEBUG
+        0x4F => 1,
+        // LT, LTEQ, GT, GTEQ, EQ, NEQ
+        0x50..=0x55 => 2,
+        // ODD, EVEN, IF
+        0x56..=0x58 => 1,
+        // AND, OR
+        0x5A..=0x5B => 2,
+        // NOT, DELTAP1, SDB, SDS
+        0x5C..=0x5F => 1,
+        // ADD, SUB, DIV, MUL
+        0x60..=0x63 => 2,
+        // ABS, NEG, FLOOR, CEILING, ROUND, NROUND
+        0x64..=0x6F => 1,
+        // WCVTF
+        0x70 => 2,
+        // DELTAP2, DELTAP3, DELTAC1, DELTAC2, DELTAC3, SROUND, S45ROUND
+        0x71..=0x77 => 1,
+        // JROT, JROF
+        0x78..=0x79 => 2,
+        // SANGW, AA
+        0x7E..=0x7F => 1,
+        // FLIPRGON, FLIPRGOFF
+        0x81..=0x82 => 2,
+        // SCANCTRL
+        0x85 => 1,
+        // SDPVTL
+        0x86..=0x87 => 2,
+        // GETINFO, IDEF
+        0x88..=0x89 => 1,
+        // ROLL
+        0x8A => 3,
+        // MAX, MIN
+        0x8B..=0x8C => 2,
+        // SCANTYPE
+        0x8D => 1,
+        // INSTCTRL
+        0x8E => 2,
+        // MDRP
+        0xC0..=0xDF => 1,
+        // MIRP
+        0xE0..=0xFF => 2,
+        _ => 0,
+    }
+}
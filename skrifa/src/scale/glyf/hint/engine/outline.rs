@@ -0,0 +1,799 @@
+//! Managing outlines.
+//!
+//! Implements 31 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#managing-outlines>
+//! and <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#moving-points>
+
+use read_fonts::tables::glyf::PointMarker;
+
+use super::{
+    super::{math, zone::ZonePointer},
+    Engine, HintErrorKind, OpResult,
+};
+
+impl<'a> Engine<'a> {
+    /// Flip point.
+    ///
+    /// FLIPPT[] (0x80)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5002>
+    pub(super) fn op_flippt(&mut self) -> OpResult {
+        let count = self.take_loop_counter();
+        if self.is_post_iup_blocked() {
+            return Ok(());
+        }
+        if !self.has_loop_args(count)? {
+            return Ok(());
+        }
+        for _ in 0..count {
+            let point = self.value_stack.pop_usize()?;
+            let zone = self.graphics.zone_mut(ZonePointer::Glyph);
+            if let Some(flags) = zone.flags.get_mut(point) {
+                flags.flip_on_curve();
+            } else {
+                self.invalid_reference()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flip range on.
+    ///
+    /// FLIPRGON[] (0x81)
+    pub(super) fn op_fliprgon(&mut self) -> OpResult {
+        self.flip_range(true)
+    }
+
+    /// Flip range off.
+    ///
+    /// FLIPRGOFF[] (0x82)
+    pub(super) fn op_fliprgoff(&mut self) -> OpResult {
+        self.flip_range(false)
+    }
+
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5056>
+    fn flip_range(&mut self, on: bool) -> OpResult {
+        let high = self.value_stack.pop_usize()?;
+        let low = self.value_stack.pop_usize()?;
+        if self.is_post_iup_blocked() {
+            return Ok(());
+        }
+        let flags = &mut self.graphics.zone_mut(ZonePointer::Glyph).flags;
+        if high >= flags.len() || low >= flags.len() {
+            return self.invalid_reference();
+        }
+        for flag in flags.iter_mut().take(high + 1).skip(low) {
+            if on {
+                flag.set_on_curve();
+            } else {
+                flag.clear_on_curve();
+            }
+        }
+        Ok(())
+    }
+
+    /// Shift point by the last point.
+    ///
+    /// SHP[a] (0x32-0x33)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5166>
+    pub(super) fn op_shp(&mut self) -> OpResult {
+        let count = self.graphics.loop_counter as usize;
+        if !self.has_loop_args(count)? {
+            self.graphics.loop_counter = 1;
+            return Ok(());
+        }
+        let Some((_, _, dx, dy)) = self.point_displacement()? else {
+            return Ok(());
+        };
+        let zp2 = self.graphics.zp2;
+        for _ in 0..count {
+            let point = self.value_stack.pop_usize()?;
+            if point >= self.zone_len(zp2) {
+                self.invalid_reference()?;
+                continue;
+            }
+            self.graphics.move_zp2_point(point, dx, dy, true);
+        }
+        self.graphics.loop_counter = 1;
+        Ok(())
+    }
+
+    /// Shift contour by the last point.
+    ///
+    /// SHC[a] (0x34-0x35)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5229>
+    pub(super) fn op_shc(&mut self) -> OpResult {
+        let contour = self.value_stack.pop()? as i16 as i32;
+        let zp2 = self.graphics.zp2;
+        let zone = self.graphics.zone(zp2);
+        let bound = if zp2 == ZonePointer::Twilight {
+            1
+        } else {
+            zone.contours.len()
+        };
+        if contour < 0 || contour as usize >= bound {
+            return self.invalid_reference();
+        }
+        let Some((ref_zone, ref_point, dx, dy)) = self.point_displacement()? else {
+            return Ok(());
+        };
+        let contour = contour as usize;
+        let zone = self.graphics.zone(zp2);
+        let start = if contour == 0 {
+            0
+        } else {
+            zone.contours[contour - 1] as usize + 1
+        };
+        // We use the number of points if in the twilight zone.
+        let limit = if zp2 == ZonePointer::Twilight {
+            zone.len()
+        } else {
+            zone.contours[contour] as usize + 1
+        };
+        for point in start..limit {
+            if ref_zone != zp2 || ref_point != point {
+                self.graphics.move_zp2_point(point, dx, dy, true);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shift zone by the last point.
+    ///
+    /// SHZ[a] (0x36-0x37)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5287>
+    pub(super) fn op_shz(&mut self) -> OpResult {
+        let zone = self.value_stack.pop()?;
+        if ZonePointer::try_from(zone).is_err() {
+            return self.invalid_reference();
+        }
+        let Some((ref_zone, ref_point, dx, dy)) = self.point_displacement()? else {
+            return Ok(());
+        };
+        let zp2 = self.graphics.zp2;
+        let zone = self.graphics.zone(zp2);
+        // SHZ doesn't move the phantom points. The twilight zone has no
+        // contours so use the number of points.
+        let limit = if zp2 == ZonePointer::Twilight {
+            zone.len()
+        } else {
+            zone.contours
+                .last()
+                .map(|end| *end as usize + 1)
+                .unwrap_or_default()
+        };
+        for point in 0..limit {
+            if ref_zone != zp2 || ref_point != point {
+                // SHZ doesn't touch the points.
+                self.graphics.move_zp2_point(point, dx, dy, false);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shift point by a pixel amount.
+    ///
+    /// SHPIX[] (0x38)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5356>
+    pub(super) fn op_shpix(&mut self) -> OpResult {
+        let amount = self.value_stack.pop()?;
+        let count = self.graphics.loop_counter as usize;
+        if !self.has_loop_args(count)? {
+            self.graphics.loop_counter = 1;
+            return Ok(());
+        }
+        let gs = &self.graphics;
+        let in_twilight = gs.zp0 == ZonePointer::Twilight
+            || gs.zp1 == ZonePointer::Twilight
+            || gs.zp2 == ZonePointer::Twilight;
+        let (fx, fy) = gs.freedom_vector;
+        let dx = math::mul14(amount, fx);
+        let dy = math::mul14(amount, fy);
+        let zp2 = gs.zp2;
+        for _ in 0..count {
+            let point = self.value_stack.pop_usize()?;
+            if point >= self.zone_len(zp2) {
+                self.invalid_reference()?;
+                continue;
+            }
+            let gs = &mut self.graphics;
+            if gs.backward_compatibility {
+                // Allow SHPIX to move points in the twilight zone.
+                // Otherwise, treat SHPIX the same as DELTAP. This unbreaks
+                // various fonts that would glitch severely after calling
+                // ALIGNRP after a blocked SHPIX.
+                let is_touched_y = gs.zone(zp2).flags[point].has_marker(PointMarker::TOUCHED_Y);
+                if in_twilight
+                    || (!(gs.did_iup_x && gs.did_iup_y)
+                        && ((gs.is_composite && fy != 0) || is_touched_y))
+                {
+                    gs.move_zp2_point(point, 0, dy, true);
+                }
+            } else {
+                gs.move_zp2_point(point, dx, dy, true);
+            }
+        }
+        self.graphics.loop_counter = 1;
+        Ok(())
+    }
+
+    /// Move stack indirect relative point.
+    ///
+    /// MSIRP[a] (0x3A-0x3B)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5439>
+    pub(super) fn op_msirp(&mut self) -> OpResult {
+        let distance = self.value_stack.pop()?;
+        let point = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp0, zp1, rp0) = (gs.zp0, gs.zp1, gs.rp0);
+        if point >= self.zone_len(zp1) || rp0 >= self.zone_len(zp0) {
+            return self.invalid_reference();
+        }
+        // The MS rasterizer does this with twilight points.
+        if zp1 == ZonePointer::Twilight {
+            let org = self.org(zp0, rp0);
+            let gs = &mut self.graphics;
+            gs.zone_mut(zp1).set_original(point, org);
+            gs.move_original(zp1, point, distance);
+            let org = self.org(zp1, point);
+            self.graphics.zone_mut(zp1).set_point(point, org);
+        }
+        let cur_dist = self.project_between(zp1, point, zp0, rp0);
+        self.graphics
+            .move_point(zp1, point, distance.wrapping_sub(cur_dist));
+        let gs = &mut self.graphics;
+        gs.rp1 = gs.rp0;
+        gs.rp2 = point;
+        if self.opcode & 1 != 0 {
+            gs.rp0 = point;
+        }
+        Ok(())
+    }
+
+    /// Move direct absolute point.
+    ///
+    /// MDAP[a] (0x2E-0x2F)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5491>
+    pub(super) fn op_mdap(&mut self) -> OpResult {
+        let point = self.value_stack.pop_usize()?;
+        let zp0 = self.graphics.zp0;
+        if point >= self.zone_len(zp0) {
+            return self.invalid_reference();
+        }
+        let distance = if self.opcode & 1 != 0 {
+            let (x, y) = self.cur(zp0, point);
+            let cur_dist = self.graphics.project(x, y);
+            self.graphics
+                .round_state
+                .round(cur_dist)
+                .wrapping_sub(cur_dist)
+        } else {
+            0
+        };
+        self.graphics.move_point(zp0, point, distance);
+        self.graphics.rp0 = point;
+        self.graphics.rp1 = point;
+        Ok(())
+    }
+
+    /// Move indirect absolute point.
+    ///
+    /// MIAP[a] (0x3E-0x3F)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5537>
+    pub(super) fn op_miap(&mut self) -> OpResult {
+        let cvt_index = self.value_stack.pop()? as u32 as usize;
+        let point = self.value_stack.pop_usize()?;
+        let zp0 = self.graphics.zp0;
+        if point >= self.zone_len(zp0) || cvt_index >= self.cvt.len() {
+            self.invalid_reference()?;
+        } else {
+            let mut distance = self.cvt[cvt_index];
+            // The behavior of MIAP is quite different when used in the
+            // twilight zone. No cut-in test is performed and the original
+            // position is set to the unrounded distance from the CVT.
+            if zp0 == ZonePointer::Twilight {
+                let (fx, fy) = self.graphics.freedom_vector;
+                let org = (math::mul14(distance, fx), math::mul14(distance, fy));
+                let zone = self.graphics.zone_mut(zp0);
+                zone.set_original(point, org);
+                zone.set_point(point, org);
+            }
+            let (x, y) = self.cur(zp0, point);
+            let org_dist = self.graphics.project(x, y);
+            if self.opcode & 1 != 0 {
+                let delta = distance.wrapping_sub(org_dist).wrapping_abs();
+                if delta > self.graphics.control_value_cutin {
+                    distance = org_dist;
+                }
+                distance = self.graphics.round_state.round(distance);
+            }
+            self.graphics
+                .move_point(zp0, point, distance.wrapping_sub(org_dist));
+        }
+        self.graphics.rp0 = point;
+        self.graphics.rp1 = point;
+        Ok(())
+    }
+
+    /// Move direct relative point.
+    ///
+    /// MDRP[abcde] (0xC0-0xDF)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5614>
+    pub(super) fn op_mdrp(&mut self) -> OpResult {
+        let point = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp0, zp1, rp0) = (gs.zp0, gs.zp1, gs.rp0);
+        if point >= self.zone_len(zp1) || rp0 >= self.zone_len(zp0) {
+            self.invalid_reference()?;
+        } else {
+            let mut org_dist = if zp0 == ZonePointer::Twilight || zp1 == ZonePointer::Twilight {
+                let (dx, dy) = sub(self.org(zp1, point), self.org(zp0, rp0));
+                self.graphics.dual_project(dx, dy)
+            } else {
+                let (dx, dy) = sub(self.orus(zp1, point), self.orus(zp0, rp0));
+                math::mul(self.graphics.dual_project(dx, dy), self.unscaled_scale())
+            };
+            let gs = &self.graphics;
+            // Single width cut-in test.
+            let cutin = gs.single_width_cutin;
+            let width = gs.single_width;
+            if cutin > 0
+                && org_dist < width.wrapping_add(cutin)
+                && org_dist > width.wrapping_sub(cutin)
+            {
+                org_dist = if org_dist >= 0 { width } else { -width };
+            }
+            let mut distance = if self.opcode & 4 != 0 {
+                gs.round_state.round(org_dist)
+            } else {
+                org_dist
+            };
+            if self.opcode & 8 != 0 {
+                distance = apply_min_distance(org_dist, distance, gs.min_distance);
+            }
+            let cur_dist = self.project_between(zp1, point, zp0, rp0);
+            self.graphics
+                .move_point(zp1, point, distance.wrapping_sub(cur_dist));
+        }
+        let gs = &mut self.graphics;
+        gs.rp1 = gs.rp0;
+        gs.rp2 = point;
+        if self.opcode & 16 != 0 {
+            gs.rp0 = point;
+        }
+        Ok(())
+    }
+
+    /// Move indirect relative point.
+    ///
+    /// MIRP[abcde] (0xE0-0xFF)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5731>
+    pub(super) fn op_mirp(&mut self) -> OpResult {
+        let cvt_index = self.value_stack.pop()?;
+        let point = self.value_stack.pop_usize()?;
+        // An index of -1 refers to an implicit CVT entry of 0.
+        let cvt_entry = (cvt_index as u32).wrapping_add(1) as usize;
+        let gs = &self.graphics;
+        let (zp0, zp1, rp0) = (gs.zp0, gs.zp1, gs.rp0);
+        if point >= self.zone_len(zp1) || cvt_entry > self.cvt.len() || rp0 >= self.zone_len(zp0) {
+            self.invalid_reference()?;
+        } else {
+            let mut cvt_dist = if cvt_entry == 0 {
+                0
+            } else {
+                self.cvt[cvt_entry - 1]
+            };
+            let gs = &self.graphics;
+            // Single width test.
+            let width = gs.single_width;
+            if cvt_dist.wrapping_sub(width).wrapping_abs() < gs.single_width_cutin {
+                cvt_dist = if cvt_dist >= 0 { width } else { -width };
+            }
+            // The MS rasterizer does this with twilight points.
+            if zp1 == ZonePointer::Twilight {
+                let (fx, fy) = gs.freedom_vector;
+                let (x, y) = self.org(zp0, rp0);
+                let org = (
+                    x.wrapping_add(math::mul14(cvt_dist, fx)),
+                    y.wrapping_add(math::mul14(cvt_dist, fy)),
+                );
+                let zone = self.graphics.zone_mut(zp1);
+                zone.set_original(point, org);
+                zone.set_point(point, org);
+            }
+            let (dx, dy) = sub(self.org(zp1, point), self.org(zp0, rp0));
+            let org_dist = self.graphics.dual_project(dx, dy);
+            let cur_dist = self.project_between(zp1, point, zp0, rp0);
+            let gs = &self.graphics;
+            // Auto-flip test.
+            if gs.auto_flip && (org_dist ^ cvt_dist) < 0 {
+                cvt_dist = cvt_dist.wrapping_neg();
+            }
+            let mut distance = if self.opcode & 4 != 0 {
+                // Only perform the cut-in test when both points refer to
+                // the same zone.
+                if zp0 == zp1 {
+                    let delta = cvt_dist.wrapping_sub(org_dist).wrapping_abs();
+                    if delta > gs.control_value_cutin {
+                        cvt_dist = org_dist;
+                    }
+                }
+                gs.round_state.round(cvt_dist)
+            } else {
+                cvt_dist
+            };
+            if self.opcode & 8 != 0 {
+                distance = apply_min_distance(org_dist, distance, gs.min_distance);
+            }
+            self.graphics
+                .move_point(zp1, point, distance.wrapping_sub(cur_dist));
+        }
+        let gs = &mut self.graphics;
+        gs.rp1 = gs.rp0;
+        if self.opcode & 16 != 0 {
+            gs.rp0 = point;
+        }
+        gs.rp2 = point;
+        Ok(())
+    }
+
+    /// Align relative point.
+    ///
+    /// ALIGNRP[] (0x3C)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5889>
+    pub(super) fn op_alignrp(&mut self) -> OpResult {
+        let count = self.graphics.loop_counter as usize;
+        let gs = &self.graphics;
+        let (zp0, zp1, rp0) = (gs.zp0, gs.zp1, gs.rp0);
+        if !self.has_loop_args(count)? || rp0 >= self.zone_len(zp0) {
+            self.graphics.loop_counter = 1;
+            return self.invalid_reference();
+        }
+        for _ in 0..count {
+            let point = self.value_stack.pop_usize()?;
+            if point >= self.zone_len(zp1) {
+                self.invalid_reference()?;
+                continue;
+            }
+            let distance = self.project_between(zp1, point, zp0, rp0);
+            self.graphics
+                .move_point(zp1, point, distance.wrapping_neg());
+        }
+        self.graphics.loop_counter = 1;
+        Ok(())
+    }
+
+    /// Move point to intersection of two lines.
+    ///
+    /// ISECT[] (0x0F)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5934>
+    pub(super) fn op_isect(&mut self) -> OpResult {
+        let b1 = self.value_stack.pop_usize()?;
+        let b0 = self.value_stack.pop_usize()?;
+        let a1 = self.value_stack.pop_usize()?;
+        let a0 = self.value_stack.pop_usize()?;
+        let point = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp0, zp1, zp2) = (gs.zp0, gs.zp1, gs.zp2);
+        let (zp0_len, zp1_len) = (self.zone_len(zp0), self.zone_len(zp1));
+        if b0 >= zp0_len
+            || b1 >= zp0_len
+            || a0 >= zp1_len
+            || a1 >= zp1_len
+            || point >= self.zone_len(zp2)
+        {
+            return self.invalid_reference();
+        }
+        let (a0, a1) = (self.cur(zp1, a0), self.cur(zp1, a1));
+        let (b0, b1) = (self.cur(zp0, b0), self.cur(zp0, b1));
+        let (dbx, dby) = sub(b1, b0);
+        let (dax, day) = sub(a1, a0);
+        let (dx, dy) = sub(b0, a0);
+        let discriminant = math::mul_div(dax, dby.wrapping_neg(), 0x40)
+            .wrapping_add(math::mul_div(day, dbx, 0x40));
+        let dot_product = math::mul_div(dax, dbx, 0x40).wrapping_add(math::mul_div(day, dby, 0x40));
+        // The discriminant and dot product are surrogates for the sine
+        // and cosine of the angle between the vectors. Reject grazing
+        // intersections by thresholding at roughly 3 degrees.
+        let new_point = if 19 * (discriminant as i64).abs() > (dot_product as i64).abs() {
+            let v = math::mul_div(dx, dby.wrapping_neg(), 0x40)
+                .wrapping_add(math::mul_div(dy, dbx, 0x40));
+            let x = math::mul_div(v, dax, discriminant);
+            let y = math::mul_div(v, day, discriminant);
+            (a0.0.wrapping_add(x), a0.1.wrapping_add(y))
+        } else {
+            // Otherwise, take the middle of the middles of A and B.
+            let mid = |a0: i32, a1: i32, b0: i32, b1: i32| {
+                ((a0 as i64 + a1 as i64 + b0 as i64 + b1 as i64) / 4) as i32
+            };
+            (mid(a0.0, a1.0, b0.0, b1.0), mid(a0.1, a1.1, b0.1, b1.1))
+        };
+        let zone = self.graphics.zone_mut(zp2);
+        zone.set_point(point, new_point);
+        zone.touch(point, true, true);
+        Ok(())
+    }
+
+    /// Align points.
+    ///
+    /// ALIGNPTS[] (0x27)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6011>
+    pub(super) fn op_alignpts(&mut self) -> OpResult {
+        let p2 = self.value_stack.pop_usize()?;
+        let p1 = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp0, zp1) = (gs.zp0, gs.zp1);
+        if p1 >= self.zone_len(zp1) || p2 >= self.zone_len(zp0) {
+            return self.invalid_reference();
+        }
+        let distance = self.project_between(zp0, p2, zp1, p1) / 2;
+        self.graphics.move_point(zp1, p1, distance);
+        self.graphics.move_point(zp0, p2, distance.wrapping_neg());
+        Ok(())
+    }
+
+    /// Interpolate point by the last relative stretch.
+    ///
+    /// IP[] (0x39)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6045>
+    pub(super) fn op_ip(&mut self) -> OpResult {
+        let count = self.graphics.loop_counter as usize;
+        let gs = &self.graphics;
+        let (zp0, zp1, zp2, rp1, rp2) = (gs.zp0, gs.zp1, gs.zp2, gs.rp1, gs.rp2);
+        if !self.has_loop_args(count)? || rp1 >= self.zone_len(zp0) {
+            self.graphics.loop_counter = 1;
+            return self.invalid_reference();
+        }
+        // We need to deal in a special way with the twilight zone where
+        // the unscaled points are always zero.
+        let in_twilight = zp0 == ZonePointer::Twilight
+            || zp1 == ZonePointer::Twilight
+            || zp2 == ZonePointer::Twilight;
+        let orus = |engine: &Self, zp: ZonePointer, point: usize| {
+            if in_twilight {
+                engine.org(zp, point)
+            } else {
+                engine.orus(zp, point)
+            }
+        };
+        let orus_base = orus(self, zp0, rp1);
+        let cur_base = self.cur(zp0, rp1);
+        // Some popular fonts call IP with bad values of rp2 so do
+        // something sane when that happens.
+        let (old_range, cur_range) = if rp2 >= self.zone_len(zp1) {
+            (0, 0)
+        } else {
+            let (dx, dy) = sub(orus(self, zp1, rp2), orus_base);
+            let old_range = self.graphics.dual_project(dx, dy);
+            let (dx, dy) = sub(self.cur(zp1, rp2), cur_base);
+            (old_range, self.graphics.project(dx, dy))
+        };
+        for _ in 0..count {
+            let point = self.value_stack.pop_usize()?;
+            if point >= self.zone_len(zp2) {
+                self.invalid_reference()?;
+                continue;
+            }
+            let (dx, dy) = sub(orus(self, zp2, point), orus_base);
+            let org_dist = self.graphics.dual_project(dx, dy);
+            let (dx, dy) = sub(self.cur(zp2, point), cur_base);
+            let cur_dist = self.graphics.project(dx, dy);
+            let new_dist = if org_dist != 0 {
+                if old_range != 0 {
+                    math::mul_div(org_dist, cur_range, old_range)
+                } else {
+                    // This is the same as what MS does for the invalid
+                    // case.
+                    org_dist
+                }
+            } else {
+                0
+            };
+            self.graphics
+                .move_point(zp2, point, new_dist.wrapping_sub(cur_dist));
+        }
+        self.graphics.loop_counter = 1;
+        Ok(())
+    }
+
+    /// Untouch point.
+    ///
+    /// UTP[] (0x29)
+    pub(super) fn op_utp(&mut self) -> OpResult {
+        let point = self.value_stack.pop_usize()?;
+        let zp0 = self.graphics.zp0;
+        if point >= self.zone_len(zp0) {
+            return self.invalid_reference();
+        }
+        let (fx, fy) = self.graphics.freedom_vector;
+        self.graphics.zone_mut(zp0).untouch(point, fx != 0, fy != 0);
+        Ok(())
+    }
+
+    /// Interpolate untouched points through the outline.
+    ///
+    /// IUP[a] (0x30-0x31)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6744>
+    pub(super) fn op_iup(&mut self) -> OpResult {
+        let is_x = self.opcode & 1 != 0;
+        let gs = &mut self.graphics;
+        // Allow IUP until it has been called on both axes. Immediately
+        // return on subsequent ones.
+        if gs.backward_compatibility {
+            if gs.did_iup_x && gs.did_iup_y {
+                return Ok(());
+            }
+            if is_x {
+                gs.did_iup_x = true;
+            } else {
+                gs.did_iup_y = true;
+            }
+        }
+        gs.zone_mut(ZonePointer::Glyph).iup(is_x);
+        Ok(())
+    }
+
+    /// Get coordinate projected onto the projection vector.
+    ///
+    /// GC[a] (0x46-0x47)
+    pub(super) fn op_gc(&mut self) -> OpResult {
+        let point = self.value_stack.pop()? as u32 as usize;
+        let zp2 = self.graphics.zp2;
+        let value = if point >= self.zone_len(zp2) {
+            self.invalid_reference()?;
+            0
+        } else if self.opcode & 1 != 0 {
+            let (x, y) = self.org(zp2, point);
+            self.graphics.dual_project(x, y)
+        } else {
+            let (x, y) = self.cur(zp2, point);
+            self.graphics.project(x, y)
+        };
+        self.value_stack.push(value)
+    }
+
+    /// Set coordinate from the stack using the projection and freedom
+    /// vectors.
+    ///
+    /// SCFS[] (0x48)
+    pub(super) fn op_scfs(&mut self) -> OpResult {
+        let value = self.value_stack.pop()?;
+        let point = self.value_stack.pop_usize()?;
+        let zp2 = self.graphics.zp2;
+        if point >= self.zone_len(zp2) {
+            return self.invalid_reference();
+        }
+        let (x, y) = self.cur(zp2, point);
+        let cur = self.graphics.project(x, y);
+        self.graphics
+            .move_point(zp2, point, value.wrapping_sub(cur));
+        // The MS rasterizer does this with twilight points.
+        if zp2 == ZonePointer::Twilight {
+            let cur = self.cur(zp2, point);
+            self.graphics.zone_mut(zp2).set_original(point, cur);
+        }
+        Ok(())
+    }
+
+    /// Measure distance.
+    ///
+    /// MD[a] (0x49-0x4A)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L4895>
+    pub(super) fn op_md(&mut self) -> OpResult {
+        let p2 = self.value_stack.pop_usize()?;
+        let p1 = self.value_stack.pop_usize()?;
+        let gs = &self.graphics;
+        let (zp0, zp1) = (gs.zp0, gs.zp1);
+        let distance = if p1 >= self.zone_len(zp0) || p2 >= self.zone_len(zp1) {
+            self.invalid_reference()?;
+            0
+        } else if self.opcode & 1 != 0 {
+            self.project_between(zp0, p1, zp1, p2)
+        } else if zp0 == ZonePointer::Twilight || zp1 == ZonePointer::Twilight {
+            let (dx, dy) = sub(self.org(zp0, p1), self.org(zp1, p2));
+            self.graphics.dual_project(dx, dy)
+        } else {
+            let (dx, dy) = sub(self.orus(zp0, p1), self.orus(zp1, p2));
+            math::mul(self.graphics.dual_project(dx, dy), self.unscaled_scale())
+        };
+        self.value_stack.push(distance)
+    }
+
+    /// Computes the displacement of the reference point used by the shift
+    /// instructions.
+    ///
+    /// Returns the reference zone and point along with the displacement.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L5126>
+    fn point_displacement(
+        &mut self,
+    ) -> Result<Option<(ZonePointer, usize, i32, i32)>, HintErrorKind> {
+        let gs = &self.graphics;
+        let (zone, point) = if self.opcode & 1 != 0 {
+            (gs.zp0, gs.rp1)
+        } else {
+            (gs.zp1, gs.rp2)
+        };
+        if point >= self.zone_len(zone) {
+            self.invalid_reference()?;
+            return Ok(None);
+        }
+        let (dx, dy) = sub(self.cur(zone, point), self.org(zone, point));
+        let gs = &self.graphics;
+        let distance = gs.project(dx, dy);
+        let (fx, fy) = gs.freedom_vector;
+        let x = math::mul_div(distance, fx, gs.fdotp);
+        let y = math::mul_div(distance, fy, gs.fdotp);
+        Ok(Some((zone, point, x, y)))
+    }
+
+    /// Returns the distance between two points projected onto the
+    /// projection vector.
+    fn project_between(&self, zone_a: ZonePointer, a: usize, zone_b: ZonePointer, b: usize) -> i32 {
+        let (dx, dy) = sub(self.cur(zone_a, a), self.cur(zone_b, b));
+        self.graphics.project(dx, dy)
+    }
+
+    /// Returns true if point movement is blocked because backward
+    /// compatibility is active and IUP has been called on both axes.
+    fn is_post_iup_blocked(&self) -> bool {
+        let gs = &self.graphics;
+        gs.backward_compatibility && gs.did_iup_x && gs.did_iup_y
+    }
+
+    /// Returns true if there are enough values on the stack for an
+    /// instruction that respects the loop counter.
+    fn has_loop_args(&self, count: usize) -> Result<bool, HintErrorKind> {
+        if self.value_stack.len() < count {
+            if self.is_pedantic {
+                return Err(HintErrorKind::ValueStackUnderflow);
+            }
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn take_loop_counter(&mut self) -> usize {
+        core::mem::replace(&mut self.graphics.loop_counter, 1) as usize
+    }
+
+    fn cur(&self, zone: ZonePointer, point: usize) -> (i32, i32) {
+        self.graphics.zone(zone).point(point).unwrap_or_default()
+    }
+
+    fn org(&self, zone: ZonePointer, point: usize) -> (i32, i32) {
+        self.graphics.zone(zone).original(point).unwrap_or_default()
+    }
+
+    fn orus(&self, zone: ZonePointer, point: usize) -> (i32, i32) {
+        self.graphics.zone(zone).unscaled(point).unwrap_or_default()
+    }
+}
+
+fn sub(a: (i32, i32), b: (i32, i32)) -> (i32, i32) {
+    (a.0.wrapping_sub(b.0), a.1.wrapping_sub(b.1))
+}
+
+fn apply_min_distance(org_dist: i32, distance: i32, min_distance: i32) -> i32 {
+    if org_dist >= 0 {
+        distance.max(min_distance)
+    } else {
+        distance.min(min_distance.wrapping_neg())
+    }
+}
@@ -0,0 +1,254 @@
+//! Value stack and stack management instructions.
+//!
+//! Implements 9 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#managing-the-stack>
+
+use super::{Engine, HintErrorKind, OpResult};
+
+/// Value stack for the TrueType interpreter.
+pub struct ValueStack<'a> {
+    values: &'a mut [i32],
+    len: usize,
+}
+
+impl<'a> ValueStack<'a> {
+    pub fn new(values: &'a mut [i32]) -> Self {
+        Self { values, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Ensures that at least `count` values are available to pop.
+    ///
+    /// Like FreeType, on underflow all arguments are replaced with zero.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L7081>
+    pub fn ensure(&mut self, count: usize, is_pedantic: bool) -> OpResult {
+        if self.len >= count {
+            return Ok(());
+        }
+        if is_pedantic {
+            return Err(HintErrorKind::ValueStackUnderflow);
+        }
+        let zeros = self
+            .values
+            .get_mut(..count)
+            .ok_or(HintErrorKind::ValueStackOverflow)?;
+        zeros.fill(0);
+        self.len = count;
+        Ok(())
+    }
+
+    pub fn push(&mut self, value: i32) -> OpResult {
+        let slot = self
+            .values
+            .get_mut(self.len)
+            .ok_or(HintErrorKind::ValueStackOverflow)?;
+        *slot = value;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Pushes all values from the given iterator, checking for available
+    /// space up front.
+    pub fn push_all(&mut self, count: usize, values: impl Iterator<Item = i32>) -> OpResult {
+        let slots = self
+            .values
+            .get_mut(self.len..self.len + count)
+            .ok_or(HintErrorKind::ValueStackOverflow)?;
+        for (slot, value) in slots.iter_mut().zip(values) {
+            *slot = value;
+        }
+        self.len += count;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Result<i32, HintErrorKind> {
+        let value = self.peek()?;
+        self.len -= 1;
+        Ok(value)
+    }
+
+    /// Pops a value for use as a point, contour or other index.
+    ///
+    /// Matches FreeType's truncation to 16 bits.
+    pub fn pop_usize(&mut self) -> Result<usize, HintErrorKind> {
+        Ok(self.pop()? as u16 as usize)
+    }
+
+    pub fn peek(&self) -> Result<i32, HintErrorKind> {
+        self.len
+            .checked_sub(1)
+            .map(|ix| self.values[ix])
+            .ok_or(HintErrorKind::ValueStackUnderflow)
+    }
+
+    fn top_mut(&mut self) -> Result<&mut i32, HintErrorKind> {
+        let ix = self
+            .len
+            .checked_sub(1)
+            .ok_or(HintErrorKind::ValueStackUnderflow)?;
+        Ok(&mut self.values[ix])
+    }
+
+    /// Applies a unary operation to the top value.
+    pub fn apply_unary(&mut self, f: impl FnOnce(i32) -> Result<i32, HintErrorKind>) -> OpResult {
+        let top = self.top_mut()?;
+        *top = f(*top)?;
+        Ok(())
+    }
+
+    /// Pops the top value and applies a binary operation with the
+    /// following value where `a` is the deeper of the two.
+    pub fn apply_binary(
+        &mut self,
+        f: impl FnOnce(i32, i32) -> Result<i32, HintErrorKind>,
+    ) -> OpResult {
+        let b = self.pop()?;
+        let a = self.top_mut()?;
+        *a = f(*a, b)?;
+        Ok(())
+    }
+
+    /// Returns the values currently on the stack.
+    pub fn values(&self) -> &[i32] {
+        &self.values[..self.len]
+    }
+
+    fn values_mut(&mut self) -> &mut [i32] {
+        &mut self.values[..self.len]
+    }
+}
+
+impl<'a> Engine<'a> {
+    /// Duplicate top stack element.
+    ///
+    /// DUP[] (0x20)
+    pub(super) fn op_dup(&mut self) -> OpResult {
+        let value = self.value_stack.peek()?;
+        self.value_stack.push(value)
+    }
+
+    /// Pop top stack element.
+    ///
+    /// POP[] (0x21)
+    pub(super) fn op_pop(&mut self) -> OpResult {
+        self.value_stack.pop()?;
+        Ok(())
+    }
+
+    /// Clear the entire stack.
+    ///
+    /// CLEAR[] (0x22)
+    pub(super) fn op_clear(&mut self) -> OpResult {
+        self.value_stack.clear();
+        Ok(())
+    }
+
+    /// Swap the top two elements on the stack.
+    ///
+    /// SWAP[] (0x23)
+    pub(super) fn op_swap(&mut self) -> OpResult {
+        let len = self.value_stack.len();
+        self.value_stack.values_mut().swap(len - 1, len - 2);
+        Ok(())
+    }
+
+    /// Returns the depth of the stack.
+    ///
+    /// DEPTH[] (0x24)
+    pub(super) fn op_depth(&mut self) -> OpResult {
+        let depth = self.value_stack.len() as i32;
+        self.value_stack.push(depth)
+    }
+
+    /// Copy the indexed element to the top of the stack.
+    ///
+    /// CINDEX[] (0x25)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3275>
+    pub(super) fn op_cindex(&mut self) -> OpResult {
+        let index = self.value_stack.pop()?;
+        let len = self.value_stack.len();
+        let value = if index <= 0 || index as usize > len {
+            if self.is_pedantic {
+                return Err(HintErrorKind::InvalidReference);
+            }
+            0
+        } else {
+            self.value_stack.values()[len - index as usize]
+        };
+        self.value_stack.push(value)
+    }
+
+    /// Move the indexed element to the top of the stack.
+    ///
+    /// MINDEX[] (0x26)
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L3245>
+    pub(super) fn op_mindex(&mut self) -> OpResult {
+        let index = self.value_stack.pop()?;
+        let len = self.value_stack.len();
+        if index <= 0 || index as usize > len {
+            if self.is_pedantic {
+                return Err(HintErrorKind::InvalidReference);
+            }
+        } else {
+            self.value_stack.values_mut()[len - index as usize..].rotate_left(1);
+        }
+        Ok(())
+    }
+
+    /// Roll the top three stack elements.
+    ///
+    /// ROLL[] (0x8A)
+    pub(super) fn op_roll(&mut self) -> OpResult {
+        let len = self.value_stack.len();
+        self.value_stack.values_mut()[len - 3..].rotate_left(1);
+        Ok(())
+    }
+
+    /// Push data from the instruction stream.
+    ///
+    /// NPUSHB[] (0x40), NPUSHW[] (0x41), PUSHB[abc] (0xB0-0xB7),
+    /// PUSHW[abc] (0xB8-0xBF)
+    pub(super) fn op_push(&mut self, count: usize, values: impl Iterator<Item = i32>) -> OpResult {
+        self.value_stack.push_all(count, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underflow_pushes_zeros() {
+        let mut values = [0; 8];
+        let mut stack = ValueStack::new(&mut values);
+        stack.push(5).unwrap();
+        stack.ensure(2, false).unwrap();
+        assert_eq!(stack.values(), &[0, 0]);
+        assert!(stack.ensure(3, true).is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        let mut values = [0; 2];
+        let mut stack = ValueStack::new(&mut values);
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        assert_eq!(stack.push(3), Err(HintErrorKind::ValueStackOverflow));
+        assert!(stack.push_all(1, [3].into_iter()).is_err());
+    }
+}
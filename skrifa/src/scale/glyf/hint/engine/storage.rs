@@ -0,0 +1,78 @@
+//! Managing the storage area and control value table.
+//!
+//! Implements 5 instructions.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#reading-and-writing-data>
+
+use super::{super::math, Engine, OpResult};
+
+impl<'a> Engine<'a> {
+    /// Read store.
+    ///
+    /// RS[] (0x43)
+    pub(super) fn op_rs(&mut self) -> OpResult {
+        let index = self.value_stack.pop()? as u32 as usize;
+        let value = match self.storage.get(index) {
+            Some(value) => *value,
+            None => {
+                self.invalid_reference()?;
+                0
+            }
+        };
+        self.value_stack.push(value)
+    }
+
+    /// Write store.
+    ///
+    /// WS[] (0x42)
+    pub(super) fn op_ws(&mut self) -> OpResult {
+        let value = self.value_stack.pop()?;
+        let index = self.value_stack.pop()? as u32 as usize;
+        match self.storage.get_mut(index) {
+            Some(slot) => *slot = value,
+            None => self.invalid_reference()?,
+        }
+        Ok(())
+    }
+
+    /// Write control value table in pixel units.
+    ///
+    /// WCVTP[] (0x44)
+    pub(super) fn op_wcvtp(&mut self) -> OpResult {
+        let value = self.value_stack.pop()?;
+        let index = self.value_stack.pop()? as u32 as usize;
+        self.write_cvt(index, value)
+    }
+
+    /// Write control value table in font units.
+    ///
+    /// WCVTF[] (0x70)
+    pub(super) fn op_wcvtf(&mut self) -> OpResult {
+        let value = self.value_stack.pop()?;
+        let index = self.value_stack.pop()? as u32 as usize;
+        self.write_cvt(index, math::mul(value, self.scale))
+    }
+
+    /// Read control value table.
+    ///
+    /// RCVT[] (0x45)
+    pub(super) fn op_rcvt(&mut self) -> OpResult {
+        let index = self.value_stack.pop()? as u32 as usize;
+        let value = match self.cvt.get(index) {
+            Some(value) => *value,
+            None => {
+                self.invalid_reference()?;
+                0
+            }
+        };
+        self.value_stack.push(value)
+    }
+
+    fn write_cvt(&mut self, index: usize, value: i32) -> OpResult {
+        match self.cvt.get_mut(index) {
+            Some(slot) => *slot = value,
+            None => self.invalid_reference()?,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Hinting error definitions.
+
+use super::code::Program;
+
+/// Errors that may occur when executing TrueType bytecode.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HintErrorKind {
+    /// Attempted to read beyond the end of the current program.
+    CodeOverflow,
+    /// Exceeded the maximum depth of the call stack.
+    CallStackOverflow,
+    /// Exceeded the capacity of the value stack.
+    ValueStackOverflow,
+    /// Not enough values on the value stack.
+    ValueStackUnderflow,
+    /// Exceeded the number of function or instruction definitions declared
+    /// in the `maxp` table.
+    TooManyDefinitions,
+    /// Encountered a function or instruction definition within another
+    /// definition.
+    NestedDefinition,
+    /// Attempted to create a definition in a glyph program.
+    DefinitionInGlyphProgram,
+    /// Attempted to call an undefined function.
+    InvalidDefinition(u32),
+    /// Encountered an undefined opcode.
+    InvalidOpcode(u8),
+    /// Encountered an `ENDF` instruction outside of a function call.
+    UnexpectedEndOfFunction,
+    /// Invalid argument to an instruction.
+    InvalidArgument,
+    /// Invalid point, contour, zone, storage or control value index.
+    InvalidReference,
+    /// Attempted to divide by zero.
+    DivideByZero,
+    /// Exceeded the execution budget.
+    ExecutionTooLong,
+    /// Encountered the `DEBUG` instruction.
+    DebugOpcode,
+}
+
+impl core::fmt::Display for HintErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CodeOverflow => write!(f, "code overflow"),
+            Self::CallStackOverflow => write!(f, "call stack overflow"),
+            Self::ValueStackOverflow => write!(f, "value stack overflow"),
+            Self::ValueStackUnderflow => write!(f, "value stack underflow"),
+            Self::TooManyDefinitions => write!(f, "too many function or instruction definitions"),
+            Self::NestedDefinition => write!(f, "nested function or instruction definition"),
+            Self::DefinitionInGlyphProgram => {
+                write!(f, "function or instruction definition in glyph program")
+            }
+            Self::InvalidDefinition(key) => write!(f, "function {key} is not defined"),
+            Self::InvalidOpcode(opcode) => write!(f, "invalid opcode {opcode:#04x}"),
+            Self::UnexpectedEndOfFunction => write!(f, "ENDF outside of function call"),
+            Self::InvalidArgument => write!(f, "invalid instruction argument"),
+            Self::InvalidReference => write!(f, "invalid reference"),
+            Self::DivideByZero => write!(f, "divide by zero"),
+            Self::ExecutionTooLong => write!(f, "execution budget exceeded"),
+            Self::DebugOpcode => write!(f, "DEBUG instruction encountered"),
+        }
+    }
+}
+
+/// Error that occurred while executing a TrueType program along with the
+/// location of the failing instruction.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HintError {
+    pub program: Program,
+    pub pc: usize,
+    pub kind: HintErrorKind,
+}
+
+impl core::fmt::Display for HintError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let program = match self.program {
+            Program::Font => "fpgm",
+            Program::ControlValue => "prep",
+            Program::Glyph => "glyf",
+        };
+        write!(f, "{} at {}:{}", self.kind, program, self.pc)
+    }
+}
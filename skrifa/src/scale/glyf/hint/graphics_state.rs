@@ -0,0 +1,544 @@
+//! Graphics state for the TrueType interpreter.
+
+use super::{
+    math,
+    zone::{Zone, ZonePointer},
+};
+use core::ops::{Deref, DerefMut};
+
+/// Unit vector in 2.14 fixed point format.
+pub type UnitVector = (i32, i32);
+
+/// The unit vector along the x axis.
+pub const X_AXIS: UnitVector = (0x4000, 0);
+
+/// The unit vector along the y axis.
+pub const Y_AXIS: UnitVector = (0, 0x4000);
+
+/// Modes that control rounding of distances.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_graphics_state#round-state>
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum RoundMode {
+    HalfGrid,
+    #[default]
+    Grid,
+    DoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    Super,
+    Super45,
+}
+
+/// Rounding state including parameters for the "super" round modes.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructions#super-round>
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RoundState {
+    pub mode: RoundMode,
+    pub threshold: i32,
+    pub phase: i32,
+    pub period: i32,
+}
+
+impl Default for RoundState {
+    fn default() -> Self {
+        Self {
+            mode: RoundMode::Grid,
+            threshold: 0,
+            phase: 0,
+            period: 64,
+        }
+    }
+}
+
+impl RoundState {
+    /// Rounds the given distance according to the current mode.
+    ///
+    /// FreeType's "engine compensation" values are always zero so they are
+    /// omitted here.
+    pub fn round(&self, distance: i32) -> i32 {
+        use RoundMode::*;
+        match self.mode {
+            HalfGrid => {
+                if distance >= 0 {
+                    let val = floor(distance).wrapping_add(32);
+                    if val < 0 {
+                        32
+                    } else {
+                        val
+                    }
+                } else {
+                    let val = -(floor(distance.wrapping_neg()).wrapping_add(32));
+                    if val > 0 {
+                        -32
+                    } else {
+                        val
+                    }
+                }
+            }
+            Grid => {
+                if distance >= 0 {
+                    round(distance).max(0)
+                } else {
+                    (-round(distance.wrapping_neg())).min(0)
+                }
+            }
+            DoubleGrid => {
+                if distance >= 0 {
+                    round_pad(distance, 32).max(0)
+                } else {
+                    (-round_pad(distance.wrapping_neg(), 32)).min(0)
+                }
+            }
+            DownToGrid => {
+                if distance >= 0 {
+                    floor(distance).max(0)
+                } else {
+                    (-floor(distance.wrapping_neg())).min(0)
+                }
+            }
+            UpToGrid => {
+                if distance >= 0 {
+                    ceil(distance).max(0)
+                } else {
+                    (-ceil(distance.wrapping_neg())).min(0)
+                }
+            }
+            Off => distance,
+            Super => {
+                if distance >= 0 {
+                    let val = (distance.wrapping_add(self.threshold - self.phase) & -self.period)
+                        .wrapping_add(self.phase);
+                    if val < 0 {
+                        self.phase
+                    } else {
+                        val
+                    }
+                } else {
+                    let val = (-((self.threshold - self.phase).wrapping_sub(distance)
+                        & -self.period))
+                        .wrapping_sub(self.phase);
+                    if val > 0 {
+                        -self.phase
+                    } else {
+                        val
+                    }
+                }
+            }
+            Super45 => {
+                if self.period == 0 {
+                    return distance;
+                }
+                if distance >= 0 {
+                    let val = (distance.wrapping_add(self.threshold - self.phase) / self.period)
+                        * self.period
+                        + self.phase;
+                    if val < 0 {
+                        self.phase
+                    } else {
+                        val
+                    }
+                } else {
+                    let val = -(((self.threshold - self.phase).wrapping_sub(distance)
+                        / self.period)
+                        * self.period)
+                        - self.phase;
+                    if val > 0 {
+                        -self.phase
+                    } else {
+                        val
+                    }
+                }
+            }
+        }
+    }
+
+    /// Configures the super round parameters from the given grid period and
+    /// selector.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L2299>
+    pub fn set_super(&mut self, grid_period: i32, selector: i32) {
+        let period = match selector & 0xC0 {
+            0 => grid_period / 2,
+            0x80 => grid_period * 2,
+            _ => grid_period,
+        };
+        let phase = match selector & 0x30 {
+            0 => 0,
+            0x10 => period / 4,
+            0x20 => period / 2,
+            _ => period * 3 / 4,
+        };
+        let threshold = if selector & 0x0F == 0 {
+            period - 1
+        } else {
+            ((selector & 0x0F) - 4) * period / 8
+        };
+        self.period = period >> 8;
+        self.phase = phase >> 8;
+        self.threshold = threshold >> 8;
+    }
+}
+
+fn floor(x: i32) -> i32 {
+    x & !63
+}
+
+fn ceil(x: i32) -> i32 {
+    floor(x.wrapping_add(63))
+}
+
+fn round(x: i32) -> i32 {
+    floor(x.wrapping_add(32))
+}
+
+fn round_pad(x: i32, n: i32) -> i32 {
+    x.wrapping_add(n / 2) & -n
+}
+
+/// Graphics state that is retained between the execution of the control
+/// value program and glyph programs.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_graphics_state>
+#[derive(Copy, Clone, Debug)]
+pub struct RetainedGraphicsState {
+    pub auto_flip: bool,
+    pub control_value_cutin: i32,
+    pub delta_base: u16,
+    pub delta_shift: u16,
+    pub instruct_control: u8,
+    pub min_distance: i32,
+    pub round_state: RoundState,
+    pub scan_control: bool,
+    pub scan_type: i32,
+    pub single_width_cutin: i32,
+    pub single_width: i32,
+}
+
+impl Default for RetainedGraphicsState {
+    fn default() -> Self {
+        // Values from FreeType's `tt_default_graphics_state`.
+        Self {
+            auto_flip: true,
+            control_value_cutin: 68,
+            delta_base: 9,
+            delta_shift: 3,
+            instruct_control: 0,
+            min_distance: 64,
+            round_state: RoundState::default(),
+            scan_control: false,
+            scan_type: 0,
+            single_width_cutin: 0,
+            single_width: 0,
+        }
+    }
+}
+
+/// Complete graphics state used during program execution.
+///
+/// This dereferences to the [`RetainedGraphicsState`] for access to the
+/// values that persist across programs.
+#[derive(Debug)]
+pub struct GraphicsState<'a> {
+    pub retained: RetainedGraphicsState,
+    pub proj_vector: UnitVector,
+    pub dual_proj_vector: UnitVector,
+    pub freedom_vector: UnitVector,
+    /// Dot product of the freedom and projection vectors.
+    pub fdotp: i32,
+    pub loop_counter: u32,
+    pub rp0: usize,
+    pub rp1: usize,
+    pub rp2: usize,
+    pub zp0: ZonePointer,
+    pub zp1: ZonePointer,
+    pub zp2: ZonePointer,
+    /// Storage for the twilight and glyph zones.
+    pub zones: [Zone<'a>; 2],
+    /// True if the current glyph is a composite.
+    pub is_composite: bool,
+    /// True when the v40 "backward compatibility" mode is active. In this
+    /// mode, most movement along the x axis is ignored.
+    pub backward_compatibility: bool,
+    pub did_iup_x: bool,
+    pub did_iup_y: bool,
+    /// True for any of the subpixel (non monochrome) hinting modes.
+    pub is_subpixel: bool,
+    /// True if the light (grayscale) ClearType mode is active.
+    pub is_grayscale_cleartype: bool,
+    /// True when hinting for vertical LCD displays.
+    pub is_vertical_lcd: bool,
+}
+
+impl<'a> GraphicsState<'a> {
+    pub fn new(zones: [Zone<'a>; 2]) -> Self {
+        Self {
+            retained: RetainedGraphicsState::default(),
+            proj_vector: X_AXIS,
+            dual_proj_vector: X_AXIS,
+            freedom_vector: X_AXIS,
+            fdotp: 0x4000,
+            loop_counter: 1,
+            rp0: 0,
+            rp1: 0,
+            rp2: 0,
+            zp0: ZonePointer::Glyph,
+            zp1: ZonePointer::Glyph,
+            zp2: ZonePointer::Glyph,
+            zones,
+            is_composite: false,
+            backward_compatibility: false,
+            did_iup_x: false,
+            did_iup_y: false,
+            is_subpixel: true,
+            is_grayscale_cleartype: true,
+            is_vertical_lcd: false,
+        }
+    }
+
+    /// Resets the non-retained state to the defaults.
+    ///
+    /// This mirrors the fixups that FreeType applies after executing the
+    /// control value program.
+    pub fn reset_non_retained(&mut self) {
+        self.proj_vector = X_AXIS;
+        self.dual_proj_vector = X_AXIS;
+        self.freedom_vector = X_AXIS;
+        self.update_projection_state();
+        self.loop_counter = 1;
+        self.rp0 = 0;
+        self.rp1 = 0;
+        self.rp2 = 0;
+        self.zp0 = ZonePointer::Glyph;
+        self.zp1 = ZonePointer::Glyph;
+        self.zp2 = ZonePointer::Glyph;
+    }
+
+    /// Recomputes the dot product of the freedom and projection vectors.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L2574>
+    pub fn update_projection_state(&mut self) {
+        let fv = self.freedom_vector;
+        let pv = self.proj_vector;
+        self.fdotp = if fv.0 == 0x4000 {
+            pv.0
+        } else if fv.1 == 0x4000 {
+            pv.1
+        } else {
+            ((pv.0 as i64 * fv.0 as i64 + pv.1 as i64 * fv.1 as i64) >> 14) as i32
+        };
+        // At small sizes, the dot product can become too small, resulting
+        // in overflows and spikes.
+        if self.fdotp.abs() < 0x400 {
+            self.fdotp = 0x4000;
+        }
+    }
+
+    /// Projects the vector (dx, dy) onto the projection vector.
+    pub fn project(&self, dx: i32, dy: i32) -> i32 {
+        match self.proj_vector {
+            X_AXIS => dx,
+            Y_AXIS => dy,
+            (x, y) => math::dot14(dx, dy, x, y),
+        }
+    }
+
+    /// Projects the vector (dx, dy) onto the dual projection vector.
+    pub fn dual_project(&self, dx: i32, dy: i32) -> i32 {
+        match self.dual_proj_vector {
+            X_AXIS => dx,
+            Y_AXIS => dy,
+            (x, y) => math::dot14(dx, dy, x, y),
+        }
+    }
+
+    /// Returns true if movement is a simple shift along the x axis.
+    fn is_move_x(&self) -> bool {
+        self.fdotp == 0x4000 && self.freedom_vector.0 == 0x4000
+    }
+
+    /// Returns true if movement is a simple shift along the y axis.
+    fn is_move_y(&self) -> bool {
+        self.fdotp == 0x4000 && self.freedom_vector.1 == 0x4000
+    }
+
+    /// Returns the zone for the given pointer.
+    pub fn zone(&self, pointer: ZonePointer) -> &Zone<'a> {
+        &self.zones[pointer as usize]
+    }
+
+    /// Returns the mutable zone for the given pointer.
+    pub fn zone_mut(&mut self, pointer: ZonePointer) -> &mut Zone<'a> {
+        &mut self.zones[pointer as usize]
+    }
+
+    /// Moves the current position of a point along the freedom vector by the
+    /// given distance as measured along the projection vector.
+    ///
+    /// This handles the v40 backward compatibility rules.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L1629>
+    pub fn move_point(&mut self, zone: ZonePointer, point: usize, distance: i32) -> Option<()> {
+        let back_compat = self.backward_compatibility;
+        let iup_done = self.did_iup_x && self.did_iup_y;
+        let (fx, fy) = self.freedom_vector;
+        let fdotp = self.fdotp;
+        let (is_move_x, is_move_y) = (self.is_move_x(), self.is_move_y());
+        let zone = self.zone_mut(zone);
+        let (mut x, mut y) = zone.point(point)?;
+        let (dx, dy) = if is_move_x {
+            (distance, 0)
+        } else if is_move_y {
+            (0, distance)
+        } else {
+            (
+                if fx != 0 {
+                    math::mul_div(distance, fx, fdotp)
+                } else {
+                    0
+                },
+                if fy != 0 {
+                    math::mul_div(distance, fy, fdotp)
+                } else {
+                    0
+                },
+            )
+        };
+        let touch_x = is_move_x || (!is_move_y && fx != 0);
+        let touch_y = is_move_y || (!is_move_x && fy != 0);
+        if touch_x && !back_compat {
+            x = x.wrapping_add(dx);
+        }
+        if touch_y && !(back_compat && iup_done) {
+            y = y.wrapping_add(dy);
+        }
+        zone.set_point(point, (x, y))?;
+        zone.touch(point, touch_x, touch_y);
+        Some(())
+    }
+
+    /// Moves the original position of a point along the freedom vector.
+    pub fn move_original(&mut self, zone: ZonePointer, point: usize, distance: i32) -> Option<()> {
+        let (fx, fy) = self.freedom_vector;
+        let fdotp = self.fdotp;
+        let (is_move_x, is_move_y) = (self.is_move_x(), self.is_move_y());
+        let zone = self.zone_mut(zone);
+        let (mut x, mut y) = zone.original(point)?;
+        if is_move_x {
+            x = x.wrapping_add(distance);
+        } else if is_move_y {
+            y = y.wrapping_add(distance);
+        } else {
+            if fx != 0 {
+                x = x.wrapping_add(math::mul_div(distance, fx, fdotp));
+            }
+            if fy != 0 {
+                y = y.wrapping_add(math::mul_div(distance, fy, fdotp));
+            }
+        }
+        zone.set_original(point, (x, y))
+    }
+
+    /// Moves a point in zone pointer 2 by the given vector, optionally
+    /// marking it as touched.
+    ///
+    /// This corresponds to FreeType's `Move_Zp2_Point` which is used by the
+    /// shift instructions.
+    pub fn move_zp2_point(&mut self, point: usize, dx: i32, dy: i32, touch: bool) -> Option<()> {
+        let back_compat = self.backward_compatibility;
+        let iup_done = self.did_iup_x && self.did_iup_y;
+        let (fx, fy) = self.freedom_vector;
+        let zp2 = self.zp2;
+        let zone = self.zone_mut(zp2);
+        let (mut x, mut y) = zone.point(point)?;
+        if fx != 0 && !back_compat {
+            x = x.wrapping_add(dx);
+        }
+        if fy != 0 && !(back_compat && iup_done) {
+            y = y.wrapping_add(dy);
+        }
+        zone.set_point(point, (x, y))?;
+        if touch {
+            zone.touch(point, fx != 0, fy != 0);
+        }
+        Some(())
+    }
+}
+
+impl Deref for GraphicsState<'_> {
+    type Target = RetainedGraphicsState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.retained
+    }
+}
+
+impl DerefMut for GraphicsState<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.retained
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoundMode, RoundState};
+
+    #[test]
+    fn round_modes() {
+        let mut state = RoundState::default();
+        let cases = [
+            (
+                RoundMode::Grid,
+                [(0, 0), (31, 0), (32, 64), (-32, -64), (100, 128)],
+            ),
+            (
+                RoundMode::HalfGrid,
+                [(0, 32), (63, 32), (64, 96), (-1, -32), (-65, -96)],
+            ),
+            (
+                RoundMode::DoubleGrid,
+                [(0, 0), (15, 0), (16, 32), (-16, -32), (47, 32)],
+            ),
+            (
+                RoundMode::DownToGrid,
+                [(0, 0), (63, 0), (64, 64), (-63, 0), (-64, -64)],
+            ),
+            (
+                RoundMode::UpToGrid,
+                [(0, 0), (1, 64), (64, 64), (-1, -64), (-65, -128)],
+            ),
+            (
+                RoundMode::Off,
+                [(0, 0), (1, 1), (64, 64), (-1, -1), (-65, -65)],
+            ),
+        ];
+        for (mode, values) in cases {
+            state.mode = mode;
+            for (input, expected) in values {
+                assert_eq!(state.round(input), expected, "{mode:?} {input}");
+            }
+        }
+    }
+
+    #[test]
+    fn super_round() {
+        let mut state = RoundState {
+            mode: RoundMode::Super,
+            ..Default::default()
+        };
+        // period = 1 pixel, phase = 0, threshold = period - 1
+        state.set_super(0x4000, 0x40);
+        assert_eq!((state.period, state.phase, state.threshold), (64, 0, 63));
+        assert_eq!(state.round(1), 64);
+        assert_eq!(state.round(-1), -64);
+        // period = 1 pixel, phase = 1/2, threshold = 1/2
+        state.set_super(0x4000, 0x48 | 0x20);
+        assert_eq!((state.period, state.phase, state.threshold), (64, 32, 32));
+        assert_eq!(state.round(40), 32);
+        assert_eq!(state.round(100), 96);
+        assert_eq!(state.round(-40), -32);
+        assert_eq!(state.round(-100), -96);
+    }
+}
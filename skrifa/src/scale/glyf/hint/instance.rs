@@ -0,0 +1,330 @@
+//! Hinting state for a font instance.
+
+use read_fonts::{
+    tables::{glyf::PointFlags, maxp::Maxp},
+    types::{F26Dot6, F2Dot14, Fixed, Point},
+    TableProvider,
+};
+
+use super::{
+    super::super::Hinting,
+    code::{Definition, DefinitionMap, Program},
+    engine::{Engine, LoopBudget},
+    error::HintError,
+    graphics_state::{GraphicsState, RetainedGraphicsState},
+    math,
+    zone::Zone,
+    HinterOutline,
+};
+
+/// Number of extra points added to the twilight zone, matching FreeType.
+const EXTRA_TWILIGHT_POINTS: usize = 4;
+
+/// Number of extra slots added to the value stack, matching FreeType.
+const EXTRA_STACK_ELEMENTS: usize = 32;
+
+/// Hinting state for a font at a particular size, location in variation
+/// space and hinting mode.
+///
+/// This contains the results of executing the font and control value
+/// programs and is used as the initial state for each glyph program.
+#[derive(Clone, Default, Debug)]
+pub struct HintInstance {
+    functions: Vec<Definition>,
+    instructions: Vec<Definition>,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    graphics: RetainedGraphicsState,
+    twilight_scaled: Vec<Point<F26Dot6>>,
+    twilight_original_scaled: Vec<Point<F26Dot6>>,
+    twilight_flags: Vec<PointFlags>,
+    scratch: Scratch,
+    max_function_defs: usize,
+    max_instruction_defs: usize,
+    glyph_count: u16,
+    axis_count: u16,
+    ppem: i32,
+    point_size: i32,
+    scale: i32,
+    mode: Hinting,
+    is_enabled: bool,
+}
+
+/// Temporary buffers used while executing glyph programs.
+///
+/// Glyph programs are allowed to modify the control value table, storage
+/// area and twilight zone but those changes must not leak into
+/// subsequent glyphs, so each glyph operates on a copy.
+#[derive(Clone, Default, Debug)]
+struct Scratch {
+    value_stack: Vec<i32>,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    twilight_unscaled: Vec<Point<i32>>,
+    twilight_scaled: Vec<Point<F26Dot6>>,
+    twilight_original_scaled: Vec<Point<F26Dot6>>,
+    twilight_flags: Vec<PointFlags>,
+}
+
+impl HintInstance {
+    /// Returns true if hinting is enabled for this instance.
+    ///
+    /// Hinting is disabled if either the font or control value programs
+    /// fail to execute.
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    /// Returns the hinting mode.
+    pub fn mode(&self) -> Hinting {
+        self.mode
+    }
+
+    /// Executes the font and control value programs for the given font
+    /// and configuration.
+    ///
+    /// The `scale` parameter is a 16.16 factor for converting font units
+    /// to 26.6 pixels.
+    pub fn reconfigure<'a>(
+        &mut self,
+        font: &impl TableProvider<'a>,
+        coords: &'a [F2Dot14],
+        ppem: i32,
+        scale: i32,
+        mode: Hinting,
+    ) -> Result<(), HintError> {
+        self.is_enabled = false;
+        self.ppem = ppem;
+        // Sizes are always computed at 72 dpi so the point size is the
+        // same as ppem.
+        self.point_size = ppem * 64;
+        self.scale = scale;
+        self.mode = mode;
+        let maxp = font.maxp().ok();
+        let max = |f: fn(&Maxp<'a>) -> Option<u16>| {
+            maxp.as_ref().and_then(f).unwrap_or_default() as usize
+        };
+        self.max_function_defs = max(Maxp::max_function_defs);
+        self.max_instruction_defs = max(Maxp::max_instruction_defs);
+        self.glyph_count = maxp
+            .as_ref()
+            .map(|maxp| maxp.num_glyphs())
+            .unwrap_or_default();
+        self.axis_count = font
+            .fvar()
+            .map(|fvar| fvar.axis_count())
+            .unwrap_or_default();
+        self.functions.clear();
+        self.instructions.clear();
+        self.cvt.clear();
+        if let Ok(cvt) = font.cvt() {
+            let values = cvt.values();
+            let cvar = font
+                .cvar()
+                .ok()
+                .filter(|_| coords.iter().any(|coord| *coord != F2Dot14::ZERO));
+            if let Some(cvar) = cvar {
+                // Apply the variation deltas to the unscaled values, rounding
+                // the result to font units.
+                let mut varied = values
+                    .iter()
+                    .map(|value| Fixed::from_i32(value.get().to_i16() as i32))
+                    .collect::<Vec<_>>();
+                let _ = cvar.deltas(self.axis_count, coords, &mut varied);
+                self.cvt
+                    .extend(varied.iter().map(|value| math::mul(value.to_i32(), scale)));
+            } else {
+                self.cvt.extend(
+                    values
+                        .iter()
+                        .map(|value| math::mul(value.get().to_i16() as i32, scale)),
+                );
+            }
+        }
+        self.storage.clear();
+        self.storage.resize(max(Maxp::max_storage), 0);
+        let twilight_len = max(Maxp::max_twilight_points) + EXTRA_TWILIGHT_POINTS;
+        self.twilight_scaled.clear();
+        self.twilight_scaled
+            .resize(twilight_len, Default::default());
+        self.twilight_original_scaled.clear();
+        self.twilight_original_scaled
+            .resize(twilight_len, Default::default());
+        self.twilight_flags.clear();
+        self.twilight_flags.resize(twilight_len, Default::default());
+        let scratch = &mut self.scratch;
+        scratch.value_stack.clear();
+        scratch
+            .value_stack
+            .resize(max(Maxp::max_stack_elements) + EXTRA_STACK_ELEMENTS, 0);
+        scratch.twilight_unscaled.clear();
+        scratch
+            .twilight_unscaled
+            .resize(twilight_len, Default::default());
+        let fpgm = font
+            .fpgm()
+            .map(|fpgm| fpgm.instructions())
+            .unwrap_or_default();
+        let prep = font
+            .prep()
+            .map(|prep| prep.instructions())
+            .unwrap_or_default();
+        // The font program is executed without a size.
+        self.graphics = RetainedGraphicsState::default();
+        self.run_control_program(Program::Font, [fpgm, prep, &[]], coords, 0, 0)?;
+        // All twilight points and storage are cleared before executing
+        // the control value program.
+        self.twilight_scaled.fill(Default::default());
+        self.twilight_original_scaled.fill(Default::default());
+        self.storage.fill(0);
+        self.graphics = RetainedGraphicsState::default();
+        self.run_control_program(
+            Program::ControlValue,
+            [fpgm, prep, &[]],
+            coords,
+            ppem,
+            scale,
+        )?;
+        self.is_enabled = true;
+        Ok(())
+    }
+
+    /// Executes the glyph program for the given outline.
+    ///
+    /// Errors that occur during glyph program execution are ignored
+    /// which matches FreeType's behavior.
+    pub fn hint(&mut self, fpgm: &[u8], prep: &[u8], outline: HinterOutline) -> bool {
+        let retained = &self.graphics;
+        let backward_compatibility =
+            self.mode != Hinting::Full && retained.instruct_control & 4 == 0;
+        if !self.is_enabled || outline.bytecode.is_empty() || retained.instruct_control & 1 != 0 {
+            // Phantom points are only updated outside of backward
+            // compatibility mode.
+            if !backward_compatibility {
+                save_phantom_points(outline.scaled, outline.phantom);
+            }
+            return true;
+        }
+        // If the control value program requests it, glyph programs
+        // begin with the default graphics state.
+        let retained = if retained.instruct_control & 2 != 0 {
+            RetainedGraphicsState::default()
+        } else {
+            *retained
+        };
+        let scratch = &mut self.scratch;
+        copy_from(&mut scratch.cvt, &self.cvt);
+        copy_from(&mut scratch.storage, &self.storage);
+        copy_from(&mut scratch.twilight_scaled, &self.twilight_scaled);
+        copy_from(
+            &mut scratch.twilight_original_scaled,
+            &self.twilight_original_scaled,
+        );
+        copy_from(&mut scratch.twilight_flags, &self.twilight_flags);
+        let twilight = Zone::new(
+            &mut scratch.twilight_unscaled,
+            &mut scratch.twilight_original_scaled,
+            &mut scratch.twilight_scaled,
+            &mut scratch.twilight_flags,
+            &[],
+        );
+        let glyph_point_count = outline.scaled.len();
+        let glyph = Zone::new(
+            outline.unscaled,
+            outline.original_scaled,
+            outline.scaled,
+            outline.flags,
+            outline.contours,
+        );
+        let mut graphics = GraphicsState::new([twilight, glyph]);
+        graphics.retained = retained;
+        // The round state is always reset before a program is executed.
+        graphics.round_state = Default::default();
+        graphics.is_composite = outline.is_composite;
+        set_mode_flags(&mut graphics, self.mode);
+        let mut engine = Engine::new(
+            graphics,
+            &mut scratch.cvt,
+            &mut scratch.storage,
+            &mut scratch.value_stack,
+            DefinitionMap::new(&mut self.functions, self.max_function_defs),
+            DefinitionMap::new(&mut self.instructions, self.max_instruction_defs),
+            [fpgm, prep, outline.bytecode],
+            LoopBudget::new(glyph_point_count, self.cvt.len(), self.glyph_count as usize),
+        );
+        engine.set_size(self.ppem, self.point_size, self.scale);
+        engine.set_coords(outline.coords, self.axis_count);
+        let _ = engine.run_program(Program::Glyph, false);
+        let graphics = engine.into_graphics();
+        if !graphics.backward_compatibility {
+            let [_, glyph] = graphics.zones;
+            save_phantom_points(glyph.points, outline.phantom);
+        }
+        true
+    }
+
+    fn run_control_program(
+        &mut self,
+        program: Program,
+        programs: [&[u8]; 3],
+        coords: &[F2Dot14],
+        ppem: i32,
+        scale: i32,
+    ) -> Result<(), HintError> {
+        let loop_budget = LoopBudget::new(0, self.cvt.len(), self.glyph_count as usize);
+        let twilight = Zone::new(
+            &mut self.scratch.twilight_unscaled,
+            &mut self.twilight_original_scaled,
+            &mut self.twilight_scaled,
+            &mut self.twilight_flags,
+            &[],
+        );
+        let mut graphics = GraphicsState::new([twilight, Zone::default()]);
+        graphics.retained = self.graphics;
+        set_mode_flags(&mut graphics, self.mode);
+        let mut engine = Engine::new(
+            graphics,
+            &mut self.cvt,
+            &mut self.storage,
+            &mut self.scratch.value_stack,
+            DefinitionMap::new(&mut self.functions, self.max_function_defs),
+            DefinitionMap::new(&mut self.instructions, self.max_instruction_defs),
+            programs,
+            loop_budget,
+        );
+        engine.set_size(ppem, ppem * 64, scale);
+        engine.set_coords(coords, self.axis_count);
+        engine.run_program(program, false)?;
+        self.graphics = engine.into_graphics().retained;
+        Ok(())
+    }
+}
+
+/// Sets the subpixel, grayscale ClearType and vertical LCD flags for
+/// the given mode.
+fn set_mode_flags(graphics: &mut GraphicsState, mode: Hinting) {
+    (
+        graphics.is_subpixel,
+        graphics.is_grayscale_cleartype,
+        graphics.is_vertical_lcd,
+    ) = match mode {
+        Hinting::Full => (false, false, false),
+        // The automatic hinter does not execute instructions.
+        Hinting::Light | Hinting::Auto => (true, true, false),
+        Hinting::LightSubpixel => (true, false, false),
+        Hinting::VerticalSubpixel => (true, false, true),
+    };
+}
+
+fn copy_from<T: Copy>(dest: &mut Vec<T>, source: &[T]) {
+    dest.clear();
+    dest.extend_from_slice(source);
+}
+
+/// Copies the hinted phantom points at the end of the outline.
+fn save_phantom_points(points: &[Point<F26Dot6>], phantom: &mut [Point<F26Dot6>]) {
+    let start = points.len().saturating_sub(phantom.len());
+    for (phantom, point) in phantom.iter_mut().zip(&points[start..]) {
+        *phantom = *point;
+    }
+}
@@ -0,0 +1,197 @@
+//! Fixed point arithmetic for the TrueType interpreter.
+//!
+//! These mirror the FreeType routines of the same names so that rounding
+//! behavior matches exactly.
+
+/// Computes `(a * b) / c` with rounding.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/base/ftcalc.c#L415>
+pub fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    let (a, b, c, sign) = abs_with_sign(a, b, c);
+    let result = if c > 0 {
+        (((a as u64) * (b as u64) + ((c as u64) >> 1)) / c as u64) as i64
+    } else {
+        0x7FFFFFFF
+    };
+    apply_sign(result, sign)
+}
+
+/// Computes `(a * b) / c` with truncation.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/base/ftcalc.c#L438>
+pub fn mul_div_no_round(a: i32, b: i32, c: i32) -> i32 {
+    let (a, b, c, sign) = abs_with_sign(a, b, c);
+    let result = if c > 0 {
+        ((a as u64) * (b as u64) / c as u64) as i64
+    } else {
+        0x7FFFFFFF
+    };
+    apply_sign(result, sign)
+}
+
+/// Multiplies a 26.6 value by a 16.16 value with rounding.
+///
+/// This is `FT_MulFix`.
+pub fn mul(a: i32, b: i32) -> i32 {
+    let ab = a as i64 * b as i64;
+    ((ab + 0x8000 - i64::from(ab < 0)) >> 16) as i32
+}
+
+/// Divides two 16.16 (or 26.6) values with rounding.
+///
+/// This is `FT_DivFix`.
+pub fn div(a: i32, b: i32) -> i32 {
+    let (a, b, _, sign) = abs_with_sign(a, b, 1);
+    let q = if b == 0 {
+        0x7FFFFFFF
+    } else {
+        (((a as u64) << 16) + ((b as u64) >> 1)) / b as u64
+    };
+    apply_sign(q as i64, sign)
+}
+
+/// Multiplies a value by a 2.14 value with rounding.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L229>
+pub fn mul14(a: i32, b: i32) -> i32 {
+    let mut v = a as i64 * b as i64;
+    v += 0x2000 + (v >> 63);
+    (v >> 14) as i32
+}
+
+/// Computes the dot product of two vectors with 2.14 rounding.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L245>
+pub fn dot14(ax: i32, ay: i32, bx: i32, by: i32) -> i32 {
+    let mut v = ax as i64 * bx as i64 + ay as i64 * by as i64;
+    v += 0x2000 + (v >> 63);
+    (v >> 14) as i32
+}
+
+/// Normalizes a vector to a unit vector in 2.14 format.
+///
+/// The zero vector must be handled by the caller.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/base/ftcalc.c#L792>
+pub fn normalize14(x: i32, y: i32) -> (i32, i32) {
+    use core::num::Wrapping as W;
+    let (mut sx, mut sy) = (W(1i32), W(1i32));
+    let mut ux = W(x as u32);
+    let mut uy = W(y as u32);
+    const ZERO: W<u32> = W(0);
+    if x < 0 {
+        ux = ZERO - ux;
+        sx = -sx;
+    }
+    if y < 0 {
+        uy = ZERO - uy;
+        sy = -sy;
+    }
+    if ux == ZERO {
+        return if uy.0 > 0 {
+            (0, (sy * W(0x4000)).0)
+        } else {
+            (0x4000, 0)
+        };
+    }
+    if uy == ZERO {
+        return (((sx * W(0x4000)).0), 0);
+    }
+    let mut len = if ux > uy {
+        ux + (uy >> 1)
+    } else {
+        uy + (ux >> 1)
+    };
+    let mut shift = W(len.0.leading_zeros() as i32);
+    shift -= W(15)
+        + if len >= (W(0xAAAAAAAAu32) >> shift.0 as usize) {
+            W(1)
+        } else {
+            W(0)
+        };
+    if shift.0 > 0 {
+        let s = shift.0 as usize;
+        ux <<= s;
+        uy <<= s;
+        len = if ux > uy {
+            ux + (uy >> 1)
+        } else {
+            uy + (ux >> 1)
+        };
+    } else {
+        let s = -shift.0 as usize;
+        ux >>= s;
+        uy >>= s;
+        len >>= s;
+    }
+    let mut b = W(0x10000) - W(len.0 as i32);
+    let x = W(ux.0 as i32);
+    let y = W(uy.0 as i32);
+    let mut z;
+    let mut u;
+    let mut v;
+    loop {
+        u = W((x + ((x * b) >> 16)).0);
+        v = W((y + ((y * b) >> 16)).0);
+        z = -(u * u + v * v) / W(0x200);
+        z = z * ((W(0x10000) + b) >> 8) / W(0x10000);
+        b += z;
+        if z <= W(0) {
+            break;
+        }
+    }
+    ((W(u.0) * sx / W(4)).0, (W(v.0) * sy / W(4)).0)
+}
+
+fn abs_with_sign(mut a: i32, mut b: i32, mut c: i32) -> (i32, i32, i32, i32) {
+    let mut sign = 1;
+    if a < 0 {
+        a = a.wrapping_neg();
+        sign = -1;
+    }
+    if b < 0 {
+        b = b.wrapping_neg();
+        sign = -sign;
+    }
+    if c < 0 {
+        c = c.wrapping_neg();
+        sign = -sign;
+    }
+    (a, b, c, sign)
+}
+
+fn apply_sign(value: i64, sign: i32) -> i32 {
+    let value = value as i32;
+    if sign < 0 {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn normalize_axes() {
+        assert_eq!(super::normalize14(100, 0), (0x4000, 0));
+        assert_eq!(super::normalize14(-5, 0), (-0x4000, 0));
+        assert_eq!(super::normalize14(0, 7), (0, 0x4000));
+        assert_eq!(super::normalize14(0, -7), (0, -0x4000));
+    }
+
+    #[test]
+    fn normalize_diagonal() {
+        // 0x4000 * sqrt(2) / 2 = 11585.2
+        let (x, y) = super::normalize14(64, 64);
+        assert!((x - 11585).abs() <= 1 && x == y);
+    }
+
+    #[test]
+    fn mul_div_rounding() {
+        assert_eq!(super::mul_div(3, 5, 2), 8);
+        assert_eq!(super::mul_div_no_round(3, 5, 2), 7);
+        assert_eq!(super::mul_div(-3, 5, 2), -8);
+        assert_eq!(super::mul14(0x4000, 100), 100);
+        assert_eq!(super::dot14(0x4000, 0, 64, 128), 64);
+    }
+}
@@ -1,5 +1,23 @@
 //! TrueType hinting.
 
+#[cfg(feature = "hinting")]
+mod code;
+#[cfg(feature = "hinting")]
+mod engine;
+#[cfg(feature = "hinting")]
+mod error;
+#[cfg(feature = "hinting")]
+mod graphics_state;
+#[cfg(feature = "hinting")]
+mod instance;
+#[cfg(feature = "hinting")]
+mod math;
+#[cfg(feature = "hinting")]
+mod zone;
+
+#[cfg(feature = "hinting")]
+pub use instance::HintInstance;
+
 use read_fonts::{
     tables::glyf::PointFlags,
     types::{F26Dot6, F2Dot14, Point},
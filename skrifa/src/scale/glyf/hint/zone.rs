@@ -0,0 +1,342 @@
+//! Point zones for the TrueType interpreter.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructing_glyphs#zones>
+
+use read_fonts::{
+    tables::glyf::{PointFlags, PointMarker},
+    types::{F26Dot6, Point},
+};
+
+use super::math;
+
+/// Selects one of the two zones.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum ZonePointer {
+    Twilight = 0,
+    #[default]
+    Glyph = 1,
+}
+
+impl TryFrom<i32> for ZonePointer {
+    type Error = ();
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Twilight),
+            1 => Ok(Self::Glyph),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A set of points along with their flags and contour structure.
+///
+/// Points are stored in 26.6 format. The `unscaled` points are used as
+/// reference positions for interpolation.
+#[derive(Default, Debug)]
+pub struct Zone<'a> {
+    pub unscaled: &'a mut [Point<i32>],
+    pub original: &'a mut [Point<F26Dot6>],
+    pub points: &'a mut [Point<F26Dot6>],
+    pub flags: &'a mut [PointFlags],
+    pub contours: &'a [u16],
+}
+
+impl<'a> Zone<'a> {
+    pub fn new(
+        unscaled: &'a mut [Point<i32>],
+        original: &'a mut [Point<F26Dot6>],
+        points: &'a mut [Point<F26Dot6>],
+        flags: &'a mut [PointFlags],
+        contours: &'a [u16],
+    ) -> Self {
+        Self {
+            unscaled,
+            original,
+            points,
+            flags,
+            contours,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Limits the zone to the first `len` points.
+    pub fn truncate(&mut self, len: usize) {
+        let len = len.min(self.len());
+        self.unscaled = &mut core::mem::take(&mut self.unscaled)[..len];
+        self.original = &mut core::mem::take(&mut self.original)[..len];
+        self.points = &mut core::mem::take(&mut self.points)[..len];
+        self.flags = &mut core::mem::take(&mut self.flags)[..len];
+    }
+
+    /// Returns the current position of the point at the given index.
+    pub fn point(&self, index: usize) -> Option<(i32, i32)> {
+        self.points
+            .get(index)
+            .map(|p| (p.x.to_bits(), p.y.to_bits()))
+    }
+
+    /// Sets the current position of the point at the given index.
+    pub fn set_point(&mut self, index: usize, (x, y): (i32, i32)) -> Option<()> {
+        let point = self.points.get_mut(index)?;
+        *point = Point::new(F26Dot6::from_bits(x), F26Dot6::from_bits(y));
+        Some(())
+    }
+
+    /// Returns the original position of the point at the given index.
+    pub fn original(&self, index: usize) -> Option<(i32, i32)> {
+        self.original
+            .get(index)
+            .map(|p| (p.x.to_bits(), p.y.to_bits()))
+    }
+
+    /// Sets the original position of the point at the given index.
+    pub fn set_original(&mut self, index: usize, (x, y): (i32, i32)) -> Option<()> {
+        let point = self.original.get_mut(index)?;
+        *point = Point::new(F26Dot6::from_bits(x), F26Dot6::from_bits(y));
+        Some(())
+    }
+
+    /// Returns the unscaled position of the point at the given index.
+    pub fn unscaled(&self, index: usize) -> Option<(i32, i32)> {
+        self.unscaled.get(index).map(|p| (p.x, p.y))
+    }
+
+    /// Marks the point at the given index as touched on the specified axes.
+    pub fn touch(&mut self, index: usize, x: bool, y: bool) {
+        if let Some(flags) = self.flags.get_mut(index) {
+            if x {
+                flags.set_marker(PointMarker::TOUCHED_X);
+            }
+            if y {
+                flags.set_marker(PointMarker::TOUCHED_Y);
+            }
+        }
+    }
+
+    /// Clears the touched markers for the point at the given index.
+    pub fn untouch(&mut self, index: usize, x: bool, y: bool) {
+        if let Some(flags) = self.flags.get_mut(index) {
+            if x {
+                flags.clear_marker(PointMarker::TOUCHED_X);
+            }
+            if y {
+                flags.clear_marker(PointMarker::TOUCHED_Y);
+            }
+        }
+    }
+
+    /// Returns the range of points for the given contour.
+    pub fn contour_range(&self, contour: usize) -> Option<core::ops::Range<usize>> {
+        let end = *self.contours.get(contour)? as usize + 1;
+        let start = if contour == 0 {
+            0
+        } else {
+            *self.contours.get(contour - 1)? as usize + 1
+        };
+        Some(start..end)
+    }
+
+    /// Interpolates untouched points along the given axis.
+    ///
+    /// This is the `IUP[a]` instruction.
+    ///
+    /// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttinterp.c#L6793>
+    pub fn iup(&mut self, is_x: bool) {
+        let marker = if is_x {
+            PointMarker::TOUCHED_X
+        } else {
+            PointMarker::TOUCHED_Y
+        };
+        let point_count = self.points.len();
+        let mut point = 0;
+        for contour in 0..self.contours.len() {
+            let mut end_point = self.contours[contour] as usize;
+            let first_point = point;
+            if end_point >= point_count {
+                end_point = point_count.saturating_sub(1);
+            }
+            while point <= end_point && !self.flags[point].has_marker(marker) {
+                point += 1;
+            }
+            if point <= end_point {
+                let first_touched = point;
+                let mut cur_touched = point;
+                point += 1;
+                while point <= end_point {
+                    if self.flags[point].has_marker(marker) {
+                        self.iup_interpolate(is_x, cur_touched + 1, point - 1, cur_touched, point);
+                        cur_touched = point;
+                    }
+                    point += 1;
+                }
+                if cur_touched == first_touched {
+                    self.iup_shift(is_x, first_point, end_point, cur_touched);
+                } else {
+                    self.iup_interpolate(
+                        is_x,
+                        cur_touched + 1,
+                        end_point,
+                        cur_touched,
+                        first_touched,
+                    );
+                    if first_touched > 0 {
+                        self.iup_interpolate(
+                            is_x,
+                            first_point,
+                            first_touched - 1,
+                            cur_touched,
+                            first_touched,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn iup_shift(&mut self, is_x: bool, p1: usize, p2: usize, p: usize) {
+        let delta = coord(self.points[p], is_x).wrapping_sub(coord(self.original[p], is_x));
+        if delta == 0 {
+            return;
+        }
+        for i in (p1..p).chain(p + 1..=p2) {
+            let point = &mut self.points[i];
+            set_coord(point, is_x, coord(*point, is_x).wrapping_add(delta));
+        }
+    }
+
+    fn iup_interpolate(
+        &mut self,
+        is_x: bool,
+        p1: usize,
+        p2: usize,
+        mut ref1: usize,
+        mut ref2: usize,
+    ) {
+        if p1 > p2 {
+            return;
+        }
+        let max_points = self.points.len();
+        if ref1 >= max_points || ref2 >= max_points {
+            return;
+        }
+        let unscaled = |i: usize| {
+            let p = self.unscaled[i];
+            if is_x {
+                p.x
+            } else {
+                p.y
+            }
+        };
+        let mut orus1 = unscaled(ref1);
+        let mut orus2 = unscaled(ref2);
+        if orus1 > orus2 {
+            core::mem::swap(&mut orus1, &mut orus2);
+            core::mem::swap(&mut ref1, &mut ref2);
+        }
+        let org1 = coord(self.original[ref1], is_x);
+        let org2 = coord(self.original[ref2], is_x);
+        let cur1 = coord(self.points[ref1], is_x);
+        let cur2 = coord(self.points[ref2], is_x);
+        let delta1 = cur1.wrapping_sub(org1);
+        let delta2 = cur2.wrapping_sub(org2);
+        let mut scale = None;
+        for i in p1..=p2 {
+            let x = coord(self.original[i], is_x);
+            let x = if x <= org1 {
+                x.wrapping_add(delta1)
+            } else if x >= org2 {
+                x.wrapping_add(delta2)
+            } else if cur1 == cur2 || orus1 == orus2 {
+                cur1
+            } else {
+                let scale = *scale.get_or_insert_with(|| {
+                    math::div(cur2.wrapping_sub(cur1), orus2.wrapping_sub(orus1))
+                });
+                let u = if is_x {
+                    self.unscaled[i].x
+                } else {
+                    self.unscaled[i].y
+                };
+                cur1.wrapping_add(math::mul(u.wrapping_sub(orus1), scale))
+            };
+            set_coord(&mut self.points[i], is_x, x);
+        }
+    }
+}
+
+fn coord(point: Point<F26Dot6>, is_x: bool) -> i32 {
+    if is_x {
+        point.x.to_bits()
+    } else {
+        point.y.to_bits()
+    }
+}
+
+fn set_coord(point: &mut Point<F26Dot6>, is_x: bool, value: i32) {
+    if is_x {
+        point.x = F26Dot6::from_bits(value);
+    } else {
+        point.y = F26Dot6::from_bits(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iup_interpolates_between_touched_points() {
+        // A single contour of four points along the x axis where the first
+        // and last have been moved.
+        let mut unscaled = [0, 10, 20, 30].map(|x| Point::new(x, 0));
+        let mut original =
+            [0, 10, 20, 30].map(|x| Point::new(F26Dot6::from_bits(x), F26Dot6::ZERO));
+        let mut points = [0, 10, 20, 60].map(|x| Point::new(F26Dot6::from_bits(x), F26Dot6::ZERO));
+        let mut flags = [PointFlags::on_curve(); 4];
+        flags[0].set_marker(PointMarker::TOUCHED_X);
+        flags[3].set_marker(PointMarker::TOUCHED_X);
+        let contours = [3];
+        let mut zone = Zone::new(
+            &mut unscaled,
+            &mut original,
+            &mut points,
+            &mut flags,
+            &contours,
+        );
+        zone.iup(true);
+        let xs = zone
+            .points
+            .iter()
+            .map(|p| p.x.to_bits())
+            .collect::<Vec<_>>();
+        assert_eq!(xs, [0, 20, 40, 60]);
+    }
+
+    #[test]
+    fn iup_shifts_with_single_touched_point() {
+        let mut unscaled = [0, 10, 20].map(|y| Point::new(0, y));
+        let mut original = [0, 10, 20].map(|y| Point::new(F26Dot6::ZERO, F26Dot6::from_bits(y)));
+        let mut points = [0, 15, 20].map(|y| Point::new(F26Dot6::ZERO, F26Dot6::from_bits(y)));
+        let mut flags = [PointFlags::on_curve(); 3];
+        flags[1].set_marker(PointMarker::TOUCHED_Y);
+        let contours = [2];
+        let mut zone = Zone::new(
+            &mut unscaled,
+            &mut original,
+            &mut points,
+            &mut flags,
+            &contours,
+        );
+        zone.iup(false);
+        let ys = zone
+            .points
+            .iter()
+            .map(|p| p.y.to_bits())
+            .collect::<Vec<_>>();
+        assert_eq!(ys, [5, 15, 25]);
+    }
+}
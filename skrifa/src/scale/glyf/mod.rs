@@ -1,12 +1,13 @@
 //! Scaling support for TrueType outlines.
 
-mod deltas;
 mod glyph;
 mod hint;
 mod mem;
 mod scaler;
 
 pub use glyph::{ScalerGlyph, ScalerOutline};
+#[cfg(feature = "hinting")]
+pub use hint::HintInstance;
 pub use hint::HinterOutline;
 pub use mem::ScalerMemory;
 pub use scaler::Scaler;
@@ -21,4 +22,4 @@ use super::Error;
 pub const COMPOSITE_RECURSION_LIMIT: usize = 32;
 
 /// Number of phantom points generated at the end of an outline.
-pub const PHANTOM_POINT_COUNT: usize = 4;
+pub const PHANTOM_POINT_COUNT: usize = crate::GLYF_PHANTOM_POINT_COUNT;
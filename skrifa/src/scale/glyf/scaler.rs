@@ -14,9 +14,11 @@ use read_fonts::{
     TableProvider,
 };
 
+use crate::glyf_deltas as deltas;
+
 use super::{
-    deltas, Error, HinterOutline, ScalerGlyph, ScalerMemory, ScalerOutline,
-    COMPOSITE_RECURSION_LIMIT, PHANTOM_POINT_COUNT,
+    Error, HinterOutline, ScalerGlyph, ScalerMemory, ScalerOutline, COMPOSITE_RECURSION_LIMIT,
+    PHANTOM_POINT_COUNT,
 };
 
 /// Scaler state for TrueType outlines.
@@ -87,7 +89,7 @@ impl<'a> Scaler<'a> {
         coords: &'a [F2Dot14],
         hint_fn: impl FnMut(HinterOutline) -> bool,
     ) -> Result<ScalerOutline<'a>, Error> {
-        ScalerInstance::new(self.clone(), memory, size, coords, hint_fn, false)
+        ScalerInstance::new(self.clone(), memory, size, coords, hint_fn, true)
             .outline(&info.glyph, info.glyph_id)
     }
 }
@@ -260,6 +262,7 @@ where
             points: &mut self.memory.scaled[..self.point_count],
             flags: &mut self.memory.flags[..self.point_count],
             contours: &mut self.memory.contours[..self.contour_count],
+            phantom_points: self.phantom,
         };
         let x_shift = self.phantom[0].x;
         if x_shift != F26Dot6::ZERO {
@@ -425,19 +428,23 @@ where
         {
             self.phantom[i] = *point;
         }
-        if is_hinted {
-            // Create a copy of our scaled points in original_scaled.
-            let original_scaled = self
-                .memory
-                .original_scaled
-                .get_mut(..other_points_end)
-                .ok_or(InsufficientMemory)?;
-            original_scaled.copy_from_slice(scaled);
-            // When hinting, round the phantom points.
-            for point in &mut scaled[phantom_start..] {
-                point.x = point.x.round();
-                point.y = point.y.round();
-            }
+        if self.is_hinted {
+            // Create a copy of our scaled points in original_scaled. This
+            // is only necessary if we have instructions.
+            let original_scaled = if is_hinted {
+                let original_scaled = self
+                    .memory
+                    .original_scaled
+                    .get_mut(..other_points_end)
+                    .ok_or(InsufficientMemory)?;
+                original_scaled.copy_from_slice(scaled);
+                original_scaled
+            } else {
+                &mut []
+            };
+            // When hinting, round the phantom points. This is done for
+            // all simple glyphs, even those without instructions.
+            round_phantom_points(&mut scaled[phantom_start..]);
             if !(self.hint_fn)(HinterOutline {
                 unscaled,
                 scaled,
@@ -646,7 +653,7 @@ where
                     .get_mut(point_range.clone())
                     .ok_or(InsufficientMemory)?;
                 // Append the current phantom points to the outline.
-                let phantom_start = self.point_count;
+                let phantom_start = self.point_count - point_base;
                 for (i, phantom) in self.phantom.iter().enumerate() {
                     scaled[phantom_start + i] = *phantom;
                     flags[phantom_start + i] = Default::default();
@@ -674,10 +681,7 @@ where
                     .get_mut(contour_base..self.contour_count)
                     .ok_or(InsufficientMemory)?;
                 // Round the phantom points.
-                for p in &mut scaled[self.point_count..] {
-                    p.x = p.x.round();
-                    p.y = p.y.round();
-                }
+                round_phantom_points(&mut scaled[phantom_start..]);
                 // Clear the "touched" flags that are used during IUP processing.
                 for flag in flags.iter_mut() {
                     flag.clear_marker(PointMarker::TOUCHED);
@@ -736,6 +740,19 @@ impl<'a, H> ScalerInstance<'a, H> {
     }
 }
 
+/// Rounds the horizontal coordinates of the first two phantom points and
+/// the vertical coordinates of the last two.
+///
+/// See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/truetype/ttgload.c#L795>
+fn round_phantom_points(phantom: &mut [Point<F26Dot6>]) {
+    if let [pp1, pp2, pp3, pp4] = phantom {
+        pp1.x = pp1.x.round();
+        pp2.x = pp2.x.round();
+        pp3.y = pp3.y.round();
+        pp4.y = pp4.y.round();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,18 +143,97 @@
 //! [vello](https://github.com/linebender/vello),
 //! [lyon](https://github.com/nical/lyon) or
 //! [pathfinder](https://github.com/servo/pathfinder) for GPU rendering.
+//!
+//! The [`Outline`] type implements [`Pen`] and captures an owned copy of
+//! the path that can be transformed, measured and replayed into other pens.
+//!
+//! ## Rasterizing
+//!
+//! For rendering without a GPU, the [`Scaler::rasterize`] method produces an
+//! anti-aliased 8-bit coverage [`Mask`] along with its placement relative
+//! to the glyph origin. The offset supports subpixel positioning:
+//!
+//! ```
+//! # use skrifa::{scale::*, GlyphId, instance::Size};
+//! # use read_fonts::types::Point;
+//! # fn build_scaler(font: read_fonts::FontRef) {
+//! let mut context = Context::new();
+//! let mut scaler = context.new_scaler()
+//!     .size(Size::new(16.0))
+//!     .fill_rule(FillRule::NonZero)
+//!     .gamma(Some(1.8))
+//!     .build(&font);
+//! let mask = scaler.rasterize(GlyphId::new(20), Point::new(0.25, 0.0));
+//! # }
+//! ```
+//!
+//! The [`Rasterizer`] type can also be used directly as a pen for arbitrary
+//! paths.
+//!
+//! ## Caching outlines
+//!
+//! Scaling a glyph requires decoding the outline and applying variation
+//! deltas and hinting. When the same glyphs are drawn repeatedly, a context
+//! can retain the resulting outlines within a memory budget. Only scalers
+//! built with a [`cache_key`](ScalerBuilder::cache_key) make use of the
+//! cache:
+//!
+//! ```
+//! # use skrifa::{scale::*, GlyphId, font::UniqueId, instance::Size};
+//! # fn build_scaler(font: read_fonts::FontRef) {
+//! let mut context = Context::new();
+//! // Allow up to 1MB of cached outlines.
+//! context.set_outline_cache_budget(1 << 20);
+//! let mut scaler = context.new_scaler()
+//!     .cache_key(Some(UniqueId::new(1, 0)))
+//!     .size(Size::new(16.0))
+//!     .build(&font);
+//! let mut outline = Outline::new();
+//! scaler.outline(GlyphId::new(20), &mut outline);
+//! # }
+//! ```
+//!
+//! ## Computing bounds
+//!
+//! The bounding box stored in the `glyf` table does not reflect variations
+//! and is not available for CFF outlines. The [`Scaler::bounds`] method
+//! computes the bounding box from the scaled outline at the configured size
+//! and location without requiring a pen:
+//!
+//! ```
+//! # use skrifa::{scale::*, GlyphId, instance::Size};
+//! # fn build_scaler(font: read_fonts::FontRef) {
+//! # let mut context = Context::new();
+//! # let mut scaler = context.new_scaler()
+//! #    .size(Size::new(16.0))
+//! #    .build(&font);
+//! let bounds = scaler.bounds(GlyphId::new(20), BoundsMode::Tight);
+//! # }
+//! ```
 
 // Temporary until new scaler API is done.
 #![allow(dead_code)]
 
+#[cfg(feature = "hinting")]
+mod autohint;
+mod bounds;
+#[cfg(feature = "hinting")]
+mod cache;
 mod cff;
 mod error;
 mod glyf;
+mod outline;
+mod outline_cache;
+mod raster;
 mod scaler;
+mod synthesis;
 
 pub use read_fonts::types::Pen;
 
+pub use bounds::BoundsMode;
 pub use error::{Error, Result};
+pub use outline::{Outline, Verb};
+pub use raster::{FillRule, Mask, Rasterizer};
 pub use scaler::{Scaler, ScalerBuilder, ScalerMetrics};
 
 use super::{
@@ -166,7 +245,11 @@ use super::{
 
 /// Modes for hinting.
 ///
-/// Only the `glyf` source supports all hinting modes.
+/// The TrueType modes are only supported by the `glyf` source and are
+/// ignored for CFF outlines. [`Hinting::Auto`] is supported for all sources.
+///
+/// Hinting is only available with the `hinting` feature. Without it, the
+/// mode can't be selected and outlines are always unhinted.
 #[cfg(feature = "hinting")]
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
 pub enum Hinting {
@@ -183,6 +266,13 @@ pub enum Hinting {
     /// horizontal direction. This is the default mode.
     #[default]
     VerticalSubpixel,
+    /// Automatic hinting that ignores any instructions in the font and
+    /// aligns outlines to the pixel grid in the vertical direction only.
+    ///
+    /// Blue zones (such as the baseline and x-height) and stems are
+    /// detected from the outlines of reference characters. This is
+    /// supported for all outline formats.
+    Auto,
 }
 
 /// Context for scaling glyphs.
@@ -200,6 +290,18 @@ pub struct Context {
     coords: Vec<NormalizedCoord>,
     /// Storage for variation settings.
     variations: Vec<VariationSetting>,
+    /// Cache of TrueType hinting instances.
+    #[cfg(feature = "hinting")]
+    hint_cache: cache::InstanceCache<glyf::HintInstance>,
+    /// Cache of metrics for automatic hinting.
+    #[cfg(feature = "hinting")]
+    autohint_cache: cache::InstanceCache<autohint::Metrics>,
+    /// Buffer for outlines that are modified after loading.
+    outline: outline::Outline,
+    /// Cache of scaled outlines.
+    outline_cache: outline_cache::OutlineCache,
+    /// Rasterizer for generating coverage masks.
+    rasterizer: raster::Rasterizer,
 }
 
 impl Context {
@@ -208,6 +310,25 @@ impl Context {
         Self::default()
     }
 
+    /// Returns the memory budget in bytes for the outline cache.
+    ///
+    /// The default budget is zero which disables the cache.
+    pub fn outline_cache_budget(&self) -> usize {
+        self.outline_cache.budget()
+    }
+
+    /// Sets the memory budget in bytes for the outline cache.
+    ///
+    /// When the budget is non-zero, outlines produced by scalers built with
+    /// a [`cache_key`](ScalerBuilder::cache_key) are retained and reused
+    /// for subsequent requests with the same font, glyph, size, location,
+    /// hinting mode and synthesis settings. The least recently used
+    /// outlines are evicted when the budget is exceeded. Setting the budget
+    /// to zero disables the cache and frees all cached outlines.
+    pub fn set_outline_cache_budget(&mut self, budget: usize) {
+        self.outline_cache.set_budget(budget);
+    }
+
     /// Returns a builder for configuring a glyph scaler.
     pub fn new_scaler(&mut self) -> ScalerBuilder {
         ScalerBuilder::new(self)
@@ -216,7 +337,7 @@ impl Context {
 
 #[cfg(test)]
 mod tests {
-    use super::{Context, Size};
+    use super::{Context, Size, UniqueId};
     use read_fonts::{scaler_test, types::GlyphId, FontRef, TableProvider};
 
     #[test]
@@ -246,6 +367,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tthint_subset() {
+        compare_glyphs(
+            font_test_data::TTHINT_SUBSET,
+            font_test_data::TTHINT_SUBSET_GLYPHS,
+            false,
+        );
+    }
+
+    #[test]
+    fn tthint_var() {
+        compare_glyphs(
+            font_test_data::TTHINT_VAR,
+            font_test_data::TTHINT_VAR_GLYPHS,
+            false,
+        );
+    }
+
+    #[cfg(feature = "hinting")]
+    #[test]
+    fn cantarell_vf_autohint() {
+        use scaler_test::PathElement;
+        fn coords(element: &PathElement) -> &[f32] {
+            match element {
+                PathElement::MoveTo(p) | PathElement::LineTo(p) => p,
+                PathElement::QuadTo(p) => p,
+                PathElement::CurveTo(p) => p,
+            }
+        }
+        let font = FontRef::new(font_test_data::CANTARELL_VF_TRIMMED).unwrap();
+        let outlines =
+            scaler_test::parse_glyph_outlines(font_test_data::CANTARELL_VF_TRIMMED_AUTOHINT_GLYPHS);
+        let mut cx = Context::new();
+        let mut path = scaler_test::Path {
+            elements: vec![],
+            is_cff: true,
+        };
+        for expected in &outlines {
+            path.elements.clear();
+            cx.new_scaler()
+                .size(Size::new(expected.size))
+                .normalized_coords(&expected.coords)
+                .hint(Some(super::Hinting::Auto))
+                .build(&font)
+                .outline(expected.glyph_id, &mut path)
+                .unwrap();
+            assert_eq!(path.elements.len(), expected.path.len());
+            // Our auto-hinter is a simplified version of FreeType's so we
+            // only require the outlines to be close. FreeType's light mode
+            // may also make small horizontal adjustments.
+            for (element, expected_element) in path.elements.iter().zip(&expected.path) {
+                for (i, (a, b)) in coords(element)
+                    .iter()
+                    .zip(coords(expected_element))
+                    .enumerate()
+                {
+                    let tolerance = if i % 2 == 0 { 0.25 } else { 0.75 };
+                    assert!(
+                        (a - b).abs() <= tolerance,
+                        "mismatch in auto-hinted glyph {} at size {}: {:?} expected {:?}",
+                        expected.glyph_id,
+                        expected.size,
+                        element,
+                        expected_element
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn overlap_flags() {
         let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
@@ -271,6 +462,259 @@ mod tests {
         );
     }
 
+    #[test]
+    fn synthetic_bold_and_oblique() {
+        use super::outline::Outline;
+        use crate::{metrics::GlyphMetrics, prelude::LocationRef};
+        let bounds = |outline: &Outline| {
+            outline.points.iter().fold(
+                [f32::MAX, f32::MAX, f32::MIN, f32::MIN],
+                |[x_min, y_min, x_max, y_max], p| {
+                    [
+                        x_min.min(p.x),
+                        y_min.min(p.y),
+                        x_max.max(p.x),
+                        y_max.max(p.y),
+                    ]
+                },
+            )
+        };
+        for (font_data, glyph_id) in [
+            (font_test_data::VAZIRMATN_VAR, GlyphId::new(1)),
+            (font_test_data::CANTARELL_VF_TRIMMED, GlyphId::new(2)),
+        ] {
+            let font = FontRef::new(font_data).unwrap();
+            let size = Size::new(16.0);
+            let advance = GlyphMetrics::new(&font, size, LocationRef::default())
+                .advance_width(glyph_id)
+                .unwrap();
+            let mut cx = Context::new();
+            let mut regular = Outline::default();
+            let metrics = cx
+                .new_scaler()
+                .size(size)
+                .build(&font)
+                .outline(glyph_id, &mut regular)
+                .unwrap();
+            assert_eq!(metrics.adjusted_advance_width, None);
+            let mut bold = Outline::default();
+            let metrics = cx
+                .new_scaler()
+                .size(size)
+                .embolden(1.0, 0.5)
+                .build(&font)
+                .outline(glyph_id, &mut bold)
+                .unwrap();
+            assert_eq!(metrics.adjusted_advance_width, Some(advance + 1.0));
+            let [x_min, y_min, x_max, y_max] = bounds(&regular);
+            let [bold_x_min, bold_y_min, bold_x_max, bold_y_max] = bounds(&bold);
+            assert!((bold_x_max - bold_x_min) - (x_max - x_min) > 0.5);
+            assert!((bold_y_max - bold_y_min) - (y_max - y_min) > 0.25);
+            // The origin side of the glyph stays approximately fixed.
+            assert!((bold_x_min - x_min).abs() < 0.25);
+            assert!((bold_y_min - y_min).abs() < 0.25);
+            let mut oblique = Outline::default();
+            let metrics = cx
+                .new_scaler()
+                .size(size)
+                .oblique(45.0)
+                .build(&font)
+                .outline(glyph_id, &mut oblique)
+                .unwrap();
+            assert_eq!(metrics.adjusted_advance_width, None);
+            for (p, q) in regular.points.iter().zip(&oblique.points) {
+                assert!((p.x + p.y - q.x).abs() < 1e-3);
+                assert_eq!(p.y, q.y);
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_match_glyf_header() {
+        use super::BoundsMode;
+        use crate::{metrics::GlyphMetrics, prelude::LocationRef};
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let glyph_metrics = GlyphMetrics::new(&font, Size::unscaled(), LocationRef::default());
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().build(&font);
+        for gid in 0..font.maxp().unwrap().num_glyphs() {
+            let gid = GlyphId::new(gid);
+            let bounds = scaler.bounds(gid, BoundsMode::ControlBox).unwrap();
+            assert_eq!(Some(bounds), glyph_metrics.bounds(gid));
+        }
+    }
+
+    #[test]
+    fn variable_bounds() {
+        use super::BoundsMode;
+        use crate::{metrics::GlyphMetrics, prelude::LocationRef};
+        use read_fonts::types::F2Dot14;
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let gid = GlyphId::new(1);
+        let default_bounds = GlyphMetrics::new(&font, Size::unscaled(), LocationRef::default())
+            .bounds(gid)
+            .unwrap();
+        let mut cx = Context::new();
+        let bounds = cx
+            .new_scaler()
+            .normalized_coords([F2Dot14::from_f32(1.0)])
+            .build(&font)
+            .bounds(gid, BoundsMode::ControlBox)
+            .unwrap();
+        assert_ne!(bounds, default_bounds);
+    }
+
+    #[test]
+    fn cff_bounds() {
+        use super::BoundsMode;
+        let font = FontRef::new(font_test_data::CANTARELL_VF_TRIMMED).unwrap();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().size(Size::new(16.0)).build(&font);
+        let gid = GlyphId::new(2);
+        let control_box = scaler.bounds(gid, BoundsMode::ControlBox).unwrap();
+        let tight = scaler.bounds(gid, BoundsMode::Tight).unwrap();
+        assert!(control_box.x_max > control_box.x_min);
+        assert!(control_box.y_max > control_box.y_min);
+        // Tight bounds are always contained in the control box
+        assert!(tight.x_min >= control_box.x_min && tight.x_max <= control_box.x_max);
+        assert!(tight.y_min >= control_box.y_min && tight.y_max <= control_box.y_max);
+    }
+
+    #[test]
+    fn cached_outlines() {
+        use super::Outline;
+        let font = FontRef::new(font_test_data::VAZIRMATN_VAR).unwrap();
+        let glyph_count = font.maxp().unwrap().num_glyphs();
+        let mut cx = Context::new();
+        let mut expected = vec![];
+        for gid in 0..glyph_count {
+            let mut outline = Outline::new();
+            cx.new_scaler()
+                .size(Size::new(16.0))
+                .build(&font)
+                .outline(GlyphId::new(gid), &mut outline)
+                .unwrap();
+            expected.push(outline);
+        }
+        cx.set_outline_cache_budget(1 << 20);
+        // The first pass fills the cache and the second reads from it
+        for _ in 0..2 {
+            let mut scaler = cx
+                .new_scaler()
+                .cache_key(Some(UniqueId::new(0, 0)))
+                .size(Size::new(16.0))
+                .build(&font);
+            for (gid, expected) in expected.iter().enumerate() {
+                let mut outline = Outline::new();
+                scaler
+                    .outline(GlyphId::new(gid as u16), &mut outline)
+                    .unwrap();
+                assert_eq!(&outline, expected);
+            }
+        }
+        assert!(cx.outline_cache.used() > 0);
+        cx.set_outline_cache_budget(0);
+        assert_eq!(cx.outline_cache.used(), 0);
+    }
+
+    #[test]
+    fn transform_outline() {
+        use super::{BoundsMode, Outline, Verb};
+        use read_fonts::types::{Pen, Point};
+        let mut outline = Outline::new();
+        outline.move_to(0.0, 0.0);
+        outline.line_to(10.0, 0.0);
+        outline.quad_to(10.0, 10.0, 0.0, 10.0);
+        outline.close();
+        assert_eq!(
+            outline.verbs(),
+            [Verb::MoveTo, Verb::LineTo, Verb::QuadTo, Verb::Close]
+        );
+        outline.transform([2.0, 0.0, 0.0, 2.0, 5.0, -5.0]);
+        assert_eq!(
+            outline.points(),
+            [
+                Point::new(5.0, -5.0),
+                Point::new(25.0, -5.0),
+                Point::new(25.0, 15.0),
+                Point::new(5.0, 15.0)
+            ]
+        );
+        let bounds = outline.bounds(BoundsMode::Tight);
+        assert_eq!(
+            [bounds.x_min, bounds.y_min, bounds.x_max, bounds.y_max],
+            [5.0, -5.0, 25.0, 15.0]
+        );
+        let mut copy = Outline::new();
+        outline.to_path(&mut copy);
+        assert_eq!(copy, outline);
+    }
+
+    #[test]
+    fn vazirmatin_var_masks() {
+        compare_masks(
+            font_test_data::VAZIRMATN_VAR,
+            font_test_data::VAZIRMATN_VAR_MASKS,
+            12,
+        );
+    }
+
+    #[test]
+    fn cantarell_vf_masks() {
+        compare_masks(
+            font_test_data::CANTARELL_VF_TRIMMED,
+            font_test_data::CANTARELL_VF_TRIMMED_MASKS,
+            // FreeType flattens cubic curves with a coarser tolerance
+            24,
+        );
+    }
+
+    #[cfg(feature = "hinting")]
+    #[test]
+    fn tthint_subset_masks() {
+        compare_masks(
+            font_test_data::TTHINT_SUBSET,
+            font_test_data::TTHINT_SUBSET_MASKS,
+            12,
+        );
+    }
+
+    /// Compares rasterized masks against those generated by FreeType,
+    /// allowing each coverage value to differ by at most `max_diff`.
+    fn compare_masks(font_data: &[u8], expected_masks: &str, max_diff: u8) {
+        use read_fonts::types::Point;
+        let font = FontRef::new(font_data).unwrap();
+        let masks = scaler_test::parse_glyph_masks(expected_masks);
+        let mut cx = Context::new();
+        for expected in &masks {
+            let builder = cx.new_scaler().size(Size::new(expected.size));
+            #[cfg(feature = "hinting")]
+            let builder = builder.hint(expected.hinting.as_deref().map(parse_hinting));
+            let mask = builder
+                .build(&font)
+                .rasterize(expected.glyph_id, Point::default())
+                .unwrap();
+            assert_eq!(
+                (mask.left, mask.top, mask.width, mask.height),
+                (expected.left, expected.top, expected.width, expected.height),
+                "placement mismatch for glyph {}",
+                expected.glyph_id
+            );
+            // FreeType accumulates coverage in fixed point cells and
+            // flattens curves differently so only antialiased edge pixels
+            // are expected to differ slightly
+            for (i, (a, b)) in mask.data.iter().zip(&expected.data).enumerate() {
+                let (x, y) = (i % mask.width as usize, i / mask.width as usize);
+                assert!(
+                    a.abs_diff(*b) <= max_diff,
+                    "coverage mismatch for glyph {} at size {} in pixel ({x}, {y}): {a} != {b}",
+                    expected.glyph_id,
+                    expected.size
+                );
+            }
+        }
+    }
+
     fn compare_glyphs(font_data: &[u8], expected_outlines: &str, is_cff: bool) {
         let font = FontRef::new(font_data).unwrap();
         let outlines = scaler_test::parse_glyph_outlines(expected_outlines);
@@ -283,20 +727,28 @@ mod tests {
             if expected_outline.size == 0.0 && !expected_outline.coords.is_empty() {
                 continue;
             }
+            #[cfg(not(feature = "hinting"))]
+            if expected_outline.hinting.is_some() {
+                continue;
+            }
             path.elements.clear();
-            let mut scaler = cx
+            let builder = cx
                 .new_scaler()
+                .cache_key(Some(UniqueId::new(0, 0)))
                 .size(Size::new(expected_outline.size))
-                .normalized_coords(&expected_outline.coords)
-                .build(&font);
+                .normalized_coords(&expected_outline.coords);
+            #[cfg(feature = "hinting")]
+            let builder = builder.hint(expected_outline.hinting.as_deref().map(parse_hinting));
+            let mut scaler = builder.build(&font);
             scaler
                 .outline(expected_outline.glyph_id, &mut path)
                 .unwrap();
             if path.elements != expected_outline.path {
                 panic!(
-                    "mismatch in glyph path for id {} (size: {}, coords: {:?}): path: {:?} expected_path: {:?}",
+                    "mismatch in glyph path for id {} (size: {}, hinting: {:?}, coords: {:?}): path: {:?} expected_path: {:?}",
                     expected_outline.glyph_id,
                     expected_outline.size,
+                    expected_outline.hinting,
                     expected_outline.coords,
                     &path.elements,
                     &expected_outline.path
@@ -304,4 +756,16 @@ mod tests {
             }
         }
     }
+
+    #[cfg(feature = "hinting")]
+    fn parse_hinting(mode: &str) -> super::Hinting {
+        use super::Hinting;
+        match mode {
+            "full" => Hinting::Full,
+            "light" => Hinting::Light,
+            "light-subpixel" => Hinting::LightSubpixel,
+            "vertical-subpixel" => Hinting::VerticalSubpixel,
+            _ => panic!("unknown hinting mode {mode}"),
+        }
+    }
 }
@@ -0,0 +1,191 @@
+//! Owned outline representation.
+//!
+//! This is also used as a buffer for post-processing by the auto-hinter and
+//! outline synthesis.
+
+use core::ops::Range;
+
+use read_fonts::types::{Pen, Point};
+
+use super::bounds::{BoundsMode, BoundsPen};
+use crate::metrics::BoundingBox;
+
+/// Path command for an outline.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Verb {
+    /// Begins a new contour. Consumes one point.
+    MoveTo,
+    /// Line segment. Consumes one point.
+    LineTo,
+    /// Quadratic bezier segment. Consumes a control point and an end point.
+    QuadTo,
+    /// Cubic bezier segment. Consumes two control points and an end point.
+    CurveTo,
+    /// Closes the current contour. Consumes no points.
+    Close,
+}
+
+impl Verb {
+    /// Returns the number of points consumed by the command.
+    fn point_count(self) -> usize {
+        match self {
+            Self::MoveTo | Self::LineTo => 1,
+            Self::QuadTo => 2,
+            Self::CurveTo => 3,
+            Self::Close => 0,
+        }
+    }
+}
+
+/// Owned sequence of path commands and points captured from a scaler.
+///
+/// An outline implements [`Pen`] so it can be passed directly to
+/// [`Scaler::outline`](super::Scaler::outline). The captured outline can
+/// then be transformed, measured and replayed into any other pen without
+/// scaling the glyph again.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Outline {
+    pub(crate) verbs: Vec<Verb>,
+    pub(crate) points: Vec<Point<f32>>,
+    /// True for each point that lies on the curve.
+    pub(crate) on_curve: Vec<bool>,
+}
+
+impl Outline {
+    /// Creates a new empty outline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the outline contains no path commands.
+    pub fn is_empty(&self) -> bool {
+        self.verbs.is_empty()
+    }
+
+    /// Returns the sequence of path commands.
+    pub fn verbs(&self) -> &[Verb] {
+        &self.verbs
+    }
+
+    /// Returns the points consumed by the path commands.
+    pub fn points(&self) -> &[Point<f32>] {
+        &self.points
+    }
+
+    /// Removes all path commands and points from the outline.
+    pub fn clear(&mut self) {
+        self.verbs.clear();
+        self.points.clear();
+        self.on_curve.clear();
+    }
+
+    /// Returns the ranges of points for each contour.
+    pub fn contours(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let mut start = 0;
+        let mut end = 0;
+        let mut verbs = self.verbs.iter().peekable();
+        core::iter::from_fn(move || loop {
+            let verb = verbs.next()?;
+            end += verb.point_count();
+            if matches!(verbs.peek(), None | Some(Verb::MoveTo)) && end > start {
+                let range = start..end;
+                start = end;
+                return Some(range);
+            }
+        })
+    }
+
+    /// Applies the affine transform `[xx, yx, xy, yy, dx, dy]` to all points
+    /// in the outline.
+    pub fn transform(&mut self, matrix: [f32; 6]) {
+        let [xx, yx, xy, yy, dx, dy] = matrix;
+        for point in &mut self.points {
+            let (x, y) = (point.x, point.y);
+            point.x = xx * x + xy * y + dx;
+            point.y = yx * x + yy * y + dy;
+        }
+    }
+
+    /// Returns the bounding box of the outline.
+    pub fn bounds(&self, mode: BoundsMode) -> BoundingBox {
+        let mut pen = BoundsPen::new(mode);
+        self.to_path(&mut pen);
+        pen.bounds()
+    }
+
+    /// Returns the approximate number of bytes of heap memory used by the
+    /// outline.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.verbs.capacity() * core::mem::size_of::<Verb>()
+            + self.points.capacity() * core::mem::size_of::<Point<f32>>()
+            + self.on_curve.capacity() * core::mem::size_of::<bool>()
+    }
+
+    /// Releases excess capacity.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.verbs.shrink_to_fit();
+        self.points.shrink_to_fit();
+        self.on_curve.shrink_to_fit();
+    }
+
+    /// Invokes the functions in the given pen for each command.
+    pub fn to_path(&self, pen: &mut impl Pen) {
+        let mut points = self.points.iter();
+        let mut next = || points.next().copied().unwrap_or_default();
+        for verb in &self.verbs {
+            match verb {
+                Verb::MoveTo => {
+                    let p = next();
+                    pen.move_to(p.x, p.y);
+                }
+                Verb::LineTo => {
+                    let p = next();
+                    pen.line_to(p.x, p.y);
+                }
+                Verb::QuadTo => {
+                    let (c, p) = (next(), next());
+                    pen.quad_to(c.x, c.y, p.x, p.y);
+                }
+                Verb::CurveTo => {
+                    let (c0, c1, p) = (next(), next(), next());
+                    pen.curve_to(c0.x, c0.y, c1.x, c1.y, p.x, p.y);
+                }
+                Verb::Close => pen.close(),
+            }
+        }
+    }
+
+    fn push(&mut self, x: f32, y: f32, on_curve: bool) {
+        self.points.push(Point::new(x, y));
+        self.on_curve.push(on_curve);
+    }
+}
+
+impl Pen for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.verbs.push(Verb::MoveTo);
+        self.push(x, y, true);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.verbs.push(Verb::LineTo);
+        self.push(x, y, true);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.verbs.push(Verb::QuadTo);
+        self.push(cx0, cy0, false);
+        self.push(x, y, true);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.verbs.push(Verb::CurveTo);
+        self.push(cx0, cy0, false);
+        self.push(cx1, cy1, false);
+        self.push(x, y, true);
+    }
+
+    fn close(&mut self) {
+        self.verbs.push(Verb::Close);
+    }
+}
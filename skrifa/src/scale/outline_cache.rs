@@ -0,0 +1,289 @@
+//! Cache of scaled glyph outlines.
+
+use std::collections::{BTreeMap, HashMap};
+
+use read_fonts::types::{F2Dot14, GlyphId};
+
+#[cfg(feature = "hinting")]
+use super::Hinting;
+use super::{outline::Outline, synthesis::Synthesis, ScalerMetrics};
+use crate::font::UniqueId;
+
+/// Approximate fixed memory cost of a cached outline, in addition to the
+/// heap memory used by the outline itself.
+const ENTRY_OVERHEAD: usize = core::mem::size_of::<Entry>() + core::mem::size_of::<GlyphId>();
+
+/// Configuration of a scaler that determines the resulting outlines.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CacheKey<'a> {
+    pub id: UniqueId,
+    pub size: f32,
+    pub coords: &'a [F2Dot14],
+    #[cfg(feature = "hinting")]
+    pub hint: Option<Hinting>,
+    pub synthesis: Synthesis,
+}
+
+/// Least recently used cache of scaled outlines with a memory budget.
+///
+/// Outlines are grouped by instance (font, size, location, hinting mode
+/// and synthesis settings) and evicted individually when the total memory
+/// used exceeds the budget. A budget of zero disables the cache.
+#[derive(Clone, Default, Debug)]
+pub struct OutlineCache {
+    budget: usize,
+    used: usize,
+    instances: Vec<Instance>,
+    /// Maps the serial number of each entry to its instance and glyph
+    /// identifier, ordered from least to most recently used.
+    lru: BTreeMap<u64, (u64, GlyphId)>,
+    serial: u64,
+    next_instance_id: u64,
+}
+
+impl OutlineCache {
+    /// Returns the memory budget in bytes.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Sets the memory budget in bytes, evicting outlines as necessary.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict(0);
+    }
+
+    /// Returns true if outlines may be stored in the cache.
+    pub fn is_enabled(&self) -> bool {
+        self.budget != 0
+    }
+
+    /// Returns the approximate number of bytes used by cached outlines.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the cached outline and metrics for the given configuration
+    /// and glyph.
+    pub fn get(&mut self, key: &CacheKey, glyph_id: GlyphId) -> Option<(&Outline, ScalerMetrics)> {
+        let instance_ix = self.instances.iter().position(|inst| inst.matches(key))?;
+        let instance = &mut self.instances[instance_ix];
+        let entry = instance.glyphs.get_mut(&glyph_id)?;
+        self.serial += 1;
+        self.lru.remove(&entry.serial);
+        entry.serial = self.serial;
+        self.lru.insert(self.serial, (instance.id, glyph_id));
+        Some((&entry.outline, entry.metrics))
+    }
+
+    /// Stores an outline and metrics for the given configuration and
+    /// glyph.
+    ///
+    /// Outlines that exceed the budget on their own are not stored.
+    pub fn insert(
+        &mut self,
+        key: &CacheKey,
+        glyph_id: GlyphId,
+        mut outline: Outline,
+        metrics: ScalerMetrics,
+    ) {
+        outline.shrink_to_fit();
+        let size = outline.heap_size() + ENTRY_OVERHEAD;
+        if size > self.budget {
+            return;
+        }
+        self.evict(size);
+        let instance_ix = match self.instances.iter().position(|inst| inst.matches(key)) {
+            Some(ix) => ix,
+            None => {
+                self.next_instance_id += 1;
+                self.instances
+                    .push(Instance::new(key, self.next_instance_id));
+                self.instances.len() - 1
+            }
+        };
+        self.serial += 1;
+        let instance = &mut self.instances[instance_ix];
+        let entry = Entry {
+            outline,
+            metrics,
+            serial: self.serial,
+            size,
+        };
+        if let Some(prev) = instance.glyphs.insert(glyph_id, entry) {
+            self.lru.remove(&prev.serial);
+            self.used -= prev.size;
+        }
+        self.lru.insert(self.serial, (instance.id, glyph_id));
+        self.used += size;
+    }
+
+    /// Evicts least recently used outlines until there is room for an
+    /// additional `size` bytes.
+    fn evict(&mut self, size: usize) {
+        while self.used + size > self.budget {
+            let Some((_, (instance_id, glyph_id))) = self.lru.pop_first() else {
+                break;
+            };
+            let Some(instance_ix) = self
+                .instances
+                .iter()
+                .position(|inst| inst.id == instance_id)
+            else {
+                continue;
+            };
+            let instance = &mut self.instances[instance_ix];
+            if let Some(entry) = instance.glyphs.remove(&glyph_id) {
+                self.used -= entry.size;
+            }
+            if instance.glyphs.is_empty() {
+                self.instances.swap_remove(instance_ix);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Instance {
+    id: u64,
+    font_id: UniqueId,
+    size: f32,
+    coords: Vec<F2Dot14>,
+    #[cfg(feature = "hinting")]
+    hint: Option<Hinting>,
+    synthesis: Synthesis,
+    glyphs: HashMap<GlyphId, Entry>,
+}
+
+impl Instance {
+    fn new(key: &CacheKey, id: u64) -> Self {
+        Self {
+            id,
+            font_id: key.id,
+            size: key.size,
+            coords: key.coords.to_vec(),
+            #[cfg(feature = "hinting")]
+            hint: key.hint,
+            synthesis: key.synthesis,
+            glyphs: HashMap::new(),
+        }
+    }
+
+    fn matches(&self, key: &CacheKey) -> bool {
+        #[cfg(feature = "hinting")]
+        if self.hint != key.hint {
+            return false;
+        }
+        self.font_id == key.id
+            && self.size == key.size
+            && self.synthesis == key.synthesis
+            && self.coords == key.coords
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    outline: Outline,
+    metrics: ScalerMetrics,
+    serial: u64,
+    /// Memory charged against the budget for this entry.
+    size: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_fonts::types::Pen;
+
+    fn key(size: f32) -> CacheKey<'static> {
+        CacheKey {
+            id: UniqueId::new(1, 0),
+            size,
+            coords: &[],
+            #[cfg(feature = "hinting")]
+            hint: None,
+            synthesis: Synthesis::default(),
+        }
+    }
+
+    fn outline(len: usize) -> Outline {
+        let mut outline = Outline::new();
+        outline.move_to(0.0, 0.0);
+        for i in 0..len {
+            outline.line_to(i as f32, 1.0);
+        }
+        outline.close();
+        outline
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut cache = OutlineCache::default();
+        assert!(!cache.is_enabled());
+        cache.insert(&key(16.0), GlyphId::new(1), outline(4), Default::default());
+        assert!(cache.get(&key(16.0), GlyphId::new(1)).is_none());
+        assert_eq!(cache.used(), 0);
+    }
+
+    #[test]
+    fn match_configuration() {
+        let mut cache = OutlineCache::default();
+        cache.set_budget(1 << 20);
+        let metrics = ScalerMetrics {
+            has_overlaps: true,
+            ..Default::default()
+        };
+        cache.insert(&key(16.0), GlyphId::new(1), outline(4), metrics);
+        let (cached, cached_metrics) = cache.get(&key(16.0), GlyphId::new(1)).unwrap();
+        assert_eq!(cached, &outline(4));
+        assert!(cached_metrics.has_overlaps);
+        assert!(cache.get(&key(16.0), GlyphId::new(2)).is_none());
+        assert!(cache.get(&key(17.0), GlyphId::new(1)).is_none());
+        let coords = [F2Dot14::from_f32(0.5)];
+        let varied = CacheKey {
+            coords: &coords,
+            ..key(16.0)
+        };
+        assert!(cache.get(&varied, GlyphId::new(1)).is_none());
+        let other_font = CacheKey {
+            id: UniqueId::new(2, 0),
+            ..key(16.0)
+        };
+        assert!(cache.get(&other_font, GlyphId::new(1)).is_none());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = OutlineCache::default();
+        let entry_size = {
+            let mut outline = outline(4);
+            outline.shrink_to_fit();
+            outline.heap_size() + ENTRY_OVERHEAD
+        };
+        cache.set_budget(entry_size * 3);
+        for gid in 0..3 {
+            cache.insert(
+                &key(16.0),
+                GlyphId::new(gid),
+                outline(4),
+                Default::default(),
+            );
+        }
+        assert_eq!(cache.used(), entry_size * 3);
+        // Touch the first entry so that the second becomes the oldest.
+        assert!(cache.get(&key(16.0), GlyphId::new(0)).is_some());
+        cache.insert(&key(16.0), GlyphId::new(3), outline(4), Default::default());
+        assert_eq!(cache.used(), entry_size * 3);
+        assert!(cache.get(&key(16.0), GlyphId::new(1)).is_none());
+        for gid in [0, 2, 3] {
+            assert!(cache.get(&key(16.0), GlyphId::new(gid)).is_some());
+        }
+        // Reducing the budget evicts immediately.
+        cache.set_budget(entry_size);
+        assert_eq!(cache.used(), entry_size);
+        assert!(cache.get(&key(16.0), GlyphId::new(3)).is_some());
+        cache.set_budget(0);
+        assert_eq!(cache.used(), 0);
+        assert!(cache.instances.is_empty());
+    }
+}
@@ -0,0 +1,493 @@
+//! Anti-aliased coverage rasterizer.
+//!
+//! This computes the exact area of each pixel covered by the outline after
+//! flattening curves to line segments. The accumulation approach is based on
+//! font-rs.
+//!
+//! See <https://github.com/raphlinus/font-rs/blob/be0ce6bf6a8c06d1a4d09ae6ca45bbfd23d6a6c4/src/raster.rs>
+
+use read_fonts::types::{Pen, Point};
+
+use super::{
+    bounds::BoundsMode,
+    outline::{Outline, Verb},
+};
+
+/// Maximum distance in pixels between a curve and its flattened
+/// approximation.
+const FLATTEN_TOLERANCE: f32 = 1.0 / 16.0;
+
+/// Maximum number of line segments generated for a single curve.
+const MAX_CURVE_SEGMENTS: usize = 128;
+
+/// Number of subpixels per pixel in each direction when rasterizing paths
+/// with overlapping contours.
+const OVERLAP_SCALE: usize = 4;
+
+/// Rule for determining which regions of an outline are filled.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum FillRule {
+    /// Regions with a non-zero winding number are filled. This is the rule
+    /// used by TrueType and PostScript outlines.
+    #[default]
+    NonZero,
+    /// Regions with an odd winding number are filled.
+    EvenOdd,
+}
+
+/// 8-bit alpha mask produced by a rasterizer.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Mask {
+    /// Horizontal position of the left edge of the mask in pixels, relative
+    /// to the glyph origin.
+    pub left: i32,
+    /// Vertical position of the top edge of the mask in pixels, relative to
+    /// the glyph origin with y increasing upward.
+    pub top: i32,
+    /// Width of the mask in pixels.
+    pub width: u32,
+    /// Height of the mask in pixels.
+    pub height: u32,
+    /// Coverage values in row major order, starting at the top row.
+    pub data: Vec<u8>,
+}
+
+/// Anti-aliased scanline rasterizer.
+///
+/// The rasterizer implements [`Pen`] and buffers the path commands it
+/// receives. The [`rasterize`](Self::rasterize) method then generates a
+/// coverage mask that tightly encloses the buffered path. Coordinates are
+/// expected in pixels with y increasing upward.
+#[derive(Clone, Default, Debug)]
+pub struct Rasterizer {
+    path: Outline,
+    accumulator: Vec<f32>,
+    width: usize,
+    height: usize,
+    fill_rule: FillRule,
+    gamma: Option<f32>,
+    has_overlaps: bool,
+}
+
+impl Rasterizer {
+    /// Creates a new rasterizer with the non-zero fill rule and no gamma
+    /// adjustment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the fill rule.
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Sets the fill rule.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.fill_rule = fill_rule;
+    }
+
+    /// Returns the gamma adjustment.
+    pub fn gamma(&self) -> Option<f32> {
+        self.gamma
+    }
+
+    /// Sets the gamma adjustment.
+    ///
+    /// When present, each coverage value `c` in the range 0..=1 is mapped to
+    /// `c^(1/gamma)` before being converted to 8 bits. Values greater than 1
+    /// produce darker anti-aliasing on light backgrounds.
+    pub fn set_gamma(&mut self, gamma: Option<f32>) {
+        self.gamma = gamma.filter(|gamma| *gamma > 0.0);
+    }
+
+    /// Returns true if the path is rasterized as containing overlapping
+    /// contours.
+    pub fn has_overlaps(&self) -> bool {
+        self.has_overlaps
+    }
+
+    /// Sets whether the path contains overlapping contours.
+    ///
+    /// Summing exact areas overestimates coverage along edges where contours
+    /// overlap. When this is set, coverage is instead computed at
+    /// 4x4 subpixel resolution and averaged which matches the behavior of
+    /// FreeType for glyphs with overlap flags.
+    pub fn set_has_overlaps(&mut self, has_overlaps: bool) {
+        self.has_overlaps = has_overlaps;
+    }
+
+    /// Removes all buffered path commands.
+    pub fn reset(&mut self) {
+        self.path.clear();
+    }
+
+    /// Generates a coverage mask for the buffered path translated by the
+    /// given offset.
+    ///
+    /// The offset is in pixels and may be fractional to support subpixel
+    /// positioning. The size and placement of the mask are determined by the
+    /// control box of the translated path, rounded outward to whole pixels.
+    pub fn rasterize(&mut self, offset: Point<f32>) -> Mask {
+        if self.path.is_empty() {
+            return Mask::default();
+        }
+        let bounds = self.path.bounds(BoundsMode::ControlBox);
+        let left = (bounds.x_min + offset.x).floor();
+        let right = (bounds.x_max + offset.x).ceil();
+        let bottom = (bounds.y_min + offset.y).floor();
+        let top = (bounds.y_max + offset.y).ceil();
+        let scale = if self.has_overlaps { OVERLAP_SCALE } else { 1 };
+        let (width, height) = ((right - left) as usize, (top - bottom) as usize);
+        self.width = width * scale;
+        self.height = height * scale;
+        // Extra space for coverage that spills past the last pixel
+        let len = self.width * self.height + 2;
+        self.accumulator.clear();
+        self.accumulator.resize(len, 0.0);
+        // Convert to raster space with y increasing downward
+        let s = scale as f32;
+        let transform =
+            |p: Point<f32>| Point::new((p.x + offset.x - left) * s, (top - (p.y + offset.y)) * s);
+        let path = core::mem::take(&mut self.path);
+        let mut points = path.points.iter().copied().map(transform);
+        let mut start = Point::default();
+        let mut current = Point::default();
+        for verb in &path.verbs {
+            match verb {
+                Verb::MoveTo => {
+                    // Contours are implicitly closed
+                    self.line(current, start);
+                    start = points.next().unwrap_or_default();
+                    current = start;
+                }
+                Verb::LineTo => {
+                    let p = points.next().unwrap_or_default();
+                    self.line(current, p);
+                    current = p;
+                }
+                Verb::QuadTo => {
+                    let (c, p) = (
+                        points.next().unwrap_or_default(),
+                        points.next().unwrap_or_default(),
+                    );
+                    self.quad(current, c, p);
+                    current = p;
+                }
+                Verb::CurveTo => {
+                    let (c0, c1, p) = (
+                        points.next().unwrap_or_default(),
+                        points.next().unwrap_or_default(),
+                        points.next().unwrap_or_default(),
+                    );
+                    self.curve(current, c0, c1, p);
+                    current = p;
+                }
+                Verb::Close => {
+                    self.line(current, start);
+                    current = start;
+                }
+            }
+        }
+        self.line(current, start);
+        self.path = path;
+        let mut coverage = self.accumulate();
+        if scale != 1 {
+            // Average each block of subpixels
+            let mut averaged = vec![0.0; width * height];
+            for (i, value) in coverage.iter().enumerate() {
+                let (x, y) = ((i % self.width) / scale, (i / self.width) / scale);
+                averaged[y * width + x] += value;
+            }
+            let inv_area = (s * s).recip();
+            averaged.iter_mut().for_each(|value| *value *= inv_area);
+            coverage = averaged;
+        }
+        Mask {
+            left: left as i32,
+            top: top as i32,
+            width: width as u32,
+            height: height as u32,
+            data: self.quantize(&coverage),
+        }
+    }
+
+    /// Converts the accumulated signed areas to coverage values in the
+    /// range 0..=1.
+    fn accumulate(&self) -> Vec<f32> {
+        let mut acc = 0.0;
+        let len = self.width * self.height;
+        self.accumulator[..len]
+            .iter()
+            .map(|area| {
+                acc += area;
+                match self.fill_rule {
+                    FillRule::NonZero => acc.abs().min(1.0),
+                    FillRule::EvenOdd => {
+                        let winding = acc.abs() % 2.0;
+                        if winding > 1.0 {
+                            2.0 - winding
+                        } else {
+                            winding
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Converts coverage values to 8 bits, applying the gamma adjustment.
+    fn quantize(&self, coverage: &[f32]) -> Vec<u8> {
+        let gamma_lut = self.gamma.map(|gamma| {
+            let mut lut = [0u8; 256];
+            for (i, value) in lut.iter_mut().enumerate() {
+                *value = ((i as f32 / 255.0).powf(1.0 / gamma) * 255.0 + 0.5) as u8;
+            }
+            lut
+        });
+        coverage
+            .iter()
+            .map(|coverage| {
+                let value = (coverage.min(1.0) * 255.0 + 0.5) as u8;
+                match &gamma_lut {
+                    Some(lut) => lut[value as usize],
+                    None => value,
+                }
+            })
+            .collect()
+    }
+
+    fn quad(&mut self, p0: Point<f32>, p1: Point<f32>, p2: Point<f32>) {
+        let dev = p0 - p1 * 2.0 + p2;
+        let dev = (dev.x * dev.x + dev.y * dev.y).sqrt();
+        let count = segment_count(dev / 4.0);
+        let mut prev = p0;
+        for i in 1..count {
+            let t = i as f32 / count as f32;
+            let mt = 1.0 - t;
+            let p = p0 * (mt * mt) + p1 * (2.0 * mt * t) + p2 * (t * t);
+            self.line(prev, p);
+            prev = p;
+        }
+        self.line(prev, p2);
+    }
+
+    fn curve(&mut self, p0: Point<f32>, p1: Point<f32>, p2: Point<f32>, p3: Point<f32>) {
+        let dev0 = p0 - p1 * 2.0 + p2;
+        let dev1 = p1 - p2 * 2.0 + p3;
+        let dev = (dev0.x * dev0.x + dev0.y * dev0.y)
+            .max(dev1.x * dev1.x + dev1.y * dev1.y)
+            .sqrt();
+        let count = segment_count(dev * 0.75);
+        let mut prev = p0;
+        for i in 1..count {
+            let t = i as f32 / count as f32;
+            let mt = 1.0 - t;
+            let p = p0 * (mt * mt * mt)
+                + p1 * (3.0 * mt * mt * t)
+                + p2 * (3.0 * mt * t * t)
+                + p3 * (t * t * t);
+            self.line(prev, p);
+            prev = p;
+        }
+        self.line(prev, p3);
+    }
+
+    /// Accumulates the signed area covered by a line segment.
+    fn line(&mut self, p0: Point<f32>, p1: Point<f32>) {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let clamp = |p: Point<f32>| Point::new(p.x.clamp(0.0, width), p.y.clamp(0.0, height));
+        let (p0, p1) = (clamp(p0), clamp(p1));
+        if p0.y == p1.y {
+            return;
+        }
+        let (dir, p0, p1) = if p0.y < p1.y {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+        let mut x = p0.x;
+        let y0 = p0.y as usize;
+        let y1 = (p1.y.ceil() as usize).min(self.height);
+        let acc = &mut self.accumulator;
+        for y in y0..y1 {
+            let line_start = y * self.width;
+            let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
+            // Stepping can drift outside the raster when an edge lies on
+            // the boundary
+            let x_next = (x + dxdy * dy).clamp(0.0, width);
+            let d = dy * dir;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+            if x1i <= x0i + 1 {
+                // Segment lies within a single pixel in this row
+                let xmf = 0.5 * (x + x_next) - x0_floor;
+                acc[line_start + x0i] += d - d * xmf;
+                acc[line_start + x0i + 1] += d * xmf;
+            } else {
+                let s = (x1 - x0).recip();
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+                acc[line_start + x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    acc[line_start + x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    acc[line_start + x0i + 1] += d * (a1 - a0);
+                    for xi in x0i + 2..x1i - 1 {
+                        acc[line_start + xi] += d * s;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    acc[line_start + x1i - 1] += d * (1.0 - a2 - am);
+                }
+                acc[line_start + x1i] += d * am;
+            }
+            x = x_next;
+        }
+    }
+}
+
+/// Returns the number of line segments required to flatten a curve with
+/// the given error bound for a single segment.
+fn segment_count(error: f32) -> usize {
+    ((error / FLATTEN_TOLERANCE).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+impl Pen for Rasterizer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.path.quad_to(cx0, cy0, x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.path.curve_to(cx0, cy0, cx1, cy1, x, y);
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(pen: &mut impl Pen, x0: f32, y0: f32, x1: f32, y1: f32) {
+        pen.move_to(x0, y0);
+        pen.line_to(x1, y0);
+        pen.line_to(x1, y1);
+        pen.line_to(x0, y1);
+        pen.close();
+    }
+
+    #[test]
+    fn empty_path() {
+        let mut rasterizer = Rasterizer::new();
+        assert_eq!(rasterizer.rasterize(Point::default()), Mask::default());
+    }
+
+    #[test]
+    fn pixel_aligned_square() {
+        let mut rasterizer = Rasterizer::new();
+        rect(&mut rasterizer, 1.0, 1.0, 4.0, 3.0);
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (1, 3, 3, 2));
+        assert!(mask.data.iter().all(|coverage| *coverage == 255));
+    }
+
+    #[test]
+    fn subpixel_offset() {
+        let mut rasterizer = Rasterizer::new();
+        rect(&mut rasterizer, 0.0, 0.0, 2.0, 1.0);
+        let mask = rasterizer.rasterize(Point::new(0.5, 0.0));
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (0, 1, 3, 1));
+        assert_eq!(mask.data, [128, 255, 128]);
+    }
+
+    #[test]
+    fn fill_rules() {
+        let mut rasterizer = Rasterizer::new();
+        // Two overlapping squares with the same winding direction
+        rect(&mut rasterizer, 0.0, 0.0, 2.0, 1.0);
+        rect(&mut rasterizer, 1.0, 0.0, 3.0, 1.0);
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!(mask.data, [255, 255, 255]);
+        rasterizer.set_fill_rule(FillRule::EvenOdd);
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!(mask.data, [255, 0, 255]);
+    }
+
+    #[test]
+    fn overlapping_contours() {
+        let mut rasterizer = Rasterizer::new();
+        // Two squares that overlap within the same column of pixels
+        rect(&mut rasterizer, 0.0, 0.0, 0.5, 1.0);
+        rect(&mut rasterizer, 0.25, 0.0, 0.75, 1.0);
+        // Summing areas counts the overlap twice
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!(mask.data, [255]);
+        rasterizer.set_has_overlaps(true);
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!(mask.data, [191]);
+    }
+
+    #[test]
+    fn gamma() {
+        let mut rasterizer = Rasterizer::new();
+        rect(&mut rasterizer, 0.0, 0.0, 1.0, 1.0);
+        rasterizer.set_gamma(Some(2.0));
+        let mask = rasterizer.rasterize(Point::new(0.75, 0.0));
+        // Coverage of 0.25 and 0.75 brightened to 0.5 and ~0.87
+        assert_eq!(mask.data, [128, 221]);
+    }
+
+    #[test]
+    fn curve_coverage() {
+        // Circle approximated with cubic curves
+        let mut rasterizer = Rasterizer::new();
+        let (r, k) = (8.0, 8.0 * 0.5522848);
+        rasterizer.move_to(r, 0.0);
+        rasterizer.curve_to(r, k, k, r, 0.0, r);
+        rasterizer.curve_to(-k, r, -r, k, -r, 0.0);
+        rasterizer.curve_to(-r, -k, -k, -r, 0.0, -r);
+        rasterizer.curve_to(k, -r, r, -k, r, 0.0);
+        rasterizer.close();
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!((mask.width, mask.height), (16, 16));
+        let area = mask.data.iter().map(|c| *c as f32 / 255.0).sum::<f32>();
+        // Flattening reduces the area slightly
+        let expected = core::f32::consts::PI * r * r;
+        assert!((area - expected).abs() / expected < 0.01);
+    }
+
+    #[test]
+    fn edge_on_boundary() {
+        // Curve that meets the left edge of the raster; accumulated error
+        // when stepping along the flattened segments must not push
+        // coverage into the neighboring pixel
+        let mut rasterizer = Rasterizer::new();
+        rasterizer.move_to(2.09375, 1.1875);
+        rasterizer.quad_to(1.0, 2.390625, 1.0, 4.421875);
+        rasterizer.quad_to(1.0, 6.53125, 2.0, 7.765625);
+        rasterizer.line_to(2.0, 9.0);
+        rasterizer.line_to(4.0, 9.0);
+        rasterizer.line_to(4.0, 1.1875);
+        rasterizer.close();
+        let mask = rasterizer.rasterize(Point::default());
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (1, 9, 3, 8));
+        // The row covering y = 4..5 is almost entirely inside the curve
+        assert_eq!(&mask.data[12..15], &[249, 255, 255]);
+    }
+}
@@ -1,16 +1,28 @@
 use super::{
-    cff, glyf, Context, Error, NormalizedCoord, Pen, Result, Size, UniqueId, VariationSetting,
+    bounds::{BoundsMode, BoundsPen},
+    cff, glyf,
+    outline::Outline,
+    outline_cache::{CacheKey, OutlineCache},
+    raster::{FillRule, Mask, Rasterizer},
+    synthesis::Synthesis,
+    Context, Error, NormalizedCoord, Pen, Result, Size, UniqueId, VariationSetting,
 };
+use crate::metrics::{BoundingBox, GlyphMetrics};
 
 #[cfg(feature = "hinting")]
-use super::Hinting;
+use super::{autohint, cache::InstanceCache, Hinting};
+#[cfg(feature = "hinting")]
+use crate::charmap::Charmap;
 
 use core::borrow::Borrow;
 use read_fonts::{
-    types::{Fixed, GlyphId},
+    types::{Fixed, GlyphId, Point},
     TableProvider,
 };
 
+#[cfg(feature = "hinting")]
+use read_fonts::types::F26Dot6;
+
 /// Information and adjusted metrics generated while scaling a glyph.
 #[derive(Copy, Clone, Default, Debug)]
 pub struct ScalerMetrics {
@@ -35,6 +47,9 @@ pub struct ScalerBuilder<'a> {
     size: Size,
     #[cfg(feature = "hinting")]
     hint: Option<Hinting>,
+    synthesis: Synthesis,
+    fill_rule: FillRule,
+    gamma: Option<f32>,
 }
 
 impl<'a> ScalerBuilder<'a> {
@@ -48,6 +63,9 @@ impl<'a> ScalerBuilder<'a> {
             size: Size::unscaled(),
             #[cfg(feature = "hinting")]
             hint: None,
+            synthesis: Synthesis::default(),
+            fill_rule: FillRule::default(),
+            gamma: None,
         }
     }
 
@@ -76,6 +94,33 @@ impl<'a> ScalerBuilder<'a> {
         self
     }
 
+    /// Sets the strength of synthetic emboldening in the horizontal and
+    /// vertical directions.
+    ///
+    /// The strengths are in pixels at the requested size, or font units for
+    /// an unscaled size, and specify the total amount by which the glyph
+    /// grows in each direction. The advance width reported in
+    /// [`ScalerMetrics`] is increased by the horizontal strength.
+    ///
+    /// FreeType's `FT_GlyphSlot_Embolden` uses a strength of `ppem / 24` in
+    /// both directions. Passing zero for both strengths disables
+    /// emboldening.
+    pub fn embolden(mut self, x_strength: f32, y_strength: f32) -> Self {
+        self.synthesis.x_strength = x_strength;
+        self.synthesis.y_strength = y_strength;
+        self
+    }
+
+    /// Sets the angle in degrees of a synthetic oblique style.
+    ///
+    /// Outlines are skewed horizontally so that positive angles slant to the
+    /// right. FreeType's `FT_GlyphSlot_Oblique` uses an angle of
+    /// approximately 12 degrees. Passing zero disables the oblique style.
+    pub fn oblique(mut self, angle: f32) -> Self {
+        self.synthesis.skew = angle.to_radians().tan();
+        self
+    }
+
     /// Specifies a variation with a set of normalized coordinates.
     ///
     /// This will clear any variations specified with the variations method.
@@ -133,6 +178,23 @@ impl<'a> ScalerBuilder<'a> {
         self
     }
 
+    /// Sets the fill rule used by [`Scaler::rasterize`].
+    ///
+    /// The default is [`FillRule::NonZero`].
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// Sets the gamma adjustment applied to coverage values by
+    /// [`Scaler::rasterize`].
+    ///
+    /// See [`Rasterizer::set_gamma`] for details.
+    pub fn gamma(mut self, gamma: Option<f32>) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
     /// Builds a scaler using the currently configured settings
     /// and the specified font.
     pub fn build(mut self, font: &impl TableProvider<'a>) -> Scaler<'a> {
@@ -140,7 +202,24 @@ impl<'a> ScalerBuilder<'a> {
         let coords = &self.context.coords[..];
         let size = self.size.ppem().unwrap_or_default();
         let outlines = if let Some(glyf) = glyf::Scaler::new(font) {
-            Some(Outlines::TrueType(glyf, &mut self.context.outline_memory))
+            #[cfg(feature = "hinting")]
+            let hinter = match self.hint {
+                Some(mode) if size > 0.0 && mode != Hinting::Auto => TrueTypeHinter::new(
+                    &mut self.context.hint_cache,
+                    self.cache_key,
+                    font,
+                    coords,
+                    size,
+                    mode,
+                ),
+                _ => None,
+            };
+            Some(Outlines::TrueType {
+                scaler: glyf,
+                buf: &mut self.context.outline_memory,
+                #[cfg(feature = "hinting")]
+                hinter,
+            })
         } else {
             cff::Scaler::new(font)
                 .ok()
@@ -150,10 +229,40 @@ impl<'a> ScalerBuilder<'a> {
                 })
                 .map(|(scaler, subfont)| Outlines::PostScript(scaler, subfont))
         };
+        #[cfg(feature = "hinting")]
+        let mut outlines = outlines;
+        #[cfg(feature = "hinting")]
+        let autohinter = match (self.hint, outlines.as_mut()) {
+            (Some(Hinting::Auto), Some(outlines)) if size > 0.0 => Some(AutoHinter::new(
+                &mut self.context.autohint_cache,
+                self.cache_key,
+                font,
+                outlines,
+                size,
+                coords,
+            )),
+            _ => None,
+        };
+        // Emboldening adjusts the advance width so we need the unmodified
+        // value for glyphs that don't otherwise report one.
+        let glyph_metrics =
+            (self.synthesis.x_strength != 0.0).then(|| GlyphMetrics::new(font, self.size, coords));
         Scaler {
             size,
             coords,
             outlines,
+            #[cfg(feature = "hinting")]
+            autohinter,
+            synthesis: self.synthesis,
+            glyph_metrics,
+            outline: &mut self.context.outline,
+            cache_key: self.cache_key,
+            #[cfg(feature = "hinting")]
+            hint: self.hint,
+            outline_cache: &mut self.context.outline_cache,
+            fill_rule: self.fill_rule,
+            gamma: self.gamma,
+            rasterizer: &mut self.context.rasterizer,
         }
     }
 
@@ -205,6 +314,20 @@ pub struct Scaler<'a> {
     size: f32,
     coords: &'a [NormalizedCoord],
     outlines: Option<Outlines<'a>>,
+    #[cfg(feature = "hinting")]
+    autohinter: Option<AutoHinter<'a>>,
+    synthesis: Synthesis,
+    glyph_metrics: Option<GlyphMetrics<'a>>,
+    /// Buffer for outlines that are modified after loading.
+    outline: &'a mut Outline,
+    /// Font identifier for caching outlines.
+    cache_key: Option<UniqueId>,
+    #[cfg(feature = "hinting")]
+    hint: Option<Hinting>,
+    outline_cache: &'a mut OutlineCache,
+    fill_rule: FillRule,
+    gamma: Option<f32>,
+    rasterizer: &'a mut Rasterizer,
 }
 
 impl<'a> Scaler<'a> {
@@ -220,12 +343,94 @@ impl<'a> Scaler<'a> {
 
     /// Loads a simple outline for the specified glyph identifier and invokes the functions
     /// in the given pen for the sequence of path commands that define the outline.
+    ///
+    /// If the context has an outline cache and the scaler was built with a
+    /// cache key, the outline is retrieved from or stored in the cache.
     pub fn outline(&mut self, glyph_id: GlyphId, pen: &mut impl Pen) -> Result<ScalerMetrics> {
-        if let Some(outlines) = &mut self.outlines {
-            outlines.outline(glyph_id, self.size, self.coords, pen)
+        let Some(id) = self.cache_key.filter(|_| self.outline_cache.is_enabled()) else {
+            return self.outline_uncached(glyph_id, pen);
+        };
+        let key = CacheKey {
+            id,
+            size: self.size,
+            coords: self.coords,
+            #[cfg(feature = "hinting")]
+            hint: self.hint,
+            synthesis: self.synthesis,
+        };
+        if let Some((outline, metrics)) = self.outline_cache.get(&key, glyph_id) {
+            outline.to_path(pen);
+            return Ok(metrics);
+        }
+        let mut outline = Outline::new();
+        let metrics = self.outline_uncached(glyph_id, &mut outline)?;
+        outline.to_path(pen);
+        self.outline_cache.insert(&key, glyph_id, outline, metrics);
+        Ok(metrics)
+    }
+
+    /// Loads the outline for the specified glyph identifier and generates an
+    /// anti-aliased coverage mask.
+    ///
+    /// The offset is added to the outline in pixels and may be fractional to
+    /// support subpixel positioning. The returned mask includes its
+    /// placement relative to the glyph origin. Glyphs with overlap flags
+    /// are rasterized with supersampling to avoid darkening regions where
+    /// contours overlap.
+    pub fn rasterize(&mut self, glyph_id: GlyphId, offset: Point<f32>) -> Result<Mask> {
+        let mut rasterizer = core::mem::take(self.rasterizer);
+        rasterizer.reset();
+        rasterizer.set_fill_rule(self.fill_rule);
+        rasterizer.set_gamma(self.gamma);
+        let result = self.outline(glyph_id, &mut rasterizer).map(|metrics| {
+            rasterizer.set_has_overlaps(metrics.has_overlaps);
+            rasterizer.rasterize(offset)
+        });
+        *self.rasterizer = rasterizer;
+        result
+    }
+
+    fn outline_uncached(&mut self, glyph_id: GlyphId, pen: &mut impl Pen) -> Result<ScalerMetrics> {
+        let Some(outlines) = &mut self.outlines else {
+            return Err(Error::NoSources);
+        };
+        #[cfg(feature = "hinting")]
+        let is_buffered = self.autohinter.is_some() || self.synthesis.is_enabled();
+        #[cfg(not(feature = "hinting"))]
+        let is_buffered = self.synthesis.is_enabled();
+        if !is_buffered {
+            return outlines.outline(glyph_id, self.size, self.coords, pen);
+        }
+        self.outline.clear();
+        #[cfg(feature = "hinting")]
+        let mut metrics = if let Some(autohinter) = &mut self.autohinter {
+            autohinter.outline(outlines, glyph_id, self.size, self.coords, self.outline)?
         } else {
-            Err(Error::NoSources)
+            outlines.outline(glyph_id, self.size, self.coords, self.outline)?
+        };
+        #[cfg(not(feature = "hinting"))]
+        let mut metrics = outlines.outline(glyph_id, self.size, self.coords, self.outline)?;
+        self.synthesis.apply(self.outline);
+        if let Some(glyph_metrics) = &self.glyph_metrics {
+            metrics.adjusted_advance_width = metrics
+                .adjusted_advance_width
+                .or_else(|| glyph_metrics.advance_width(glyph_id))
+                .map(|advance| advance + self.synthesis.x_strength);
         }
+        self.outline.to_path(pen);
+        Ok(metrics)
+    }
+
+    /// Computes the bounding box of the outline for the specified glyph
+    /// identifier.
+    ///
+    /// The result reflects the size, variation coordinates, hinting and
+    /// synthesis settings of the scaler. Empty glyphs produce an empty
+    /// bounding box at the origin.
+    pub fn bounds(&mut self, glyph_id: GlyphId, mode: BoundsMode) -> Result<BoundingBox> {
+        let mut pen = BoundsPen::new(mode);
+        self.outline(glyph_id, &mut pen)?;
+        Ok(pen.bounds())
     }
 }
 
@@ -233,7 +438,12 @@ impl<'a> Scaler<'a> {
 // for now: we'll replace this with a real cache.
 #[allow(clippy::large_enum_variant)]
 enum Outlines<'a> {
-    TrueType(glyf::Scaler<'a>, &'a mut Vec<u8>),
+    TrueType {
+        scaler: glyf::Scaler<'a>,
+        buf: &'a mut Vec<u8>,
+        #[cfg(feature = "hinting")]
+        hinter: Option<TrueTypeHinter<'a>>,
+    },
     PostScript(cff::Scaler<'a>, cff::Subfont),
 }
 
@@ -246,8 +456,17 @@ impl<'a> Outlines<'a> {
         pen: &mut impl Pen,
     ) -> Result<ScalerMetrics> {
         match self {
-            Self::TrueType(scaler, buf) => {
-                let glyph = scaler.glyph(glyph_id, false)?;
+            Self::TrueType {
+                scaler,
+                buf,
+                #[cfg(feature = "hinting")]
+                hinter,
+            } => {
+                #[cfg(feature = "hinting")]
+                let with_hinting = hinter.is_some();
+                #[cfg(not(feature = "hinting"))]
+                let with_hinting = false;
+                let glyph = scaler.glyph(glyph_id, with_hinting)?;
                 let buf_size = glyph.required_buffer_size();
                 if buf.len() < buf_size {
                     buf.resize(buf_size, 0);
@@ -255,6 +474,23 @@ impl<'a> Outlines<'a> {
                 let memory = glyph
                     .memory_from_buffer(&mut buf[..])
                     .ok_or(Error::InsufficientMemory)?;
+                #[cfg(feature = "hinting")]
+                if let Some(hinter) = hinter {
+                    let outline =
+                        scaler.hinted_outline(memory, &glyph, size, coords, |outline| {
+                            hinter.instance.hint(hinter.fpgm, hinter.prep, outline)
+                        })?;
+                    outline.to_path(pen)?;
+                    // Hinting may modify the advance width so report the
+                    // distance between the horizontal phantom points.
+                    let [pp1, pp2, ..] = outline.phantom_points;
+                    let advance = (pp2.x - pp1.x).round();
+                    return Ok(ScalerMetrics {
+                        has_overlaps: glyph.has_overlaps,
+                        adjusted_advance_width: Some(advance.to_f32()),
+                        ..Default::default()
+                    });
+                }
                 let outline = scaler.outline(memory, &glyph, size, coords)?;
                 outline.to_path(pen)?;
                 Ok(ScalerMetrics {
@@ -275,3 +511,100 @@ impl<'a> Outlines<'a> {
         }
     }
 }
+
+/// State for executing TrueType hinting instructions.
+#[cfg(feature = "hinting")]
+struct TrueTypeHinter<'a> {
+    instance: &'a mut glyf::HintInstance,
+    fpgm: &'a [u8],
+    prep: &'a [u8],
+}
+
+#[cfg(feature = "hinting")]
+impl<'a> TrueTypeHinter<'a> {
+    /// Returns a hinter for the given configuration, executing the font and
+    /// control value programs if a matching instance is not found in the
+    /// cache.
+    ///
+    /// Returns `None` if either program fails, in which case glyphs are
+    /// loaded without hinting.
+    fn new(
+        cache: &'a mut InstanceCache<glyf::HintInstance>,
+        cache_key: Option<UniqueId>,
+        font: &impl TableProvider<'a>,
+        coords: &'a [NormalizedCoord],
+        size: f32,
+        mode: Hinting,
+    ) -> Option<Self> {
+        let units_per_em = font.head().ok()?.units_per_em();
+        if units_per_em == 0 {
+            return None;
+        }
+        // This must match the scale factor computed by the glyf scaler.
+        let scale =
+            F26Dot6::from_bits((size * 64.) as i32) / F26Dot6::from_bits(units_per_em as i32);
+        let ppem = size.round() as i32;
+        let (is_cached, instance) = cache.instance(cache_key, size, coords, mode);
+        if !is_cached {
+            // A failed configuration remains in the cache as a disabled
+            // instance so the programs are not executed again.
+            let _ = instance.reconfigure(font, coords, ppem, scale.to_bits(), mode);
+        }
+        if !instance.is_enabled() {
+            return None;
+        }
+        Some(Self {
+            instance,
+            fpgm: font
+                .fpgm()
+                .map(|fpgm| fpgm.instructions())
+                .unwrap_or_default(),
+            prep: font
+                .prep()
+                .map(|prep| prep.instructions())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// State for automatic hinting.
+#[cfg(feature = "hinting")]
+struct AutoHinter<'a> {
+    metrics: &'a autohint::Metrics,
+}
+
+#[cfg(feature = "hinting")]
+impl<'a> AutoHinter<'a> {
+    /// Returns a hinter for the given configuration, computing the metrics
+    /// from the reference characters if they are not found in the cache.
+    fn new(
+        cache: &'a mut InstanceCache<autohint::Metrics>,
+        cache_key: Option<UniqueId>,
+        font: &impl TableProvider<'a>,
+        outlines: &mut Outlines<'a>,
+        size: f32,
+        coords: &'a [NormalizedCoord],
+    ) -> Self {
+        let (is_cached, metrics) = cache.instance(cache_key, size, coords, Hinting::Auto);
+        if !is_cached {
+            let charmap = Charmap::new(font);
+            *metrics = autohint::Metrics::new(size, &charmap, |glyph_id, outline| {
+                outlines.outline(glyph_id, size, coords, outline).is_ok()
+            });
+        }
+        Self { metrics }
+    }
+
+    fn outline(
+        &mut self,
+        outlines: &mut Outlines<'a>,
+        glyph_id: GlyphId,
+        size: f32,
+        coords: &'a [NormalizedCoord],
+        outline: &mut Outline,
+    ) -> Result<ScalerMetrics> {
+        let metrics = outlines.outline(glyph_id, size, coords, outline)?;
+        autohint::hint_outline(self.metrics, outline);
+        Ok(metrics)
+    }
+}
@@ -0,0 +1,309 @@
+//! Synthetic emboldening and oblique styles.
+//!
+//! These are used to simulate bold and italic faces for families that do
+//! not provide them.
+//!
+//! Based on FreeType's `ftsynth.c` and `FT_Outline_EmboldenXY`.
+//!
+//! See <https://gitlab.freedesktop.org/freetype/freetype/-/blob/57617782464411201ce7bbc93b086c1b4d7d84a5/src/base/ftoutln.c#L904>
+
+use read_fonts::types::Point;
+
+use super::outline::Outline;
+
+/// Adjustments applied to scaled outlines.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct Synthesis {
+    /// Emboldening strength in the horizontal direction.
+    pub x_strength: f32,
+    /// Emboldening strength in the vertical direction.
+    pub y_strength: f32,
+    /// Horizontal shear factor for the oblique style.
+    pub skew: f32,
+}
+
+impl Synthesis {
+    /// Returns true if any adjustments are applied.
+    pub fn is_enabled(&self) -> bool {
+        self.x_strength != 0.0 || self.y_strength != 0.0 || self.skew != 0.0
+    }
+
+    /// Applies the adjustments to the given outline.
+    pub fn apply(&self, outline: &mut Outline) {
+        embolden(outline, self.x_strength, self.y_strength);
+        if self.skew != 0.0 {
+            for point in &mut outline.points {
+                point.x += point.y * self.skew;
+            }
+        }
+    }
+}
+
+/// Direction of the outer contours of an outline.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Orientation {
+    /// Outer contours are clockwise, as in TrueType outlines.
+    Clockwise,
+    /// Outer contours are counter-clockwise, as in PostScript outlines.
+    CounterClockwise,
+}
+
+/// Computes the orientation of the outline from its signed area.
+///
+/// Returns `None` for empty or degenerate outlines.
+fn orientation(outline: &Outline) -> Option<Orientation> {
+    let mut area = 0.0;
+    for contour in outline.contours() {
+        let points = &outline.points[contour];
+        let Some(mut prev) = points.last() else {
+            continue;
+        };
+        for point in points {
+            area += (point.y - prev.y) * (point.x + prev.x);
+            prev = point;
+        }
+    }
+    if area > 0.0 {
+        Some(Orientation::CounterClockwise)
+    } else if area < 0.0 {
+        Some(Orientation::Clockwise)
+    } else {
+        None
+    }
+}
+
+/// Emboldens the outline by the given strengths.
+///
+/// The glyph grows by the full strength in each direction while the
+/// bottom left corner remains approximately fixed.
+pub fn embolden(outline: &mut Outline, x_strength: f32, y_strength: f32) {
+    let x_strength = x_strength / 2.0;
+    let y_strength = y_strength / 2.0;
+    if x_strength == 0.0 && y_strength == 0.0 {
+        return;
+    }
+    let Some(orientation) = orientation(outline) else {
+        return;
+    };
+    let is_clockwise = orientation == Orientation::Clockwise;
+    let contours = outline.contours().collect::<Vec<_>>();
+    for contour in contours {
+        embolden_contour(
+            &mut outline.points[contour],
+            x_strength,
+            y_strength,
+            is_clockwise,
+        );
+    }
+}
+
+/// Shifts each point of the contour along the bisector of its adjacent
+/// segments.
+fn embolden_contour(
+    points: &mut [Point<f32>],
+    x_strength: f32,
+    y_strength: f32,
+    is_clockwise: bool,
+) {
+    let Some(last) = points.len().checked_sub(1) else {
+        return;
+    };
+    let next = |ix: usize| if ix < last { ix + 1 } else { 0 };
+    let mut in_dir = Point::<f32>::default();
+    let mut in_len = 0.0;
+    let mut anchor = Point::<f32>::default();
+    let mut anchor_len = 0.0;
+    // Counter j cycles though the points; counter i advances only when
+    // points are moved; anchor k marks the first moved point.
+    let mut i = last;
+    let mut j = 0;
+    let mut k = None;
+    while j != i && Some(i) != k {
+        let (out_dir, out_len) = if Some(j) != k {
+            let out = points[j] - points[i];
+            let len = out.x.hypot(out.y);
+            if len == 0.0 {
+                j = next(j);
+                continue;
+            }
+            (Point::new(out.x / len, out.y / len), len)
+        } else {
+            (anchor, anchor_len)
+        };
+        if in_len != 0.0 {
+            if k.is_none() {
+                k = Some(i);
+                anchor = in_dir;
+                anchor_len = in_len;
+            }
+            let mut d = in_dir.x * out_dir.x + in_dir.y * out_dir.y;
+            // Shift only if the turn is less than ~160 degrees.
+            let shift = if d > -0.9375 {
+                d += 1.0;
+                // Shift components along the lateral bisector in the proper
+                // orientation.
+                let mut shift = Point::new(in_dir.y + out_dir.y, in_dir.x + out_dir.x);
+                let mut q = out_dir.x * in_dir.y - out_dir.y * in_dir.x;
+                if is_clockwise {
+                    shift.x = -shift.x;
+                    q = -q;
+                } else {
+                    shift.y = -shift.y;
+                }
+                // Restrict the shift magnitude to better handle collapsing
+                // segments.
+                let len = in_len.min(out_len);
+                shift.x *= if x_strength * q <= len * d {
+                    x_strength / d
+                } else {
+                    len / q
+                };
+                shift.y *= if y_strength * q <= len * d {
+                    y_strength / d
+                } else {
+                    len / q
+                };
+                shift
+            } else {
+                Point::default()
+            };
+            while i != j {
+                points[i].x += x_strength + shift.x;
+                points[i].y += y_strength + shift.y;
+                i = next(i);
+            }
+        } else {
+            i = j;
+        }
+        in_dir = out_dir;
+        in_len = out_len;
+        j = next(j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use read_fonts::types::Pen;
+
+    fn square(outline: &mut Outline, points: &[(f32, f32)]) {
+        outline.move_to(points[0].0, points[0].1);
+        for point in &points[1..] {
+            outline.line_to(point.0, point.1);
+        }
+        outline.close();
+    }
+
+    fn points(outline: &Outline) -> Vec<(f32, f32)> {
+        outline.points.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn orientations() {
+        let mut outline = Outline::default();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+        );
+        assert_eq!(orientation(&outline), Some(Orientation::CounterClockwise));
+        outline.clear();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)],
+        );
+        assert_eq!(orientation(&outline), Some(Orientation::Clockwise));
+        outline.clear();
+        assert_eq!(orientation(&outline), None);
+    }
+
+    #[test]
+    fn embolden_both_orientations() {
+        let mut outline = Outline::default();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+        );
+        embolden(&mut outline, 2.0, 4.0);
+        assert_eq!(
+            points(&outline),
+            [(0.0, 0.0), (12.0, 0.0), (12.0, 14.0), (0.0, 14.0)]
+        );
+        outline.clear();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)],
+        );
+        embolden(&mut outline, 2.0, 4.0);
+        assert_eq!(
+            points(&outline),
+            [(0.0, 0.0), (0.0, 14.0), (12.0, 14.0), (12.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn embolden_counter() {
+        // Outer counter-clockwise contour with an inner clockwise hole. The
+        // hole shrinks while the outer contour grows.
+        let mut outline = Outline::default();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (30.0, 0.0), (30.0, 30.0), (0.0, 30.0)],
+        );
+        square(
+            &mut outline,
+            &[(10.0, 10.0), (10.0, 20.0), (20.0, 20.0), (20.0, 10.0)],
+        );
+        embolden(&mut outline, 2.0, 2.0);
+        assert_eq!(
+            points(&outline)[4..],
+            [(12.0, 12.0), (12.0, 20.0), (20.0, 20.0), (20.0, 12.0)]
+        );
+    }
+
+    #[test]
+    fn embolden_ignores_duplicate_points() {
+        let mut outline = Outline::default();
+        square(
+            &mut outline,
+            &[
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ],
+        );
+        embolden(&mut outline, 2.0, 2.0);
+        assert_eq!(
+            points(&outline),
+            [
+                (0.0, 0.0),
+                (12.0, 0.0),
+                (12.0, 0.0),
+                (12.0, 12.0),
+                (0.0, 12.0),
+                (0.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn oblique() {
+        let mut outline = Outline::default();
+        square(
+            &mut outline,
+            &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)],
+        );
+        let synthesis = Synthesis {
+            skew: 0.5,
+            ..Default::default()
+        };
+        assert!(synthesis.is_enabled());
+        synthesis.apply(&mut outline);
+        assert_eq!(
+            points(&outline),
+            [(0.0, 0.0), (10.0, 0.0), (15.0, 10.0), (5.0, 10.0)]
+        );
+    }
+}
@@ -58,7 +58,12 @@ pub struct LocalizedStrings<'a> {
 impl<'a> LocalizedStrings<'a> {
     /// Creates a new localized string iterator from the given font and string identifier.
     pub fn new(font: &impl TableProvider<'a>, id: StringId) -> Self {
-        let name = font.name().ok();
+        Self::from_name(font.name().ok(), id)
+    }
+
+    /// Creates a new localized string iterator from the given name table and
+    /// string identifier.
+    pub(crate) fn from_name(name: Option<Name<'a>>, id: StringId) -> Self {
         let records = name
             .as_ref()
             .map(|name| name.name_record().iter())
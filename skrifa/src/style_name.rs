@@ -0,0 +1,561 @@
+//! Axis values and style names from the STAT table.
+//!
+//! The [STAT](https://learn.microsoft.com/en-us/typography/opentype/spec/stat)
+//! table assigns names to positions along the design axes of a font family,
+//! such as "Bold" for a weight of 700. This provides lookup of the axis
+//! values that apply to an arbitrary location in a variable font along with
+//! composition of style names from those values.
+//!
+//! # Example
+//! The following function will print the style name of a font at a given
+//! weight:
+//! ```
+//! use skrifa::MetadataProvider;
+//!
+//! fn print_style_name<'a>(font: &impl MetadataProvider<'a>, weight: f32) {
+//!     let location = font.axes().location([("wght", weight)]);
+//!     if let Some(name) = font.axis_values().style_name(&location) {
+//!         println!("{name}");
+//!     }
+//! }
+//! ```
+
+use read_fonts::{
+    tables::{
+        name::Name,
+        stat::{self, AxisRecord, AxisValueTableFlags, Stat},
+    },
+    types::{NameId, Tag},
+    TableProvider,
+};
+
+use crate::{
+    instance::{LocationRef, NormalizedCoord},
+    string::{LocalizedStrings, StringId},
+    variation::AxisCollection,
+};
+
+/// Named position along one or more design axes.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables>
+pub struct AxisValue<'a> {
+    raw: stat::AxisValue<'a>,
+    design_axes: &'a [AxisRecord],
+}
+
+impl Clone for AxisValue<'_> {
+    fn clone(&self) -> Self {
+        let raw = match &self.raw {
+            stat::AxisValue::Format1(item) => stat::AxisValue::Format1(item.clone()),
+            stat::AxisValue::Format2(item) => stat::AxisValue::Format2(item.clone()),
+            stat::AxisValue::Format3(item) => stat::AxisValue::Format3(item.clone()),
+            stat::AxisValue::Format4(item) => stat::AxisValue::Format4(item.clone()),
+        };
+        Self {
+            raw,
+            design_axes: self.design_axes,
+        }
+    }
+}
+
+impl<'a> AxisValue<'a> {
+    /// Returns the format of the underlying axis value table.
+    ///
+    /// * Format 1: a single value on a single axis.
+    /// * Format 2: a range of values on a single axis.
+    /// * Format 3: a single value on a single axis, with a linked value.
+    /// * Format 4: a combination of values on multiple axes.
+    pub fn format(&self) -> u16 {
+        match &self.raw {
+            stat::AxisValue::Format1(_) => 1,
+            stat::AxisValue::Format2(_) => 2,
+            stat::AxisValue::Format3(_) => 3,
+            stat::AxisValue::Format4(_) => 4,
+        }
+    }
+
+    /// Returns the localized string identifier for the name of the value.
+    pub fn name_id(&self) -> StringId {
+        self.raw.value_name_id()
+    }
+
+    /// Returns true if the name of the value may be omitted when composing
+    /// style names.
+    pub fn is_elidable(&self) -> bool {
+        self.raw
+            .flags()
+            .contains(AxisValueTableFlags::ELIDABLE_AXIS_VALUE_NAME)
+    }
+
+    /// Returns true if the value describes an attribute of other fonts in
+    /// the family that were released earlier and lack STAT data.
+    pub fn is_older_sibling_attribute(&self) -> bool {
+        self.raw
+            .flags()
+            .contains(AxisValueTableFlags::OLDER_SIBLING_FONT_ATTRIBUTE)
+    }
+
+    /// Returns an iterator over the axis tag and user space value pairs that
+    /// define the position.
+    ///
+    /// For values with a range, this yields the nominal value.
+    pub fn values(&self) -> impl Iterator<Item = (Tag, f32)> + 'a + Clone {
+        let copy = self.clone();
+        (0..self.axis_count()).filter_map(move |i| {
+            let (axis_index, value) = copy.value_at(i)?;
+            Some((copy.design_axes.get(axis_index)?.axis_tag(), value))
+        })
+    }
+
+    /// Returns the minimum and maximum user space values of the range for
+    /// format 2 values.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        match &self.raw {
+            stat::AxisValue::Format2(item) => Some((
+                item.range_min_value().to_f64() as f32,
+                item.range_max_value().to_f64() as f32,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Returns the user space value of the style-linked position for format
+    /// 3 values.
+    ///
+    /// This is commonly used to link a "Regular" weight to "Bold".
+    pub fn linked_value(&self) -> Option<f32> {
+        match &self.raw {
+            stat::AxisValue::Format3(item) => Some(item.linked_value().to_f64() as f32),
+            _ => None,
+        }
+    }
+
+    fn axis_count(&self) -> usize {
+        match &self.raw {
+            stat::AxisValue::Format4(item) => item.axis_values().len(),
+            _ => 1,
+        }
+    }
+
+    /// Returns the design axis index and value at the given index.
+    fn value_at(&self, index: usize) -> Option<(usize, f32)> {
+        let (axis_index, value) = match &self.raw {
+            stat::AxisValue::Format1(item) => (item.axis_index(), item.value()),
+            stat::AxisValue::Format2(item) => (item.axis_index(), item.nominal_value()),
+            stat::AxisValue::Format3(item) => (item.axis_index(), item.value()),
+            stat::AxisValue::Format4(item) => {
+                let record = item.axis_values().get(index)?;
+                (record.axis_index(), record.value())
+            }
+        };
+        if index >= self.axis_count() {
+            return None;
+        }
+        Some((axis_index as usize, value.to_f64() as f32))
+    }
+
+    /// Returns the ordering of the value when composing names.
+    ///
+    /// For values that span multiple axes, this is the lowest ordering of
+    /// those axes.
+    fn ordering(&self) -> u16 {
+        (0..self.axis_count())
+            .filter_map(|i| self.design_axes.get(self.value_at(i)?.0))
+            .map(|axis| axis.axis_ordering())
+            .min()
+            .unwrap_or(u16::MAX)
+    }
+}
+
+/// Collection of axis values in a font.
+///
+/// See the [`AxisValue`] type for more detail.
+#[derive(Clone)]
+pub struct AxisValueCollection<'a> {
+    stat: Option<Stat<'a>>,
+    axes: AxisCollection<'a>,
+    name: Option<Name<'a>>,
+}
+
+impl<'a> AxisValueCollection<'a> {
+    /// Creates a new axis value collection from the given font.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        Self {
+            stat: font.stat().ok(),
+            axes: AxisCollection::new(font),
+            name: font.name().ok(),
+        }
+    }
+
+    /// Returns the number of axis values in the collection.
+    pub fn len(&self) -> usize {
+        self.stat
+            .as_ref()
+            .map(|stat| stat.axis_value_count() as usize)
+            .unwrap_or(0)
+    }
+
+    /// Returns true if the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the axis value at the given index.
+    pub fn get(&self, index: usize) -> Option<AxisValue<'a>> {
+        let stat = self.stat.as_ref()?;
+        let raw = stat
+            .offset_to_axis_values()
+            .ok()?
+            .axis_values()
+            .get(index)
+            .ok()?;
+        Some(AxisValue {
+            raw,
+            design_axes: stat.design_axes().ok()?,
+        })
+    }
+
+    /// Returns an iterator over the axis values in the collection.
+    pub fn iter(&self) -> impl Iterator<Item = AxisValue<'a>> + 'a + Clone {
+        let copy = self.clone();
+        (0..self.len()).filter_map(move |i| copy.get(i))
+    }
+
+    /// Returns the axis values that describe the given location in
+    /// normalized variation space, in the order that their names should
+    /// appear in a style name.
+    ///
+    /// Values are selected as follows:
+    ///
+    /// 1. Format 4 values that exactly match the location on all of their
+    ///    axes, preferring those that cover more axes.
+    /// 2. For each remaining design axis, a format 1 or 3 value that exactly
+    ///    matches the location, otherwise the format 2 value with a range that
+    ///    contains the location and the closest nominal value.
+    ///
+    /// Design axes that are not present in the `fvar` table (such as the
+    /// italic axis in a family split across multiple files) match the first
+    /// value defined for the axis. Values marked as older sibling attributes
+    /// are ignored.
+    pub fn matching<'b>(&self, location: impl Into<LocationRef<'b>>) -> Vec<AxisValue<'a>> {
+        let Some(design_axes) = self.stat.as_ref().and_then(|stat| stat.design_axes().ok()) else {
+            return vec![];
+        };
+        let coords = location.into().coords();
+        let values = self
+            .iter()
+            .filter(|value| !value.is_older_sibling_attribute())
+            .collect::<Vec<_>>();
+        let mut covered = vec![false; design_axes.len()];
+        let mut result = vec![];
+        let mut multi_axis = values
+            .iter()
+            .filter(|value| value.format() == 4)
+            .filter(|value| {
+                (0..value.axis_count()).all(|i| {
+                    value
+                        .value_at(i)
+                        .map(|(axis_index, value)| {
+                            self.is_at_position(&values, design_axes, coords, axis_index, value)
+                        })
+                        .unwrap_or(false)
+                })
+            })
+            .collect::<Vec<_>>();
+        // Stable sort keeps the font order for values covering the same
+        // number of axes.
+        multi_axis.sort_by_key(|value| core::cmp::Reverse(value.axis_count()));
+        for value in multi_axis {
+            let axis_indices = (0..value.axis_count())
+                .filter_map(|i| value.value_at(i).map(|(axis_index, _)| axis_index))
+                .collect::<Vec<_>>();
+            if axis_indices
+                .iter()
+                .any(|ix| covered.get(*ix).copied().unwrap_or(true))
+            {
+                continue;
+            }
+            for ix in axis_indices {
+                covered[ix] = true;
+            }
+            result.push(value.clone());
+        }
+        for (axis_index, _) in design_axes.iter().enumerate() {
+            if covered[axis_index] {
+                continue;
+            }
+            let mut candidates = single_axis_values(&values, axis_index);
+            let best = match self.fvar_index(design_axes, axis_index) {
+                Some(fvar_index) => {
+                    let coord = coord_at(coords, Some(fvar_index));
+                    let normalize = |value| self.normalize(design_axes, axis_index, value);
+                    candidates
+                        .clone()
+                        .find(|value| {
+                            value.format() != 2
+                                && value.value_at(0).and_then(|(_, value)| normalize(value))
+                                    == Some(coord)
+                        })
+                        .or_else(|| {
+                            candidates
+                                .filter_map(|value| {
+                                    let (min, max) = value.range()?;
+                                    let (min, max) = (normalize(min)?, normalize(max)?);
+                                    if coord < min || coord > max {
+                                        return None;
+                                    }
+                                    let nominal = normalize(value.value_at(0)?.1)?;
+                                    let distance =
+                                        (nominal.to_bits() as i32 - coord.to_bits() as i32).abs();
+                                    Some((distance, value))
+                                })
+                                .min_by_key(|(distance, _)| *distance)
+                                .map(|(_, value)| value)
+                        })
+                }
+                None => candidates.next(),
+            };
+            if let Some(best) = best {
+                result.push(best.clone());
+            }
+        }
+        result.sort_by_key(|value| value.ordering());
+        result
+    }
+
+    /// Returns the string identifiers for the components of the style name
+    /// at the given location in normalized variation space.
+    ///
+    /// Elidable values are omitted. If all values are elided, this contains
+    /// the elided fallback name, which is usually "Regular".
+    pub fn style_name_ids<'b>(&self, location: impl Into<LocationRef<'b>>) -> Vec<StringId> {
+        let ids = self
+            .matching(location)
+            .iter()
+            .filter(|value| !value.is_elidable())
+            .map(|value| value.name_id())
+            .collect::<Vec<_>>();
+        if !ids.is_empty() {
+            return ids;
+        }
+        let fallback = self
+            .stat
+            .as_ref()
+            .and_then(|stat| stat.elided_fallback_name_id())
+            .unwrap_or(NameId::SUBFAMILY_NAME);
+        vec![fallback]
+    }
+
+    /// Returns the style name at the given location in normalized variation
+    /// space.
+    ///
+    /// The name is composed from the best available English strings for the
+    /// identifiers returned by [`Self::style_name_ids`], separated by
+    /// spaces.
+    pub fn style_name<'b>(&self, location: impl Into<LocationRef<'b>>) -> Option<String> {
+        let names = self
+            .style_name_ids(location)
+            .into_iter()
+            .map(|id| self.string(id))
+            .collect::<Option<Vec<_>>>()?;
+        Some(names.join(" "))
+    }
+
+    /// Returns the full name, composed of the family name and the style name,
+    /// at the given location in normalized variation space.
+    ///
+    /// The family name is the typographic family name if present, otherwise
+    /// the legacy family name.
+    pub fn full_name<'b>(&self, location: impl Into<LocationRef<'b>>) -> Option<String> {
+        let family = self
+            .string(NameId::TYPOGRAPHIC_FAMILY_NAME)
+            .or_else(|| self.string(NameId::FAMILY_NAME))?;
+        Some(format!("{family} {}", self.style_name(location)?))
+    }
+
+    fn string(&self, id: StringId) -> Option<String> {
+        LocalizedStrings::from_name(self.name.clone(), id)
+            .english_or_first()
+            .map(|string| string.to_string())
+    }
+
+    /// Returns true if the location is at the given user space value on the
+    /// design axis.
+    ///
+    /// For axes that are not in `fvar`, the position is implied by the first
+    /// value defined for the axis.
+    fn is_at_position(
+        &self,
+        values: &[AxisValue<'a>],
+        design_axes: &[AxisRecord],
+        coords: &[NormalizedCoord],
+        axis_index: usize,
+        value: f32,
+    ) -> bool {
+        match self.fvar_index(design_axes, axis_index) {
+            Some(fvar_index) => {
+                self.normalize(design_axes, axis_index, value)
+                    == Some(coord_at(coords, Some(fvar_index)))
+            }
+            None => single_axis_values(values, axis_index)
+                .next()
+                .and_then(|first| first.value_at(0))
+                .map(|(_, first)| first == value)
+                .unwrap_or(true),
+        }
+    }
+
+    /// Returns the index of the `fvar` axis for the given design axis.
+    fn fvar_index(&self, design_axes: &[AxisRecord], axis_index: usize) -> Option<usize> {
+        let tag = design_axes.get(axis_index)?.axis_tag();
+        self.axes.get_by_tag(tag).map(|axis| axis.index())
+    }
+
+    /// Normalizes a user space value on the given design axis.
+    fn normalize(
+        &self,
+        design_axes: &[AxisRecord],
+        axis_index: usize,
+        value: f32,
+    ) -> Option<NormalizedCoord> {
+        let axis = self.axes.get(self.fvar_index(design_axes, axis_index)?)?;
+        Some(self.axes.normalize_axis(&axis, value))
+    }
+}
+
+/// Returns an iterator over the values that apply to only the given design
+/// axis.
+fn single_axis_values<'a, 'b>(
+    values: &'b [AxisValue<'a>],
+    axis_index: usize,
+) -> impl Iterator<Item = &'b AxisValue<'a>> + Clone {
+    values.iter().filter(move |value| {
+        value.format() != 4 && value.value_at(0).map(|(ix, _)| ix) == Some(axis_index)
+    })
+}
+
+/// Returns the coordinate at the given index, or the default coordinate if
+/// it is missing.
+fn coord_at(coords: &[NormalizedCoord], index: Option<usize>) -> NormalizedCoord {
+    index
+        .and_then(|index| coords.get(index).copied())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MetadataProvider as _;
+    use font_test_data::VAZIRMATN_VAR;
+    use read_fonts::{FontData, FontRead, FontRef};
+
+    #[rustfmt::skip]
+    static STAT: &[u8] = &[
+        // version 1.1, design axis size and count, design axes offset
+        0, 1, 0, 1, 0, 8, 0, 2, 0, 0, 0, 20,
+        // axis value count and offset, elided fallback name id
+        0, 7, 0, 0, 0, 36, 1, 54,
+        // design axes: wght and ital
+        b'w', b'g', b'h', b't', 1, 0, 0, 0,
+        b'i', b't', b'a', b'l', 1, 1, 0, 1,
+        // axis value offsets
+        0, 14, 0, 34, 0, 50, 0, 62, 0, 82, 0, 94, 0, 114,
+        // format 2: wght 300 in 100..=350
+        0, 2, 0, 0, 0, 0, 1, 44, 1, 44, 0, 0, 0, 100, 0, 0, 1, 94, 0, 0,
+        // format 3: wght 400 linked to 700, elidable
+        0, 3, 0, 0, 0, 2, 1, 45, 1, 144, 0, 0, 2, 188, 0, 0,
+        // format 1: wght 700
+        0, 1, 0, 0, 0, 0, 1, 46, 2, 188, 0, 0,
+        // format 2: wght 900 in 700..=900
+        0, 2, 0, 0, 0, 0, 1, 47, 3, 132, 0, 0, 2, 188, 0, 0, 3, 132, 0, 0,
+        // format 1: ital 0, elidable
+        0, 1, 0, 1, 0, 2, 1, 48, 0, 0, 0, 0,
+        // format 4: wght 900 and ital 0
+        0, 4, 0, 2, 0, 0, 1, 49, 0, 0, 3, 132, 0, 0, 0, 1, 0, 0, 0, 0,
+        // format 1: wght 700, older sibling attribute
+        0, 1, 0, 0, 0, 1, 1, 50, 2, 188, 0, 0,
+    ];
+
+    #[test]
+    fn axis_values() {
+        let font = FontRef::new(VAZIRMATN_VAR).unwrap();
+        let axis_values = font.axis_values();
+        assert_eq!(axis_values.len(), 3);
+        let values = axis_values
+            .iter()
+            .map(|value| {
+                (
+                    value.format(),
+                    value.name_id().to_u16(),
+                    value.values().collect::<Vec<_>>(),
+                    value.is_elidable(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let wght = Tag::new(b"wght");
+        assert_eq!(
+            values,
+            [
+                (1, 258, vec![(wght, 100.0)], false),
+                (1, 261, vec![(wght, 400.0)], true),
+                (1, 264, vec![(wght, 700.0)], false),
+            ]
+        );
+    }
+
+    #[test]
+    fn font_style_names() {
+        let font = FontRef::new(VAZIRMATN_VAR).unwrap();
+        let axis_values = font.axis_values();
+        let name = |weight: f32| {
+            let location = font.axes().location([("wght", weight)]);
+            axis_values.style_name(&location).unwrap()
+        };
+        assert_eq!(name(100.0), "Thin");
+        assert_eq!(name(400.0), "Regular");
+        assert_eq!(name(700.0), "Bold");
+        // No matching value so we use the elided fallback
+        assert_eq!(name(500.0), "Regular");
+        // The trimmed font has no family name
+        assert!(axis_values.full_name(LocationRef::default()).is_none());
+    }
+
+    #[test]
+    fn matching_formats() {
+        let font = FontRef::new(VAZIRMATN_VAR).unwrap();
+        let axis_values = AxisValueCollection {
+            stat: Some(Stat::read(FontData::new(STAT)).unwrap()),
+            ..font.axis_values()
+        };
+        let matching = |weight: f32| {
+            let location = font.axes().location([("wght", weight)]);
+            let matching = axis_values
+                .matching(&location)
+                .iter()
+                .map(|value| (value.format(), value.name_id().to_u16()))
+                .collect::<Vec<_>>();
+            let name_ids = axis_values
+                .style_name_ids(&location)
+                .iter()
+                .map(|id| id.to_u16())
+                .collect::<Vec<_>>();
+            (matching, name_ids)
+        };
+        // Range match, ital axis is not in fvar so uses its first value
+        assert_eq!(matching(200.0), (vec![(2, 300), (1, 304)], vec![300]));
+        // Linked value is elided
+        assert_eq!(matching(400.0), (vec![(3, 301), (1, 304)], vec![310]));
+        // Exact match is preferred over a range and the older sibling
+        // attribute is ignored
+        assert_eq!(matching(700.0), (vec![(1, 302), (1, 304)], vec![302]));
+        assert_eq!(matching(800.0), (vec![(2, 303), (1, 304)], vec![303]));
+        // Multiple axis value covers both axes
+        assert_eq!(matching(900.0), (vec![(4, 305)], vec![305]));
+        // Nothing on the weight axis
+        assert_eq!(matching(380.0), (vec![(1, 304)], vec![310]));
+        let linked = axis_values.get(1).unwrap();
+        assert_eq!(linked.linked_value(), Some(700.0));
+        assert_eq!(axis_values.get(0).unwrap().range(), Some((100.0, 350.0)));
+        assert!(axis_values.get(6).unwrap().is_older_sibling_attribute());
+    }
+}
@@ -187,7 +187,6 @@ impl<'a> AxisCollection<'a> {
         for coord in location.iter_mut() {
             *coord = NormalizedCoord::default();
         }
-        let avar_mappings = self.avar.as_ref().map(|avar| avar.axis_segment_maps());
         for setting in settings.into_iter() {
             let setting = setting.into();
             // To permit non-linear interpolation, iterate over all axes to ensure we match
@@ -201,19 +200,25 @@ impl<'a> AxisCollection<'a> {
                 .filter(|v| v.1.tag() == setting.selector)
             {
                 if let Some(target_coord) = location.get_mut(i) {
-                    let coord = axis.record.normalize(Fixed::from_f64(setting.value as f64));
-                    *target_coord = avar_mappings
-                        .as_ref()
-                        .and_then(|mappings| mappings.get(i).transpose().ok())
-                        .flatten()
-                        .map(|mapping| mapping.apply(coord))
-                        .unwrap_or(coord)
-                        .to_f2dot14();
+                    *target_coord = self.normalize_axis(&axis, setting.value);
                 }
             }
         }
     }
 
+    /// Returns a normalized coordinate for the given user coordinate on the
+    /// specified axis, applying any axis variation remapping.
+    pub(crate) fn normalize_axis(&self, axis: &Axis, coord: f32) -> NormalizedCoord {
+        let coord = axis.record.normalize(Fixed::from_f64(coord as f64));
+        self.avar
+            .as_ref()
+            .and_then(|avar| avar.axis_segment_maps().get(axis.index).transpose().ok())
+            .flatten()
+            .map(|mapping| mapping.apply(coord))
+            .unwrap_or(coord)
+            .to_f2dot14()
+    }
+
     /// Given an iterator of variation settings in user space, returns a
     /// new iterator yielding those settings that are valid for this axis
     /// collection.